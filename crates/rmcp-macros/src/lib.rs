@@ -31,6 +31,23 @@ mod tool_router;
 ///     // handling tool request
 /// }
 /// ```
+///
+/// ## Return type
+///
+/// A tool returns anything implementing `IntoCallToolResult`. An `Err` of a
+/// `Result<T, rmcp::ErrorData>` or `Result<T, rmcp::handler::server::tool::ToolError>` fails the
+/// request, while an `Err` of a `Result<T, E>` whose error is content, e.g. a `String`, is sent
+/// as an error result the model can read.
+///
+/// ```rust,ignore
+/// #[tool]
+/// pub async fn divide(&self, Parameters(Divide { a, b }): Parameters<Divide>) -> Result<String, ToolError> {
+///     if b == 0 {
+///         return Err(ToolError::InvalidParams("`b` must not be zero".into()));
+///     }
+///     Ok((a / b).to_string())
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn tool(attr: TokenStream, input: TokenStream) -> TokenStream {
     tool::tool(attr.into(), input.into())
@@ -151,9 +168,9 @@ pub fn tool_router(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///     async fn list_tools(
 ///         &self,
 ///         _request: Option<PaginatedRequestParam>,
-///         _context: RequestContext<RoleServer>,
+///         context: RequestContext<RoleServer>,
 ///     ) -> Result<ListToolsResult, rmcp::ErrorData> {
-///         let items = self.tool_router.list_all();
+///         let items = self.tool_router.list_allowed(&context.extensions);
 ///         Ok(ListToolsResult::with_all_items(items))
 ///     }
 /// }
@@ -21,15 +21,12 @@ fn extract_json_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
     None
 }
 
-/// Extract schema expression from a function's return type
+/// Extract the structured output type from a function's return type
 /// Handles patterns like Json<T> and Result<Json<T>, E>
-fn extract_schema_from_return_type(ret_type: &syn::Type) -> Option<Expr> {
+fn extract_output_type(ret_type: &syn::Type) -> Option<&syn::Type> {
     // First, try direct Json<T>
     if let Some(inner_type) = extract_json_inner_type(ret_type) {
-        return syn::parse2::<Expr>(quote! {
-            rmcp::handler::server::tool::cached_schema_for_type::<#inner_type>()
-        })
-        .ok();
+        return Some(inner_type);
     }
 
     // Then, try Result<Json<T>, E>
@@ -54,13 +51,9 @@ fn extract_schema_from_return_type(ret_type: &syn::Type) -> Option<Expr> {
         _ => return None,
     };
 
-    let inner_type = extract_json_inner_type(ok_type)?;
-
-    syn::parse2::<Expr>(quote! {
-        rmcp::handler::server::tool::cached_schema_for_type::<#inner_type>()
-    })
-    .ok()
+    extract_json_inner_type(ok_type)
 }
+
 #[derive(FromMeta, Default, Debug)]
 #[darling(default)]
 pub struct ToolAttribute {
@@ -87,6 +80,8 @@ pub struct ResolvedToolAttribute {
     pub output_schema: Option<Expr>,
     pub annotations: Expr,
     pub icons: Option<Expr>,
+    /// The type the built tool is cached by, if it only depends on types
+    pub cache_key: Option<syn::Type>,
 }
 
 impl ResolvedToolAttribute {
@@ -99,6 +94,7 @@ impl ResolvedToolAttribute {
             output_schema,
             annotations,
             icons,
+            cache_key,
         } = self;
         let description = if let Some(description) = description {
             quote! { Some(#description.into()) }
@@ -122,18 +118,30 @@ impl ResolvedToolAttribute {
         };
         let doc_comment = format!("Generated tool metadata function for {name}");
         let doc_attr: syn::Attribute = parse_quote!(#[doc = #doc_comment]);
+        let tool = quote! {
+            rmcp::model::Tool {
+                name: #name.into(),
+                title: #title,
+                description: #description,
+                input_schema: #input_schema,
+                output_schema: #output_schema,
+                annotations: #annotations,
+                icons: #icons,
+            }
+        };
+        let body = if let Some(cache_key) = cache_key {
+            quote! {
+                static CACHE: rmcp::handler::server::tool::ToolCache =
+                    rmcp::handler::server::tool::ToolCache::new();
+                CACHE.get_or_init::<#cache_key>(|| #tool)
+            }
+        } else {
+            tool
+        };
         let tokens = quote! {
             #doc_attr
             pub fn #fn_ident() -> rmcp::model::Tool {
-                rmcp::model::Tool {
-                    name: #name.into(),
-                    title: #title,
-                    description: #description,
-                    input_schema: #input_schema,
-                    output_schema: #output_schema,
-                    annotations: #annotations,
-                    icons: #icons,
-                }
+                #body
             }
         };
         syn::parse2::<ImplItemFn>(tokens)
@@ -188,26 +196,40 @@ pub fn tool(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
     let fn_ident = &fn_item.sig.ident;
 
     let tool_attr_fn_ident = format_ident!("{}_tool_attr", fn_ident);
+    // try to find some parameters wrapper in the function
+    let params_ty = crate::common::find_parameters_type_impl(&fn_item);
+    let output_ty = match &fn_item.sig.output {
+        syn::ReturnType::Type(_, ret_type) => extract_output_type(ret_type),
+        _ => None,
+    };
+    // the tool only depends on the parameters and output types, unless some schema or icons are
+    // given as expressions, which may be evaluated to something else on each call
+    let cache_key = (attribute.input_schema.is_none()
+        && attribute.output_schema.is_none()
+        && attribute.icons.is_none())
+    .then(|| {
+        let params_ty = params_ty
+            .as_deref()
+            .map_or_else(|| quote! { () }, ToTokens::to_token_stream);
+        let output_ty = output_ty.map_or_else(|| quote! { () }, ToTokens::to_token_stream);
+        parse_quote! { (#params_ty, #output_ty) }
+    });
     let input_schema_expr = if let Some(input_schema) = attribute.input_schema {
         input_schema
+    } else if let Some(params_ty) = &params_ty {
+        // if found, use the Parameters schema
+        syn::parse2::<Expr>(quote! {
+            rmcp::handler::server::common::cached_schema_for_type::<#params_ty>()
+        })?
     } else {
-        // try to find some parameters wrapper in the function
-        let params_ty = crate::common::find_parameters_type_impl(&fn_item);
-        if let Some(params_ty) = params_ty {
-            // if found, use the Parameters schema
-            syn::parse2::<Expr>(quote! {
-                rmcp::handler::server::common::cached_schema_for_type::<#params_ty>()
-            })?
-        } else {
-            // if not found, use a default empty JSON schema object
-            // TODO: should be updated according to the new specifications
-            syn::parse2::<Expr>(quote! {
-                std::sync::Arc::new(serde_json::json!({
-                    "type": "object",
-                    "properties": {}
-                }).as_object().unwrap().clone())
-            })?
-        }
+        // if not found, use a default empty JSON schema object
+        // TODO: should be updated according to the new specifications
+        syn::parse2::<Expr>(quote! {
+            std::sync::Arc::new(serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }).as_object().unwrap().clone())
+        })?
     };
     let annotations_expr = if let Some(annotations) = attribute.annotations {
         let ToolAnnotationsAttribute {
@@ -242,10 +264,11 @@ pub fn tool(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
     // Handle output_schema - either explicit or generated from return type
     let output_schema_expr = attribute.output_schema.or_else(|| {
         // Try to generate schema from return type
-        match &fn_item.sig.output {
-            syn::ReturnType::Type(_, ret_type) => extract_schema_from_return_type(ret_type),
-            _ => None,
-        }
+        output_ty.map(|output_ty| {
+            parse_quote! {
+                rmcp::handler::server::tool::cached_schema_for_type::<#output_ty>()
+            }
+        })
     });
 
     let description_expr = if let Some(s) = attribute.description {
@@ -264,6 +287,7 @@ pub fn tool(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
         annotations: annotations_expr,
         title: attribute.title,
         icons: attribute.icons,
+        cache_key,
     };
     let tool_attr_fn = resolved_tool_attr.into_fn(tool_attr_fn_ident)?;
     // modify the the input function
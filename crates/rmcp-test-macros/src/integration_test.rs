@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Expr, FnArg, ItemFn, LitStr, Type, meta::ParseNestedMeta, parse::Parser};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+struct IntegrationTestAttribute {
+    server: Option<Expr>,
+    client: Option<Expr>,
+    timeout: Option<Duration>,
+}
+
+impl IntegrationTestAttribute {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("server") {
+            self.server = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("client") {
+            self.client = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("timeout") {
+            let timeout: LitStr = meta.value()?.parse()?;
+            self.timeout = Some(parse_duration(&timeout)?);
+        } else {
+            return Err(meta.error("expected `server`, `client` or `timeout`"));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a duration like `500ms` or `5s`
+fn parse_duration(lit: &LitStr) -> syn::Result<Duration> {
+    let value = lit.value();
+    let parsed = if let Some(millis) = value.strip_suffix("ms") {
+        millis.trim().parse().map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.trim().parse().map(Duration::from_secs)
+    } else {
+        return Err(syn::Error::new(
+            lit.span(),
+            "expected a duration in `ms` or `s`, e.g. \"5s\"",
+        ));
+    };
+    parsed.map_err(|error| syn::Error::new(lit.span(), error))
+}
+
+pub fn mcp_integration_test(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let mut attribute = IntegrationTestAttribute::default();
+    syn::meta::parser(|meta| attribute.parse(meta)).parse2(attr)?;
+    let server = attribute
+        .server
+        .ok_or_else(|| syn::Error::new(proc_macro2::Span::call_site(), "missing `server = ...`"))?;
+    let client = attribute.client.unwrap_or_else(|| syn::parse_quote!(()));
+    let timeout_millis = attribute.timeout.unwrap_or(DEFAULT_TIMEOUT).as_millis() as u64;
+
+    let mut body_fn: ItemFn = syn::parse2(input)?;
+    if body_fn.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            body_fn.sig.fn_token,
+            "the test function must be async",
+        ));
+    }
+    // the running services are passed by reference, to reach their handlers, or their peers
+    let by_ref = matches!(
+        body_fn.sig.inputs.first(),
+        Some(FnArg::Typed(arg)) if matches!(*arg.ty, Type::Reference(_))
+    );
+    let peers = match (body_fn.sig.inputs.len(), by_ref) {
+        (1, false) => quote! { client.peer().clone() },
+        (2, false) => quote! { client.peer().clone(), server.peer().clone() },
+        (1, true) => quote! { &client },
+        (2, true) => quote! { &client, &server },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &body_fn.sig.inputs,
+                "expected `(client: Peer<RoleClient>)`, \
+                 `(client: Peer<RoleClient>, server: Peer<RoleServer>)`, \
+                 or the same with `&RunningService<..>`",
+            ));
+        }
+    };
+    let attrs = std::mem::take(&mut body_fn.attrs);
+    let vis = body_fn.vis.clone();
+    let name = body_fn.sig.ident.clone();
+    let output = body_fn.sig.output.clone();
+    let body_name = format_ident!("__{}_body", name);
+    body_fn.sig.ident = body_name.clone();
+
+    Ok(quote! {
+        #[::tokio::test]
+        #(#attrs)*
+        #vis async fn #name() #output {
+            #body_fn
+
+            let timeout = ::std::time::Duration::from_millis(#timeout_millis);
+            let (server_transport, client_transport) = ::tokio::io::duplex(4096);
+            let (server, client) = ::tokio::join!(
+                ::rmcp::serve_server(#server, server_transport),
+                ::rmcp::serve_client(#client, client_transport),
+            );
+            let server = server.expect("the server failed to initialize");
+            let client = client.expect("the client failed to initialize");
+
+            let output = ::tokio::time::timeout(timeout, #body_name(#peers))
+                .await
+                .unwrap_or_else(|_| panic!("the test timed out after {timeout:?}"));
+
+            let client_quit = client.cancel().await.expect("the client task panicked");
+            assert!(
+                matches!(client_quit, ::rmcp::service::QuitReason::Cancelled),
+                "the client stopped with {client_quit:?}"
+            );
+            let server_quit = ::tokio::time::timeout(timeout, server.waiting())
+                .await
+                .unwrap_or_else(|_| panic!("the server didn't stop after {timeout:?}"))
+                .expect("the server task panicked");
+            assert!(
+                matches!(server_quit, ::rmcp::service::QuitReason::Closed),
+                "the server stopped with {server_quit:?}"
+            );
+            output
+        }
+    })
+}
@@ -0,0 +1,49 @@
+use proc_macro::TokenStream;
+
+mod integration_test;
+
+/// # mcp_integration_test
+///
+/// Run a test against a server and a client connected in memory.
+///
+/// The server and the client are initialized, the test function is called with the peer of the
+/// client, and with the peer of the server if it takes a second argument. Both are then shut down,
+/// and the test fails if one of them didn't stop cleanly, or if it ran longer than its timeout.
+///
+/// To reach the handlers, e.g. to check what they recorded, the test function can take
+/// `&RunningService<RoleClient, _>` and `&RunningService<RoleServer, _>` instead of the peers.
+///
+/// ## Usage
+///
+/// | field     | type     | usage |
+/// | :-        | :-       | :-    |
+/// | `server`  | `Expr`   | The server handler. |
+/// | `client`  | `Expr`   | The client handler. Defaults to `()`. |
+/// | `timeout` | `String` | How long the test may run, e.g. `"500ms"` or `"5s"`. Defaults to `"10s"`. |
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// #[mcp_integration_test(server = Calculator::new(), timeout = "5s")]
+/// async fn test_sum(client: Peer<RoleClient>) -> anyhow::Result<()> {
+///     let result = client.call_tool(call("sum", json!({ "a": 1, "b": 2 }))).await?;
+///     assert_eq!(result.content[0].as_text().unwrap().text, "3");
+///     Ok(())
+/// }
+///
+/// #[mcp_integration_test(server = Calculator::new())]
+/// async fn test_calls_counted(
+///     client: &RunningService<RoleClient, ()>,
+///     server: &RunningService<RoleServer, Calculator>,
+/// ) -> anyhow::Result<()> {
+///     client.call_tool(call("sum", json!({ "a": 1, "b": 2 }))).await?;
+///     assert_eq!(server.service().calls(), 1);
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn mcp_integration_test(attr: TokenStream, input: TokenStream) -> TokenStream {
+    integration_test::mcp_integration_test(attr.into(), input.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
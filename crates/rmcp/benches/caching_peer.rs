@@ -0,0 +1,86 @@
+//! Compare listing the tools of a server from many concurrent tasks through the peer, which sends
+//! every request, and through a caching peer, which answers from its cache once it's filled.
+//!
+//! ```bash
+//! cargo bench -p rmcp --features server,client --bench caching_peer
+//! ```
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use futures::future::join_all;
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
+    model::{JsonObject, ListToolsResult, PaginatedRequestParam, Tool},
+    service::{CachingPeer, RequestContext},
+};
+
+const TOOLS: usize = 50;
+
+#[derive(Debug, Clone)]
+struct ManyTools;
+
+impl ServerHandler for ManyTools {
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult::with_all_items(
+            (0..TOOLS)
+                .map(|i| Tool::new(format!("tool_{i}"), "a tool", JsonObject::new()))
+                .collect(),
+        ))
+    }
+}
+
+fn caching_peer(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime");
+    let (server, client) = runtime.block_on(async {
+        let (server_transport, client_transport) = tokio::io::duplex(64 * 1024);
+        let (server, client) = tokio::join!(
+            ManyTools.serve(server_transport),
+            ().serve(client_transport)
+        );
+        (server.expect("server"), client.expect("client"))
+    });
+    let peer = client.peer().clone();
+    let caching = CachingPeer::new(peer.clone());
+
+    let mut group = c.benchmark_group("list_tools");
+    for concurrency in [1, 16, 64] {
+        group.bench_with_input(
+            BenchmarkId::new("peer", concurrency),
+            &concurrency,
+            |b, &concurrency| {
+                b.to_async(&runtime).iter(|| {
+                    join_all((0..concurrency).map(|_| {
+                        let peer = peer.clone();
+                        tokio::spawn(async move { peer.list_tools(None).await })
+                    }))
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("caching_peer", concurrency),
+            &concurrency,
+            |b, &concurrency| {
+                b.to_async(&runtime).iter(|| {
+                    join_all((0..concurrency).map(|_| {
+                        let caching = caching.clone();
+                        tokio::spawn(async move { caching.list_tools(None).await })
+                    }))
+                })
+            },
+        );
+    }
+    group.finish();
+    drop(caching);
+    runtime.block_on(async {
+        client.cancel().await.expect("client");
+        server.cancel().await.expect("server");
+    });
+}
+
+criterion_group!(benches, caching_peer);
+criterion_main!(benches);
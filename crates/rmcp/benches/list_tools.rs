@@ -0,0 +1,100 @@
+//! Compare listing the tools of a server built for each of 10,000 concurrent requests, e.g. a
+//! server per session, with the tools built by the `#[tool]` macros and with the tools built from
+//! freshly generated schemas.
+//!
+//! ```bash
+//! cargo bench -p rmcp --features server,macros --bench list_tools
+//! ```
+use std::sync::Arc;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rmcp::{
+    handler::server::{
+        router::tool::ToolRouter,
+        tool::schema_for_type,
+        wrapper::{Json, Parameters},
+    },
+    model::Tool,
+    tool, tool_router,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const REQUESTS: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+struct Measurement {
+    /// The measured value
+    value: f64,
+    /// The unit of the value
+    unit: String,
+    /// The tags of the measurement
+    tags: Vec<String>,
+}
+
+struct Bench;
+
+macro_rules! bench_tools {
+    ($($name:ident),*) => {
+        #[tool_router]
+        impl Bench {
+            $(
+                #[tool(description = "Echo a measurement")]
+                async fn $name(&self, Parameters(measurement): Parameters<Measurement>) -> Json<Measurement> {
+                    Json(measurement)
+                }
+            )*
+        }
+
+        const TOOL_NAMES: &[&str] = &[$(stringify!($name)),*];
+    };
+}
+
+bench_tools!(
+    t00, t01, t02, t03, t04, t05, t06, t07, t08, t09, t10, t11, t12, t13, t14, t15, t16, t17, t18,
+    t19
+);
+
+/// The tools as built before they were cached, generating their schemas on each call
+fn uncached_tools() -> Vec<Tool> {
+    TOOL_NAMES
+        .iter()
+        .map(|name| Tool {
+            output_schema: Some(Arc::new(schema_for_type::<Measurement>())),
+            ..Tool::new(
+                *name,
+                "Echo a measurement",
+                Arc::new(schema_for_type::<Measurement>()),
+            )
+        })
+        .collect()
+}
+
+fn cached_tools() -> Vec<Tool> {
+    let router: ToolRouter<Bench> = Bench::tool_router();
+    router.list_all()
+}
+
+fn list_tools(c: &mut Criterion) {
+    assert_eq!(cached_tools().len(), TOOL_NAMES.len());
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("list_tools");
+    group.sample_size(10);
+    for (name, tools) in [
+        ("uncached", uncached_tools as fn() -> Vec<Tool>),
+        ("cached", cached_tools),
+    ] {
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async move {
+                let requests = (0..REQUESTS).map(|_| tokio::spawn(async move { tools().len() }));
+                for request in requests.collect::<Vec<_>>() {
+                    request.await.expect("list tools");
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, list_tools);
+criterion_main!(benches);
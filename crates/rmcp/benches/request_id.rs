@@ -0,0 +1,32 @@
+//! Compare the request id providers, generating an id and serializing the request carrying it.
+//!
+//! ```bash
+//! cargo bench -p rmcp --features server --bench request_id
+//! ```
+use criterion::{Criterion, criterion_group, criterion_main};
+use rmcp::{
+    model::{ServerJsonRpcMessage, ServerRequest},
+    service::{AtomicU32RequestIdProvider, AtomicU64RequestIdProvider, RequestIdProvider},
+};
+
+fn bench_provider(c: &mut Criterion, name: &str, provider: &dyn RequestIdProvider) {
+    let request = ServerRequest::PingRequest(Default::default());
+    c.bench_function(&format!("{name}/next_request_id"), |b| {
+        b.iter(|| provider.next_request_id())
+    });
+    c.bench_function(&format!("{name}/serialize_request"), |b| {
+        b.iter(|| {
+            let message =
+                ServerJsonRpcMessage::request(request.clone(), provider.next_request_id());
+            serde_json::to_vec(&message).expect("serializable request")
+        })
+    });
+}
+
+fn request_id(c: &mut Criterion) {
+    bench_provider(c, "atomic_u32", &AtomicU32RequestIdProvider::default());
+    bench_provider(c, "atomic_u64", &AtomicU64RequestIdProvider::default());
+}
+
+criterion_group!(benches, request_id);
+criterion_main!(benches);
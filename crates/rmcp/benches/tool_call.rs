@@ -0,0 +1,108 @@
+//! Compare calling the tool of a single-tool router through a route calling its handler directly
+//! and through a route boxing the future of the handler again, as routes were built before.
+//!
+//! ```bash
+//! cargo bench -p rmcp --features server,client,macros --bench tool_call
+//! ```
+use criterion::{Criterion, criterion_group, criterion_main};
+use futures::FutureExt;
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
+    handler::server::{
+        router::tool::{ToolRoute, ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+    },
+    model::{CallToolRequestParam, ClientRequest},
+    service::RequestContext,
+    tool, tool_router,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SumRequest {
+    a: i64,
+    b: i64,
+}
+
+#[derive(Debug, Clone)]
+struct Calculator;
+
+#[tool_router]
+impl Calculator {
+    #[tool(description = "Add two integers")]
+    async fn sum(&self, Parameters(SumRequest { a, b }): Parameters<SumRequest>) -> String {
+        (a + b).to_string()
+    }
+}
+
+/// Sends the context of each ping, a context of a request can only be built by a running service
+#[derive(Debug, Clone)]
+struct ContextCapture(mpsc::UnboundedSender<RequestContext<RoleServer>>);
+
+impl ServerHandler for ContextCapture {
+    async fn ping(&self, context: RequestContext<RoleServer>) -> Result<(), ErrorData> {
+        let _ = self.0.send(context);
+        Ok(())
+    }
+}
+
+/// The single-tool router with the route boxing the future of the handler again
+fn double_boxed_router() -> ToolRouter<Calculator> {
+    let route = Calculator::sum_tool_attr();
+    ToolRouter::new().with_route(ToolRoute::new_dyn(route, |context| {
+        context.invoke(Calculator::sum).boxed()
+    }))
+}
+
+fn tool_call(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let context = runtime.block_on(async {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let (server, client) = tokio::join!(
+            ContextCapture(tx).serve(server_transport),
+            ().serve(client_transport)
+        );
+        let (server, client) = (server.expect("server"), client.expect("client"));
+        client
+            .send_request(ClientRequest::PingRequest(Default::default()))
+            .await
+            .expect("ping");
+        let context = rx.recv().await.expect("ping context");
+        // the context stays usable once the services are gone
+        std::mem::forget((server, client));
+        context
+    });
+    let arguments = serde_json::json!({ "a": 1, "b": 2 })
+        .as_object()
+        .cloned()
+        .expect("object arguments");
+
+    let mut group = c.benchmark_group("tool_call");
+    for (name, router) in [
+        ("route", Calculator::tool_router()),
+        ("double_boxed_route", double_boxed_router()),
+    ] {
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| {
+                let call = ToolCallContext::new(
+                    &Calculator,
+                    CallToolRequestParam {
+                        name: "sum".into(),
+                        arguments: Some(arguments.clone()),
+                    },
+                    context.clone(),
+                );
+                let router = &router;
+                async move { router.call(call).await.expect("tool result") }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tool_call);
+criterion_main!(benches);
@@ -1,4 +1,5 @@
 pub mod progress;
+pub mod sampling;
 use crate::{
     error::ErrorData as McpError,
     model::*,
@@ -56,6 +57,13 @@ impl<H: ClientHandler> Service<RoleClient> for H {
             ServerNotification::PromptListChangedNotification(_notification_no_param) => {
                 self.on_prompt_list_changed(context).await
             }
+            ServerNotification::ResourceChunkNotification(notification) => {
+                self.on_resource_chunk(notification.params, context).await
+            }
+            ServerNotification::ResourceChunkEndNotification(notification) => {
+                self.on_resource_chunk_end(notification.params, context)
+                    .await
+            }
         };
         Ok(())
     }
@@ -105,17 +113,17 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
     /// The user's response including action (accept/decline/cancel) and optional data
     ///
     /// # Default Behavior
-    /// The default implementation automatically declines all elicitation requests.
+    /// The default implementation cancels all elicitation requests, as no user was asked.
     /// Real clients should override this to provide user interaction.
     fn create_elicitation(
         &self,
         request: CreateElicitationRequestParam,
         context: RequestContext<RoleClient>,
     ) -> impl Future<Output = Result<CreateElicitationResult, McpError>> + Send + '_ {
-        // Default implementation declines all requests - real clients should override this
+        // Default implementation cancels all requests - real clients should override this
         let _ = (request, context);
         std::future::ready(Ok(CreateElicitationResult {
-            action: ElicitationAction::Decline,
+            action: ElicitationAction::Cancel,
             content: None,
         }))
     }
@@ -148,6 +156,20 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
     ) -> impl Future<Output = ()> + Send + '_ {
         std::future::ready(())
     }
+    fn on_resource_chunk(
+        &self,
+        params: ResourceChunkNotificationParam,
+        context: NotificationContext<RoleClient>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        std::future::ready(())
+    }
+    fn on_resource_chunk_end(
+        &self,
+        params: ResourceChunkEndNotificationParam,
+        context: NotificationContext<RoleClient>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        std::future::ready(())
+    }
     fn on_resource_list_changed(
         &self,
         context: NotificationContext<RoleClient>,
@@ -0,0 +1,282 @@
+use std::future::Future;
+
+use crate::{
+    error::ErrorData as McpError,
+    handler::client::ClientHandler,
+    model::{ClientCapabilities, ClientInfo, CreateMessageRequestParam, CreateMessageResult},
+    service::{RequestContext, RoleClient},
+};
+
+/// Something creating the messages asked by the sampling requests of a server, e.g. a LLM.
+///
+/// It's implemented for the async closures taking a [`CreateMessageRequestParam`].
+pub trait SamplingBackend: Send + Sync + 'static {
+    fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+    ) -> impl Future<Output = Result<CreateMessageResult, McpError>> + Send + '_;
+}
+
+impl<F, Fut> SamplingBackend for F
+where
+    F: Fn(CreateMessageRequestParam) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<CreateMessageResult, McpError>> + Send + 'static,
+{
+    fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+    ) -> impl Future<Output = Result<CreateMessageResult, McpError>> + Send + '_ {
+        (self)(params)
+    }
+}
+
+/// A client forwarding the sampling requests of the server to a [`SamplingBackend`].
+///
+/// ```rust,ignore
+/// let client = ForwardingSamplingHandler::new(|params: CreateMessageRequestParam| async move {
+///     my_llm.complete(params).await
+/// })
+/// .serve(transport)
+/// .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ForwardingSamplingHandler<B> {
+    backend: B,
+    info: ClientInfo,
+}
+
+impl<B: SamplingBackend> ForwardingSamplingHandler<B> {
+    /// Forward to `backend`, the client declaring the sampling capability
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            info: ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_sampling().build(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Use another client info, which should keep the sampling capability
+    pub fn with_info(mut self, info: ClientInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
+
+impl<B: SamplingBackend> ClientHandler for ForwardingSamplingHandler<B> {
+    fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        _context: RequestContext<RoleClient>,
+    ) -> impl Future<Output = Result<CreateMessageResult, McpError>> + Send + '_ {
+        self.backend.create_message(params)
+    }
+
+    fn get_info(&self) -> ClientInfo {
+        self.info.clone()
+    }
+}
+
+#[cfg(feature = "openai")]
+#[cfg_attr(docsrs, doc(cfg(feature = "openai")))]
+pub use openai::OpenAISamplingBackend;
+
+#[cfg(feature = "openai")]
+mod openai {
+    use serde::Deserialize;
+    use serde_json::{Value, json};
+
+    use super::SamplingBackend;
+    use crate::{
+        error::ErrorData as McpError,
+        model::{
+            Content, CreateMessageRequestParam, CreateMessageResult, RawContent, Role,
+            SamplingMessage,
+        },
+    };
+
+    /// A [`SamplingBackend`] calling the `/chat/completions` API of OpenAI, or of any server
+    /// compatible with it.
+    ///
+    /// The model is the default model, unless a hint of the request selects one of the
+    /// [`models`](Self::with_models) it may use. The hints are advisory, a hint names a model or a
+    /// family of models, so it selects the first of these models it is a substring of, e.g.
+    /// `"mini"` selects `"gpt-4o-mini"`. A hint is never sent as a model.
+    #[derive(Debug, Clone)]
+    pub struct OpenAISamplingBackend {
+        client: reqwest::Client,
+        api_key: String,
+        base_url: String,
+        default_model: String,
+        models: Vec<String>,
+    }
+
+    impl OpenAISamplingBackend {
+        pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+        pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+        pub fn new(api_key: impl Into<String>) -> Self {
+            Self {
+                client: reqwest::Client::new(),
+                api_key: api_key.into(),
+                base_url: Self::DEFAULT_BASE_URL.to_string(),
+                default_model: Self::DEFAULT_MODEL.to_string(),
+                models: Vec::new(),
+            }
+        }
+
+        /// The url the `/chat/completions` path is appended to, e.g. `http://localhost:8080/v1`
+        pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+            self.base_url = base_url.into();
+            self
+        }
+
+        /// The model used when no hint of the request selects one of the models
+        pub fn with_default_model(mut self, model: impl Into<String>) -> Self {
+            self.default_model = model.into();
+            self
+        }
+
+        /// The models the hints of the requests may select, in order of preference. None by
+        /// default, the default model is always used.
+        pub fn with_models(mut self, models: impl IntoIterator<Item = impl Into<String>>) -> Self {
+            self.models = models.into_iter().map(Into::into).collect();
+            self
+        }
+
+        /// The model selected by the first hint matching one of the models, or the default one
+        fn model(&self, params: &CreateMessageRequestParam) -> &str {
+            let hints = params
+                .model_preferences
+                .as_ref()
+                .and_then(|preferences| preferences.hints.as_deref())
+                .unwrap_or_default();
+            hints
+                .iter()
+                .filter_map(|hint| hint.name.as_deref())
+                .filter(|hint| !hint.is_empty())
+                .find_map(|hint| self.models.iter().find(|model| model.contains(hint)))
+                .unwrap_or(&self.default_model)
+        }
+
+        pub fn with_client(mut self, client: reqwest::Client) -> Self {
+            self.client = client;
+            self
+        }
+
+        fn request_body(&self, params: &CreateMessageRequestParam) -> Result<Value, McpError> {
+            let model = self.model(params);
+            let mut messages = Vec::with_capacity(params.messages.len() + 1);
+            if let Some(system_prompt) = &params.system_prompt {
+                messages.push(json!({ "role": "system", "content": system_prompt }));
+            }
+            for message in &params.messages {
+                messages.push(chat_message(message)?);
+            }
+            let mut body = json!({
+                "model": model,
+                "messages": messages,
+                "max_tokens": params.max_tokens,
+            });
+            if let Some(temperature) = params.temperature {
+                body["temperature"] = json!(temperature);
+            }
+            if let Some(stop_sequences) = &params.stop_sequences {
+                body["stop"] = json!(stop_sequences);
+            }
+            Ok(body)
+        }
+    }
+
+    fn chat_message(message: &SamplingMessage) -> Result<Value, McpError> {
+        let role = match message.role {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        };
+        let content = match &message.content.raw {
+            RawContent::Text(text) => json!(text.text),
+            RawContent::Image(image) => json!([{
+                "type": "image_url",
+                "image_url": { "url": format!("data:{};base64,{}", image.mime_type, image.data) },
+            }]),
+            _ => {
+                return Err(McpError::invalid_params(
+                    "only text and image messages can be sampled",
+                    None,
+                ));
+            }
+        };
+        Ok(json!({ "role": role, "content": content }))
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ChatCompletion {
+        model: String,
+        choices: Vec<ChatChoice>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ChatChoice {
+        message: ChatMessage,
+        finish_reason: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ChatMessage {
+        content: Option<String>,
+    }
+
+    impl SamplingBackend for OpenAISamplingBackend {
+        async fn create_message(
+            &self,
+            params: CreateMessageRequestParam,
+        ) -> Result<CreateMessageResult, McpError> {
+            let body = self.request_body(&params)?;
+            let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+            let response = self
+                .client
+                .post(url)
+                .bearer_auth(&self.api_key)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            let status = response.status();
+            if !status.is_success() {
+                // the body may hold details of the provider, it's not sent to the server
+                let text = response.text().await.unwrap_or_default();
+                tracing::debug!(%status, body = %text, "chat completion failed");
+                return Err(McpError::internal_error(
+                    format!("chat completion failed with status {status}"),
+                    None,
+                ));
+            }
+            let completion: ChatCompletion = response
+                .json()
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            let choice =
+                completion.choices.into_iter().next().ok_or_else(|| {
+                    McpError::internal_error("chat completion without choice", None)
+                })?;
+            let stop_reason = choice.finish_reason.map(|reason| match reason.as_str() {
+                "stop" => CreateMessageResult::STOP_REASON_END_TURN.to_string(),
+                "length" => CreateMessageResult::STOP_REASON_END_MAX_TOKEN.to_string(),
+                _ => reason,
+            });
+            Ok(CreateMessageResult {
+                model: completion.model,
+                stop_reason,
+                message: SamplingMessage::new(
+                    Role::Assistant,
+                    Content::text(choice.message.content.unwrap_or_default()),
+                ),
+            })
+        }
+    }
+}
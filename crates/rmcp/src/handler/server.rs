@@ -1,13 +1,22 @@
+#[cfg(feature = "resource-streaming")]
+use crate::service::ResponseSent;
 use crate::{
     error::ErrorData as McpError,
     model::*,
     service::{NotificationContext, RequestContext, RoleServer, Service, ServiceRole},
 };
 
+#[cfg(feature = "audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+pub mod audit;
 pub mod common;
+pub mod completion;
 pub mod prompt;
 mod resource;
 pub mod router;
+#[cfg(feature = "resource-streaming")]
+#[cfg_attr(docsrs, doc(cfg(feature = "resource-streaming")))]
+pub mod streaming;
 pub mod tool;
 pub mod wrapper;
 impl<H: ServerHandler> Service<RoleServer> for H {
@@ -48,10 +57,28 @@ impl<H: ServerHandler> Service<RoleServer> for H {
                 .list_resource_templates(request.params, context)
                 .await
                 .map(ServerResult::ListResourceTemplatesResult),
-            ClientRequest::ReadResourceRequest(request) => self
-                .read_resource(request.params, context)
-                .await
-                .map(ServerResult::ReadResourceResult),
+            ClientRequest::ReadResourceRequest(request) => {
+                #[cfg(feature = "resource-streaming")]
+                if context.peer.supports_streaming_resources() {
+                    let peer = context.peer.clone();
+                    let request_id = context.id.clone();
+                    let response_sent = context.extensions.get::<ResponseSent>().cloned();
+                    let streaming::StreamingReadResourceResult { result, streams } = self
+                        .read_resource_streaming(request.params, context)
+                        .await?;
+                    streaming::spawn_resource_streams(
+                        &peer,
+                        request_id,
+                        response_sent,
+                        &result,
+                        streams,
+                    );
+                    return Ok(ServerResult::ReadResourceResult(result));
+                }
+                self.read_resource(request.params, context)
+                    .await
+                    .map(ServerResult::ReadResourceResult)
+            }
             ClientRequest::SubscribeRequest(request) => self
                 .subscribe(request.params, context)
                 .await
@@ -168,6 +195,21 @@ pub trait ServerHandler: Sized + Send + Sync + 'static {
             McpError::method_not_found::<ReadResourceRequestMethod>(),
         ))
     }
+    /// Called instead of [`ServerHandler::read_resource`] when the client declared the
+    /// `streamingResources` capability, so the result may contain
+    /// [`ResourceContents::StreamingResource`] entries, whose chunks are sent after the response.
+    ///
+    /// Defaults to [`ServerHandler::read_resource`].
+    #[cfg(feature = "resource-streaming")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resource-streaming")))]
+    fn read_resource_streaming(
+        &self,
+        request: ReadResourceRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> impl Future<Output = Result<streaming::StreamingReadResourceResult, McpError>> + Send + '_
+    {
+        async move { self.read_resource(request, context).await.map(Into::into) }
+    }
     fn subscribe(
         &self,
         request: SubscribeRequestParam,
@@ -224,6 +266,38 @@ pub trait ServerHandler: Sized + Send + Sync + 'static {
     ) -> impl Future<Output = ()> + Send + '_ {
         std::future::ready(())
     }
+    /// The current roots of the client, when they are listed after a change by
+    /// [`ServerHandler::list_changed_roots`]
+    fn on_roots_changed(
+        &self,
+        roots: ListRootsResult,
+        context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        std::future::ready(())
+    }
+    /// List the roots of the client and pass them to [`ServerHandler::on_roots_changed`].
+    ///
+    /// The roots aren't listed unless a server opts in, by calling this from
+    /// [`ServerHandler::on_roots_list_changed`]:
+    /// ```rust,ignore
+    /// fn on_roots_list_changed(
+    ///     &self,
+    ///     context: NotificationContext<RoleServer>,
+    /// ) -> impl Future<Output = ()> + Send + '_ {
+    ///     self.list_changed_roots(context)
+    /// }
+    /// ```
+    fn list_changed_roots(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        async move {
+            match context.peer.list_roots().await {
+                Ok(roots) => self.on_roots_changed(roots, context).await,
+                Err(error) => tracing::warn!(%error, "failed to list the changed roots"),
+            }
+        }
+    }
 
     fn get_info(&self) -> ServerInfo {
         ServerInfo::default()
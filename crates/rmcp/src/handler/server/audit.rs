@@ -0,0 +1,260 @@
+//! An audit trail of the requests handled by a server, see [`AuditService`].
+use std::{fs::File, future::Future, io::Write, path::Path, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    RoleServer, Service,
+    model::{ClientRequest, RequestId, ServerInfo, ServerResult},
+    service::{NotificationContext, RequestContext, ServiceRole},
+};
+
+/// A request handled by the server.
+///
+/// The arguments are not kept, only their SHA-256 hash, so the trail can prove which arguments a
+/// request had without storing them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditRecord {
+    /// When the request was received
+    pub timestamp: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub method: String,
+    /// The subject of the [`AuthContext`](crate::transport::streamable_http_server::auth::AuthContext)
+    /// of the request, if authenticated
+    pub principal: Option<String>,
+    /// The SHA-256 hash of the json params of the request
+    #[serde(serialize_with = "serialize_hex")]
+    pub arguments_hash: [u8; 32],
+    /// Whether the request succeeded, a tool call failing with `is_error` did not
+    pub success: bool,
+}
+
+fn serialize_hex<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    serializer.serialize_str(&hex)
+}
+
+/// Where the audit records are sent, e.g. a [`FileAuditSink`].
+///
+/// Any `Fn(AuditRecord) -> impl Future<Output = ()>` is a sink.
+pub trait AuditSink: Send + Sync + 'static {
+    fn record(&self, record: AuditRecord) -> BoxFuture<'_, ()>;
+}
+
+impl<F, Fut> AuditSink for F
+where
+    F: Fn(AuditRecord) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn record(&self, record: AuditRecord) -> BoxFuture<'_, ()> {
+        Box::pin(self(record))
+    }
+}
+
+/// Append the records to a file, one json object per line.
+///
+/// The file is opened in append mode, so every line is written at the end of the file at once,
+/// even if other processes append to it too.
+#[derive(Debug, Clone)]
+pub struct FileAuditSink {
+    file: Arc<File>,
+}
+
+impl FileAuditSink {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            file: Arc::new(file),
+        })
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&self, record: AuditRecord) -> BoxFuture<'_, ()> {
+        let file = self.file.clone();
+        Box::pin(async move {
+            let mut line = match serde_json::to_vec(&record) {
+                Ok(line) => line,
+                Err(error) => {
+                    tracing::error!(%error, "failed to serialize audit record");
+                    return;
+                }
+            };
+            line.push(b'\n');
+            let written = tokio::task::spawn_blocking(move || {
+                (&*file).write_all(&line).and_then(|_| (&*file).flush())
+            })
+            .await;
+            match written {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => tracing::error!(%error, "failed to write audit record"),
+                Err(error) => tracing::error!(%error, "failed to write audit record"),
+            }
+        })
+    }
+}
+
+/// Send an [`AuditRecord`] for every request handled by the inner service, including the
+/// failed ones.
+///
+/// The record is sent once the request is handled, and the response waits for the sink, so a
+/// request is never answered without being recorded. A request whose handling is dropped before
+/// it completes, e.g. timed out by an outer [`TimeoutService`](crate::service::TimeoutService)
+/// or aborted by the shutdown of the server, is recorded as failed in the background.
+///
+/// ```rust,ignore
+/// let server = AuditService::new(Counter::new(), FileAuditSink::open("audit.ndjson")?);
+/// server.serve(stdio()).await?;
+/// ```
+pub struct AuditService<S> {
+    inner: S,
+    sink: Arc<dyn AuditSink>,
+}
+
+impl<S> std::fmt::Debug for AuditService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditService").finish_non_exhaustive()
+    }
+}
+
+impl<S> AuditService<S> {
+    pub fn new(inner: S, sink: impl AuditSink) -> Self {
+        Self {
+            inner,
+            sink: Arc::new(sink),
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+/// The tower [`Layer`](tower_layer::Layer) of [`AuditService`], the services it wraps share the
+/// same sink.
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+#[derive(Clone)]
+pub struct AuditLayer {
+    sink: Arc<dyn AuditSink>,
+}
+
+#[cfg(feature = "tower")]
+impl std::fmt::Debug for AuditLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLayer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tower")]
+impl AuditLayer {
+    pub fn new(sink: impl AuditSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+        }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> tower_layer::Layer<S> for AuditLayer {
+    type Service = AuditService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuditService {
+            inner,
+            sink: self.sink.clone(),
+        }
+    }
+}
+
+/// Send the record of a request as failed if it's dropped before the request is handled.
+struct PendingRecord {
+    sink: Arc<dyn AuditSink>,
+    record: Option<AuditRecord>,
+}
+
+impl Drop for PendingRecord {
+    fn drop(&mut self) {
+        let Some(record) = self.record.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::error!(request_id = %record.request_id, "no runtime to send the audit record");
+            return;
+        };
+        let sink = self.sink.clone();
+        runtime.spawn(async move { sink.record(record).await });
+    }
+}
+
+fn hash_params(request: &ClientRequest) -> [u8; 32] {
+    let params = serde_json::to_value(request)
+        .ok()
+        .and_then(|mut request| request.get_mut("params").map(serde_json::Value::take))
+        .unwrap_or_default();
+    Sha256::digest(params.to_string().as_bytes()).into()
+}
+
+#[cfg_attr(
+    not(feature = "transport-streamable-http-server"),
+    allow(unused_variables)
+)]
+fn principal(context: &RequestContext<RoleServer>) -> Option<String> {
+    #[cfg(feature = "transport-streamable-http-server")]
+    if let Some(auth) = context
+        .extensions
+        .get::<crate::transport::streamable_http_server::auth::AuthContext>()
+    {
+        return auth.subject.clone();
+    }
+    None
+}
+
+impl<S: Service<RoleServer>> Service<RoleServer> for AuditService<S> {
+    async fn handle_request(
+        &self,
+        request: <RoleServer as ServiceRole>::PeerReq,
+        context: RequestContext<RoleServer>,
+    ) -> Result<<RoleServer as ServiceRole>::Resp, crate::ErrorData> {
+        let mut pending = PendingRecord {
+            sink: self.sink.clone(),
+            record: Some(AuditRecord {
+                timestamp: Utc::now(),
+                request_id: context.id.clone(),
+                method: request.method().to_owned(),
+                principal: principal(&context),
+                arguments_hash: hash_params(&request),
+                success: false,
+            }),
+        };
+        let result = self.inner.handle_request(request, context).await;
+        if let Some(mut record) = pending.record.take() {
+            record.success = match &result {
+                // a tool reports its failures in its result
+                Ok(ServerResult::CallToolResult(result)) => result.is_error != Some(true),
+                Ok(_) => true,
+                Err(_) => false,
+            };
+            self.sink.record(record).await;
+        }
+        result
+    }
+
+    fn handle_notification(
+        &self,
+        notification: <RoleServer as ServiceRole>::PeerNot,
+        context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = Result<(), crate::ErrorData>> + Send + '_ {
+        self.inner.handle_notification(notification, context)
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.inner.get_info()
+    }
+}
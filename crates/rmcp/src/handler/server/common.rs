@@ -27,28 +27,26 @@ pub fn schema_for_type<T: JsonSchema>() -> JsonObject {
     }
 }
 
-/// Call [`schema_for_type`] with a cache
+/// Call [`schema_for_type`] with a cache shared by all threads, so the schema of a type is generated
+/// once per process
 pub fn cached_schema_for_type<T: JsonSchema + std::any::Any>() -> Arc<JsonObject> {
-    thread_local! {
-        static CACHE_FOR_TYPE: std::sync::RwLock<HashMap<TypeId, Arc<JsonObject>>> = Default::default();
-    };
-    CACHE_FOR_TYPE.with(|cache| {
-        if let Some(x) = cache
-            .read()
-            .expect("schema cache lock poisoned")
-            .get(&TypeId::of::<T>())
-        {
-            x.clone()
-        } else {
-            let schema = schema_for_type::<T>();
-            let schema = Arc::new(schema);
-            cache
-                .write()
-                .expect("schema cache lock poisoned")
-                .insert(TypeId::of::<T>(), schema.clone());
-            schema
-        }
-    })
+    static CACHE_FOR_TYPE: std::sync::OnceLock<
+        std::sync::RwLock<HashMap<TypeId, Arc<JsonObject>>>,
+    > = std::sync::OnceLock::new();
+    let cache = CACHE_FOR_TYPE.get_or_init(Default::default);
+    if let Some(x) = cache
+        .read()
+        .expect("schema cache lock poisoned")
+        .get(&TypeId::of::<T>())
+    {
+        return x.clone();
+    }
+    cache
+        .write()
+        .expect("schema cache lock poisoned")
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Arc::new(schema_for_type::<T>()))
+        .clone()
 }
 
 /// Trait for extracting parts from a context, unifying tool and prompt extraction
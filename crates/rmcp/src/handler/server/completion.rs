@@ -0,0 +1,489 @@
+//! Ranking of completion candidates for [`ServerHandler::complete`](crate::ServerHandler::complete)
+//! implementations.
+//!
+//! ```rust
+//! use rmcp::{handler::server::completion::CompletionRanker, model::CompletionInfo};
+//!
+//! let ranked = CompletionRanker::new().rank("py", &["Rust", "Python", "Pyre", "Java"]);
+//! let completion = CompletionInfo::from_ranked(ranked, 10);
+//! assert_eq!(completion.values, vec!["Pyre", "Python"]);
+//! ```
+//!
+//! The completions which only depend on their request can be cached with
+//! [`CachedCompletionService`], or its tower layer `CachedCompletionLayer`.
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    RoleServer, Service,
+    model::{ClientRequest, CompleteRequestParam, Reference, ServerInfo, ServerResult},
+    service::{NotificationContext, RequestContext, ServiceRole},
+};
+
+/// A completion candidate with its relevance score, higher is better
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedCandidate {
+    pub value: String,
+    /// A score in `0.0..=1.0`
+    pub score: f64,
+}
+
+/// Ranks completion candidates against a partial input.
+///
+/// The comparison is case insensitive. Candidates are scored by the kind of match, in this
+/// order: exact, prefix, substring, acronym (`"js"` for `"JavaScript"`), subsequence (`"rs"` for
+/// `"Rust"`), and at last similar enough by Levenshtein distance to catch typos.
+/// Within a kind of match, the candidates closer to the input by edit distance rank first.
+#[derive(Debug, Clone)]
+pub struct CompletionRanker {
+    min_similarity: f64,
+}
+
+impl Default for CompletionRanker {
+    fn default() -> Self {
+        Self {
+            min_similarity: Self::DEFAULT_MIN_SIMILARITY,
+        }
+    }
+}
+
+impl CompletionRanker {
+    /// Default similarity required for a candidate matching by edit distance only
+    pub const DEFAULT_MIN_SIMILARITY: f64 = 0.6;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the similarity, in `0.0..=1.0`, required for a candidate matching by edit distance only
+    pub fn min_similarity(mut self, min_similarity: f64) -> Self {
+        self.min_similarity = min_similarity;
+        self
+    }
+
+    /// Rank the candidates matching the input, best first.
+    ///
+    /// An empty input matches every candidate with a score of zero, keeping their order.
+    pub fn rank(&self, input: &str, candidates: &[&str]) -> Vec<RankedCandidate> {
+        if input.is_empty() {
+            return candidates
+                .iter()
+                .map(|candidate| RankedCandidate {
+                    value: candidate.to_string(),
+                    score: 0.0,
+                })
+                .collect();
+        }
+        let input_lower = input.to_lowercase();
+        let mut ranked: Vec<_> = candidates
+            .iter()
+            .filter_map(|candidate| {
+                let score = self.score(&input_lower, candidate)?;
+                Some(RankedCandidate {
+                    value: candidate.to_string(),
+                    score,
+                })
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.value.cmp(&b.value)));
+        ranked
+    }
+
+    fn score(&self, input_lower: &str, candidate: &str) -> Option<f64> {
+        let candidate_lower = candidate.to_lowercase();
+        let similarity = similarity(input_lower, &candidate_lower);
+        let base = if candidate_lower == input_lower {
+            return Some(1.0);
+        } else if candidate_lower.starts_with(input_lower) {
+            0.9
+        } else if candidate_lower.contains(input_lower) {
+            0.8
+        } else if is_acronym_match(input_lower, candidate) {
+            0.7
+        } else if is_subsequence_match(input_lower, &candidate_lower) {
+            0.6
+        } else if similarity >= self.min_similarity {
+            return Some(0.4 * similarity);
+        } else {
+            return None;
+        };
+        // keep the bonus below the gap between two kinds of match
+        Some(base + 0.09 * similarity)
+    }
+}
+
+/// Similarity in `0.0..=1.0` derived from the Levenshtein distance
+fn similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / max_len as f64
+}
+
+/// Levenshtein distance between two strings, counted in chars
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Check if the input matches the first letters of the words, or the uppercase letters of a
+/// camelCase candidate
+fn is_acronym_match(input_lower: &str, candidate: &str) -> bool {
+    let input_chars: Vec<char> = input_lower.chars().collect();
+    let words: Vec<&str> = candidate.split_whitespace().collect();
+    let mut first_chars: Vec<char> = if words.len() > 1 {
+        words
+            .iter()
+            .filter_map(|word| word.chars().next())
+            .flat_map(char::to_lowercase)
+            .collect()
+    } else {
+        candidate
+            .chars()
+            .filter(|c| c.is_uppercase())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    if first_chars.is_empty() {
+        first_chars.extend(
+            candidate
+                .chars()
+                .next()
+                .into_iter()
+                .flat_map(char::to_lowercase),
+        );
+    }
+    // a two letters input may also be the start of a single word, e.g. "go" for "Go"
+    if input_chars.len() == 2 && first_chars.len() == 1 {
+        first_chars = candidate
+            .chars()
+            .take(2)
+            .flat_map(char::to_lowercase)
+            .collect();
+    }
+    input_chars == first_chars
+}
+
+/// Check if the input is a subsequence of the candidate, e.g. "rs" in "rust"
+fn is_subsequence_match(input_lower: &str, candidate_lower: &str) -> bool {
+    let mut candidate_chars = candidate_lower.chars();
+    input_lower
+        .chars()
+        .all(|input_char| candidate_chars.any(|c| c == input_char))
+}
+
+/// The cache key of a completion, everything the completion may depend on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CompletionKey {
+    reference_type: &'static str,
+    /// The name or uri of the reference
+    reference: String,
+    argument: String,
+    value: String,
+    /// The arguments of the context, sorted by name
+    context: Vec<(String, String)>,
+    /// The authenticated client, whose completions may be filtered by an acl
+    principal: Option<String>,
+}
+
+fn reference_name(reference: &Reference) -> &str {
+    match reference {
+        Reference::Prompt(prompt) => &prompt.name,
+        Reference::Resource(resource) => &resource.uri,
+        Reference::Tool(tool) => &tool.name,
+    }
+}
+
+impl CompletionKey {
+    #[cfg_attr(
+        not(feature = "transport-streamable-http-server"),
+        allow(unused_variables)
+    )]
+    fn new(request: &CompleteRequestParam, context: &RequestContext<RoleServer>) -> Self {
+        let argument = request
+            .tool_argument_name()
+            .unwrap_or(&request.argument.name);
+        let mut arguments: Vec<(String, String)> = request
+            .context
+            .as_ref()
+            .and_then(|context| context.arguments.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        arguments.sort();
+        #[cfg(feature = "transport-streamable-http-server")]
+        let principal = context
+            .extensions
+            .get::<crate::transport::streamable_http_server::auth::AuthContext>()
+            .map(|auth| {
+                serde_json::json!({
+                    "subject": auth.subject,
+                    "scopes": auth.scopes,
+                    "claims": auth.claims,
+                })
+                .to_string()
+            });
+        #[cfg(not(feature = "transport-streamable-http-server"))]
+        let principal = None;
+        Self {
+            reference_type: request.r#ref.reference_type(),
+            reference: reference_name(&request.r#ref).to_owned(),
+            argument: argument.to_owned(),
+            value: request.argument.value.clone(),
+            context: arguments,
+            principal,
+        }
+    }
+}
+
+type CompletionCache = HashMap<CompletionKey, (Instant, ServerResult)>;
+
+/// Cache the completions of the inner service, for the completions which only depend on their
+/// request, like the values of an enum ranked by the value being completed.
+///
+/// A completion is cached by reference, argument, value, context and authenticated client, so
+/// a client never gets the completions of another request. At most
+/// [`capacity`](Self::with_capacity) completions are kept, the oldest ones are evicted first.
+/// The other requests are passed through.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use rmcp::{ServerHandler, handler::server::completion::CachedCompletionService};
+/// # struct Languages;
+/// # impl ServerHandler for Languages {}
+/// let server = CachedCompletionService::new(Languages).with_ttl(Duration::from_secs(300));
+/// // server.serve(transport).await?;
+/// ```
+pub struct CachedCompletionService<S> {
+    inner: S,
+    ttl: Duration,
+    capacity: usize,
+    cache: Arc<Mutex<CompletionCache>>,
+}
+
+impl<S> std::fmt::Debug for CachedCompletionService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCompletionService")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> CachedCompletionService<S> {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            ttl: Self::DEFAULT_TTL,
+            capacity: Self::DEFAULT_CAPACITY,
+            cache: Default::default(),
+        }
+    }
+
+    /// How long a completion is cached, 60 seconds by default
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Keep at most `capacity` completions, 1024 by default
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Forget the completions of every argument of `reference`, e.g. when its data changed
+    pub fn invalidate(&self, reference: &Reference) {
+        invalidate(&self.cache, reference);
+    }
+
+    /// Forget every completion
+    pub fn invalidate_all(&self) {
+        lock_cache(&self.cache).clear();
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, CompletionCache> {
+        lock_cache(&self.cache)
+    }
+
+    fn cached(&self, key: &CompletionKey) -> Option<ServerResult> {
+        let mut cache = self.lock_cache();
+        match cache.get(key) {
+            Some((cached_at, result)) if cached_at.elapsed() < self.ttl => Some(result.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: CompletionKey, result: ServerResult) {
+        let mut cache = self.lock_cache();
+        if cache.len() >= self.capacity && !cache.contains_key(&key) {
+            cache.retain(|_, (cached_at, _)| cached_at.elapsed() < self.ttl);
+            if cache.len() >= self.capacity {
+                let oldest = cache
+                    .iter()
+                    .min_by_key(|(_, (cached_at, _))| *cached_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    cache.remove(&oldest);
+                }
+            }
+        }
+        cache.insert(key, (Instant::now(), result));
+    }
+}
+
+fn lock_cache(cache: &Mutex<CompletionCache>) -> std::sync::MutexGuard<'_, CompletionCache> {
+    cache.lock().expect("completion cache lock poisoned")
+}
+
+fn invalidate(cache: &Mutex<CompletionCache>, reference: &Reference) {
+    let reference_type = reference.reference_type();
+    let name = reference_name(reference);
+    lock_cache(cache)
+        .retain(|key, _| !(key.reference_type == reference_type && key.reference == name));
+}
+
+/// The tower [`Layer`](tower_layer::Layer) of [`CachedCompletionService`], the services it wraps
+/// share the same cache.
+///
+/// A server creating a service per session, like the streamable http server, keeps the cached
+/// completions across the sessions, and can still flush them from the layer:
+/// ```rust,ignore
+/// let cache = CachedCompletionLayer::new().with_ttl(Duration::from_secs(300));
+/// let service = StreamableHttpService::new(
+///     { let cache = cache.clone(); move || Ok(cache.layer(Languages::new())) },
+///     Default::default(),
+///     Default::default(),
+/// );
+/// // once the languages changed
+/// cache.invalidate(&Reference::for_prompt("code_review"));
+/// ```
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+#[derive(Clone)]
+pub struct CachedCompletionLayer {
+    ttl: Duration,
+    capacity: usize,
+    cache: Arc<Mutex<CompletionCache>>,
+}
+
+#[cfg(feature = "tower")]
+impl std::fmt::Debug for CachedCompletionLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCompletionLayer")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tower")]
+impl Default for CachedCompletionLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tower")]
+impl CachedCompletionLayer {
+    pub fn new() -> Self {
+        Self {
+            ttl: CachedCompletionService::<()>::DEFAULT_TTL,
+            capacity: CachedCompletionService::<()>::DEFAULT_CAPACITY,
+            cache: Default::default(),
+        }
+    }
+
+    /// How long a completion is cached, 60 seconds by default
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Keep at most `capacity` completions, 1024 by default
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Forget the completions of every argument of `reference` in the services of the layer
+    pub fn invalidate(&self, reference: &Reference) {
+        invalidate(&self.cache, reference);
+    }
+
+    /// Forget every completion of the services of the layer
+    pub fn invalidate_all(&self) {
+        lock_cache(&self.cache).clear();
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> tower_layer::Layer<S> for CachedCompletionLayer {
+    type Service = CachedCompletionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CachedCompletionService {
+            inner,
+            ttl: self.ttl,
+            capacity: self.capacity,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<S: Service<RoleServer>> Service<RoleServer> for CachedCompletionService<S> {
+    async fn handle_request(
+        &self,
+        request: <RoleServer as ServiceRole>::PeerReq,
+        context: RequestContext<RoleServer>,
+    ) -> Result<<RoleServer as ServiceRole>::Resp, crate::ErrorData> {
+        let ClientRequest::CompleteRequest(complete) = &request else {
+            return self.inner.handle_request(request, context).await;
+        };
+        let key = CompletionKey::new(&complete.params, &context);
+        if let Some(result) = self.cached(&key) {
+            return Ok(result);
+        }
+        let result = self.inner.handle_request(request, context).await?;
+        self.insert(key, result.clone());
+        Ok(result)
+    }
+
+    fn handle_notification(
+        &self,
+        notification: <RoleServer as ServiceRole>::PeerNot,
+        context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = Result<(), crate::ErrorData>> + Send + '_ {
+        self.inner.handle_notification(notification, context)
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.inner.get_info()
+    }
+}
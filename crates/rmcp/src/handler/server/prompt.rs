@@ -348,11 +348,22 @@ pub fn cached_arguments_from_schema<T: schemars::JsonSchema + std::any::Any>()
                 .and_then(|d| d.as_str())
                 .map(|s| s.to_string());
 
+            let default_value = prop_schema
+                .get("default")
+                .and_then(|d| d.as_str())
+                .map(|s| s.to_string());
+            let validation_pattern = prop_schema
+                .get("pattern")
+                .and_then(|p| p.as_str())
+                .map(|s| s.to_string());
+
             arguments.push(crate::model::PromptArgument {
                 name: name.clone(),
                 title: None,
                 description,
                 required: Some(required.contains(name.as_str())),
+                default_value,
+                validation_pattern,
             });
         }
 
@@ -6,17 +6,36 @@ use tool::{IntoToolRoute, ToolRoute};
 use super::ServerHandler;
 use crate::{
     RoleServer, Service,
-    model::{ClientRequest, ListPromptsResult, ListToolsResult, ServerResult},
+    model::{
+        ClientRequest, CompleteResult, CompletionInfo, ListPromptsResult, ListToolsResult,
+        PromptsCapability, ServerCapabilities, ServerResult, ToolsCapability,
+    },
     service::NotificationContext,
 };
 
+pub mod fs_watch;
+#[cfg(feature = "pagination")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pagination")))]
+pub mod pagination;
 pub mod prompt;
+#[cfg(feature = "uri-templates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uri-templates")))]
+pub mod resource;
 pub mod tool;
 
+#[cfg(feature = "pagination")]
+pub use pagination::ServerPaginationState;
+
 pub struct Router<S> {
     pub tool_router: tool::ToolRouter<S>,
     pub prompt_router: prompt::PromptRouter<S>,
     pub service: Arc<S>,
+    /// The largest number of tools per page of `tools/list`, a client may request smaller pages
+    /// in the `_meta` of the request. All the tools are listed at once if `None`
+    #[cfg(feature = "pagination")]
+    pub tool_page_size: Option<usize>,
+    #[cfg(feature = "pagination")]
+    tool_pages: ServerPaginationState<crate::model::Tool>,
 }
 
 impl<S> Router<S>
@@ -28,9 +47,21 @@ where
             tool_router: tool::ToolRouter::new(),
             prompt_router: prompt::PromptRouter::new(),
             service: Arc::new(service),
+            #[cfg(feature = "pagination")]
+            tool_page_size: None,
+            #[cfg(feature = "pagination")]
+            tool_pages: ServerPaginationState::default(),
         }
     }
 
+    /// List the tools by pages of `page_size`, see [`ServerPaginationState`]
+    #[cfg(feature = "pagination")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pagination")))]
+    pub fn with_tool_page_size(mut self, page_size: usize) -> Self {
+        self.tool_page_size = Some(page_size);
+        self
+    }
+
     pub fn with_tool<R, A>(mut self, route: R) -> Self
     where
         R: IntoToolRoute<S, A>,
@@ -60,6 +91,25 @@ where
         }
         self
     }
+
+    /// The capabilities of the service, plus the `tools` and `prompts` ones if the router has
+    /// routes for them, so they don't have to be declared by hand.
+    ///
+    /// The capabilities declared by the service are kept as they are, e.g. with `listChanged`.
+    pub fn capabilities(&self) -> ServerCapabilities {
+        let mut capabilities = self.service.get_info().capabilities;
+        if !self.tool_router.map.is_empty() {
+            capabilities
+                .tools
+                .get_or_insert_with(ToolsCapability::default);
+        }
+        if !self.prompt_router.map.is_empty() {
+            capabilities
+                .prompts
+                .get_or_insert_with(PromptsCapability::default);
+        }
+        capabilities
+    }
 }
 
 impl<S> Service<RoleServer> for Router<S>
@@ -98,12 +148,34 @@ where
                         .await
                 }
             }
-            ClientRequest::ListToolsRequest(_) => {
-                let tools = self.tool_router.list_all();
-                Ok(ServerResult::ListToolsResult(ListToolsResult {
-                    tools,
-                    next_cursor: None,
-                }))
+            #[cfg_attr(not(feature = "pagination"), allow(unused_variables))]
+            ClientRequest::ListToolsRequest(request) => {
+                #[cfg(feature = "pagination")]
+                if let Some(max_page_size) = self.tool_page_size {
+                    let params = request.params.unwrap_or_default();
+                    // a client can't request larger pages than the server's
+                    let page_size = context
+                        .meta
+                        .get_page_size()
+                        .map_or(max_page_size, |page_size| {
+                            (page_size as usize).min(max_page_size)
+                        });
+                    // the snapshot holds all the tools, the allowed ones are filtered by page
+                    let (tools, next_cursor) = self.tool_pages.page(
+                        self.tool_router.list_all(),
+                        params.cursor.as_ref(),
+                        page_size,
+                        |tool| self.tool_router.is_allowed(&tool.name, &context.extensions),
+                    )?;
+                    return Ok(ServerResult::ListToolsResult(ListToolsResult {
+                        tools,
+                        next_cursor,
+                    }));
+                }
+                let tools = self.tool_router.list_allowed(&context.extensions);
+                Ok(ServerResult::ListToolsResult(
+                    ListToolsResult::with_all_items(tools),
+                ))
             }
             ClientRequest::GetPromptRequest(request) => {
                 if self.prompt_router.has_route(request.params.name.as_ref()) {
@@ -128,11 +200,50 @@ where
                     next_cursor: None,
                 }))
             }
+            ClientRequest::CompleteRequest(request) => {
+                // checked first, like by `tools/call`, so a denied client can't probe which tools
+                // exist nor the values of their schemas
+                if let Some(tool_name) = request.params.r#ref.as_tool_name() {
+                    if !self.tool_router.is_allowed(tool_name, &context.extensions) {
+                        return Err(crate::ErrorData::forbidden("Forbidden", None));
+                    }
+                }
+                let Some(completion) = self.tool_router.complete(&request.params) else {
+                    return self
+                        .service
+                        .handle_request(ClientRequest::CompleteRequest(request), context)
+                        .await;
+                };
+                // the service may complete the argument with values unknown to the schema, if it
+                // fails the completion of the schema is still valid
+                let partial = request.params.argument.value.clone();
+                let completion = match self
+                    .service
+                    .handle_request(ClientRequest::CompleteRequest(request), context)
+                    .await
+                {
+                    Ok(ServerResult::CompleteResult(result))
+                        if !result.completion.values.is_empty() =>
+                    {
+                        let mut merged = CompletionInfo::merge(completion, result.completion);
+                        merged.sort_by_prefix(&partial);
+                        merged
+                    }
+                    Err(error) => {
+                        tracing::debug!(%error, "falling back to the completion of the tool schema");
+                        completion
+                    }
+                    _ => completion,
+                };
+                Ok(ServerResult::CompleteResult(CompleteResult { completion }))
+            }
             rest => self.service.handle_request(rest, context).await,
         }
     }
 
     fn get_info(&self) -> <RoleServer as crate::service::ServiceRole>::Info {
-        self.service.get_info()
+        let mut info = self.service.get_info();
+        info.capabilities = self.capabilities();
+        info
     }
 }
@@ -0,0 +1,230 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+use super::tool::ToolRouter;
+use crate::{
+    RoleServer,
+    handler::server::tool::ToolCallContext,
+    model::{CallToolResult, Extensions, Tool},
+    service::Peer,
+};
+
+/// The modification times and sizes of the definition files, to detect their changes
+type Snapshot = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// A [`ToolRouter`] whose tool definitions are reloaded from a directory when they change.
+///
+/// Every `.json` file of the directory holds the definition of a tool of the router, as in a
+/// `tools/list` result, i.e. its `name`, `description`, `inputSchema` and so on. The definition
+/// of a file replaces the one of the route with the same name, while the route keeps its
+/// handler, so descriptions and schemas can be tuned without restarting the server. A file
+/// naming a tool without route is skipped, and the routes without file keep their definition.
+///
+/// The directory is polled, and the peer set by [`FsWatchedToolRouter::set_peer`] is sent a
+/// `notifications/tools/list_changed` after each change.
+///
+/// ```rust,ignore
+/// let tools = FsWatchedToolRouter::new(Self::tool_router(), "./tools");
+/// // polled as long as the guard is kept
+/// let _watching = tools.watch();
+/// ```
+pub struct FsWatchedToolRouter<S> {
+    base: ToolRouter<S>,
+    dir: PathBuf,
+    poll_interval: Duration,
+    current: Arc<RwLock<ToolRouter<S>>>,
+    snapshot: Arc<Mutex<Snapshot>>,
+    peer: Arc<Mutex<Option<Peer<RoleServer>>>>,
+}
+
+impl<S> Clone for FsWatchedToolRouter<S> {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            dir: self.dir.clone(),
+            poll_interval: self.poll_interval,
+            current: self.current.clone(),
+            snapshot: self.snapshot.clone(),
+            peer: self.peer.clone(),
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for FsWatchedToolRouter<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FsWatchedToolRouter")
+            .field("dir", &self.dir)
+            .field("poll_interval", &self.poll_interval)
+            .finish()
+    }
+}
+
+impl<S> FsWatchedToolRouter<S>
+where
+    S: Send + Sync + 'static,
+{
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Load the definitions of the directory over the routes of `base`.
+    ///
+    /// A directory failing to load is logged, and the definitions of `base` are used until it
+    /// loads.
+    pub fn new(base: ToolRouter<S>, dir: impl Into<PathBuf>) -> Self {
+        let router = Self {
+            current: Arc::new(RwLock::new(base.clone())),
+            base,
+            dir: dir.into(),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            snapshot: Default::default(),
+            peer: Default::default(),
+        };
+        if let Err(error) = router.load() {
+            tracing::warn!(dir = %router.dir.display(), %error, "failed to load tool definitions");
+        }
+        router
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_peer(self, peer: Peer<RoleServer>) -> Self {
+        self.set_peer(peer);
+        self
+    }
+
+    /// Set the peer notified of the changes, e.g. once the server is running
+    pub fn set_peer(&self, peer: Peer<RoleServer>) {
+        *self.peer.lock().expect("peer poisoned") = Some(peer);
+    }
+
+    /// The router with the definitions loaded last
+    pub fn router(&self) -> ToolRouter<S> {
+        self.current.read().expect("router poisoned").clone()
+    }
+
+    pub async fn call(
+        &self,
+        context: ToolCallContext<'_, S>,
+    ) -> Result<CallToolResult, crate::ErrorData> {
+        let router = self.router();
+        router.call(context).await
+    }
+
+    pub fn list_all(&self) -> Vec<Tool> {
+        self.current.read().expect("router poisoned").list_all()
+    }
+
+    /// See [`ToolRouter::list_allowed`]
+    pub fn list_allowed(&self, extensions: &Extensions) -> Vec<Tool> {
+        self.current
+            .read()
+            .expect("router poisoned")
+            .list_allowed(extensions)
+    }
+
+    /// Reload the definitions if the files changed, and notify the peer.
+    ///
+    /// Returns whether the files changed.
+    pub async fn reload(&self) -> std::io::Result<bool> {
+        let this = self.clone();
+        let changed = tokio::task::spawn_blocking(move || this.load())
+            .await
+            .map_err(std::io::Error::other)??;
+        if changed {
+            let peer = self.peer.lock().expect("peer poisoned").clone();
+            if let Some(peer) = peer {
+                if let Err(error) = peer.notify_tool_list_changed().await {
+                    tracing::warn!(%error, "failed to notify the tool list change");
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Poll the directory in a background task, until the returned guard is dropped
+    #[must_use = "the directory is no longer polled once the guard is dropped"]
+    pub fn watch(&self) -> DropGuard {
+        let ct = CancellationToken::new();
+        let this = self.clone();
+        tokio::spawn({
+            let ct = ct.clone();
+            async move {
+                let mut interval = tokio::time::interval(this.poll_interval);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = ct.cancelled() => break,
+                        _ = interval.tick() => {}
+                    }
+                    if let Err(error) = this.reload().await {
+                        let dir = this.dir.display();
+                        tracing::warn!(%dir, %error, "failed to reload tool definitions");
+                    }
+                }
+            }
+        });
+        ct.drop_guard()
+    }
+
+    /// Load the definitions if the files changed since the last load
+    fn load(&self) -> std::io::Result<bool> {
+        let snapshot = snapshot(&self.dir)?;
+        if *self.snapshot.lock().expect("snapshot poisoned") == snapshot {
+            return Ok(false);
+        }
+        let mut router = self.base.clone();
+        for (path, _, _) in &snapshot {
+            let tool: Tool = match std::fs::read(path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_slice(&content).map_err(|e| e.to_string()))
+            {
+                Ok(tool) => tool,
+                Err(error) => {
+                    // likely written right now, it's read again on the next change
+                    tracing::warn!(path = %path.display(), %error, "invalid tool definition");
+                    continue;
+                }
+            };
+            match router.map.get_mut(&tool.name) {
+                Some(route) => route.set_attr(tool),
+                None => {
+                    let path = path.display();
+                    tracing::warn!(%path, name = %tool.name, "no route for the tool definition")
+                }
+            }
+        }
+        let mut current = self.current.write().expect("router poisoned");
+        let (previous, next) = (current.list_all(), router.list_all());
+        for (old, new) in Tool::diff(&previous, &next).changed {
+            let diff = Tool::display_diff(old, new);
+            tracing::debug!(tool = %new, %diff, "tool definition reloaded");
+        }
+        *current = router;
+        drop(current);
+        *self.snapshot.lock().expect("snapshot poisoned") = snapshot;
+        Ok(true)
+    }
+}
+
+fn snapshot(dir: &Path) -> std::io::Result<Snapshot> {
+    let mut snapshot = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            let metadata = std::fs::metadata(&path)?;
+            snapshot.push((path, metadata.modified().ok(), metadata.len()));
+        }
+    }
+    snapshot.sort();
+    Ok(snapshot)
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{ErrorData, model::Cursor};
+
+/// Paginate lists from a snapshot kept on the server between the requests.
+///
+/// The full list is stored by the request of the first page, and the next pages are taken from
+/// it, so the pages stay consistent even if the list changes in between, unlike
+/// [`ListToolsResult::paginate`](crate::model::ListToolsResult::paginate) which slices the
+/// current list at the offset of the cursor. The cursors are made by [`Cursor::encode`], with the
+/// id of the snapshot in the high 32 bits of the offset.
+///
+/// The snapshots are shared by the clients, so they must hold the unfiltered list: the items a
+/// client may see are filtered on every page, and a client sending the cursor of another one
+/// gets nothing it couldn't list itself. A snapshot expires after a time to live, and only the
+/// last [`capacity`](Self::with_capacity) snapshots are kept, a cursor of a dropped snapshot is
+/// rejected as invalid params and the client has to list again from the start. An unchanged list
+/// reuses its last snapshot.
+pub struct ServerPaginationState<T> {
+    snapshots: Mutex<Snapshots<T>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+struct Snapshots<T> {
+    next_id: u32,
+    /// From the oldest to the newest
+    snapshots: VecDeque<Snapshot<T>>,
+}
+
+struct Snapshot<T> {
+    id: u32,
+    items: Vec<T>,
+    expires_at: Instant,
+}
+
+impl<T> std::fmt::Debug for ServerPaginationState<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerPaginationState")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for ServerPaginationState<T> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TTL)
+    }
+}
+
+impl<T> ServerPaginationState<T> {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+    pub const DEFAULT_CAPACITY: usize = 16;
+
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            snapshots: Mutex::new(Snapshots {
+                next_id: 0,
+                snapshots: VecDeque::new(),
+            }),
+            ttl,
+            capacity: Self::DEFAULT_CAPACITY,
+        }
+    }
+
+    /// Keep at most `capacity` snapshots, 16 by default
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+impl<T: Clone + PartialEq> ServerPaginationState<T> {
+    /// Take a page of at most `page_size` of the items passing `allowed`, and the cursor of the
+    /// next page if there is one.
+    ///
+    /// Without a cursor the page is the start of `items`, which are kept for the next pages if
+    /// they don't fit in it. With a cursor `items` are ignored and the page is taken from the
+    /// list stored by the first page.
+    pub fn page(
+        &self,
+        items: Vec<T>,
+        cursor: Option<&Cursor>,
+        page_size: usize,
+        allowed: impl Fn(&T) -> bool,
+    ) -> Result<(Vec<T>, Option<Cursor>), ErrorData> {
+        let page_size = page_size.max(1);
+        let now = Instant::now();
+        let mut state = self.snapshots.lock().expect("pagination state poisoned");
+        state.snapshots.retain(|snapshot| snapshot.expires_at > now);
+        let Some(cursor) = cursor else {
+            let allowed_count = items.iter().filter(|item| allowed(item)).count();
+            if allowed_count <= page_size {
+                return Ok((
+                    items.into_iter().filter(|item| allowed(item)).collect(),
+                    None,
+                ));
+            }
+            let id = match state.snapshots.back_mut() {
+                Some(last) if last.items == items => {
+                    last.expires_at = now + self.ttl;
+                    last.id
+                }
+                _ => {
+                    let id = state.next_id;
+                    state.next_id = state.next_id.wrapping_add(1);
+                    state.snapshots.push_back(Snapshot {
+                        id,
+                        items,
+                        expires_at: now + self.ttl,
+                    });
+                    if state.snapshots.len() > self.capacity {
+                        state.snapshots.pop_front();
+                    }
+                    id
+                }
+            };
+            let snapshot = state.snapshots.back().expect("snapshot just stored");
+            return Ok(Self::take(id, &snapshot.items, 0, page_size, allowed));
+        };
+        let offset = cursor
+            .decode()
+            .map_err(|error| ErrorData::invalid_params(format!("invalid cursor: {error}"), None))?;
+        let id = (offset >> 32) as u32;
+        let snapshot = state
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.id == id)
+            .ok_or_else(|| ErrorData::invalid_params("expired cursor", None))?;
+        Ok(Self::take(
+            id,
+            &snapshot.items,
+            offset as u32 as usize,
+            page_size,
+            allowed,
+        ))
+    }
+
+    /// The page starting at the `offset`-th allowed item of a snapshot
+    fn take(
+        id: u32,
+        items: &[T],
+        offset: usize,
+        page_size: usize,
+        allowed: impl Fn(&T) -> bool,
+    ) -> (Vec<T>, Option<Cursor>) {
+        let mut allowed_items = items.iter().filter(|item| allowed(item)).skip(offset);
+        let page: Vec<T> = allowed_items.by_ref().take(page_size).cloned().collect();
+        let end = offset + page.len();
+        let next_cursor = allowed_items
+            .next()
+            .map(|_| Cursor::encode((u64::from(id) << 32) | end as u64));
+        (page, next_cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(_: &i32) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_page_from_snapshot() {
+        let state = ServerPaginationState::default();
+        let (page, cursor) = state.page((0..5).collect(), None, 2, all).unwrap();
+        assert_eq!(page, vec![0, 1]);
+
+        // the next pages come from the snapshot, whatever the current items are
+        let (page, cursor) = state.page(vec![], cursor.as_ref(), 2, all).unwrap();
+        assert_eq!(page, vec![2, 3]);
+        let (page, cursor) = state.page(vec![], cursor.as_ref(), 2, all).unwrap();
+        assert_eq!(page, vec![4]);
+        assert_eq!(cursor, None);
+
+        let (page, cursor) = state.page(vec![0, 1], None, 2, all).unwrap();
+        assert_eq!(page, vec![0, 1]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_pages_are_filtered_by_client() {
+        let state = ServerPaginationState::default();
+        let even = |item: &i32| item % 2 == 0;
+        let (page, cursor) = state.page((0..10).collect(), None, 2, even).unwrap();
+        assert_eq!(page, vec![0, 2]);
+        // another client following the cursor only gets the items it's allowed to see
+        let odd = |item: &i32| item % 2 == 1;
+        let (page, _) = state.page(vec![], cursor.as_ref(), 2, odd).unwrap();
+        assert_eq!(page, vec![5, 7]);
+        let (page, cursor) = state.page(vec![], cursor.as_ref(), 2, even).unwrap();
+        assert_eq!(page, vec![4, 6]);
+        let (page, cursor) = state.page(vec![], cursor.as_ref(), 2, even).unwrap();
+        assert_eq!(page, vec![8]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_snapshots_are_bounded() {
+        let state = ServerPaginationState::default().with_capacity(2);
+        let (_, first) = state.page((0..5).collect(), None, 2, all).unwrap();
+        // an unchanged list reuses its snapshot
+        let (_, again) = state.page((0..5).collect(), None, 2, all).unwrap();
+        assert_eq!(first, again);
+        state.page((1..6).collect(), None, 2, all).unwrap();
+        assert!(state.page(vec![], first.as_ref(), 2, all).is_ok());
+        state.page((2..7).collect(), None, 2, all).unwrap();
+        let error = state.page(vec![], first.as_ref(), 2, all).unwrap_err();
+        assert_eq!(error.message, "expired cursor");
+    }
+
+    #[test]
+    fn test_invalid_and_expired_cursors() {
+        let state = ServerPaginationState::new(Duration::ZERO);
+        let (_, cursor) = state.page((0..5).collect(), None, 2, all).unwrap();
+        let error = state.page(vec![], cursor.as_ref(), 2, all).unwrap_err();
+        assert_eq!(error.code, crate::model::ErrorCode::INVALID_PARAMS);
+        assert_eq!(error.message, "expired cursor");
+
+        let error = state
+            .page(vec![0], Some(&Cursor::new("0:2")), 2, all)
+            .unwrap_err();
+        assert_eq!(error.code, crate::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.starts_with("invalid cursor"));
+    }
+}
@@ -108,12 +108,16 @@ where
 pub struct PromptRouter<S> {
     #[allow(clippy::type_complexity)]
     pub map: std::collections::HashMap<Cow<'static, str>, PromptRoute<S>>,
+
+    /// Check that the required arguments of a prompt are given before dispatching it
+    validation: bool,
 }
 
 impl<S> Default for PromptRouter<S> {
     fn default() -> Self {
         Self {
             map: std::collections::HashMap::new(),
+            validation: true,
         }
     }
 }
@@ -122,6 +126,7 @@ impl<S> Clone for PromptRouter<S> {
     fn clone(&self) -> Self {
         Self {
             map: self.map.clone(),
+            validation: self.validation,
         }
     }
 }
@@ -142,9 +147,17 @@ where
     pub fn new() -> Self {
         Self {
             map: std::collections::HashMap::new(),
+            validation: true,
         }
     }
 
+    /// Dispatch the requests missing required arguments to the handlers, which check them
+    /// themselves
+    pub fn without_validation(mut self) -> Self {
+        self.validation = false;
+        self
+    }
+
     pub fn with_route<R, A: 'static>(mut self, route: R) -> Self
     where
         R: IntoPromptRoute<S, A>,
@@ -173,7 +186,7 @@ where
 
     pub async fn get_prompt(
         &self,
-        context: PromptContext<'_, S>,
+        mut context: PromptContext<'_, S>,
     ) -> Result<GetPromptResult, crate::ErrorData> {
         let item = self.map.get(context.name.as_str()).ok_or_else(|| {
             crate::ErrorData::invalid_params(
@@ -183,6 +196,30 @@ where
                 })),
             )
         })?;
+        context.arguments = item.attr.apply_arguments(context.arguments.take())?;
+        if self.validation {
+            // checked after the defaults are applied, an argument with a default is never missing
+            let missing: Vec<_> = item
+                .attr
+                .arguments
+                .iter()
+                .flatten()
+                .filter(|argument| argument.required == Some(true))
+                .filter(|argument| {
+                    !context
+                        .arguments
+                        .as_ref()
+                        .is_some_and(|arguments| arguments.contains_key(&argument.name))
+                })
+                .map(|argument| argument.name.as_str())
+                .collect();
+            if !missing.is_empty() {
+                return Err(crate::ErrorData::invalid_params(
+                    format!("missing required arguments: {}", missing.join(", ")),
+                    Some(serde_json::json!({ "missing": missing })),
+                ));
+            }
+        }
         (item.get)(context).await
     }
 
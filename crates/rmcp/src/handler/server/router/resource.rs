@@ -0,0 +1,133 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use futures::future::BoxFuture;
+
+use crate::model::{ReadResourceRequestParam, ReadResourceResult, ResourceTemplate, UriTemplate};
+
+/// Read the resources of an uri template, given the variables matched in the uri.
+///
+/// It's implemented for the async closures taking the request and the variables.
+pub trait ResourceHandler: Send + Sync + 'static {
+    fn read(
+        &self,
+        request: ReadResourceRequestParam,
+        variables: HashMap<String, String>,
+    ) -> BoxFuture<'_, Result<ReadResourceResult, crate::ErrorData>>;
+}
+
+impl<F, Fut> ResourceHandler for F
+where
+    F: Fn(ReadResourceRequestParam, HashMap<String, String>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<ReadResourceResult, crate::ErrorData>> + Send + 'static,
+{
+    fn read(
+        &self,
+        request: ReadResourceRequestParam,
+        variables: HashMap<String, String>,
+    ) -> BoxFuture<'_, Result<ReadResourceResult, crate::ErrorData>> {
+        Box::pin((self)(request, variables))
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceRoute {
+    pub template: ResourceTemplate,
+    pub handler: Arc<dyn ResourceHandler>,
+    uri_template: UriTemplate,
+}
+
+impl std::fmt::Debug for ResourceRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceRoute")
+            .field("name", &self.template.name)
+            .field("uri_template", &self.template.uri_template)
+            .finish()
+    }
+}
+
+impl ResourceRoute {
+    pub fn new(template: ResourceTemplate, handler: impl ResourceHandler) -> Self {
+        Self {
+            uri_template: template.to_uri_template(),
+            template,
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+/// Route the uris of read requests to the handler of the first template they match.
+///
+/// # Example
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use rmcp::{handler::server::router::resource::ResourceRouter, model::*};
+/// let template = RawResourceTemplate {
+///     uri_template: "users://{id}".into(),
+///     name: "user".into(),
+///     title: None,
+///     description: None,
+///     mime_type: None,
+/// };
+/// let router = ResourceRouter::new().with_route(
+///     template.no_annotation(),
+///     |request: ReadResourceRequestParam, variables: HashMap<String, String>| async move {
+///         Ok(ReadResourceResult {
+///             contents: vec![ResourceContents::text(&variables["id"], request.uri)],
+///         })
+///     },
+/// );
+/// let (_handler, variables) = router.route("users://42").unwrap();
+/// assert_eq!(variables["id"], "42");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResourceRouter {
+    pub routes: Vec<ResourceRoute>,
+}
+
+impl ResourceRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_route(mut self, template: ResourceTemplate, handler: impl ResourceHandler) -> Self {
+        self.add_route(ResourceRoute::new(template, handler));
+        self
+    }
+
+    /// Add a route, the routes are matched in the order they were added
+    pub fn add_route(&mut self, route: ResourceRoute) {
+        self.routes.push(route);
+    }
+
+    pub fn merge(&mut self, other: ResourceRouter) {
+        self.routes.extend(other.routes);
+    }
+
+    pub fn list_all(&self) -> Vec<ResourceTemplate> {
+        self.routes
+            .iter()
+            .map(|route| route.template.clone())
+            .collect()
+    }
+
+    /// Find the handler of the first template matching `uri`, with the variables it matched
+    pub fn route(&self, uri: &str) -> Option<(&dyn ResourceHandler, HashMap<String, String>)> {
+        self.routes.iter().find_map(|route| {
+            let variables = route.uri_template.matches(uri)?;
+            Some((route.handler.as_ref(), variables))
+        })
+    }
+
+    pub async fn read(
+        &self,
+        request: ReadResourceRequestParam,
+    ) -> Result<ReadResourceResult, crate::ErrorData> {
+        let (handler, variables) = self.route(&request.uri).ok_or_else(|| {
+            crate::ErrorData::resource_not_found(
+                "resource not found",
+                Some(serde_json::json!({ "uri": request.uri })),
+            )
+        })?;
+        handler.read(request, variables).await
+    }
+}
@@ -1,19 +1,120 @@
 use std::{borrow::Cow, sync::Arc};
 
-use futures::{FutureExt, future::BoxFuture};
+use futures::future::BoxFuture;
 use schemars::JsonSchema;
 
+#[cfg(feature = "schema-validation")]
+use crate::model::SchemaValidator;
+#[cfg(feature = "transport-streamable-http-server")]
+use crate::transport::streamable_http_server::auth::AuthContext;
 use crate::{
-    handler::server::tool::{
-        CallToolHandler, DynCallToolHandler, ToolCallContext, schema_for_type,
+    handler::server::{
+        completion::CompletionRanker,
+        tool::{CallToolHandler, DynCallToolHandler, ToolCallContext, schema_for_type},
+    },
+    model::{
+        CallToolResult, CompleteRequestParam, CompletionInfo, Extensions, JsonObject, Tool,
+        ToolAnnotations,
     },
-    model::{CallToolResult, Tool, ToolAnnotations},
 };
 
+/// Decide which tools a client is allowed to list and call, see [`ToolRouter::with_acl`]
+#[cfg(feature = "transport-streamable-http-server")]
+pub trait ToolAcl: Send + Sync + 'static {
+    fn allows(&self, tool_name: &str, auth: &AuthContext) -> bool;
+}
+
+#[cfg(feature = "transport-streamable-http-server")]
+impl<F> ToolAcl for F
+where
+    F: Fn(&str, &AuthContext) -> bool + Send + Sync + 'static,
+{
+    fn allows(&self, tool_name: &str, auth: &AuthContext) -> bool {
+        self(tool_name, auth)
+    }
+}
+
+/// Allow the tools by the roles of the client.
+///
+/// The roles are read from the `roles` claim of the [`AuthContext`], a string or an array of
+/// strings, as set by e.g. [`JwtAuthLayer`](crate::transport::auth::jwt::JwtAuthLayer).
+///
+/// ```rust
+/// # use rmcp::handler::server::router::tool::RoleBasedAcl;
+/// let acl = RoleBasedAcl::new()
+///     .with_role("reader", ["search"])
+///     .with_role("admin", ["search", "delete"]);
+/// ```
+#[cfg(feature = "transport-streamable-http-server")]
+#[derive(Debug, Clone, Default)]
+pub struct RoleBasedAcl {
+    /// The names of the tools allowed to each role
+    pub roles: std::collections::HashMap<String, std::collections::HashSet<String>>,
+}
+
+#[cfg(feature = "transport-streamable-http-server")]
+impl RoleBasedAcl {
+    pub const ROLES_CLAIM: &str = "roles";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow some tools to a role, in addition to the ones allowed before
+    pub fn with_role(
+        mut self,
+        role: impl Into<String>,
+        tools: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.roles
+            .entry(role.into())
+            .or_default()
+            .extend(tools.into_iter().map(Into::into));
+        self
+    }
+}
+
+#[cfg(feature = "transport-streamable-http-server")]
+impl ToolAcl for RoleBasedAcl {
+    fn allows(&self, tool_name: &str, auth: &AuthContext) -> bool {
+        let allowed = |role: &serde_json::Value| {
+            role.as_str()
+                .and_then(|role| self.roles.get(role))
+                .is_some_and(|tools| tools.contains(tool_name))
+        };
+        match auth.claims.get(Self::ROLES_CLAIM) {
+            Some(serde_json::Value::Array(roles)) => roles.iter().any(allowed),
+            Some(role) => allowed(role),
+            None => false,
+        }
+    }
+}
+
 pub struct ToolRoute<S> {
     #[allow(clippy::type_complexity)]
     pub call: Arc<DynCallToolHandler<S>>,
     pub attr: crate::model::Tool,
+    #[cfg(feature = "schema-validation")]
+    input_validator: CompiledInputSchema,
+}
+
+/// The input schema of a route, compiled when the route is created
+#[cfg(feature = "schema-validation")]
+#[derive(Clone)]
+struct CompiledInputSchema {
+    /// The schema it's compiled from, to tell if `attr` was changed since
+    source: Arc<JsonObject>,
+    validator: Arc<SchemaValidator>,
+}
+
+#[cfg(feature = "schema-validation")]
+impl CompiledInputSchema {
+    fn new(tool: &Tool) -> Self {
+        Self {
+            source: tool.input_schema.clone(),
+            validator: Arc::new(tool.input_validator()),
+        }
+    }
 }
 
 impl<S> std::fmt::Debug for ToolRoute<S> {
@@ -31,6 +132,8 @@ impl<S> Clone for ToolRoute<S> {
         Self {
             call: self.call.clone(),
             attr: self.attr.clone(),
+            #[cfg(feature = "schema-validation")]
+            input_validator: self.input_validator.clone(),
         }
     }
 }
@@ -40,12 +143,15 @@ impl<S: Send + Sync + 'static> ToolRoute<S> {
     where
         C: CallToolHandler<S, A> + Send + Sync + Clone + 'static,
     {
+        let attr = attr.into();
         Self {
             call: Arc::new(move |context: ToolCallContext<S>| {
                 let call = call.clone();
-                context.invoke(call).boxed()
+                context.invoke(call)
             }),
-            attr: attr.into(),
+            #[cfg(feature = "schema-validation")]
+            input_validator: CompiledInputSchema::new(&attr),
+            attr,
         }
     }
     pub fn new_dyn<C>(attr: impl Into<Tool>, call: C) -> Self
@@ -57,14 +163,39 @@ impl<S: Send + Sync + 'static> ToolRoute<S> {
             + Sync
             + 'static,
     {
+        let attr = attr.into();
         Self {
             call: Arc::new(call),
-            attr: attr.into(),
+            #[cfg(feature = "schema-validation")]
+            input_validator: CompiledInputSchema::new(&attr),
+            attr,
         }
     }
     pub fn name(&self) -> &str {
         &self.attr.name
     }
+    /// Replace the definition of the tool, compiling its input schema again
+    pub fn set_attr(&mut self, attr: Tool) {
+        #[cfg(feature = "schema-validation")]
+        {
+            self.input_validator = CompiledInputSchema::new(&attr);
+        }
+        self.attr = attr;
+    }
+    /// Validate call arguments against the input schema, see [`Tool::validate_input`]
+    #[cfg(feature = "schema-validation")]
+    pub fn validate_input(
+        &self,
+        arguments: &JsonObject,
+    ) -> Result<(), Vec<crate::model::SchemaValidationError>> {
+        let arguments = serde_json::Value::Object(arguments.clone());
+        // `attr` is public, its schema may have been replaced since the route was created
+        if Arc::ptr_eq(&self.input_validator.source, &self.attr.input_schema) {
+            self.input_validator.validator.validate(&arguments)
+        } else {
+            self.attr.input_validator().validate(&arguments)
+        }
+    }
 }
 
 pub trait IntoToolRoute<S, A> {
@@ -166,12 +297,34 @@ where
         self
     }
 }
-#[derive(Debug)]
 pub struct ToolRouter<S> {
     #[allow(clippy::type_complexity)]
     pub map: std::collections::HashMap<Cow<'static, str>, ToolRoute<S>>,
 
     pub transparent_when_not_found: bool,
+
+    /// Validate the arguments of a call against the tool input schema before dispatching it
+    #[cfg(feature = "schema-validation")]
+    pub schema_validation: bool,
+
+    /// Restrict the tools each client can list and call, see [`ToolAcl`]
+    #[cfg(feature = "transport-streamable-http-server")]
+    pub acl: Option<Arc<dyn ToolAcl>>,
+}
+
+impl<S> std::fmt::Debug for ToolRouter<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("ToolRouter");
+        debug.field("map", &self.map).field(
+            "transparent_when_not_found",
+            &self.transparent_when_not_found,
+        );
+        #[cfg(feature = "schema-validation")]
+        debug.field("schema_validation", &self.schema_validation);
+        #[cfg(feature = "transport-streamable-http-server")]
+        debug.field("acl", &self.acl.is_some());
+        debug.finish()
+    }
 }
 
 impl<S> Default for ToolRouter<S> {
@@ -179,6 +332,10 @@ impl<S> Default for ToolRouter<S> {
         Self {
             map: std::collections::HashMap::new(),
             transparent_when_not_found: false,
+            #[cfg(feature = "schema-validation")]
+            schema_validation: true,
+            #[cfg(feature = "transport-streamable-http-server")]
+            acl: None,
         }
     }
 }
@@ -187,6 +344,10 @@ impl<S> Clone for ToolRouter<S> {
         Self {
             map: self.map.clone(),
             transparent_when_not_found: self.transparent_when_not_found,
+            #[cfg(feature = "schema-validation")]
+            schema_validation: self.schema_validation,
+            #[cfg(feature = "transport-streamable-http-server")]
+            acl: self.acl.clone(),
         }
     }
 }
@@ -208,8 +369,27 @@ where
         Self {
             map: std::collections::HashMap::new(),
             transparent_when_not_found: false,
+            #[cfg(feature = "schema-validation")]
+            schema_validation: true,
+            #[cfg(feature = "transport-streamable-http-server")]
+            acl: None,
         }
     }
+    /// Enable or disable the validation of call arguments against the tool input schema,
+    /// it's enabled by default.
+    #[cfg(feature = "schema-validation")]
+    pub fn with_schema_validation(mut self, enabled: bool) -> Self {
+        self.schema_validation = enabled;
+        self
+    }
+    /// Only list and dispatch the tools allowed by the acl to the client of the request.
+    ///
+    /// A request without [`AuthContext`] is allowed no tool.
+    #[cfg(feature = "transport-streamable-http-server")]
+    pub fn with_acl(mut self, acl: Arc<dyn ToolAcl>) -> Self {
+        self.acl = Some(acl);
+        self
+    }
     pub fn with_route<R, A>(mut self, route: R) -> Self
     where
         R: IntoToolRoute<S, A>,
@@ -238,11 +418,27 @@ where
         &self,
         context: ToolCallContext<'_, S>,
     ) -> Result<CallToolResult, crate::ErrorData> {
+        // checked first, so a denied client can't probe which tools exist
+        if !self.is_allowed(context.name(), &context.request_context.extensions) {
+            return Err(crate::ErrorData::forbidden("Forbidden", None));
+        }
         let item = self
             .map
             .get(context.name())
             .ok_or_else(|| crate::ErrorData::invalid_params("tool not found", None))?;
 
+        #[cfg(feature = "schema-validation")]
+        if self.schema_validation {
+            let empty = crate::model::JsonObject::new();
+            let arguments = context.arguments.as_ref().unwrap_or(&empty);
+            if let Err(errors) = item.validate_input(arguments) {
+                return Err(crate::ErrorData::invalid_params(
+                    "invalid tool arguments",
+                    Some(serde_json::json!(errors)),
+                ));
+            }
+        }
+
         let result = (item.call)(context).await?;
 
         Ok(result)
@@ -251,6 +447,119 @@ where
     pub fn list_all(&self) -> Vec<crate::model::Tool> {
         self.map.values().map(|item| item.attr.clone()).collect()
     }
+
+    /// List the tools the client of a request is allowed to call, all of them without acl
+    pub fn list_allowed(&self, extensions: &Extensions) -> Vec<crate::model::Tool> {
+        self.map
+            .values()
+            .filter(|item| self.is_allowed(&item.attr.name, extensions))
+            .map(|item| item.attr.clone())
+            .collect()
+    }
+
+    /// Whether the client of a request is allowed to call a tool, always without acl
+    #[cfg_attr(
+        not(feature = "transport-streamable-http-server"),
+        allow(unused_variables)
+    )]
+    pub fn is_allowed(&self, tool_name: &str, extensions: &Extensions) -> bool {
+        #[cfg(feature = "transport-streamable-http-server")]
+        if let Some(acl) = &self.acl {
+            return extensions
+                .get::<AuthContext>()
+                .is_some_and(|auth| acl.allows(tool_name, auth));
+        }
+        true
+    }
+
+    /// Complete a tool argument from the `enum`, `examples` and `default` of its input schema.
+    ///
+    /// Returns `None` when the request doesn't reference a tool of this router, or when the
+    /// schema of the argument has no such values.
+    pub fn complete(&self, request: &CompleteRequestParam) -> Option<CompletionInfo> {
+        let values =
+            self.argument_values(request.r#ref.as_tool_name()?, request.tool_argument_name()?)?;
+        Some(Self::rank_values(&values, &request.argument.value))
+    }
+
+    /// Complete the argument `arg_name` of the tool `tool_name` from the values suggested by
+    /// its input schema, empty if there are none.
+    pub fn complete_argument(
+        &self,
+        tool_name: &str,
+        arg_name: &str,
+        partial: &str,
+    ) -> CompletionInfo {
+        self.argument_values(tool_name, arg_name)
+            .map(|values| Self::rank_values(&values, partial))
+            .unwrap_or_default()
+    }
+
+    fn argument_values(&self, tool_name: &str, arg_name: &str) -> Option<Vec<String>> {
+        let item = self.map.get(tool_name)?;
+        let schema = &item.attr.input_schema;
+        let property = schema.get("properties")?.get(arg_name)?;
+        let mut values = Vec::new();
+        collect_schema_values(schema, property, &mut values);
+        (!values.is_empty()).then_some(values)
+    }
+
+    fn rank_values(values: &[String], partial: &str) -> CompletionInfo {
+        let candidates: Vec<&str> = values.iter().map(String::as_str).collect();
+        let ranked = CompletionRanker::new().rank(partial, &candidates);
+        CompletionInfo::from_ranked(ranked, CompletionInfo::MAX_VALUES)
+    }
+}
+
+/// Collect the values suggested by a property schema, following local `$ref`s and the variants
+/// of `anyOf` and `oneOf` as generated for options and enums.
+fn collect_schema_values(root: &JsonObject, schema: &serde_json::Value, values: &mut Vec<String>) {
+    fn push(value: &serde_json::Value, values: &mut Vec<String>) {
+        let value = match value {
+            serde_json::Value::String(value) => value.clone(),
+            serde_json::Value::Null => return,
+            value => value.to_string(),
+        };
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix('#'))
+    {
+        if let Some(resolved) = serde_json::Value::Object(root.clone()).pointer(target) {
+            collect_schema_values(root, resolved, values);
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        for variant in schema
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            collect_schema_values(root, variant, values);
+        }
+    }
+    if let Some(value) = schema.get("const") {
+        push(value, values);
+    }
+    for key in ["enum", "examples"] {
+        for value in schema
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            push(value, values);
+        }
+    }
+    if let Some(value) = schema.get("default") {
+        push(value, values);
+    }
 }
 
 impl<S> std::ops::Add<ToolRouter<S>> for ToolRouter<S>
@@ -0,0 +1,128 @@
+//! Streaming resources, whose contents are sent as `notifications/resources/chunk` messages after
+//! the response of a `resources/read` request, to the clients declaring the `streamingResources`
+//! capability.
+//!
+//! A server returns them from
+//! [`ServerHandler::read_resource_streaming`](crate::ServerHandler::read_resource_streaming):
+//! ```rust
+//! use rmcp::{handler::server::streaming::StreamingReadResourceResult, model::ReadResourceResult};
+//!
+//! let chunks = futures::stream::iter([Ok(bytes::Bytes::from_static(b"large"))]);
+//! let result = StreamingReadResourceResult::new(ReadResourceResult { contents: vec![] })
+//!     .with_stream("file:///large.bin", None, chunks);
+//! assert_eq!(result.result.contents.len(), 1);
+//! assert_eq!(result.streams.len(), 1);
+//! ```
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use bytes::Bytes;
+use futures::Stream;
+
+use crate::{
+    model::{ErrorData, ReadResourceResult, RequestId, ResourceContents},
+    service::{Peer, ResponseSent, RoleServer},
+};
+
+pub type ResourceChunkStream = Pin<Box<dyn Stream<Item = Result<Bytes, ErrorData>> + Send>>;
+
+/// The result of a `resources/read` request with streaming resources.
+///
+/// The chunk streams can't be serialized, so they are kept beside the result, by the stream token
+/// of their [`ResourceContents::StreamingResource`]. They are sent once the result is.
+pub struct StreamingReadResourceResult {
+    pub result: ReadResourceResult,
+    pub streams: HashMap<String, ResourceChunkStream>,
+}
+
+impl std::fmt::Debug for StreamingReadResourceResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingReadResourceResult")
+            .field("result", &self.result)
+            .field("streams", &self.streams.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl From<ReadResourceResult> for StreamingReadResourceResult {
+    fn from(result: ReadResourceResult) -> Self {
+        Self::new(result)
+    }
+}
+
+impl StreamingReadResourceResult {
+    pub fn new(result: ReadResourceResult) -> Self {
+        Self {
+            result,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Add a streaming resource with a freshly generated stream token.
+    pub fn with_stream(
+        mut self,
+        uri: impl Into<String>,
+        mime_type: Option<String>,
+        stream: impl Stream<Item = Result<Bytes, ErrorData>> + Send + 'static,
+    ) -> Self {
+        static NEXT_STREAM_TOKEN: AtomicU64 = AtomicU64::new(0);
+        let stream_token = format!(
+            "resource-stream-{}",
+            NEXT_STREAM_TOKEN.fetch_add(1, Ordering::Relaxed)
+        );
+        self.result
+            .contents
+            .push(ResourceContents::StreamingResource {
+                uri: uri.into(),
+                mime_type,
+                stream_token: stream_token.clone(),
+                meta: None,
+            });
+        self.streams.insert(stream_token, Box::pin(stream));
+        self
+    }
+}
+
+/// Send the chunks of every [`ResourceContents::StreamingResource`] of the result in the
+/// background, once the response is sent.
+pub(crate) fn spawn_resource_streams(
+    peer: &Peer<RoleServer>,
+    request_id: RequestId,
+    response_sent: Option<ResponseSent>,
+    result: &ReadResourceResult,
+    mut streams: HashMap<String, ResourceChunkStream>,
+) {
+    for contents in &result.contents {
+        let ResourceContents::StreamingResource { stream_token, .. } = contents else {
+            continue;
+        };
+        let Some(stream) = streams.remove(stream_token) else {
+            tracing::warn!(%stream_token, "streaming resource has no stream to send");
+            continue;
+        };
+        let peer = peer.clone();
+        let request_id = request_id.clone();
+        let response_sent = response_sent.clone();
+        let stream_token = stream_token.clone();
+        tokio::spawn(async move {
+            if let Some(response_sent) = response_sent {
+                if !response_sent.wait().await {
+                    tracing::debug!(%stream_token, "response not sent, dropping the streaming resource");
+                    return;
+                }
+            }
+            if let Err(error) = peer
+                .send_resource_stream(request_id, stream_token.clone(), stream)
+                .await
+            {
+                tracing::error!(%stream_token, %error, "failed to send streaming resource");
+            }
+        });
+    }
+    for stream_token in streams.keys() {
+        tracing::warn!(%stream_token, "stream of no streaming resource, dropping it");
+    }
+}
@@ -1,7 +1,10 @@
 use std::{
+    any::{Any, TypeId},
     borrow::Cow,
+    collections::HashMap,
     future::{Future, Ready},
     marker::PhantomData,
+    sync::{OnceLock, RwLock},
 };
 
 use futures::future::{BoxFuture, FutureExt};
@@ -15,7 +18,7 @@ pub use super::{
 use crate::{
     RoleServer,
     handler::server::wrapper::Parameters,
-    model::{CallToolRequestParam, CallToolResult, IntoContents, JsonObject},
+    model::{CallToolRequestParam, CallToolResult, IntoContents, JsonObject, Tool},
     service::RequestContext,
 };
 
@@ -28,6 +31,43 @@ pub fn parse_json_object<T: DeserializeOwned>(input: JsonObject) -> Result<T, cr
         )
     })
 }
+
+/// The [`Tool`]s built by a `#[tool]` function, so they are built once per process.
+///
+/// The tools are keyed by a type, e.g. the types of the parameters and output of the function, as
+/// a function of a generic impl builds a tool per instance of the generics.
+pub struct ToolCache(OnceLock<RwLock<HashMap<TypeId, Tool>>>);
+
+impl ToolCache {
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Get the tool of `K`, building it with `init` the first time
+    pub fn get_or_init<K: Any>(&self, init: impl FnOnce() -> Tool) -> Tool {
+        let cache = self.0.get_or_init(Default::default);
+        if let Some(tool) = cache
+            .read()
+            .expect("tool cache lock poisoned")
+            .get(&TypeId::of::<K>())
+        {
+            return tool.clone();
+        }
+        cache
+            .write()
+            .expect("tool cache lock poisoned")
+            .entry(TypeId::of::<K>())
+            .or_insert_with(init)
+            .clone()
+    }
+}
+
+impl Default for ToolCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct ToolCallContext<'s, S> {
     pub request_context: RequestContext<RoleServer>,
     pub service: &'s S,
@@ -94,6 +134,69 @@ impl<T: IntoCallToolResult> IntoCallToolResult for Result<T, crate::ErrorData> {
     }
 }
 
+/// An error of a tool handler, sent to the client as the error of the `tools/call` request.
+///
+/// A tool returning `Result<T, ToolError>` fails the request with the matching [`ErrorData`],
+/// while a tool returning `Result<T, E>` with `E: IntoContents` succeeds with an error result
+/// the model can read.
+///
+/// The detail of a [`ToolError::Internal`] error is logged on the server, the client is only
+/// told the call failed with an internal error.
+///
+/// ```rust
+/// # use rmcp::{handler::server::tool::ToolError, model::{ErrorCode, ErrorData}};
+/// let error: ErrorData = ToolError::InvalidParams("`x` must be positive".into()).into();
+/// assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+/// ```
+///
+/// [`ErrorData`]: crate::ErrorData
+#[derive(Debug, thiserror::Error)]
+pub enum ToolError {
+    #[error("tool not found")]
+    NotFound,
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+    #[error("internal error: {0}")]
+    Internal(Box<dyn std::error::Error + Send + Sync>),
+    #[error("forbidden")]
+    Forbidden,
+    /// The tool can't be called right now, e.g. a backend it depends on is down
+    #[error("tool unavailable")]
+    Unavailable,
+}
+
+impl ToolError {
+    pub fn internal(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        ToolError::Internal(error.into())
+    }
+}
+
+impl From<ToolError> for crate::ErrorData {
+    fn from(error: ToolError) -> Self {
+        let message = error.to_string();
+        match error {
+            ToolError::NotFound | ToolError::InvalidParams(_) => {
+                crate::ErrorData::invalid_params(message, None)
+            }
+            ToolError::Internal(error) => {
+                tracing::error!(%error, "tool failed with an internal error");
+                crate::ErrorData::internal_error("internal error", None)
+            }
+            ToolError::Unavailable => crate::ErrorData::internal_error(message, None),
+            ToolError::Forbidden => crate::ErrorData::forbidden(message, None),
+        }
+    }
+}
+
+impl<T: IntoCallToolResult> IntoCallToolResult for Result<T, ToolError> {
+    fn into_call_tool_result(self) -> Result<CallToolResult, crate::ErrorData> {
+        match self {
+            Ok(value) => value.into_call_tool_result(),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
 pin_project_lite::pin_project! {
     #[project = IntoCallToolResultFutProj]
     pub enum IntoCallToolResultFut<F, R> {
@@ -159,7 +159,7 @@ pub use service::{Peer, Service, ServiceError, ServiceExt};
 pub use service::{RoleClient, serve_client};
 #[cfg(feature = "server")]
 #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
-pub use service::{RoleServer, serve_server};
+pub use service::{RoleServer, serve_server, serve_server_lazy, serve_server_with_config};
 
 pub mod handler;
 pub mod transport;
@@ -7,6 +7,7 @@ mod meta;
 mod prompt;
 mod resource;
 mod serde_impl;
+mod spec;
 mod tool;
 pub use annotated::*;
 pub use capabilities::*;
@@ -17,6 +18,7 @@ pub use prompt::*;
 pub use resource::*;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
+pub use spec::*;
 pub use tool::*;
 
 /// A JSON object type alias for convenient handling of JSON data.
@@ -129,7 +131,10 @@ const_string!(JsonRpcVersion2_0 = "2.0");
 ///
 /// This ensures compatibility between clients and servers by specifying
 /// which version of the Model Context Protocol is being used.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd)]
+///
+/// Versions are ordered by their date. A version which isn't a `YYYY-MM-DD` date is older than
+/// any dated version.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ProtocolVersion(Cow<'static, str>);
 
@@ -151,6 +156,54 @@ impl ProtocolVersion {
     pub const V_2024_11_05: Self = Self(Cow::Borrowed("2024-11-05"));
     //  Keep LATEST at 2025-03-26 until full 2025-06-18 compliance and automated testing are in place.
     pub const LATEST: Self = Self::V_2025_03_26;
+    /// The versions known by this sdk, oldest first
+    pub const KNOWN: &'static [Self] =
+        &[Self::V_2024_11_05, Self::V_2025_03_26, Self::V_2025_06_18];
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parse a `YYYY-MM-DD` version into `(year, month, day)`
+    pub fn parse(s: &str) -> Option<(u32, u32, u32)> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 10
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || [0, 1, 2, 3, 5, 6, 8, 9]
+                .iter()
+                .any(|&i| !bytes[i].is_ascii_digit())
+        {
+            return None;
+        }
+        let year = s[0..4].parse().ok()?;
+        let month = s[5..7].parse().ok()?;
+        let day = s[8..10].parse().ok()?;
+        ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some((year, month, day))
+    }
+
+    /// Check if this version is one of the [known](Self::KNOWN) versions
+    pub fn is_supported(&self) -> bool {
+        Self::KNOWN.contains(self)
+    }
+}
+
+impl Ord for ProtocolVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // as `parse` only accepts one spelling of a date, this is consistent with `Eq`
+        match (Self::parse(&self.0), Self::parse(&other.0)) {
+            (Some(date), Some(other_date)) => date.cmp(&other_date),
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (None, None) => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for ProtocolVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Serialize for ProtocolVersion {
@@ -198,6 +251,42 @@ impl NumberOrString {
             NumberOrString::String(s) => Value::String(s.to_string()),
         }
     }
+
+    /// The string, if this is a string identifier
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            NumberOrString::Number(_) => None,
+            NumberOrString::String(s) => Some(s),
+        }
+    }
+
+    /// The number, if this is a numeric identifier
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            NumberOrString::Number(n) => Some(*n),
+            NumberOrString::String(_) => None,
+        }
+    }
+
+    /// The number, if this is a numeric identifier in the range of `u32`
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_i64().and_then(|n| u32::try_from(n).ok())
+    }
+}
+
+/// Parse the [`Display`](std::fmt::Display) form of an identifier.
+///
+/// An integer written the way it's displayed (no sign for positive numbers, no leading zeros)
+/// is a numeric identifier, anything else is a string identifier, so `"007"` stays a string.
+impl std::str::FromStr for NumberOrString {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<i64>() {
+            Ok(n) if n.to_string() == s => Ok(NumberOrString::Number(n)),
+            _ => Ok(NumberOrString::String(s.into())),
+        }
+    }
 }
 
 impl std::fmt::Display for NumberOrString {
@@ -448,6 +537,10 @@ impl ErrorCode {
     pub const INVALID_PARAMS: Self = Self(-32602);
     pub const INTERNAL_ERROR: Self = Self(-32603);
     pub const PARSE_ERROR: Self = Self(-32700);
+    /// Implementation-defined server error for requests rejected by a rate limit.
+    pub const RATE_LIMITED: Self = Self(-32029);
+    /// Implementation-defined server error for requests the client isn't allowed to make.
+    pub const FORBIDDEN: Self = Self(-32028);
 }
 
 /// Error information for JSON-RPC error responses.
@@ -499,6 +592,93 @@ impl ErrorData {
     pub fn internal_error(message: impl Into<Cow<'static, str>>, data: Option<Value>) -> Self {
         Self::new(ErrorCode::INTERNAL_ERROR, message, data)
     }
+    pub fn rate_limited(message: impl Into<Cow<'static, str>>, data: Option<Value>) -> Self {
+        Self::new(ErrorCode::RATE_LIMITED, message, data)
+    }
+    pub fn forbidden(message: impl Into<Cow<'static, str>>, data: Option<Value>) -> Self {
+        Self::new(ErrorCode::FORBIDDEN, message, data)
+    }
+
+    /// Create an [`ErrorDataBuilder`], the code defaults to [`ErrorCode::INTERNAL_ERROR`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rmcp::model::{ErrorCode, ErrorData};
+    /// let error = ErrorData::builder()
+    ///     .code(ErrorCode::INVALID_PARAMS)
+    ///     .message("x must be positive")
+    ///     .data(serde_json::json!({"field": "x"}))
+    ///     .build();
+    /// assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    /// ```
+    pub fn builder() -> ErrorDataBuilder {
+        ErrorDataBuilder::default()
+    }
+
+    /// Convert an http error status from an upstream service into a json-rpc error.
+    ///
+    /// The status and the response body are kept in [`ErrorData::data`].
+    ///
+    /// | status | code |
+    /// |:-:|:-:|
+    /// | 400 | [`ErrorCode::INVALID_PARAMS`] |
+    /// | 404 | [`ErrorCode::RESOURCE_NOT_FOUND`] |
+    /// | 429 | [`ErrorCode::RATE_LIMITED`] |
+    /// | other 4xx | [`ErrorCode::INVALID_REQUEST`] |
+    /// | 500 and others | [`ErrorCode::INTERNAL_ERROR`] |
+    pub fn from_http_status(status: u16, body: &str) -> Self {
+        let code = match status {
+            400 => ErrorCode::INVALID_PARAMS,
+            404 => ErrorCode::RESOURCE_NOT_FOUND,
+            429 => ErrorCode::RATE_LIMITED,
+            401..=499 => ErrorCode::INVALID_REQUEST,
+            _ => ErrorCode::INTERNAL_ERROR,
+        };
+        Self::new(
+            code,
+            format!("upstream responded with http status {status}"),
+            Some(serde_json::json!({
+                "status": status,
+                "body": body,
+            })),
+        )
+    }
+}
+
+/// Builder for [`ErrorData`], see [`ErrorData::builder`].
+#[derive(Debug, Clone)]
+pub struct ErrorDataBuilder {
+    code: ErrorCode,
+    message: Cow<'static, str>,
+    data: Option<Value>,
+}
+
+impl Default for ErrorDataBuilder {
+    fn default() -> Self {
+        Self {
+            code: ErrorCode::INTERNAL_ERROR,
+            message: Cow::Borrowed(""),
+            data: None,
+        }
+    }
+}
+
+impl ErrorDataBuilder {
+    pub fn code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+    pub fn message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = message.into();
+        self
+    }
+    pub fn data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+    pub fn build(self) -> ErrorData {
+        ErrorData::new(self.code, self.message, self.data)
+    }
 }
 
 /// Represents any JSON-RPC message that can be sent or received.
@@ -584,6 +764,83 @@ impl<Req, Resp, Not> JsonRpcMessage<Req, Resp, Not> {
             _ => None,
         }
     }
+
+    /// The id of a request, a response or an error
+    pub fn id(&self) -> Option<&RequestId> {
+        match self {
+            JsonRpcMessage::Request(r) => Some(&r.id),
+            JsonRpcMessage::Response(r) => Some(&r.id),
+            JsonRpcMessage::Error(e) => Some(&e.id),
+            JsonRpcMessage::Notification(_) => None,
+        }
+    }
+    pub fn is_request(&self) -> bool {
+        matches!(self, JsonRpcMessage::Request(_))
+    }
+    pub fn is_notification(&self) -> bool {
+        matches!(self, JsonRpcMessage::Notification(_))
+    }
+    /// Transform the request, other messages are kept as they are
+    pub fn map_request<F, U>(self, f: F) -> JsonRpcMessage<U, Resp, Not>
+    where
+        F: FnOnce(Req) -> U,
+    {
+        match self {
+            JsonRpcMessage::Request(r) => JsonRpcMessage::Request(JsonRpcRequest {
+                jsonrpc: r.jsonrpc,
+                id: r.id,
+                request: f(r.request),
+            }),
+            JsonRpcMessage::Response(r) => JsonRpcMessage::Response(r),
+            JsonRpcMessage::Notification(n) => JsonRpcMessage::Notification(n),
+            JsonRpcMessage::Error(e) => JsonRpcMessage::Error(e),
+        }
+    }
+    /// Convert the request into one of its variants, e.g. a [`CallToolRequest`] out of a
+    /// [`ClientRequest`].
+    ///
+    /// Returns `None` if this isn't a request, and the conversion error if the request is of
+    /// another type.
+    pub fn try_into_typed_request<T>(self) -> Option<Result<(T, RequestId), T::Error>>
+    where
+        T: TryFrom<Req>,
+    {
+        self.into_request()
+            .map(|(request, id)| T::try_from(request).map(|request| (request, id)))
+    }
+}
+
+impl<Req: HasMethod, Resp, Not> JsonRpcMessage<Req, Resp, Not> {
+    /// The method of the request, `None` for other messages
+    pub fn request_method(&self) -> Option<&str> {
+        match self {
+            JsonRpcMessage::Request(r) => Some(r.request.method()),
+            _ => None,
+        }
+    }
+}
+
+/// A request which knows its method name
+pub trait HasMethod {
+    fn method(&self) -> &str;
+}
+
+impl<P> HasMethod for Request<String, P> {
+    fn method(&self) -> &str {
+        &self.method
+    }
+}
+
+impl HasMethod for ClientRequest {
+    fn method(&self) -> &str {
+        ClientRequest::method(self)
+    }
+}
+
+impl HasMethod for ServerRequest {
+    fn method(&self) -> &str {
+        ServerRequest::method(self)
+    }
 }
 
 // =============================================================================
@@ -739,15 +996,69 @@ impl Default for Implementation {
 }
 
 impl Implementation {
-    pub fn from_build_env() -> Self {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
         Implementation {
-            name: env!("CARGO_CRATE_NAME").to_owned(),
+            name: name.into(),
             title: None,
-            version: env!("CARGO_PKG_VERSION").to_owned(),
+            version: version.into(),
             icons: None,
             website_url: None,
         }
     }
+
+    pub fn builder() -> ImplementationBuilder {
+        ImplementationBuilder::default()
+    }
+
+    /// The name and version of this crate, `rmcp`, as it's where the `env!` macros are expanded.
+    ///
+    /// Use [`Implementation::new`] to report the name and version of your application.
+    pub fn from_build_env() -> Self {
+        Self::new(env!("CARGO_CRATE_NAME"), env!("CARGO_PKG_VERSION"))
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+}
+
+/// Build an [`Implementation`], the name and version default to the ones of
+/// [`Implementation::from_build_env`]
+#[derive(Debug, Clone, Default)]
+pub struct ImplementationBuilder {
+    implementation: Implementation,
+}
+
+impl ImplementationBuilder {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.implementation.name = name.into();
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.implementation.version = version.into();
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.implementation.title = Some(title.into());
+        self
+    }
+
+    pub fn icons(mut self, icons: Vec<Icon>) -> Self {
+        self.implementation.icons = Some(icons);
+        self
+    }
+
+    pub fn website_url(mut self, website_url: impl Into<String>) -> Self {
+        self.implementation.website_url = Some(website_url.into());
+        self
+    }
+
+    pub fn build(self) -> Implementation {
+        self.implementation
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
@@ -755,7 +1066,26 @@ impl Implementation {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PaginatedRequestParam {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub cursor: Option<String>,
+    pub cursor: Option<Cursor>,
+}
+
+impl PaginatedRequestParam {
+    pub fn with_cursor(mut self, cursor: impl Into<Cursor>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// The offset carried by the cursor, see [`Cursor::decode`]
+    #[cfg(feature = "base64")]
+    pub fn decode_cursor(&self) -> Option<u64> {
+        self.cursor.as_ref()?.decode().ok()
+    }
+
+    /// Make the cursor of an offset, see [`Cursor::encode`]
+    #[cfg(feature = "base64")]
+    pub fn encode_cursor(offset: u64) -> Cursor {
+        Cursor::encode(offset)
+    }
 }
 // =============================================================================
 // PROGRESS AND PAGINATION
@@ -782,7 +1112,106 @@ pub struct ProgressNotificationParam {
 
 pub type ProgressNotification = Notification<ProgressNotificationMethod, ProgressNotificationParam>;
 
-pub type Cursor = String;
+/// An opaque pagination cursor.
+///
+/// Clients must pass the cursors they receive back as they are, so any string is accepted on the
+/// wire. A server may use [`Cursor::encode`] and [`Cursor::decode`] to carry an offset, the
+/// decoding rejects the cursors it didn't encode, e.g. one edited by a client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CursorError {
+    #[error("cursor is not valid base64")]
+    InvalidEncoding,
+    #[error("cursor has an invalid length")]
+    InvalidLength,
+    #[error("cursor checksum mismatch")]
+    ChecksumMismatch,
+}
+
+impl Cursor {
+    pub fn new(cursor: impl Into<String>) -> Self {
+        Self(cursor.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    /// Encode an offset, as url safe base64 of the offset followed by a checksum
+    #[cfg(feature = "base64")]
+    pub fn encode(offset: u64) -> Cursor {
+        use base64::engine::{Engine, general_purpose::URL_SAFE_NO_PAD};
+
+        let offset = offset.to_be_bytes();
+        let mut bytes = [0; 12];
+        bytes[..8].copy_from_slice(&offset);
+        bytes[8..].copy_from_slice(&Self::checksum(&offset).to_be_bytes());
+        Cursor(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    /// Decode the offset of a cursor made by [`Cursor::encode`]
+    #[cfg(feature = "base64")]
+    pub fn decode(&self) -> Result<u64, CursorError> {
+        use base64::engine::{Engine, general_purpose::URL_SAFE_NO_PAD};
+
+        let bytes = URL_SAFE_NO_PAD
+            .decode(&self.0)
+            .map_err(|_| CursorError::InvalidEncoding)?;
+        let bytes: [u8; 12] = bytes.try_into().map_err(|_| CursorError::InvalidLength)?;
+        let (offset, checksum) = bytes.split_at(8);
+        let offset: [u8; 8] = offset.try_into().expect("split at 8");
+        if checksum != Self::checksum(&offset).to_be_bytes() {
+            return Err(CursorError::ChecksumMismatch);
+        }
+        Ok(u64::from_be_bytes(offset))
+    }
+
+    /// Check if this cursor was made by [`Cursor::encode`]
+    #[cfg(feature = "base64")]
+    pub fn is_valid(&self) -> bool {
+        self.decode().is_ok()
+    }
+
+    /// FNV-1a, catches edited cursors but isn't meant to resist forgery
+    #[cfg(feature = "base64")]
+    fn checksum(bytes: &[u8]) -> u32 {
+        bytes.iter().fold(0x811c9dc5, |hash, byte| {
+            (hash ^ u32::from(*byte)).wrapping_mul(0x01000193)
+        })
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Cursor {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(value: Cursor) -> Self {
+        value.0
+    }
+}
 
 macro_rules! paginated_result {
     ($t:ident {
@@ -806,6 +1235,35 @@ macro_rules! paginated_result {
                     $i_item: items,
                 }
             }
+
+            /// Take the page of `items` requested by `request`, of at most `page_size` items.
+            ///
+            /// The page starts at the offset of the cursor. The page size may be the one requested
+            /// in the `_meta` of the request, see [`Meta::get_page_size`]. A cursor not made by
+            /// [`Cursor::encode`] is rejected as invalid params.
+            #[cfg(feature = "base64")]
+            pub fn paginate(
+                mut items: $t_item,
+                request: Option<&PaginatedRequestParam>,
+                page_size: u32,
+            ) -> Result<Self, ErrorData> {
+                let offset = match request.and_then(|request| request.cursor.as_ref()) {
+                    Some(cursor) => cursor.decode().map_err(|error| {
+                        ErrorData::invalid_params(format!("invalid cursor: {error}"), None)
+                    })?,
+                    None => 0,
+                };
+                let page_size = page_size.max(1);
+                let offset = usize::try_from(offset).unwrap_or(usize::MAX).min(items.len());
+                let end = offset.saturating_add(page_size as usize).min(items.len());
+                let next_cursor = (end < items.len()).then(|| Cursor::encode(end as u64));
+                items.truncate(end);
+                items.drain(..offset);
+                Ok(Self {
+                    next_cursor,
+                    $i_item: items,
+                })
+            }
         }
     };
 }
@@ -895,6 +1353,45 @@ pub struct ResourceUpdatedNotificationParam {
 pub type ResourceUpdatedNotification =
     Notification<ResourceUpdatedNotificationMethod, ResourceUpdatedNotificationParam>;
 
+const_string!(ResourceChunkNotificationMethod = "notifications/resources/chunk");
+/// Parameters for a chunk of a streaming resource
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ResourceChunkNotificationParam {
+    /// The id of the `resources/read` request which returned the stream
+    pub request_id: RequestId,
+    /// The token of the stream, as declared in [`ResourceContents::StreamingResource`]
+    pub stream_token: String,
+    /// The zero-based index of this chunk in the stream
+    pub index: u64,
+    /// The base64-encoded chunk data
+    pub data: String,
+}
+/// Notification carrying a chunk of a streaming resource
+pub type ResourceChunkNotification =
+    Notification<ResourceChunkNotificationMethod, ResourceChunkNotificationParam>;
+
+const_string!(ResourceChunkEndNotificationMethod = "notifications/resources/chunk_end");
+/// Parameters for the end of a streaming resource
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ResourceChunkEndNotificationParam {
+    /// The id of the `resources/read` request which returned the stream
+    pub request_id: RequestId,
+    /// The token of the stream which is ended
+    pub stream_token: String,
+    /// The count of chunks sent for this stream
+    pub total_chunks: u64,
+    /// Set if the stream was terminated by an error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorData>,
+}
+/// Notification sent after the last chunk of a streaming resource
+pub type ResourceChunkEndNotification =
+    Notification<ResourceChunkEndNotificationMethod, ResourceChunkEndNotificationParam>;
+
 // =============================================================================
 // PROMPT MANAGEMENT
 // =============================================================================
@@ -1012,6 +1509,22 @@ pub struct SamplingMessage {
     pub content: Content,
 }
 
+impl SamplingMessage {
+    pub fn new(role: Role, content: Content) -> Self {
+        Self { role, content }
+    }
+
+    /// Create a text message from the user.
+    pub fn user_text(text: impl Into<String>) -> Self {
+        Self::new(Role::User, Content::text(text))
+    }
+
+    /// Create a text message from the assistant.
+    pub fn assistant_text(text: impl Into<String>) -> Self {
+        Self::new(Role::Assistant, Content::text(text))
+    }
+}
+
 /// Specifies how much context should be included in sampling requests.
 ///
 /// This allows clients to control what additional context information
@@ -1098,6 +1611,116 @@ pub struct ModelHint {
     pub name: Option<String>,
 }
 
+impl CreateMessageRequestParam {
+    /// Create a [`CreateMessageRequestParamBuilder`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rmcp::model::CreateMessageRequestParam;
+    /// let params = CreateMessageRequestParam::builder()
+    ///     .system_prompt("You are a helpful assistant.")
+    ///     .user("What is the capital of France?")
+    ///     .assistant("Paris.")
+    ///     .user("And of Germany?")
+    ///     .max_tokens(100)
+    ///     .build();
+    /// assert_eq!(params.messages.len(), 3);
+    /// ```
+    pub fn builder() -> CreateMessageRequestParamBuilder {
+        CreateMessageRequestParamBuilder::default()
+    }
+}
+
+/// Builder for [`CreateMessageRequestParam`], see [`CreateMessageRequestParam::builder`].
+#[derive(Debug, Clone)]
+pub struct CreateMessageRequestParamBuilder {
+    params: CreateMessageRequestParam,
+}
+
+impl Default for CreateMessageRequestParamBuilder {
+    fn default() -> Self {
+        Self {
+            params: CreateMessageRequestParam {
+                messages: Vec::new(),
+                model_preferences: None,
+                system_prompt: None,
+                include_context: None,
+                temperature: None,
+                max_tokens: Self::DEFAULT_MAX_TOKENS,
+                stop_sequences: None,
+                metadata: None,
+            },
+        }
+    }
+}
+
+impl CreateMessageRequestParamBuilder {
+    /// The max tokens used if [`CreateMessageRequestParamBuilder::max_tokens`] is not called.
+    pub const DEFAULT_MAX_TOKENS: u32 = 1024;
+
+    /// Append a message to the conversation.
+    pub fn message(&mut self, role: Role, content: Content) -> &mut Self {
+        self.params
+            .messages
+            .push(SamplingMessage::new(role, content));
+        self
+    }
+
+    /// Append a text message from the user.
+    pub fn user(&mut self, text: impl Into<String>) -> &mut Self {
+        self.message(Role::User, Content::text(text))
+    }
+
+    /// Append a text message from the assistant.
+    pub fn assistant(&mut self, text: impl Into<String>) -> &mut Self {
+        self.message(Role::Assistant, Content::text(text))
+    }
+
+    pub fn system_prompt(&mut self, system_prompt: impl Into<String>) -> &mut Self {
+        self.params.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    pub fn max_tokens(&mut self, max_tokens: u32) -> &mut Self {
+        self.params.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn temperature(&mut self, temperature: f32) -> &mut Self {
+        self.params.temperature = Some(temperature);
+        self
+    }
+
+    pub fn include_context(&mut self, include_context: ContextInclusion) -> &mut Self {
+        self.params.include_context = Some(include_context);
+        self
+    }
+
+    /// Append a model hint to the model preferences.
+    pub fn model_hint(&mut self, name: impl Into<String>) -> &mut Self {
+        let preferences = self
+            .params
+            .model_preferences
+            .get_or_insert(ModelPreferences {
+                hints: None,
+                cost_priority: None,
+                speed_priority: None,
+                intelligence_priority: None,
+            });
+        preferences
+            .hints
+            .get_or_insert_with(Vec::new)
+            .push(ModelHint {
+                name: Some(name.into()),
+            });
+        self
+    }
+
+    pub fn build(&self) -> CreateMessageRequestParam {
+        self.params.clone()
+    }
+}
+
 // =============================================================================
 // COMPLETION AND AUTOCOMPLETE
 // =============================================================================
@@ -1159,6 +1782,18 @@ pub struct CompleteRequestParam {
     pub context: Option<CompletionContext>,
 }
 
+impl CompleteRequestParam {
+    /// The name of the tool argument being completed, if this request references a tool
+    pub fn tool_argument_name(&self) -> Option<&str> {
+        match &self.r#ref {
+            Reference::Tool(tool_ref) => {
+                Some(tool_ref.argument.as_deref().unwrap_or(&self.argument.name))
+            }
+            _ => None,
+        }
+    }
+}
+
 pub type CompleteRequest = Request<CompleteRequestMethod, CompleteRequestParam>;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
@@ -1216,6 +1851,61 @@ impl CompletionInfo {
         })
     }
 
+    /// Create CompletionInfo from the best `max` ranked candidates, `max` is capped to
+    /// [`Self::MAX_VALUES`]
+    #[cfg(feature = "server")]
+    pub fn from_ranked(
+        ranked: Vec<crate::handler::server::completion::RankedCandidate>,
+        max: usize,
+    ) -> Self {
+        let total = ranked.len();
+        let max = max.min(Self::MAX_VALUES);
+        Self {
+            values: ranked
+                .into_iter()
+                .take(max)
+                .map(|candidate| candidate.value)
+                .collect(),
+            total: Some(u32::try_from(total).unwrap_or(u32::MAX)),
+            has_more: Some(total > max),
+        }
+    }
+
+    /// Merge the completions of two providers, e.g. a tool router and a handler.
+    ///
+    /// The values of `a` come first, a value of `b` already in `a` is skipped. The totals are
+    /// summed if both are known, and there are more results if either has more. The values beyond
+    /// [`Self::MAX_VALUES`] are dropped, and then there are more results.
+    pub fn merge(a: CompletionInfo, b: CompletionInfo) -> CompletionInfo {
+        let mut values = a.values;
+        let mut seen: std::collections::HashSet<String> = values.iter().cloned().collect();
+        values.extend(
+            b.values
+                .into_iter()
+                .filter(|value| seen.insert(value.clone())),
+        );
+        let truncated = values.len() > Self::MAX_VALUES;
+        values.truncate(Self::MAX_VALUES);
+        let has_more = if truncated || a.has_more == Some(true) || b.has_more == Some(true) {
+            Some(true)
+        } else {
+            a.has_more.or(b.has_more)
+        };
+        CompletionInfo {
+            values,
+            total: a.total.zip(b.total).map(|(a, b)| a.saturating_add(b)),
+            has_more,
+        }
+    }
+
+    /// Move the values starting with `prefix`, ignoring case, before the others, keeping the
+    /// order of the values otherwise
+    pub fn sort_by_prefix(&mut self, prefix: &str) {
+        let prefix = prefix.to_lowercase();
+        self.values
+            .sort_by_key(|value| !value.to_lowercase().starts_with(&prefix));
+    }
+
     /// Check if this completion response indicates more results are available
     pub fn has_more_results(&self) -> bool {
         self.has_more.unwrap_or(false)
@@ -1254,6 +1944,8 @@ pub enum Reference {
     Resource(ResourceReference),
     #[serde(rename = "ref/prompt")]
     Prompt(PromptReference),
+    #[serde(rename = "ref/tool")]
+    Tool(ToolReference),
 }
 
 impl Reference {
@@ -1273,11 +1965,20 @@ impl Reference {
         Self::Resource(ResourceReference { uri: uri.into() })
     }
 
+    /// Create a tool reference
+    pub fn for_tool(name: impl Into<String>) -> Self {
+        Self::Tool(ToolReference {
+            name: name.into(),
+            argument: None,
+        })
+    }
+
     /// Get the reference type as a string
     pub fn reference_type(&self) -> &'static str {
         match self {
             Self::Prompt(_) => "ref/prompt",
             Self::Resource(_) => "ref/resource",
+            Self::Tool(_) => "ref/tool",
         }
     }
 
@@ -1296,6 +1997,14 @@ impl Reference {
             _ => None,
         }
     }
+
+    /// Extract tool name if this is a tool reference
+    pub fn as_tool_name(&self) -> Option<&str> {
+        match self {
+            Self::Tool(tool_ref) => Some(&tool_ref.name),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -1304,6 +2013,16 @@ pub struct ResourceReference {
     pub uri: String,
 }
 
+/// Reference to an argument of a tool, completed from the tool input schema
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ToolReference {
+    pub name: String,
+    /// The argument to complete, defaults to the name of the request argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PromptReference {
@@ -1586,6 +2305,101 @@ paginated_result!(
     }
 );
 
+impl ListToolsResult {
+    /// Find a tool by name
+    pub fn get(&self, name: &str) -> Option<&Tool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    /// Append the tools of `other`, e.g. the next page of a listing.
+    ///
+    /// A tool of `other` named like one of `self` is skipped. The next cursor is the one of
+    /// `other`, as it's the latest page.
+    pub fn merge(mut self, other: ListToolsResult) -> ListToolsResult {
+        let mut names: std::collections::HashSet<_> =
+            self.tools.iter().map(|tool| tool.name.clone()).collect();
+        self.tools.extend(
+            other
+                .tools
+                .into_iter()
+                .filter(|tool| names.insert(tool.name.clone())),
+        );
+        self.next_cursor = other.next_cursor;
+        self
+    }
+
+    /// List the tools of several servers concurrently.
+    ///
+    /// The results are indexed by the position of their peer in `peers`, in this order.
+    #[cfg(feature = "client")]
+    pub async fn merge_from_peers(
+        peers: &[crate::Peer<crate::RoleClient>],
+    ) -> Vec<(usize, Result<ListToolsResult, crate::ServiceError>)> {
+        futures::future::join_all(peers.iter().enumerate().map(|(index, peer)| async move {
+            let result = peer
+                .list_all_tools()
+                .await
+                .map(ListToolsResult::with_all_items);
+            (index, result)
+        }))
+        .await
+    }
+
+    /// Merge the tools of several servers, e.g. listed by [`ListToolsResult::merge_from_peers`].
+    ///
+    /// A tool named like a tool of another server is renamed `{index}_{name}`, with the index of
+    /// its result in `results`, so the tools of each server can be told apart.
+    ///
+    /// Fails if two tools still have the same name once renamed, e.g. a tool renamed `1_foo` and
+    /// a tool already named so.
+    pub fn merge_all(results: Vec<ListToolsResult>) -> Result<ListToolsResult, ToolNameCollision> {
+        let mut servers_by_name: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        for result in &results {
+            let names: std::collections::HashSet<_> =
+                result.tools.iter().map(|tool| tool.name.as_ref()).collect();
+            for name in names {
+                *servers_by_name.entry(name.to_owned()).or_default() += 1;
+            }
+        }
+        let tools: Vec<Tool> = results
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, result)| {
+                let servers_by_name = &servers_by_name;
+                result.tools.into_iter().map(move |mut tool| {
+                    if servers_by_name[tool.name.as_ref()] > 1 {
+                        tool.name = format!("{index}_{}", tool.name).into();
+                    }
+                    tool
+                })
+            })
+            .collect();
+        let mut names = std::collections::HashSet::new();
+        if let Some(tool) = tools.iter().find(|tool| !names.insert(tool.name.as_ref())) {
+            return Err(ToolNameCollision {
+                name: tool.name.to_string(),
+            });
+        }
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    /// Index the tools by name, for repeated lookups
+    pub fn into_map(self) -> std::collections::HashMap<String, Tool> {
+        self.tools
+            .into_iter()
+            .map(|tool| (tool.name.to_string(), tool))
+            .collect()
+    }
+}
+
+/// Two tools have the same name in the result of [`ListToolsResult::merge_all`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("several tools are named `{name}` once merged")]
+pub struct ToolNameCollision {
+    pub name: String,
+}
+
 const_string!(CallToolRequestMethod = "tools/call");
 /// Parameters for calling a tool provided by an MCP server.
 ///
@@ -1602,6 +2416,77 @@ pub struct CallToolRequestParam {
     pub arguments: Option<JsonObject>,
 }
 
+impl CallToolRequestParam {
+    /// The argument named `key`, if any
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.arguments.as_ref()?.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key)?.as_i64()
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key)?.as_f64()
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key)?.as_bool()
+    }
+
+    pub fn get_object(&self, key: &str) -> Option<&JsonObject> {
+        self.get(key)?.as_object()
+    }
+
+    pub fn get_array(&self, key: &str) -> Option<&Vec<Value>> {
+        self.get(key)?.as_array()
+    }
+
+    /// Read a required argument, or the `invalid_params` error to answer the call with if it's
+    /// missing or of another type
+    fn get_required<'a, T>(
+        &'a self,
+        key: &str,
+        kind: &str,
+        read: impl FnOnce(&'a Value) -> Option<T>,
+    ) -> Result<T, ErrorData> {
+        let value = self.get(key).ok_or_else(|| {
+            ErrorData::invalid_params(format!("missing required argument `{key}`"), None)
+        })?;
+        read(value).ok_or_else(|| {
+            ErrorData::invalid_params(format!("argument `{key}` must be {kind}"), None)
+        })
+    }
+
+    pub fn get_required_str(&self, key: &str) -> Result<&str, ErrorData> {
+        self.get_required(key, "a string", Value::as_str)
+    }
+
+    pub fn get_required_i64(&self, key: &str) -> Result<i64, ErrorData> {
+        self.get_required(key, "an integer", Value::as_i64)
+    }
+
+    pub fn get_required_f64(&self, key: &str) -> Result<f64, ErrorData> {
+        self.get_required(key, "a number", Value::as_f64)
+    }
+
+    pub fn get_required_bool(&self, key: &str) -> Result<bool, ErrorData> {
+        self.get_required(key, "a boolean", Value::as_bool)
+    }
+
+    pub fn get_required_object(&self, key: &str) -> Result<&JsonObject, ErrorData> {
+        self.get_required(key, "an object", Value::as_object)
+    }
+
+    pub fn get_required_array(&self, key: &str) -> Result<&Vec<Value>, ErrorData> {
+        self.get_required(key, "an array", Value::as_array)
+    }
+}
+
 /// Request to call a specific tool
 pub type CallToolRequest = Request<CallToolRequestMethod, CallToolRequestParam>;
 
@@ -1638,6 +2523,35 @@ pub struct GetPromptResult {
     pub messages: Vec<PromptMessage>,
 }
 
+impl GetPromptResult {
+    /// Append the messages of `other`, e.g. a prompt assembled from several handlers.
+    ///
+    /// The description is the one of `self`, or the one of `other` if `self` has none.
+    pub fn merge(mut self, other: GetPromptResult) -> GetPromptResult {
+        self.messages.extend(other.messages);
+        self.description = self.description.or(other.description);
+        self
+    }
+
+    /// Insert a text message with system instructions before the messages.
+    ///
+    /// Prompts have no system role, so the message is sent with the user role.
+    pub fn prepend_system(mut self, system_text: impl Into<String>) -> GetPromptResult {
+        self.messages.insert(
+            0,
+            PromptMessage::new_text(PromptMessageRole::User, system_text),
+        );
+        self
+    }
+
+    /// Append a user text message after the messages
+    pub fn append_user(mut self, user_text: impl Into<String>) -> GetPromptResult {
+        self.messages
+            .push(PromptMessage::new_text(PromptMessageRole::User, user_text));
+        self
+    }
+}
+
 // =============================================================================
 // MESSAGE TYPE UNIONS
 // =============================================================================
@@ -1676,6 +2590,16 @@ macro_rules! ts_union {
                 $U::$V(Box::new(value))
             }
         }
+        impl TryFrom<$U> for $V {
+            type Error = $U;
+            fn try_from(value: $U) -> Result<Self, Self::Error> {
+                match value {
+                    $U::$V(value) => Ok(*value),
+                    #[allow(unreachable_patterns)]
+                    other => Err(other),
+                }
+            }
+        }
         ts_union!(@impl_from $U {$($rest)*});
     };
     (@impl_from $U: ident {$(|)? $V:ident $($rest:tt)*}) => {
@@ -1684,6 +2608,16 @@ macro_rules! ts_union {
                 $U::$V(value)
             }
         }
+        impl TryFrom<$U> for $V {
+            type Error = $U;
+            fn try_from(value: $U) -> Result<Self, Self::Error> {
+                match value {
+                    $U::$V(value) => Ok(value),
+                    #[allow(unreachable_patterns)]
+                    other => Err(other),
+                }
+            }
+        }
         ts_union!(@impl_from $U {$($rest)*});
     };
     (@impl_from $U: ident  { ; }) => {};
@@ -1755,6 +2689,17 @@ ts_union!(
     | CreateElicitationRequest;
 );
 
+impl ServerRequest {
+    pub fn method(&self) -> &'static str {
+        match &self {
+            ServerRequest::PingRequest(r) => r.method.as_str(),
+            ServerRequest::CreateMessageRequest(r) => r.method.as_str(),
+            ServerRequest::ListRootsRequest(r) => r.method.as_str(),
+            ServerRequest::CreateElicitationRequest(r) => r.method.as_str(),
+        }
+    }
+}
+
 ts_union!(
     export type ServerNotification =
     | CancelledNotification
@@ -1763,7 +2708,9 @@ ts_union!(
     | ResourceUpdatedNotification
     | ResourceListChangedNotification
     | ToolListChangedNotification
-    | PromptListChangedNotification;
+    | PromptListChangedNotification
+    | ResourceChunkNotification
+    | ResourceChunkEndNotification;
 );
 
 ts_union!(
@@ -1790,28 +2737,6 @@ impl ServerResult {
 
 pub type ServerJsonRpcMessage = JsonRpcMessage<ServerRequest, ServerResult, ServerNotification>;
 
-impl TryInto<CancelledNotification> for ServerNotification {
-    type Error = ServerNotification;
-    fn try_into(self) -> Result<CancelledNotification, Self::Error> {
-        if let ServerNotification::CancelledNotification(t) = self {
-            Ok(t)
-        } else {
-            Err(self)
-        }
-    }
-}
-
-impl TryInto<CancelledNotification> for ClientNotification {
-    type Error = ClientNotification;
-    fn try_into(self) -> Result<CancelledNotification, Self::Error> {
-        if let ClientNotification::CancelledNotification(t) = self {
-            Ok(t)
-        } else {
-            Err(self)
-        }
-    }
-}
-
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -1822,6 +2747,528 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_json_rpc_message_helpers() {
+        let request = ClientJsonRpcMessage::request(
+            ClientRequest::CallToolRequest(CallToolRequest {
+                method: Default::default(),
+                params: CallToolRequestParam {
+                    name: "sum".into(),
+                    arguments: None,
+                },
+                extensions: Default::default(),
+            }),
+            NumberOrString::Number(1),
+        );
+        assert!(request.is_request());
+        assert!(!request.is_notification());
+        assert_eq!(request.id(), Some(&NumberOrString::Number(1)));
+        assert_eq!(request.request_method(), Some("tools/call"));
+
+        let (call, id) = request
+            .clone()
+            .try_into_typed_request::<CallToolRequest>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(call.params.name, "sum");
+        assert_eq!(id, NumberOrString::Number(1));
+        let not_a_ping = request
+            .clone()
+            .try_into_typed_request::<PingRequest>()
+            .unwrap();
+        assert!(matches!(not_a_ping, Err(ClientRequest::CallToolRequest(_))));
+
+        let mapped = request.map_request(|request| request.method().to_owned());
+        assert_eq!(
+            mapped.into_request(),
+            Some(("tools/call".to_owned(), NumberOrString::Number(1)))
+        );
+
+        let notification = ClientJsonRpcMessage::notification(
+            ClientNotification::InitializedNotification(Default::default()),
+        );
+        assert!(notification.is_notification());
+        assert_eq!(notification.id(), None);
+        assert_eq!(notification.request_method(), None);
+        assert!(
+            notification
+                .clone()
+                .try_into_typed_request::<PingRequest>()
+                .is_none()
+        );
+        let notification = notification.map_request(|_| unreachable!());
+        assert!(notification.into_notification().is_some());
+
+        let error = ServerJsonRpcMessage::error(
+            ErrorData::internal_error("oops", None),
+            NumberOrString::String("a".into()),
+        );
+        assert_eq!(error.id(), Some(&NumberOrString::String("a".into())));
+        assert!(!error.is_request());
+        let response =
+            ServerJsonRpcMessage::response(ServerResult::empty(()), NumberOrString::Number(2));
+        assert_eq!(response.id(), Some(&NumberOrString::Number(2)));
+
+        let ping = ServerJsonRpcMessage::request(
+            ServerRequest::PingRequest(Default::default()),
+            NumberOrString::Number(3),
+        );
+        assert_eq!(ping.request_method(), Some("ping"));
+        let custom = JsonRpcMessage::<Request, DefaultResponse, Notification>::request(
+            Request {
+                method: "custom/method".to_owned(),
+                params: JsonObject::new(),
+                extensions: Default::default(),
+            },
+            NumberOrString::Number(4),
+        );
+        assert_eq!(custom.request_method(), Some("custom/method"));
+    }
+
+    #[test]
+    fn test_protocol_version_ordering() {
+        use std::cmp::Ordering::*;
+
+        let version = |s: &str| serde_json::from_value::<ProtocolVersion>(json!(s)).unwrap();
+        assert_eq!(ProtocolVersion::parse("2025-03-26"), Some((2025, 3, 26)));
+        for invalid in [
+            "",
+            "2025-3-26",
+            "2025-13-01",
+            "2025-00-10",
+            "2025-01-32",
+            "latest",
+        ] {
+            assert_eq!(ProtocolVersion::parse(invalid), None, "{invalid:?}");
+        }
+
+        let cases = [
+            (
+                ProtocolVersion::V_2024_11_05,
+                ProtocolVersion::V_2025_03_26,
+                Less,
+            ),
+            (
+                ProtocolVersion::V_2025_03_26,
+                ProtocolVersion::V_2025_06_18,
+                Less,
+            ),
+            (
+                ProtocolVersion::V_2025_06_18,
+                ProtocolVersion::V_2024_11_05,
+                Greater,
+            ),
+            (
+                ProtocolVersion::LATEST,
+                ProtocolVersion::V_2025_03_26,
+                Equal,
+            ),
+            (version("2025-03-26"), ProtocolVersion::V_2025_03_26, Equal),
+            (
+                version("2026-01-01"),
+                ProtocolVersion::V_2025_06_18,
+                Greater,
+            ),
+            (version("2025-12-01"), version("2026-01-01"), Less),
+            // not lexicographic
+            (version("10000-01-01"), version("2026-01-01"), Less),
+            (version("draft"), ProtocolVersion::V_2024_11_05, Less),
+            (version("draft"), version("draft"), Equal),
+            (version("draft-a"), version("draft-b"), Less),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(a.cmp(&b), expected, "{a} vs {b}");
+            assert_eq!(b.cmp(&a), expected.reverse(), "{b} vs {a}");
+            assert_eq!(a.partial_cmp(&b), Some(expected));
+            assert_eq!(a == b, expected == Equal);
+        }
+
+        for known in ProtocolVersion::KNOWN {
+            assert!(known.is_supported());
+        }
+        assert!(ProtocolVersion::LATEST.is_supported());
+        assert!(!version("2026-01-01").is_supported());
+        assert!(!version("2024-01-01").is_supported());
+        assert!(!version("draft").is_supported());
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_cursor_round_trip() {
+        for offset in [0, 1, 50, u32::MAX as u64, u64::MAX] {
+            let cursor = Cursor::encode(offset);
+            assert!(cursor.is_valid());
+            assert_eq!(cursor.decode(), Ok(offset));
+            // unchanged over the wire
+            let json =
+                serde_json::to_value(PaginatedRequestParam::default().with_cursor(cursor.clone()))
+                    .unwrap();
+            assert_eq!(json, json!({ "cursor": cursor.as_str() }));
+            let param: PaginatedRequestParam = serde_json::from_value(json).unwrap();
+            assert_eq!(param.cursor.unwrap().decode(), Ok(offset));
+        }
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_paginate_with_page_size() {
+        let tool = |i: usize| Tool::new(format!("tool{i}"), "", Arc::new(JsonObject::new()));
+        let tools: Vec<Tool> = (0..5).map(tool).collect();
+        let names = |result: &ListToolsResult| -> Vec<String> {
+            result
+                .tools
+                .iter()
+                .map(|tool| tool.name.to_string())
+                .collect()
+        };
+
+        let meta = Meta::builder().page_size(2).build();
+        assert_eq!(
+            serde_json::to_value(&meta).unwrap(),
+            json!({ "pageSize": 2 })
+        );
+        let page_size = meta.get_page_size().unwrap();
+        let request = PaginatedRequestParam::default();
+        let first = ListToolsResult::paginate(tools.clone(), Some(&request), page_size).unwrap();
+        assert_eq!(names(&first), ["tool0", "tool1"]);
+        let cursor = first.next_cursor.unwrap();
+        let request = request.with_cursor(cursor);
+        assert_eq!(request.decode_cursor(), Some(2));
+        let second = ListToolsResult::paginate(tools.clone(), Some(&request), page_size).unwrap();
+        assert_eq!(names(&second), ["tool2", "tool3"]);
+        let request = request.with_cursor(second.next_cursor.unwrap());
+        let last = ListToolsResult::paginate(tools.clone(), Some(&request), page_size).unwrap();
+        assert_eq!(names(&last), ["tool4"]);
+        assert_eq!(last.next_cursor, None);
+
+        // without a request, the first page
+        let all = ListToolsResult::paginate(tools.clone(), None, 3).unwrap();
+        assert_eq!(names(&all), ["tool0", "tool1", "tool2"]);
+        assert_eq!(
+            all.next_cursor,
+            Some(PaginatedRequestParam::encode_cursor(3))
+        );
+
+        let invalid = PaginatedRequestParam::default().with_cursor("not a cursor");
+        assert_eq!(invalid.decode_cursor(), None);
+        let error = ListToolsResult::paginate(tools, Some(&invalid), 3).unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_tampered_cursor_is_rejected() {
+        let cursor = Cursor::encode(100).into_inner();
+        // edit every character in turn
+        for i in 0..cursor.len() {
+            let mut tampered = cursor.clone().into_bytes();
+            tampered[i] = if tampered[i] == b'A' { b'B' } else { b'A' };
+            let tampered = Cursor::new(String::from_utf8(tampered).unwrap());
+            assert_ne!(tampered.decode(), Ok(100), "{tampered}");
+            assert!(!tampered.is_valid(), "{tampered}");
+        }
+        assert_eq!(
+            Cursor::new("not base64!").decode(),
+            Err(CursorError::InvalidEncoding)
+        );
+        assert_eq!(
+            Cursor::new("AAAA").decode(),
+            Err(CursorError::InvalidLength)
+        );
+        assert_eq!(
+            Cursor::new("AAAAAAAAAAAAAAAA").decode(),
+            Err(CursorError::ChecksumMismatch)
+        );
+
+        // cursors of other servers are still accepted on the wire
+        let param: PaginatedRequestParam =
+            serde_json::from_value(json!({ "cursor": "page=2" })).unwrap();
+        assert_eq!(param.cursor, Some(Cursor::from("page=2")));
+        assert!(!param.cursor.unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_call_tool_argument_accessors() {
+        let request = CallToolRequestParam {
+            name: "search".into(),
+            arguments: json!({
+                "query": "rust",
+                "limit": 10,
+                "threshold": 0.5,
+                "exact": true,
+                "filters": { "lang": "en" },
+                "tags": ["a", "b"],
+            })
+            .as_object()
+            .cloned(),
+        };
+        assert_eq!(request.get_str("query"), Some("rust"));
+        assert_eq!(request.get_i64("limit"), Some(10));
+        assert_eq!(request.get_f64("threshold"), Some(0.5));
+        assert_eq!(request.get_f64("limit"), Some(10.0));
+        assert_eq!(request.get_bool("exact"), Some(true));
+        assert_eq!(request.get_object("filters").unwrap()["lang"], "en");
+        assert_eq!(request.get_array("tags").unwrap().len(), 2);
+        assert_eq!(request.get_str("limit"), None);
+        assert_eq!(request.get_str("missing"), None);
+
+        assert_eq!(request.get_required_str("query").unwrap(), "rust");
+        assert_eq!(request.get_required_i64("limit").unwrap(), 10);
+        assert!(request.get_required_bool("exact").unwrap());
+        assert_eq!(request.get_required_array("tags").unwrap()[1], "b");
+
+        let no_arguments = CallToolRequestParam {
+            name: "search".into(),
+            arguments: None,
+        };
+        assert_eq!(no_arguments.get_str("query"), None);
+    }
+
+    #[test]
+    fn test_call_tool_missing_required_argument() {
+        let request = CallToolRequestParam {
+            name: "search".into(),
+            arguments: json!({ "limit": "ten" }).as_object().cloned(),
+        };
+        let error = request.get_required_str("query").unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert_eq!(error.message, "missing required argument `query`");
+        let error = request.get_required_i64("limit").unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert_eq!(error.message, "argument `limit` must be an integer");
+    }
+
+    #[test]
+    fn test_implementation_constructors() {
+        let implementation = Implementation::new("my-app", "1.2.3");
+        assert_eq!(implementation.name, "my-app");
+        assert_eq!(implementation.version, "1.2.3");
+        assert_eq!(implementation.title, None);
+
+        let implementation = Implementation::builder()
+            .name("my-app")
+            .version("2.0.0")
+            .title("My App")
+            .website_url("https://example.com")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&implementation).unwrap(),
+            json!({
+                "name": "my-app",
+                "version": "2.0.0",
+                "title": "My App",
+                "websiteUrl": "https://example.com"
+            })
+        );
+
+        let implementation = Implementation::from_build_env().with_version("0.0.1");
+        assert_eq!(implementation.name, "rmcp");
+        assert_eq!(implementation.version, "0.0.1");
+    }
+
+    #[test]
+    fn test_completion_info_merge() {
+        let info = |values: &[&str], total: Option<u32>, has_more: Option<bool>| CompletionInfo {
+            values: values.iter().map(|value| value.to_string()).collect(),
+            total,
+            has_more,
+        };
+        let merged = CompletionInfo::merge(
+            info(&["python", "rust"], Some(2), Some(false)),
+            info(&["rust", "pyre"], Some(5), Some(true)),
+        );
+        assert_eq!(
+            merged,
+            info(&["python", "rust", "pyre"], Some(7), Some(true))
+        );
+
+        let merged = CompletionInfo::merge(info(&["a"], Some(1), None), info(&["b"], None, None));
+        assert_eq!(merged, info(&["a", "b"], None, None));
+        let merged = CompletionInfo::merge(
+            info(&["a"], None, Some(false)),
+            info(&["b"], None, Some(false)),
+        );
+        assert_eq!(merged.has_more, Some(false));
+
+        let many: Vec<String> = (0..CompletionInfo::MAX_VALUES)
+            .map(|i| i.to_string())
+            .collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        let merged = CompletionInfo::merge(info(&many, None, None), info(&["more"], None, None));
+        assert_eq!(merged.values.len(), CompletionInfo::MAX_VALUES);
+        assert_eq!(merged.has_more, Some(true));
+
+        let mut sorted = info(&["Java", "Python", "CPython", "pyre"], None, None);
+        sorted.sort_by_prefix("py");
+        assert_eq!(sorted.values, ["Python", "pyre", "Java", "CPython"]);
+    }
+
+    #[test]
+    fn test_get_prompt_result_composition() {
+        let text = |message: &PromptMessage| match &message.content {
+            PromptMessageContent::Text { text } => text.clone(),
+            content => panic!("unexpected content {content:?}"),
+        };
+        let base = GetPromptResult {
+            description: None,
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::Assistant,
+                "example answer",
+            )],
+        };
+        let examples = GetPromptResult {
+            description: Some("examples".into()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                "example question",
+            )],
+        };
+        let result = base
+            .merge(examples)
+            .prepend_system("be concise")
+            .append_user("the question");
+        assert_eq!(result.description.as_deref(), Some("examples"));
+        let texts: Vec<_> = result.messages.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            [
+                "be concise",
+                "example answer",
+                "example question",
+                "the question"
+            ]
+        );
+        assert_eq!(result.messages[0].role, PromptMessageRole::User);
+        assert_eq!(result.messages[3].role, PromptMessageRole::User);
+
+        let described = GetPromptResult {
+            description: Some("first".into()),
+            messages: vec![],
+        };
+        let merged = described.merge(result);
+        assert_eq!(merged.description.as_deref(), Some("first"));
+        assert_eq!(merged.messages.len(), 4);
+    }
+
+    #[test]
+    fn test_list_tools_result_helpers() {
+        let tool = |name: &'static str, description: &'static str| {
+            Tool::new(name, description, Arc::new(JsonObject::new()))
+        };
+        let first = ListToolsResult {
+            next_cursor: Some("page-2".into()),
+            tools: vec![tool("sum", "first"), tool("sub", "first")],
+        };
+        assert_eq!(first.get("sub").unwrap().name, "sub");
+        assert!(first.get("mul").is_none());
+
+        let second = ListToolsResult {
+            next_cursor: None,
+            tools: vec![tool("sum", "second"), tool("mul", "second")],
+        };
+        let merged = first.merge(second);
+        let names: Vec<_> = merged.tools.iter().map(|tool| tool.name.as_ref()).collect();
+        assert_eq!(names, ["sum", "sub", "mul"]);
+        assert_eq!(
+            merged.get("sum").unwrap().description.as_deref(),
+            Some("first")
+        );
+        assert_eq!(merged.next_cursor, None);
+
+        let merged_all = ListToolsResult::merge_all(vec![
+            ListToolsResult::with_all_items(vec![tool("sum", "first"), tool("sub", "first")]),
+            ListToolsResult::with_all_items(vec![tool("sum", "second"), tool("mul", "second")]),
+        ])
+        .unwrap();
+        let names: Vec<_> = merged_all
+            .tools
+            .iter()
+            .map(|tool| tool.name.as_ref())
+            .collect();
+        assert_eq!(names, ["0_sum", "sub", "1_sum", "mul"]);
+        assert_eq!(merged_all.next_cursor, None);
+
+        // the second `sum` is renamed like a tool of the first server
+        let collision = ListToolsResult::merge_all(vec![
+            ListToolsResult::with_all_items(vec![tool("sum", "first"), tool("1_sum", "first")]),
+            ListToolsResult::with_all_items(vec![tool("sum", "second")]),
+        ]);
+        assert_eq!(
+            collision.unwrap_err(),
+            ToolNameCollision {
+                name: "1_sum".into()
+            }
+        );
+
+        let map = merged.into_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["mul"].description.as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn test_number_or_string_from_str() {
+        let parse = |s: &str| s.parse::<NumberOrString>().unwrap();
+        assert_eq!(parse("0"), NumberOrString::Number(0));
+        assert_eq!(parse("42"), NumberOrString::Number(42));
+        assert_eq!(parse("-7"), NumberOrString::Number(-7));
+        assert_eq!(
+            parse(&u32::MAX.to_string()),
+            NumberOrString::Number(u32::MAX as i64)
+        );
+        assert_eq!(
+            parse(&i64::MAX.to_string()),
+            NumberOrString::Number(i64::MAX)
+        );
+        // not the display form of a number
+        for s in [
+            "",
+            "007",
+            "+1",
+            " 1",
+            "1.0",
+            "9223372036854775808",
+            "req-1",
+            "ü-2",
+            "42 ",
+        ] {
+            assert_eq!(parse(s), NumberOrString::String(s.into()), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_number_or_string_display_round_trip() {
+        for id in [
+            NumberOrString::Number(0),
+            NumberOrString::Number(-1),
+            NumberOrString::Number(u32::MAX as i64),
+            NumberOrString::String("".into()),
+            NumberOrString::String("request-1".into()),
+            NumberOrString::String("요청 ü 🦀".into()),
+        ] {
+            let displayed = id.to_string();
+            assert!(!displayed.contains('"'), "{displayed}");
+            assert_eq!(displayed.parse::<NumberOrString>().unwrap(), id);
+        }
+        assert_eq!(NumberOrString::String("a b".into()).to_string(), "a b");
+    }
+
+    #[test]
+    fn test_number_or_string_accessors() {
+        let number = NumberOrString::Number(u32::MAX as i64);
+        assert_eq!(number.as_u32(), Some(u32::MAX));
+        assert_eq!(number.as_i64(), Some(u32::MAX as i64));
+        assert_eq!(number.as_str(), None);
+        assert_eq!(NumberOrString::Number(u32::MAX as i64 + 1).as_u32(), None);
+        assert_eq!(NumberOrString::Number(-1).as_u32(), None);
+
+        let string = NumberOrString::String("ü".into());
+        assert_eq!(string.as_str(), Some("ü"));
+        assert_eq!(string.as_u32(), None);
+        assert_eq!(NumberOrString::String("".into()).as_str(), Some(""));
+        // a string of digits is still a string
+        assert_eq!(NumberOrString::String("1".into()).as_u32(), None);
+    }
+
     #[test]
     fn test_notification_serde() {
         let raw = json!( {
@@ -2158,4 +3605,41 @@ mod tests {
         );
         assert_eq!(json["serverInfo"]["websiteUrl"], "https://docs.example.com");
     }
+
+    #[test]
+    fn test_error_data_builder() {
+        let error = ErrorData::builder()
+            .code(ErrorCode::INVALID_PARAMS)
+            .message("x must be positive")
+            .data(json!({"field": "x"}))
+            .build();
+        assert_eq!(
+            error,
+            ErrorData::invalid_params("x must be positive", Some(json!({"field": "x"})))
+        );
+
+        let error = ErrorData::builder().message("oops").build();
+        assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+        assert_eq!(error.data, None);
+    }
+
+    #[test]
+    fn test_error_data_from_http_status() {
+        let cases = [
+            (400, ErrorCode::INVALID_PARAMS),
+            (404, ErrorCode::RESOURCE_NOT_FOUND),
+            (429, ErrorCode::RATE_LIMITED),
+            (403, ErrorCode::INVALID_REQUEST),
+            (500, ErrorCode::INTERNAL_ERROR),
+            (503, ErrorCode::INTERNAL_ERROR),
+        ];
+        for (status, code) in cases {
+            let error = ErrorData::from_http_status(status, "upstream body");
+            assert_eq!(error.code, code, "status {status}");
+            assert_eq!(
+                error.data,
+                Some(json!({"status": status, "body": "upstream body"}))
+            );
+        }
+    }
 }
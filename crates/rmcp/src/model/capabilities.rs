@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, marker::PhantomData};
 
 use paste::paste;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 use super::JsonObject;
 pub type ExperimentalCapabilities = BTreeMap<String, JsonObject>;
@@ -78,6 +78,10 @@ pub struct ClientCapabilities {
     /// Capability to handle elicitation requests from servers for interactive user input
     #[serde(skip_serializing_if = "Option::is_none")]
     pub elicitation: Option<ElicitationCapability>,
+    /// Capability to receive [`ResourceContents::StreamingResource`](super::ResourceContents::StreamingResource)
+    /// as chunk notifications
+    #[serde(rename = "streamingResources", skip_serializing_if = "Option::is_none")]
+    pub streaming_resources: Option<JsonObject>,
 }
 
 ///
@@ -273,6 +277,7 @@ builder! {
         roots: RootsCapabilities,
         sampling: JsonObject,
         elicitation: ElicitationCapability,
+        streaming_resources: JsonObject,
     }
 }
 
@@ -288,8 +293,8 @@ impl<const E: bool, const S: bool>
 }
 
 #[cfg(feature = "elicitation")]
-impl<const E: bool, const R: bool, const S: bool>
-    ClientCapabilitiesBuilder<ClientCapabilitiesBuilderState<E, R, S, true>>
+impl<const E: bool, const R: bool, const S: bool, const SR: bool>
+    ClientCapabilitiesBuilder<ClientCapabilitiesBuilderState<E, R, S, true, SR>>
 {
     /// Enable JSON Schema validation for elicitation responses.
     /// When enabled, the client will validate user input against the requested_schema
@@ -302,6 +307,73 @@ impl<const E: bool, const R: bool, const S: bool>
     }
 }
 
+fn set_extension<T: Serialize>(
+    experimental: &mut Option<ExperimentalCapabilities>,
+    key: &str,
+    value: T,
+) -> Result<(), serde_json::Error> {
+    let value = match serde_json::to_value(value)? {
+        serde_json::Value::Object(object) => object,
+        value => {
+            return Err(serde::ser::Error::custom(format!(
+                "extension {key} is not a JSON object: {value}"
+            )));
+        }
+    };
+    experimental
+        .get_or_insert_with(Default::default)
+        .insert(key.to_owned(), value);
+    Ok(())
+}
+
+fn get_extension<T: DeserializeOwned>(
+    experimental: &Option<ExperimentalCapabilities>,
+    key: &str,
+) -> Option<T> {
+    let value = experimental.as_ref()?.get(key)?;
+    serde_json::from_value(serde_json::Value::Object(value.clone())).ok()
+}
+
+impl ServerCapabilities {
+    /// Declare a vendor-specific extension in the `experimental` capabilities, e.g.
+    /// `capabilities.extension("acme/fileWatch", FileWatch { recursive: true })`.
+    ///
+    /// Fails if `value` doesn't serialize to a JSON object, as required for `experimental`
+    /// capabilities.
+    pub fn extension<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> Result<(), serde_json::Error> {
+        set_extension(&mut self.experimental, key, value)
+    }
+
+    /// The extension declared under `key`, `None` if it's missing or isn't a `T`
+    pub fn get_extension<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        get_extension(&self.experimental, key)
+    }
+}
+
+impl ClientCapabilities {
+    /// Declare a vendor-specific extension in the `experimental` capabilities, usually to mirror
+    /// an extension of the server the client understands.
+    ///
+    /// Fails if `value` doesn't serialize to a JSON object, as required for `experimental`
+    /// capabilities.
+    pub fn extension<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> Result<(), serde_json::Error> {
+        set_extension(&mut self.experimental, key, value)
+    }
+
+    /// The extension declared under `key`, `None` if it's missing or isn't a `T`
+    pub fn get_extension<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        get_extension(&self.experimental, key)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -343,4 +415,41 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_extensions() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct FileWatch {
+            recursive: bool,
+        }
+
+        let mut server = ServerCapabilities::default();
+        server
+            .extension("acme/fileWatch", FileWatch { recursive: true })
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&server).unwrap(),
+            serde_json::json!({ "experimental": { "acme/fileWatch": { "recursive": true } } })
+        );
+
+        // the client mirrors the extension it understands
+        let mut client = ClientCapabilities::default();
+        let file_watch: FileWatch = server.get_extension("acme/fileWatch").unwrap();
+        client.extension("acme/fileWatch", file_watch).unwrap();
+        assert_eq!(
+            client.get_extension("acme/fileWatch"),
+            Some(FileWatch { recursive: true })
+        );
+        assert_eq!(client.get_extension::<FileWatch>("acme/codeExec"), None);
+        client
+            .extension(
+                "acme/codeExec",
+                serde_json::json!({ "languages": ["python"] }),
+            )
+            .unwrap();
+        assert_eq!(client.get_extension::<FileWatch>("acme/codeExec"), None);
+        // the experimental capabilities are objects
+        assert!(client.extension("acme/version", 2).is_err());
+        assert_eq!(client.get_extension::<u32>("acme/version"), None);
+    }
 }
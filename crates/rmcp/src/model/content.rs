@@ -1,6 +1,8 @@
 //! Content sent around agents, extensions, and LLMs
 //! The various content types can be display to humans but also understood by models
 //! They include optional annotations used to help inform agent usage
+#[cfg(feature = "base64")]
+use base64::engine::{Engine, general_purpose::STANDARD as BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -100,6 +102,13 @@ impl RawContent {
         })
     }
 
+    pub fn audio<S: Into<String>, T: Into<String>>(data: S, mime_type: T) -> Self {
+        RawContent::Audio(RawAudioContent {
+            data: data.into(),
+            mime_type: mime_type.into(),
+        })
+    }
+
     pub fn resource(resource: ResourceContents) -> Self {
         RawContent::Resource(RawEmbeddedResource {
             meta: None,
@@ -135,6 +144,14 @@ impl RawContent {
         }
     }
 
+    /// Get the audio content if this is an AudioContent variant
+    pub fn as_audio(&self) -> Option<&RawAudioContent> {
+        match self {
+            RawContent::Audio(audio) => Some(audio),
+            _ => None,
+        }
+    }
+
     /// Get the resource content if this is an ImageContent variant
     pub fn as_resource(&self) -> Option<&RawEmbeddedResource> {
         match self {
@@ -162,10 +179,72 @@ impl Content {
         RawContent::text(text).no_annotation()
     }
 
+    /// Create an image content from base64-encoded data, see [`Content::image_bytes`] for raw bytes.
     pub fn image<S: Into<String>, T: Into<String>>(data: S, mime_type: T) -> Self {
         RawContent::image(data, mime_type).no_annotation()
     }
 
+    /// Create an image content from raw bytes, the data will be base64-encoded.
+    #[cfg(feature = "base64")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+    pub fn image_bytes<T: Into<String>>(data: impl AsRef<[u8]>, mime_type: T) -> Self {
+        Self::image(BASE64_STANDARD.encode(data), mime_type)
+    }
+
+    /// Create an audio content from base64-encoded data, see [`Content::audio_bytes`] for raw bytes.
+    pub fn audio<S: Into<String>, T: Into<String>>(data: S, mime_type: T) -> Self {
+        RawContent::audio(data, mime_type).no_annotation()
+    }
+
+    /// Create an audio content from raw bytes, the data will be base64-encoded.
+    #[cfg(feature = "base64")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+    pub fn audio_bytes<T: Into<String>>(data: impl AsRef<[u8]>, mime_type: T) -> Self {
+        Self::audio(BASE64_STANDARD.encode(data), mime_type)
+    }
+
+    /// Read a file into a content, the MIME type is guessed from the file extension.
+    ///
+    /// Images and audios become [`RawContent::Image`] and [`RawContent::Audio`], any other file
+    /// becomes an embedded resource with a `file://` uri, as text if it's valid UTF-8. The uri is
+    /// made of the absolute path, percent-encoded.
+    #[cfg(feature = "mime-guess")]
+    pub fn from_file_path(path: &std::path::Path) -> Result<Self, std::io::Error> {
+        let data = std::fs::read(path)?;
+        let mime_type = mime_guess::from_path(path).first_or_octet_stream();
+        let content = match mime_type.type_() {
+            mime_guess::mime::IMAGE => Self::image_bytes(data, mime_type.essence_str()),
+            mime_guess::mime::AUDIO => Self::audio_bytes(data, mime_type.essence_str()),
+            _ => {
+                let uri = url::Url::from_file_path(std::path::absolute(path)?)
+                    .map_err(|()| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("no file uri for {}", path.display()),
+                        )
+                    })?
+                    .to_string();
+                let mime_type = Some(mime_type.essence_str().to_string());
+                let resource = match String::from_utf8(data) {
+                    Ok(text) => ResourceContents::TextResourceContents {
+                        uri,
+                        mime_type,
+                        text,
+                        meta: None,
+                    },
+                    Err(e) => ResourceContents::BlobResourceContents {
+                        uri,
+                        mime_type,
+                        blob: BASE64_STANDARD.encode(e.into_bytes()),
+                        meta: None,
+                    },
+                };
+                Self::resource(resource)
+            }
+        };
+        Ok(content)
+    }
+
     pub fn resource(resource: ResourceContents) -> Self {
         RawContent::resource(resource).no_annotation()
     }
@@ -290,4 +369,76 @@ mod tests {
             panic!("Expected ResourceLink variant");
         }
     }
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+    const WAV: &[u8] = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_image_content_from_bytes_round_trip() {
+        let content = Content::image_bytes(PNG, "image/png");
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["type"], "image");
+        assert_eq!(json["mimeType"], "image/png");
+
+        let content: Content = serde_json::from_value(json).unwrap();
+        let image = content.as_image().expect("image content");
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(BASE64_STANDARD.decode(&image.data).unwrap(), PNG);
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_audio_content_from_bytes_round_trip() {
+        let content = Content::audio_bytes(WAV, "audio/wav");
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["type"], "audio");
+        assert_eq!(json["mimeType"], "audio/wav");
+
+        let content: Content = serde_json::from_value(json).unwrap();
+        let audio = content.as_audio().expect("audio content");
+        assert_eq!(audio.mime_type, "audio/wav");
+        assert_eq!(BASE64_STANDARD.decode(&audio.data).unwrap(), WAV);
+    }
+
+    #[cfg(feature = "mime-guess")]
+    #[test]
+    fn test_content_from_file_path() {
+        let dir = std::env::temp_dir().join(format!("rmcp-content-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let png = dir.join("image.png");
+        let wav = dir.join("sound.wav");
+        let txt = dir.join("notes.txt");
+        std::fs::write(&png, PNG).unwrap();
+        std::fs::write(&wav, WAV).unwrap();
+        std::fs::write(&txt, "hello").unwrap();
+
+        let image = Content::from_file_path(&png).unwrap();
+        assert_eq!(image.as_image().unwrap().mime_type, "image/png");
+        let audio = Content::from_file_path(&wav).unwrap();
+        assert!(audio.as_audio().unwrap().mime_type.contains("wav"));
+        let text = Content::from_file_path(&txt).unwrap();
+        assert!(matches!(
+            &text.as_resource().unwrap().resource,
+            ResourceContents::TextResourceContents { text, .. } if text == "hello"
+        ));
+        assert!(Content::from_file_path(&dir.join("missing.png")).is_err());
+
+        // the uri is absolute and percent-encoded
+        let spaced = dir.join("my notes #1.txt");
+        std::fs::write(&spaced, "hello").unwrap();
+        let spaced = Content::from_file_path(&spaced).unwrap();
+        let uri = spaced.as_resource().unwrap().resource.uri();
+        assert!(uri.starts_with("file:///"), "{uri}");
+        assert!(uri.ends_with("/my%20notes%20%231.txt"), "{uri}");
+        let relative = Content::from_file_path(std::path::Path::new("Cargo.toml")).unwrap();
+        let expected =
+            url::Url::from_file_path(std::env::current_dir().unwrap().join("Cargo.toml")).unwrap();
+        assert_eq!(
+            relative.as_resource().unwrap().resource.uri(),
+            expected.as_str()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -93,6 +93,25 @@ impl Extensions {
             .and_then(|boxed| (**boxed).as_any().downcast_ref())
     }
 
+    /// Check whether a type was inserted on this `Extensions`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rmcp::model::Extensions;
+    /// let mut ext = Extensions::new();
+    /// assert!(!ext.contains::<i32>());
+    /// ext.insert(5i32);
+    ///
+    /// assert!(ext.contains::<i32>());
+    /// assert!(!ext.contains::<u32>());
+    /// ```
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map
+            .as_ref()
+            .is_some_and(|map| map.contains_key(&TypeId::of::<T>()))
+    }
+
     /// Get a mutable reference to a type previously inserted on this `Extensions`.
     ///
     /// # Example
@@ -335,3 +354,41 @@ fn test_extensions() {
     assert_eq!(extensions.get::<bool>(), None);
     assert_eq!(extensions.get(), Some(&MyType(10)));
 }
+
+#[test]
+fn test_extensions_clone_is_deep() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Session {
+        id: String,
+        scopes: Vec<String>,
+    }
+
+    let mut extensions = Extensions::new();
+    assert!(!extensions.contains::<Session>());
+    extensions.insert(Session {
+        id: "a".into(),
+        scopes: vec!["read".into()],
+    });
+    assert!(extensions.contains::<Session>());
+
+    let mut cloned = extensions.clone();
+    cloned
+        .get_mut::<Session>()
+        .unwrap()
+        .scopes
+        .push("write".into());
+    cloned.insert(7u64);
+
+    // the original is untouched by changes to the clone
+    assert_eq!(extensions.get::<Session>().unwrap().scopes, vec!["read"]);
+    assert!(!extensions.contains::<u64>());
+    assert_eq!(
+        cloned.get::<Session>().unwrap().scopes,
+        vec!["read", "write"]
+    );
+
+    assert_eq!(cloned.remove::<Session>().unwrap().id, "a");
+    assert!(!cloned.contains::<Session>());
+    assert!(extensions.contains::<Session>());
+    assert_eq!(cloned.len(), 1);
+}
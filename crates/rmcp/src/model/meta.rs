@@ -96,6 +96,8 @@ variant_extension! {
         ResourceListChangedNotification
         ToolListChangedNotification
         PromptListChangedNotification
+        ResourceChunkNotification
+        ResourceChunkEndNotification
     }
 }
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
@@ -103,11 +105,36 @@ variant_extension! {
 #[serde(transparent)]
 pub struct Meta(pub JsonObject);
 const PROGRESS_TOKEN_FIELD: &str = "progressToken";
+const TRACE_PARENT_FIELD: &str = "traceparent";
+const TRACE_STATE_FIELD: &str = "tracestate";
+const PAGE_SIZE_FIELD: &str = "pageSize";
+const ACCEPT_FIELD: &str = "accept";
 impl Meta {
     pub fn new() -> Self {
         Self(JsonObject::new())
     }
 
+    /// No metadata, same as [`Meta::new`]
+    pub fn empty() -> Self {
+        Self::new()
+    }
+
+    /// Build the metadata of a request or a notification.
+    ///
+    /// ```rust
+    /// # use rmcp::model::{Meta, NumberOrString, ProgressToken};
+    /// let meta = Meta::builder()
+    ///     .progress_token(ProgressToken(NumberOrString::Number(1)))
+    ///     .trace_parent("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")
+    ///     .custom("tenant", "acme".into())
+    ///     .build();
+    /// assert_eq!(meta["progressToken"], 1);
+    /// assert_eq!(meta["tenant"], "acme");
+    /// ```
+    pub fn builder() -> MetaBuilder {
+        MetaBuilder::default()
+    }
+
     pub(crate) fn static_empty() -> &'static Self {
         static EMPTY: std::sync::OnceLock<Meta> = std::sync::OnceLock::new();
         EMPTY.get_or_init(Default::default)
@@ -145,6 +172,42 @@ impl Meta {
         };
     }
 
+    /// The number of items per page requested by a `*/list` request, an extension to the
+    /// specification which servers are free to ignore
+    pub fn get_page_size(&self) -> Option<u32> {
+        self.0
+            .get(PAGE_SIZE_FIELD)
+            .and_then(Value::as_u64)
+            .and_then(|page_size| u32::try_from(page_size).ok())
+    }
+
+    pub fn set_page_size(&mut self, page_size: u32) {
+        self.0
+            .insert(PAGE_SIZE_FIELD.to_string(), Value::Number(page_size.into()));
+    }
+
+    /// The MIME types preferred by a `resources/read` request, like the HTTP `Accept` header, an
+    /// extension to the specification which servers are free to ignore, see
+    /// [`ResourceContents::negotiate`](super::ResourceContents::negotiate)
+    pub fn get_accept(&self) -> Option<Vec<String>> {
+        let accept = self.0.get(ACCEPT_FIELD)?.as_array()?;
+        Some(
+            accept
+                .iter()
+                .filter_map(|accept| accept.as_str().map(str::to_owned))
+                .collect(),
+        )
+    }
+
+    pub fn set_accept(&mut self, accept: impl IntoIterator<Item = impl Into<String>>) {
+        let accept = accept
+            .into_iter()
+            .map(|accept| Value::String(accept.into()))
+            .collect();
+        self.0
+            .insert(ACCEPT_FIELD.to_string(), Value::Array(accept));
+    }
+
     pub fn extend(&mut self, other: Meta) {
         for (k, v) in other.0.into_iter() {
             self.0.insert(k, v);
@@ -152,6 +215,51 @@ impl Meta {
     }
 }
 
+/// Builder of [`Meta`], see [`Meta::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct MetaBuilder {
+    meta: Meta,
+}
+
+impl MetaBuilder {
+    pub fn progress_token(mut self, token: ProgressToken) -> Self {
+        self.meta.set_progress_token(token);
+        self
+    }
+
+    /// The W3C trace context `traceparent` of the request, to continue its trace on the peer
+    pub fn trace_parent(self, trace_parent: &str) -> Self {
+        self.custom(TRACE_PARENT_FIELD, Value::String(trace_parent.to_owned()))
+    }
+
+    /// The W3C trace context `tracestate` of the request
+    pub fn trace_state(self, trace_state: &str) -> Self {
+        self.custom(TRACE_STATE_FIELD, Value::String(trace_state.to_owned()))
+    }
+
+    /// The number of items per page of a `*/list` request, see [`Meta::get_page_size`]
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.meta.set_page_size(page_size);
+        self
+    }
+
+    /// The MIME types preferred by a `resources/read` request, see [`Meta::get_accept`]
+    pub fn accept(mut self, accept: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.meta.set_accept(accept);
+        self
+    }
+
+    /// Set any other field, replacing the value set before for the same key
+    pub fn custom(mut self, key: &str, value: Value) -> Self {
+        self.meta.0.insert(key.to_owned(), value);
+        self
+    }
+
+    pub fn build(self) -> Meta {
+        self.meta
+    }
+}
+
 impl Deref for Meta {
     type Target = JsonObject;
 
@@ -186,3 +294,40 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_meta_builder() {
+        let meta = Meta::builder()
+            .progress_token(ProgressToken(NumberOrString::String("p-1".into())))
+            .trace_parent("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")
+            .trace_state("vendor=value")
+            .custom("tenant", json!("acme"))
+            .custom("tenant", json!({ "id": 7 }))
+            .build();
+        assert_eq!(
+            serde_json::to_value(&meta).unwrap(),
+            json!({
+                "progressToken": "p-1",
+                "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+                "tracestate": "vendor=value",
+                "tenant": { "id": 7 },
+            })
+        );
+        assert_eq!(
+            meta.get_progress_token(),
+            Some(ProgressToken(NumberOrString::String("p-1".into())))
+        );
+        let round_trip: Meta =
+            serde_json::from_value(serde_json::to_value(&meta).unwrap()).unwrap();
+        assert_eq!(round_trip, meta);
+
+        assert_eq!(Meta::builder().build(), Meta::empty());
+        assert!(Meta::empty().is_empty());
+    }
+}
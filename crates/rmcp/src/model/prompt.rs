@@ -2,7 +2,7 @@ use base64::engine::{Engine, general_purpose::STANDARD as BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    AnnotateAble, Annotations, Icon, RawEmbeddedResource, RawImageContent,
+    AnnotateAble, Annotations, ErrorData, Icon, JsonObject, RawEmbeddedResource, RawImageContent,
     content::{EmbeddedResource, ImageContent},
     resource::ResourceContents,
 };
@@ -28,6 +28,23 @@ pub struct Prompt {
 }
 
 impl Prompt {
+    /// Apply the defaults and validation patterns of the declared arguments to the arguments
+    /// of a `prompts/get` request, see [`PromptArgument::apply`].
+    pub fn apply_arguments(
+        &self,
+        arguments: Option<JsonObject>,
+    ) -> Result<Option<JsonObject>, ErrorData> {
+        let Some(declared) = &self.arguments else {
+            return Ok(arguments);
+        };
+        let had_arguments = arguments.is_some();
+        let mut arguments = arguments.unwrap_or_default();
+        for argument in declared {
+            argument.apply(&mut arguments)?;
+        }
+        Ok((had_arguments || !arguments.is_empty()).then_some(arguments))
+    }
+
     /// Create a new prompt with the given name, description and arguments
     pub fn new<N, D>(
         name: N,
@@ -50,6 +67,7 @@ impl Prompt {
 
 /// Represents a prompt argument that can be passed to customize the prompt
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PromptArgument {
     /// The name of the argument
@@ -63,6 +81,137 @@ pub struct PromptArgument {
     /// Whether this argument is required
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+    /// The value used when the argument is not provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// A regular expression the argument value must match, as a JSON Schema `pattern`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_pattern: Option<String>,
+}
+
+impl PromptArgument {
+    pub fn builder() -> PromptArgumentBuilder {
+        PromptArgumentBuilder::default()
+    }
+
+    /// Fill in the default value of this argument when it's missing, and check the value
+    /// against the validation pattern.
+    ///
+    /// The pattern is only checked when the `prompt-validation` feature is enabled, otherwise a
+    /// warning is logged.
+    pub fn apply(&self, arguments: &mut JsonObject) -> Result<(), ErrorData> {
+        if !arguments.contains_key(&self.name) {
+            if let Some(default_value) = &self.default_value {
+                arguments.insert(self.name.clone(), default_value.clone().into());
+            }
+        }
+        #[cfg(not(feature = "prompt-validation"))]
+        if let Some(pattern) = &self.validation_pattern {
+            tracing::warn!(
+                argument = %self.name,
+                %pattern,
+                "validation pattern not checked, enable the `prompt-validation` feature"
+            );
+        }
+        #[cfg(feature = "prompt-validation")]
+        if let (Some(pattern), Some(value)) = (&self.validation_pattern, arguments.get(&self.name))
+        {
+            let regex = cached_regex(pattern).map_err(|e| {
+                ErrorData::internal_error(
+                    format!(
+                        "invalid validation pattern for argument '{}': {e}",
+                        self.name
+                    ),
+                    None,
+                )
+            })?;
+            let matched = match value {
+                serde_json::Value::String(value) => regex.is_match(value),
+                value => regex.is_match(&value.to_string()),
+            };
+            if !matched {
+                return Err(ErrorData::invalid_params(
+                    format!(
+                        "argument '{}' does not match pattern '{pattern}'",
+                        self.name
+                    ),
+                    Some(serde_json::json!({ "argument": self.name, "pattern": pattern })),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compile a validation pattern with a cache shared by all threads, so each pattern is compiled
+/// once per process
+#[cfg(feature = "prompt-validation")]
+fn cached_regex(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    static CACHE_FOR_PATTERN: std::sync::OnceLock<
+        std::sync::RwLock<std::collections::HashMap<String, regex::Regex>>,
+    > = std::sync::OnceLock::new();
+    let cache = CACHE_FOR_PATTERN.get_or_init(Default::default);
+    if let Some(regex) = cache
+        .read()
+        .expect("pattern cache lock poisoned")
+        .get(pattern)
+    {
+        return Ok(regex.clone());
+    }
+    let regex = regex::Regex::new(pattern)?;
+    cache
+        .write()
+        .expect("pattern cache lock poisoned")
+        .insert(pattern.to_owned(), regex.clone());
+    Ok(regex)
+}
+
+/// Builder for [`PromptArgument`]
+#[derive(Debug, Clone, Default)]
+pub struct PromptArgumentBuilder {
+    name: String,
+    title: Option<String>,
+    description: Option<String>,
+    required: Option<bool>,
+    default_value: Option<String>,
+    validation_pattern: Option<String>,
+}
+
+impl PromptArgumentBuilder {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+        self
+    }
+    pub fn default_value(mut self, default_value: impl Into<String>) -> Self {
+        self.default_value = Some(default_value.into());
+        self
+    }
+    pub fn validation_pattern(mut self, validation_pattern: impl Into<String>) -> Self {
+        self.validation_pattern = Some(validation_pattern.into());
+        self
+    }
+    pub fn build(self) -> PromptArgument {
+        PromptArgument {
+            name: self.name,
+            title: self.title,
+            description: self.description,
+            required: self.required,
+            default_value: self.default_value,
+            validation_pattern: self.validation_pattern,
+        }
+    }
 }
 
 /// Represents the role of a message sender in a prompt conversation
@@ -263,4 +412,64 @@ mod tests {
             panic!("Expected ResourceLink variant");
         }
     }
+
+    #[test]
+    fn test_prompt_argument_default_value() {
+        let prompt = Prompt::new(
+            "greet",
+            Some("greet someone"),
+            Some(vec![
+                PromptArgument::builder()
+                    .name("name")
+                    .required(true)
+                    .build(),
+                PromptArgument::builder()
+                    .name("greeting")
+                    .default_value("Hello")
+                    .build(),
+            ]),
+        );
+        let json = serde_json::to_value(&prompt).unwrap();
+        assert_eq!(json["arguments"][1]["defaultValue"], "Hello");
+
+        let arguments = prompt
+            .apply_arguments(Some(crate::model::object(
+                serde_json::json!({ "name": "Ferris" }),
+            )))
+            .unwrap()
+            .unwrap();
+        assert_eq!(arguments["greeting"], "Hello");
+
+        let arguments = prompt
+            .apply_arguments(Some(crate::model::object(
+                serde_json::json!({ "name": "Ferris", "greeting": "Hi" }),
+            )))
+            .unwrap()
+            .unwrap();
+        assert_eq!(arguments["greeting"], "Hi");
+    }
+
+    #[cfg(feature = "prompt-validation")]
+    #[test]
+    fn test_prompt_argument_validation_pattern() {
+        let argument = PromptArgument::builder()
+            .name("code")
+            .validation_pattern("^[A-Z]{3}$")
+            .build();
+        let mut arguments = crate::model::object(serde_json::json!({ "code": "ABC" }));
+        assert!(argument.apply(&mut arguments).is_ok());
+        let mut arguments = crate::model::object(serde_json::json!({ "code": "abcd" }));
+        let error = argument.apply(&mut arguments).unwrap_err();
+        assert_eq!(error.code, crate::model::ErrorCode::INVALID_PARAMS);
+        // the compiled pattern is reused
+        let mut arguments = crate::model::object(serde_json::json!({ "code": "XYZ" }));
+        assert!(argument.apply(&mut arguments).is_ok());
+
+        let argument = PromptArgument::builder()
+            .name("code")
+            .validation_pattern("[A-Z")
+            .build();
+        let error = argument.apply(&mut arguments).unwrap_err();
+        assert_eq!(error.code, crate::model::ErrorCode::INTERNAL_ERROR);
+    }
 }
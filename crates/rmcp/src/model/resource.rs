@@ -1,3 +1,6 @@
+#[cfg(feature = "uri-templates")]
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::{Annotated, Icon, Meta};
@@ -49,6 +52,282 @@ pub struct RawResourceTemplate {
 
 pub type ResourceTemplate = Annotated<RawResourceTemplate>;
 
+#[cfg(feature = "uri-templates")]
+impl RawResourceTemplate {
+    /// Parse [`RawResourceTemplate::uri_template`] as an [`UriTemplate`].
+    pub fn to_uri_template(&self) -> UriTemplate {
+        UriTemplate::new(self.uri_template.clone())
+    }
+
+    /// Expand the uri template, every variable of the template must have a value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use rmcp::model::{RawResourceTemplate, TemplateError};
+    /// let template = RawResourceTemplate {
+    ///     uri_template: "repo://{owner}/{name}".into(),
+    ///     name: "repository".into(),
+    ///     title: None,
+    ///     description: None,
+    ///     mime_type: None,
+    /// };
+    /// let vars = HashMap::from([("owner", "modelcontextprotocol"), ("name", "rust sdk")]);
+    /// assert_eq!(template.expand(&vars).unwrap(), "repo://modelcontextprotocol/rust%20sdk");
+    /// assert_eq!(
+    ///     template.expand(&HashMap::from([("owner", "modelcontextprotocol")])),
+    ///     Err(TemplateError::MissingVariable("name".into()))
+    /// );
+    /// ```
+    pub fn expand(&self, vars: &HashMap<&str, &str>) -> Result<String, TemplateError> {
+        let template = self.to_uri_template();
+        let mut variables = HashMap::new();
+        for name in template.variables() {
+            let Some(value) = vars.get(name.as_str()) else {
+                return Err(TemplateError::MissingVariable(name));
+            };
+            variables.insert(name, value.to_string());
+        }
+        Ok(template.expand(&variables))
+    }
+
+    /// Match an uri against the uri template, see [`UriTemplate::matches`].
+    pub fn matches(&self, uri: &str) -> Option<HashMap<String, String>> {
+        self.to_uri_template().matches(uri)
+    }
+}
+
+#[cfg(feature = "uri-templates")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TemplateError {
+    #[error("no value for the variable `{0}` of the uri template")]
+    MissingVariable(String),
+}
+
+/// Everything but the unreserved characters of an uri, which are the only ones kept by the simple
+/// expansion of RFC 6570, so a value can't add a path, a query or a fragment to the uri.
+#[cfg(feature = "uri-templates")]
+const URI_TEMPLATE_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+#[cfg(feature = "uri-templates")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UriTemplatePart {
+    Literal(String),
+    Variable(String),
+}
+
+/// A [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570) level 1 uri template, like `file://{path}`.
+///
+/// Expanded values are percent-encoded but for the unreserved characters, as by the simple
+/// expansion of RFC 6570, so a value like `/tmp/foo.txt` is expanded to `%2Ftmp%2Ffoo.txt` and
+/// can't add segments to the uri. An unclosed `{` is kept as a literal.
+///
+/// # Example
+/// ```rust
+/// # use rmcp::model::UriTemplate;
+/// let template = UriTemplate::new("repo://{owner}/{name}");
+/// assert_eq!(template.variables(), vec!["owner", "name"]);
+/// let vars = template.matches("repo://modelcontextprotocol/rust-sdk").unwrap();
+/// assert_eq!(vars["owner"], "modelcontextprotocol");
+/// assert_eq!(vars["name"], "rust-sdk");
+/// ```
+#[cfg(feature = "uri-templates")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    template: String,
+    parts: Vec<UriTemplatePart>,
+}
+
+#[cfg(feature = "uri-templates")]
+impl UriTemplate {
+    pub fn new(template: impl Into<String>) -> Self {
+        let template = template.into();
+        let mut parts = Vec::new();
+        let mut rest = template.as_str();
+        while !rest.is_empty() {
+            let Some((literal, expression)) = rest
+                .split_once('{')
+                .and_then(|(literal, tail)| Some((literal, tail.split_once('}')?)))
+            else {
+                Self::push_literal(&mut parts, rest);
+                break;
+            };
+            let (name, tail) = expression;
+            Self::push_literal(&mut parts, literal);
+            parts.push(UriTemplatePart::Variable(name.to_string()));
+            rest = tail;
+        }
+        Self { template, parts }
+    }
+
+    fn push_literal(parts: &mut Vec<UriTemplatePart>, literal: &str) {
+        if literal.is_empty() {
+            return;
+        }
+        if let Some(UriTemplatePart::Literal(last)) = parts.last_mut() {
+            last.push_str(literal);
+        } else {
+            parts.push(UriTemplatePart::Literal(literal.to_string()));
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// The names of the variables in this template, in order of appearance.
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables: Vec<String> = Vec::new();
+        for part in &self.parts {
+            if let UriTemplatePart::Variable(name) = part {
+                if !variables.contains(name) {
+                    variables.push(name.clone());
+                }
+            }
+        }
+        variables
+    }
+
+    /// Expand the template, undefined variables expand to an empty string.
+    pub fn expand(&self, variables: &HashMap<String, String>) -> String {
+        let mut uri = String::with_capacity(self.template.len());
+        for part in &self.parts {
+            match part {
+                UriTemplatePart::Literal(literal) => uri.push_str(literal),
+                UriTemplatePart::Variable(name) => {
+                    if let Some(value) = variables.get(name) {
+                        uri.extend(percent_encoding::utf8_percent_encode(
+                            value,
+                            URI_TEMPLATE_ENCODE_SET,
+                        ));
+                    }
+                }
+            }
+        }
+        uri
+    }
+
+    /// Match an uri against this template, returns the decoded value of every variable on success.
+    ///
+    /// A variable takes everything up to the first occurrence of the literal following it,
+    /// or the rest of the uri if it's the last part of the template.
+    pub fn matches(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let mut variables = HashMap::new();
+        let mut rest = uri;
+        let mut parts = self.parts.iter().peekable();
+        while let Some(part) = parts.next() {
+            match part {
+                UriTemplatePart::Literal(literal) => {
+                    rest = rest.strip_prefix(literal.as_str())?;
+                }
+                UriTemplatePart::Variable(name) => {
+                    let value = match parts.peek() {
+                        Some(UriTemplatePart::Literal(next)) => {
+                            let end = rest.find(next.as_str())?;
+                            let (value, tail) = rest.split_at(end);
+                            rest = tail;
+                            value
+                        }
+                        // adjacent variables are ambiguous, the first one takes nothing
+                        Some(UriTemplatePart::Variable(_)) => "",
+                        None => std::mem::take(&mut rest),
+                    };
+                    let value = percent_encoding::percent_decode_str(value)
+                        .decode_utf8()
+                        .ok()?
+                        .into_owned();
+                    match variables.get(name) {
+                        Some(existing) if existing != &value => return None,
+                        _ => {
+                            variables.insert(name.clone(), value);
+                        }
+                    }
+                }
+            }
+        }
+        rest.is_empty().then_some(variables)
+    }
+}
+
+#[cfg(feature = "uri-templates")]
+impl std::fmt::Display for UriTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+#[cfg(feature = "uri-templates")]
+impl std::str::FromStr for UriTemplate {
+    type Err = std::convert::Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+/// Build an uri from an [`UriTemplate`].
+///
+/// # Example
+/// ```rust
+/// # use rmcp::model::UriTemplateBuilder;
+/// let uri = UriTemplateBuilder::new("file:///tmp/{name}").expand("name", "foo.txt");
+/// assert_eq!(uri, "file:///tmp/foo.txt");
+///
+/// let uri = UriTemplateBuilder::new("repo://{owner}/{name}")
+///     .with("owner", "modelcontextprotocol")
+///     .with("name", "rust-sdk")
+///     .build();
+/// assert_eq!(uri, "repo://modelcontextprotocol/rust-sdk");
+/// ```
+#[cfg(feature = "uri-templates")]
+#[derive(Debug, Clone)]
+pub struct UriTemplateBuilder {
+    template: UriTemplate,
+    variables: HashMap<String, String>,
+}
+
+#[cfg(feature = "uri-templates")]
+impl UriTemplateBuilder {
+    pub fn new(template: impl Into<UriTemplate>) -> Self {
+        Self {
+            template: template.into(),
+            variables: HashMap::new(),
+        }
+    }
+
+    /// Set the value of a variable.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the value of a variable and expand the template.
+    pub fn expand(self, name: impl Into<String>, value: impl Into<String>) -> String {
+        self.with(name, value).build()
+    }
+
+    pub fn build(self) -> String {
+        self.template.expand(&self.variables)
+    }
+}
+
+#[cfg(feature = "uri-templates")]
+impl From<&str> for UriTemplate {
+    fn from(template: &str) -> Self {
+        Self::new(template)
+    }
+}
+
+#[cfg(feature = "uri-templates")]
+impl From<String> for UriTemplate {
+    fn from(template: String) -> Self {
+        Self::new(template)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -71,6 +350,19 @@ pub enum ResourceContents {
         #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
         meta: Option<Meta>,
     },
+    /// A resource whose content is delivered by `notifications/resources/chunk` messages
+    /// with the same `stream_token`, followed by a `notifications/resources/chunk_end`.
+    ///
+    /// Only the metadata is serialized, the chunks are sent by the server after the response.
+    #[serde(rename_all = "camelCase")]
+    StreamingResource {
+        uri: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+        stream_token: String,
+        #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+        meta: Option<Meta>,
+    },
 }
 
 impl ResourceContents {
@@ -82,6 +374,120 @@ impl ResourceContents {
             meta: None,
         }
     }
+
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::TextResourceContents { uri, .. }
+            | Self::BlobResourceContents { uri, .. }
+            | Self::StreamingResource { uri, .. } => uri,
+        }
+    }
+
+    pub fn mime_type(&self) -> Option<&str> {
+        match self {
+            Self::TextResourceContents { mime_type, .. }
+            | Self::BlobResourceContents { mime_type, .. }
+            | Self::StreamingResource { mime_type, .. } => mime_type.as_deref(),
+        }
+    }
+
+    /// Pick the contents matching best the accepted MIME types of a `resources/read` request, set
+    /// in its `_meta` (see [`Meta::get_accept`](crate::model::Meta::get_accept)), like an HTTP
+    /// server does with the `Accept` header.
+    ///
+    /// Each accepted type may be a range (`text/*`, `*/*`) with a `q` weight, 1 by default. The
+    /// contents are weighted by the most specific range matching their MIME type, and the first
+    /// contents with the highest weight is picked. `None` is returned if no contents is
+    /// acceptable; without accepted type, the first contents is picked.
+    ///
+    /// ```rust
+    /// # use rmcp::model::{Meta, ResourceContents};
+    /// let contents = [
+    ///     ResourceContents::TextResourceContents {
+    ///         uri: "config://app".into(),
+    ///         mime_type: Some("text/plain".into()),
+    ///         text: "port: 8080".into(),
+    ///         meta: None,
+    ///     },
+    ///     ResourceContents::TextResourceContents {
+    ///         uri: "config://app".into(),
+    ///         mime_type: Some("application/json".into()),
+    ///         text: r#"{"port":8080}"#.into(),
+    ///         meta: None,
+    ///     },
+    /// ];
+    /// let meta = Meta::builder()
+    ///     .accept(["text/*;q=0.5", "application/json"])
+    ///     .build();
+    /// let accept = meta.get_accept().unwrap_or_default();
+    /// let best = ResourceContents::negotiate(&contents, &accept).unwrap();
+    /// assert_eq!(best.mime_type(), Some("application/json"));
+    /// ```
+    pub fn negotiate<'a>(
+        contents: &'a [ResourceContents],
+        accept: &[String],
+    ) -> Option<&'a ResourceContents> {
+        let ranges: Vec<MediaRange> = accept
+            .iter()
+            .flat_map(|accept| accept.split(','))
+            .filter_map(MediaRange::parse)
+            .collect();
+        if ranges.is_empty() {
+            return contents.first();
+        }
+        let mut best: Option<(&ResourceContents, f32)> = None;
+        for candidate in contents {
+            let quality = MediaRange::quality(&ranges, candidate.mime_type());
+            if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((candidate, quality));
+            }
+        }
+        best.map(|(contents, _)| contents)
+    }
+}
+
+/// An accepted MIME type of [`ResourceContents::negotiate`], e.g. `text/*;q=0.5`
+#[derive(Debug)]
+struct MediaRange {
+    /// The type and subtype in lowercase, `*` for any
+    essence: String,
+    quality: f32,
+}
+
+impl MediaRange {
+    fn parse(range: &str) -> Option<Self> {
+        let mut parts = range.split(';');
+        let essence = parts.next()?.trim().to_ascii_lowercase();
+        if essence.is_empty() {
+            return None;
+        }
+        let quality = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .map_or(1.0, |q| q.clamp(0.0, 1.0));
+        Some(Self { essence, quality })
+    }
+
+    /// How specific the range is for `mime_type`, `None` if it doesn't match
+    fn specificity(&self, mime_type: Option<&str>) -> Option<u8> {
+        if self.essence == "*" || self.essence == "*/*" {
+            return Some(0);
+        }
+        let mime_type = mime_type?.split(';').next()?.trim().to_ascii_lowercase();
+        match self.essence.strip_suffix("/*") {
+            Some(ty) => (mime_type.split('/').next() == Some(ty)).then_some(1),
+            None => (mime_type == self.essence).then_some(2),
+        }
+    }
+
+    /// The weight of the most specific range matching `mime_type`, 0 if none does
+    fn quality(ranges: &[MediaRange], mime_type: Option<&str>) -> f32 {
+        ranges
+            .iter()
+            .filter_map(|range| Some((range.specificity(mime_type)?, range.quality)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(0.0, |(_, quality)| quality)
+    }
 }
 
 impl RawResource {
@@ -141,4 +547,142 @@ mod tests {
         assert!(json.contains("mimeType"));
         assert!(!json.contains("mime_type"));
     }
+
+    #[test]
+    fn test_streaming_resource_serialization() {
+        let contents = ResourceContents::StreamingResource {
+            uri: "file:///large.bin".to_string(),
+            mime_type: None,
+            stream_token: "resource-stream-0".to_string(),
+            meta: None,
+        };
+        let json = serde_json::to_value(&contents).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "uri": "file:///large.bin", "streamToken": "resource-stream-0" })
+        );
+        let deserialized: ResourceContents = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, contents);
+    }
+
+    #[cfg(feature = "uri-templates")]
+    #[test]
+    fn test_uri_template_expand() {
+        assert_eq!(
+            UriTemplateBuilder::new("file:///tmp/{name}").expand("name", "foo-1_a~.txt"),
+            "file:///tmp/foo-1_a~.txt"
+        );
+        assert_eq!(
+            UriTemplateBuilder::new("file://{path}").expand("path", "/tmp/my file%.txt"),
+            "file://%2Ftmp%2Fmy%20file%25.txt"
+        );
+        // a value can't add a path, a query or a fragment
+        assert_eq!(
+            UriTemplateBuilder::new("file:///srv/{name}").expand("name", "a/../b?x#y:z"),
+            "file:///srv/a%2F..%2Fb%3Fx%23y%3Az"
+        );
+        // undefined variables expand to nothing
+        assert_eq!(
+            UriTemplateBuilder::new("search://{query}/{page}")
+                .with("query", "rust")
+                .build(),
+            "search://rust/"
+        );
+        // unclosed expression is a literal
+        assert_eq!(
+            UriTemplate::new("file://{path").variables(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[cfg(feature = "uri-templates")]
+    #[test]
+    fn test_uri_template_matches() {
+        let template = UriTemplate::new("file://{path}");
+        assert_eq!(template.variables(), vec!["path"]);
+        let vars = template.matches("file:///tmp/my%20file.txt").unwrap();
+        assert_eq!(vars["path"], "/tmp/my file.txt");
+        assert!(template.matches("http://example.com").is_none());
+
+        let template = UriTemplate::new("repo://{owner}/{name}/issues");
+        assert!(template.matches("repo://a/b/pulls").is_none());
+        let vars = template.matches("repo://a/b/issues").unwrap();
+        assert_eq!(vars["owner"], "a");
+        assert_eq!(vars["name"], "b");
+
+        let mut variables = HashMap::new();
+        variables.insert("owner".to_string(), "x y".to_string());
+        variables.insert("name".to_string(), "z".to_string());
+        let uri = template.expand(&variables);
+        assert_eq!(template.matches(&uri), Some(variables));
+    }
+
+    #[cfg(feature = "uri-templates")]
+    #[test]
+    fn test_resource_template_expand_and_match() {
+        let template = RawResourceTemplate {
+            uri_template: "users://{id}/files/{path}".into(),
+            name: "user file".into(),
+            title: None,
+            description: None,
+            mime_type: None,
+        };
+        let vars = HashMap::from([("id", "42"), ("path", "notes/a b.txt"), ("unused", "x")]);
+        let uri = template.expand(&vars).unwrap();
+        assert_eq!(uri, "users://42/files/notes%2Fa%20b.txt");
+        let matched = template.matches(&uri).unwrap();
+        assert_eq!(matched["id"], "42");
+        assert_eq!(matched["path"], "notes/a b.txt");
+
+        assert_eq!(
+            template.expand(&HashMap::from([("id", "42")])),
+            Err(TemplateError::MissingVariable("path".into()))
+        );
+        assert!(template.matches("users://42/dirs/notes").is_none());
+    }
+
+    #[test]
+    fn test_negotiate_resource_contents() {
+        let contents = |mime_type: Option<&str>| ResourceContents::TextResourceContents {
+            uri: "config://app".into(),
+            mime_type: mime_type.map(Into::into),
+            text: String::new(),
+            meta: None,
+        };
+        let contents = [
+            contents(Some("text/plain; charset=utf-8")),
+            contents(Some("application/json")),
+            contents(Some("text/markdown")),
+            contents(None),
+        ];
+        let negotiate = |accept: &[&str]| {
+            let accept: Vec<String> = accept.iter().map(|a| a.to_string()).collect();
+            ResourceContents::negotiate(&contents, &accept).map(|c| c.mime_type())
+        };
+
+        assert_eq!(negotiate(&[]), Some(Some("text/plain; charset=utf-8")));
+        assert_eq!(
+            negotiate(&["application/json"]),
+            Some(Some("application/json"))
+        );
+        assert_eq!(
+            negotiate(&["TEXT/PLAIN"]),
+            Some(Some("text/plain; charset=utf-8"))
+        );
+        // the most specific range wins, then the highest weight
+        assert_eq!(
+            negotiate(&["text/*;q=0.8, text/markdown", "*/*;q=0.1"]),
+            Some(Some("text/markdown"))
+        );
+        assert_eq!(
+            negotiate(&["text/markdown;q=0.2", "application/json;q=0.9"]),
+            Some(Some("application/json"))
+        );
+        assert_eq!(
+            negotiate(&["text/*;q=0", "*/*"]),
+            Some(Some("application/json"))
+        );
+        assert_eq!(negotiate(&["image/png"]), None);
+        assert_eq!(negotiate(&["application/json;q=0"]), None);
+    }
 }
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::{Prompt, Resource, Tool};
+
+/// The tools, prompts and resources of a server, e.g. for an IDE to show what a server offers.
+///
+/// A client gets the spec of its server with `Peer<RoleClient>::fetch_server_spec`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSpec {
+    pub tools: Vec<Tool>,
+    pub prompts: Vec<Prompt>,
+    pub resources: Vec<Resource>,
+}
+
+impl ServerSpec {
+    pub const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+    /// Describe the server as a single JSON Schema document.
+    ///
+    /// The document has a `tools`, a `prompts` and a `resources` property, each with a property
+    /// per tool, prompt and resource uri:
+    /// - a tool has an `input` property with its input schema, and an `output` property with its
+    ///   output schema if any. The tool schemas are embedded with an `$id`, so their local `$ref`s
+    ///   still resolve.
+    /// - a prompt has an `arguments` property, an object of string arguments
+    /// - a resource is described by its name, description and mime type
+    pub fn to_json_schema(&self) -> Value {
+        let tools: Map<String, Value> = self
+            .tools
+            .iter()
+            .map(|tool| {
+                let mut properties = Map::new();
+                properties.insert(
+                    "input".into(),
+                    embedded_schema(
+                        &tool.input_schema,
+                        format!("urn:mcp:tool:{}:input", tool.name),
+                    ),
+                );
+                if let Some(output_schema) = &tool.output_schema {
+                    properties.insert(
+                        "output".into(),
+                        embedded_schema(
+                            output_schema,
+                            format!("urn:mcp:tool:{}:output", tool.name),
+                        ),
+                    );
+                }
+                let mut schema = json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": ["input"],
+                });
+                describe(
+                    &mut schema,
+                    tool.title.as_deref(),
+                    tool.description.as_deref(),
+                );
+                (tool.name.to_string(), schema)
+            })
+            .collect();
+
+        let prompts: Map<String, Value> = self
+            .prompts
+            .iter()
+            .map(|prompt| {
+                let arguments = prompt.arguments.as_deref().unwrap_or_default();
+                let properties: Map<String, Value> = arguments
+                    .iter()
+                    .map(|argument| {
+                        let mut schema = json!({ "type": "string" });
+                        describe(
+                            &mut schema,
+                            argument.title.as_deref(),
+                            argument.description.as_deref(),
+                        );
+                        if let Some(default_value) = &argument.default_value {
+                            schema["default"] = json!(default_value);
+                        }
+                        if let Some(pattern) = &argument.validation_pattern {
+                            schema["pattern"] = json!(pattern);
+                        }
+                        (argument.name.clone(), schema)
+                    })
+                    .collect();
+                let required: Vec<&str> = arguments
+                    .iter()
+                    .filter(|argument| argument.required == Some(true))
+                    .map(|argument| argument.name.as_str())
+                    .collect();
+                let mut schema = json!({
+                    "type": "object",
+                    "properties": {
+                        "arguments": {
+                            "type": "object",
+                            "properties": properties,
+                            "required": required,
+                        },
+                    },
+                });
+                describe(
+                    &mut schema,
+                    prompt.title.as_deref(),
+                    prompt.description.as_deref(),
+                );
+                (prompt.name.clone(), schema)
+            })
+            .collect();
+
+        let resources: Map<String, Value> = self
+            .resources
+            .iter()
+            .map(|resource| {
+                let mut schema = json!({ "title": resource.name });
+                if let Some(description) = &resource.description {
+                    schema["description"] = json!(description);
+                }
+                if let Some(mime_type) = &resource.mime_type {
+                    schema["contentMediaType"] = json!(mime_type);
+                }
+                (resource.uri.clone(), schema)
+            })
+            .collect();
+
+        let section = |properties: Map<String, Value>| {
+            json!({
+                "type": "object",
+                "properties": properties,
+            })
+        };
+        json!({
+            "$schema": Self::JSON_SCHEMA_DRAFT,
+            "type": "object",
+            "properties": {
+                "tools": section(tools),
+                "prompts": section(prompts),
+                "resources": section(resources),
+            },
+        })
+    }
+}
+
+/// A schema with an `$id`, unless it has one, so its `#` references resolve against itself
+fn embedded_schema(schema: &super::JsonObject, id: String) -> Value {
+    let mut schema = schema.clone();
+    schema.entry("$id").or_insert(Value::String(id));
+    Value::Object(schema)
+}
+
+fn describe(schema: &mut Value, title: Option<&str>, description: Option<&str>) {
+    if let Some(title) = title {
+        schema["title"] = json!(title);
+    }
+    if let Some(description) = description {
+        schema["description"] = json!(description);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::model::{PromptArgument, RawResource};
+
+    #[test]
+    fn test_to_json_schema() {
+        let input_schema = crate::model::object(json!({
+            "type": "object",
+            "properties": { "unit": { "$ref": "#/definitions/Unit" } },
+            "definitions": { "Unit": { "enum": ["celsius", "kelvin"] } },
+        }));
+        let spec = ServerSpec {
+            tools: vec![Tool::new(
+                "convert",
+                "Convert a temperature",
+                Arc::new(input_schema),
+            )],
+            prompts: vec![Prompt::new(
+                "greet",
+                Some("Greet someone"),
+                Some(vec![
+                    PromptArgument::builder()
+                        .name("name")
+                        .required(true)
+                        .build(),
+                ]),
+            )],
+            resources: vec![Resource::new(
+                RawResource::new("file:///notes.txt", "notes"),
+                None,
+            )],
+        };
+        let schema = spec.to_json_schema();
+        assert_eq!(schema["$schema"], ServerSpec::JSON_SCHEMA_DRAFT);
+
+        let convert = &schema["properties"]["tools"]["properties"]["convert"];
+        assert_eq!(convert["description"], "Convert a temperature");
+        let input = &convert["properties"]["input"];
+        assert_eq!(input["$id"], "urn:mcp:tool:convert:input");
+        assert_eq!(input["properties"]["unit"]["$ref"], "#/definitions/Unit");
+        assert!(convert["properties"].get("output").is_none());
+
+        let greet = &schema["properties"]["prompts"]["properties"]["greet"];
+        assert_eq!(
+            greet["properties"]["arguments"],
+            json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"],
+            })
+        );
+
+        let notes = &schema["properties"]["resources"]["properties"]["file:///notes.txt"];
+        assert_eq!(notes["title"], "notes");
+    }
+}
@@ -89,6 +89,11 @@ impl ToolAnnotations {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn builder() -> ToolAnnotationsBuilder {
+        ToolAnnotationsBuilder::default()
+    }
+
     pub fn with_title<T>(title: T) -> Self
     where
         T: Into<String>,
@@ -132,6 +137,50 @@ impl ToolAnnotations {
     pub fn is_idempotent(&self) -> bool {
         self.idempotent_hint.unwrap_or(false)
     }
+
+    /// The tool is hinted read only, and not hinted destructive.
+    ///
+    /// Like all the hints, it can't be trusted for tools of untrusted servers.
+    pub fn is_safe(&self) -> bool {
+        self.read_only_hint == Some(true) && self.destructive_hint != Some(true)
+    }
+}
+
+/// Build a [`ToolAnnotations`], the hints not set are left out
+#[derive(Debug, Clone, Default)]
+pub struct ToolAnnotationsBuilder {
+    annotations: ToolAnnotations,
+}
+
+impl ToolAnnotationsBuilder {
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.annotations.title = Some(title.into());
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.annotations.read_only_hint = Some(read_only);
+        self
+    }
+
+    pub fn destructive(mut self, destructive: bool) -> Self {
+        self.annotations.destructive_hint = Some(destructive);
+        self
+    }
+
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.annotations.idempotent_hint = Some(idempotent);
+        self
+    }
+
+    pub fn open_world(mut self, open_world: bool) -> Self {
+        self.annotations.open_world_hint = Some(open_world);
+        self
+    }
+
+    pub fn build(self) -> ToolAnnotations {
+        self.annotations
+    }
 }
 
 impl Tool {
@@ -176,4 +225,473 @@ impl Tool {
     pub fn schema_as_json_value(&self) -> Value {
         Value::Object(self.input_schema.as_ref().clone())
     }
+
+    /// Compare two tool lists by name, e.g. before and after a configuration reload.
+    ///
+    /// A tool present in both lists is reported as changed when its description or input schema
+    /// differs. A non-empty diff is a good reason to send a `ToolListChangedNotification`.
+    pub fn diff<'a>(old: &'a [Tool], new: &'a [Tool]) -> ToolDiff<'a> {
+        let old_by_name: std::collections::HashMap<&str, &Tool> =
+            old.iter().map(|tool| (tool.name.as_ref(), tool)).collect();
+        let new_by_name: std::collections::HashMap<&str, &Tool> =
+            new.iter().map(|tool| (tool.name.as_ref(), tool)).collect();
+        let mut diff = ToolDiff::default();
+        for tool in new {
+            match old_by_name.get(tool.name.as_ref()) {
+                None => diff.added.push(tool),
+                Some(previous)
+                    if previous.description != tool.description
+                        || previous.input_schema != tool.input_schema =>
+                {
+                    diff.changed.push((previous, tool))
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|tool| !new_by_name.contains_key(tool.name.as_ref()))
+            .collect();
+        diff
+    }
+
+    /// Describe the fields that differ between two versions of a tool, e.g. after a reload.
+    ///
+    /// There is a line `path: old -> new` per changed value, with the JSON path of the value in
+    /// the tool definition, and `-` for a missing value. The diff is empty for equal tools.
+    pub fn display_diff(old: &Tool, new: &Tool) -> String {
+        fn diff_values(path: &str, old: Option<&Value>, new: Option<&Value>, out: &mut String) {
+            match (old, new) {
+                (Some(Value::Object(old)), Some(Value::Object(new))) => {
+                    let keys: std::collections::BTreeSet<&String> =
+                        old.keys().chain(new.keys()).collect();
+                    for key in keys {
+                        let path = if path.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{path}.{key}")
+                        };
+                        diff_values(&path, old.get(key), new.get(key), out);
+                    }
+                }
+                (old, new) if old != new => {
+                    let show = |value: Option<&Value>| value.map_or("-".into(), Value::to_string);
+                    out.push_str(&format!("{path}: {} -> {}\n", show(old), show(new)));
+                }
+                _ => {}
+            }
+        }
+
+        let mut out = String::new();
+        diff_values(
+            "",
+            serde_json::to_value(old).ok().as_ref(),
+            serde_json::to_value(new).ok().as_ref(),
+            &mut out,
+        );
+        out
+    }
+
+    /// Validate tool call arguments against the input schema of this tool.
+    ///
+    /// All violations are reported, not only the first one. A malformed input schema is reported
+    /// as a single error at the root path.
+    ///
+    /// Generated schemas mark optional fields with the OpenAPI `nullable` keyword, which is not
+    /// part of JSON Schema, so a `null` is accepted wherever the subschema is `nullable`, including
+    /// in the `definitions` (or `$defs`) referenced with `$ref`.
+    ///
+    /// The schema is compiled on each call, the tool router compiles it once per route instead.
+    #[cfg(feature = "schema-validation")]
+    pub fn validate_input(&self, args: &JsonObject) -> Result<(), Vec<SchemaValidationError>> {
+        self.input_validator()
+            .validate(&Value::Object(args.clone()))
+    }
+
+    #[cfg(feature = "schema-validation")]
+    pub(crate) fn input_validator(&self) -> SchemaValidator {
+        SchemaValidator::new(self.schema_as_json_value(), false)
+    }
+}
+
+/// Show the tool as `name(param: type, optional?: type) - description`, with the description
+/// truncated to 80 characters.
+impl std::fmt::Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const MAX_DESCRIPTION_CHARS: usize = 80;
+
+        let required: Vec<&str> = self
+            .input_schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        write!(f, "{}(", self.name)?;
+        let properties = self
+            .input_schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten();
+        for (i, (name, schema)) in properties.enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            let optional = if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            };
+            let ty = match schema.get("type") {
+                Some(Value::String(ty)) => ty.clone(),
+                Some(Value::Array(types)) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                _ => "any".into(),
+            };
+            write!(f, "{name}{optional}: {ty}")?;
+        }
+        f.write_str(")")?;
+        if let Some(description) = &self.description {
+            match description.char_indices().nth(MAX_DESCRIPTION_CHARS) {
+                Some((end, _)) => write!(f, " - {}...", &description[..end])?,
+                None => write!(f, " - {description}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A JSON schema compiled once, to validate many instances, see [`Tool::validate_input`].
+///
+/// The `format` keyword is only asserted if `validate_formats` is set, as it's an annotation by
+/// default since draft 2019-09.
+#[cfg(feature = "schema-validation")]
+#[derive(Clone)]
+pub(crate) struct SchemaValidator {
+    schema: Value,
+    /// The error of a malformed schema, reported on each validation
+    validator: Result<Arc<jsonschema::Validator>, String>,
+}
+
+#[cfg(feature = "schema-validation")]
+impl std::fmt::Debug for SchemaValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaValidator")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+#[cfg(feature = "schema-validation")]
+impl SchemaValidator {
+    pub(crate) fn new(schema: Value, validate_formats: bool) -> Self {
+        let validator = jsonschema::options()
+            .should_validate_formats(validate_formats)
+            .build(&schema)
+            .map(Arc::new)
+            .map_err(|e| e.to_string());
+        Self { schema, validator }
+    }
+
+    pub(crate) fn validate(&self, instance: &Value) -> Result<(), Vec<SchemaValidationError>> {
+        let validator = self.validator.as_ref().map_err(|e| {
+            vec![SchemaValidationError {
+                path: String::new(),
+                message: format!("invalid schema: {e}"),
+            }]
+        })?;
+        let errors: Vec<_> = validator
+            .iter_errors(instance)
+            .filter(|e| {
+                let nullable = e.instance.is_null()
+                    && e.schema_path
+                        .as_str()
+                        .rsplit_once('/')
+                        .and_then(|(parent, _)| resolve_schema_path(&self.schema, parent))
+                        .and_then(|parent| parent.get("nullable"))
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                !nullable
+            })
+            .map(|e| SchemaValidationError {
+                path: e.instance_path.to_string(),
+                message: e.to_string(),
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Find the subschema at a schema path of a validation error, following the local `$ref` of the
+/// path, e.g. `/properties/home/$ref/properties/zip` through `#/definitions/Address`.
+#[cfg(feature = "schema-validation")]
+fn resolve_schema_path<'a>(schema: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = schema;
+    for segment in path.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        current = match (segment.as_str(), current) {
+            ("$ref", Value::Object(object)) => {
+                let reference = object.get("$ref")?.as_str()?;
+                schema.pointer(reference.strip_prefix('#')?)?
+            }
+            (key, Value::Object(object)) => object.get(key)?,
+            (index, Value::Array(items)) => items.get(index.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// The difference between two tool lists, see [`Tool::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToolDiff<'a> {
+    pub added: Vec<&'a Tool>,
+    pub removed: Vec<&'a Tool>,
+    /// Pairs of `(old, new)` versions of the same tool
+    pub changed: Vec<(&'a Tool, &'a Tool)>,
+}
+
+impl ToolDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A violation of a tool input schema, see [`Tool::validate_input`].
+#[cfg(feature = "schema-validation")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct SchemaValidationError {
+    /// JSON pointer to the offending value in the arguments, empty for the root
+    pub path: String,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn add_tool() -> Tool {
+        Tool::new(
+            "add",
+            "add two numbers",
+            crate::model::object(json!({
+                "type": "object",
+                "properties": {
+                    "a": { "type": "number" },
+                    "b": { "type": "number" }
+                },
+                "required": ["a", "b"]
+            })),
+        )
+    }
+
+    #[test]
+    fn test_display_tool() {
+        assert_eq!(
+            add_tool().to_string(),
+            "add(a: number, b: number) - add two numbers"
+        );
+
+        let tool = Tool::new(
+            "search",
+            "x".repeat(100),
+            crate::model::object(json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": ["integer", "null"] },
+                    "filter": { "$ref": "#/$defs/Filter" }
+                },
+                "required": ["query"]
+            })),
+        );
+        assert_eq!(
+            tool.to_string(),
+            format!(
+                "search(filter?: any, limit?: integer | null, query: string) - {}...",
+                "x".repeat(80)
+            )
+        );
+    }
+
+    #[test]
+    fn test_display_diff() {
+        let old = add_tool();
+        assert_eq!(Tool::display_diff(&old, &old), "");
+
+        let mut new = add_tool();
+        new.description = Some("add two integers".into());
+        new.input_schema = Arc::new(crate::model::object(json!({
+            "type": "object",
+            "properties": {
+                "a": { "type": "integer" },
+                "b": { "type": "number" }
+            },
+            "required": ["a", "b"]
+        })));
+        new.annotations = Some(ToolAnnotations::builder().read_only(true).build());
+        assert_eq!(
+            Tool::display_diff(&old, &new),
+            "annotations: - -> {\"readOnlyHint\":true}\n\
+             description: \"add two numbers\" -> \"add two integers\"\n\
+             inputSchema.properties.a.type: \"number\" -> \"integer\"\n"
+        );
+    }
+
+    #[test]
+    fn test_tool_annotations_builder() {
+        let annotations = ToolAnnotations::builder()
+            .title("Search")
+            .read_only(true)
+            .open_world(true)
+            .build();
+        assert_eq!(
+            serde_json::to_value(&annotations).unwrap(),
+            json!({ "title": "Search", "readOnlyHint": true, "openWorldHint": true })
+        );
+        assert!(annotations.is_safe());
+
+        assert!(!ToolAnnotations::new().is_safe());
+        assert!(
+            !ToolAnnotations::builder()
+                .read_only(false)
+                .build()
+                .is_safe()
+        );
+        let contradictory = ToolAnnotations::builder()
+            .read_only(true)
+            .destructive(true)
+            .build();
+        assert!(!contradictory.is_safe());
+        assert!(
+            ToolAnnotations::builder()
+                .read_only(true)
+                .destructive(false)
+                .build()
+                .is_safe()
+        );
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_validate_input_accepts_valid_arguments() {
+        let args = crate::model::object(json!({ "a": 1, "b": 2.5 }));
+        assert_eq!(add_tool().validate_input(&args), Ok(()));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_validate_input_accepts_null_for_nullable() {
+        let tool = Tool::new(
+            "count",
+            "count things",
+            crate::model::object(json!({
+                "type": "object",
+                "properties": {
+                    "count": { "type": "integer", "nullable": true },
+                    "label": { "type": "string" }
+                }
+            })),
+        );
+        let args = crate::model::object(json!({ "count": null }));
+        assert_eq!(tool.validate_input(&args), Ok(()));
+        let args = crate::model::object(json!({ "label": null }));
+        let errors = tool.validate_input(&args).unwrap_err();
+        assert_eq!(errors[0].path, "/label");
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_validate_input_resolves_refs() {
+        let tool = Tool::new(
+            "ship",
+            "ship a parcel",
+            crate::model::object(json!({
+                "type": "object",
+                "properties": {
+                    "from": { "$ref": "#/definitions/Address" },
+                    "to": { "$ref": "#/definitions/Address" }
+                },
+                "required": ["from", "to"],
+                "definitions": {
+                    "Address": {
+                        "type": "object",
+                        "properties": {
+                            "street": { "type": "string" },
+                            "zip": { "type": "string", "nullable": true }
+                        },
+                        "required": ["street", "zip"]
+                    }
+                }
+            })),
+        );
+        let args = crate::model::object(json!({
+            "from": { "street": "1 Main St", "zip": null },
+            "to": { "street": "2 Main St", "zip": "10001" }
+        }));
+        assert_eq!(tool.validate_input(&args), Ok(()));
+        let args = crate::model::object(json!({
+            "from": { "street": 1, "zip": null },
+            "to": { "street": "2 Main St" }
+        }));
+        let errors = tool.validate_input(&args).unwrap_err();
+        let paths: Vec<_> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["/from/street", "/to"]);
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_validate_input_reports_all_errors() {
+        let args = crate::model::object(json!({ "a": "one" }));
+        let errors = add_tool().validate_input(&args).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.path == "/a"));
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.path.is_empty() && e.message.contains("\"b\""))
+        );
+    }
+
+    #[test]
+    fn test_tool_diff() {
+        let echo = Tool::new("echo", "echo the input", JsonObject::new());
+        let old = vec![
+            add_tool(),
+            echo.clone(),
+            Tool::new("sum", "sum", JsonObject::new()),
+        ];
+        assert!(Tool::diff(&old, &old).is_empty());
+
+        let mut new_echo = echo.clone();
+        new_echo.description = Some("echo the input back".into());
+        let mut new_add = add_tool();
+        // only description and schema changes are relevant
+        new_add.title = Some("Add".into());
+        let new = vec![
+            new_add,
+            new_echo.clone(),
+            Tool::new("time", "time", JsonObject::new()),
+        ];
+
+        let diff = Tool::diff(&old, &new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "time");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "sum");
+        assert_eq!(diff.changed, vec![(&echo, &new_echo)]);
+    }
 }
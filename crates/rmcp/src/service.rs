@@ -5,8 +5,8 @@ use crate::{
     error::ErrorData as McpError,
     model::{
         CancelledNotification, CancelledNotificationParam, Extensions, GetExtensions, GetMeta,
-        JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, Meta,
-        NumberOrString, ProgressToken, RequestId, ServerJsonRpcMessage,
+        HasMethod, JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest,
+        JsonRpcResponse, Meta, NumberOrString, ProgressToken, RequestId, ServerJsonRpcMessage,
     },
     transport::{DynamicTransportError, IntoTransport, Transport},
 };
@@ -16,15 +16,43 @@ mod client;
 #[cfg(feature = "client")]
 #[cfg_attr(docsrs, doc(cfg(feature = "client")))]
 pub use client::*;
+#[cfg(feature = "client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+mod caching;
+#[cfg(feature = "client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+pub use caching::CachingPeer;
 #[cfg(feature = "server")]
 #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
 mod server;
 #[cfg(feature = "server")]
 #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
 pub use server::*;
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+mod circuit_breaker;
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+pub use circuit_breaker::*;
+#[cfg(all(feature = "server", feature = "elicitation", feature = "schemars"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "server", feature = "elicitation", feature = "schemars")))
+)]
+mod elicitation_queue;
+#[cfg(all(feature = "server", feature = "elicitation", feature = "schemars"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "server", feature = "elicitation", feature = "schemars")))
+)]
+pub use elicitation_queue::ElicitationQueue;
+mod debounce;
+mod layer;
+pub use layer::*;
 #[cfg(feature = "tower")]
 #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
 mod tower;
+pub use debounce::DebounceNotifier;
 use tokio_util::sync::{CancellationToken, DropGuard};
 #[cfg(feature = "tower")]
 #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
@@ -39,12 +67,19 @@ pub enum ServiceError {
     TransportSend(DynamicTransportError),
     #[error("Transport closed")]
     TransportClosed,
+    /// The queue of the messages to send is full, see [`Peer::try_send_notification`]
+    #[error("Send queue full")]
+    QueueFull,
     #[error("Unexpected response type")]
     UnexpectedResponse,
     #[error("task cancelled for reason {}", reason.as_deref().unwrap_or("<unknown>"))]
     Cancelled { reason: Option<String> },
     #[error("request timeout after {}", chrono::Duration::from_std(*timeout).unwrap_or_default())]
     Timeout { timeout: Duration },
+    /// The request wasn't sent, as the peer is considered unavailable, e.g. by a
+    /// `CircuitBreakerSampling` whose circuit is open
+    #[error("Peer unavailable")]
+    Unavailable,
 }
 
 trait TransferObject:
@@ -65,12 +100,12 @@ impl<T> TransferObject for T where
 
 #[allow(private_bounds, reason = "there's no the third implementation")]
 pub trait ServiceRole: std::fmt::Debug + Send + Sync + 'static + Copy + Clone {
-    type Req: TransferObject + GetMeta + GetExtensions;
+    type Req: TransferObject + GetMeta + GetExtensions + HasMethod;
     type Resp: TransferObject;
     type Not: TryInto<CancelledNotification, Error = Self::Not>
         + From<CancelledNotification>
         + TransferObject;
-    type PeerReq: TransferObject + GetMeta + GetExtensions;
+    type PeerReq: TransferObject + GetMeta + GetExtensions + HasMethod;
     type PeerResp: TransferObject;
     type PeerNot: TryInto<CancelledNotification, Error = Self::PeerNot>
         + From<CancelledNotification>
@@ -112,6 +147,28 @@ pub trait ServiceExt<R: ServiceRole>: Service<R> + Sized {
     fn into_dyn(self) -> Box<dyn DynService<R>> {
         Box::new(self)
     }
+    /// Wrap this service in a tower [`Layer`](tower_layer::Layer), e.g. a [`TimeoutLayer`]
+    ///
+    /// The layers are applied inside out, the last one added handles the requests first:
+    /// ```rust,ignore
+    /// let service = Counter::new()
+    ///     .with_layer(TimeoutLayer::new(Duration::from_secs(30)))
+    ///     .with_layer(TracingLayer);
+    /// ```
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    fn with_layer<L: tower_layer::Layer<Self>>(self, layer: L) -> L::Service {
+        layer.layer(self)
+    }
+    /// Fail the requests not handled within `timeout` with an internal error, and cancel them
+    fn with_timeout(self, timeout: std::time::Duration) -> TimeoutService<Self> {
+        TimeoutService::new(self, timeout)
+    }
+    /// Handle every request in a `mcp_request` span, with its method and id, and log the failed
+    /// ones
+    fn with_tracing(self) -> TracingService<Self> {
+        TracingService::new(self)
+    }
     fn serve<T, E, A>(
         self,
         transport: T,
@@ -194,11 +251,15 @@ use std::{
     collections::{HashMap, VecDeque},
     ops::Deref,
     sync::{Arc, atomic::AtomicU64},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use tokio::sync::mpsc;
 
+/// Generate the ids of the requests sent to the peer, e.g. [`AtomicU32RequestIdProvider`].
+///
+/// An id must not be reused while a request with it is pending. A server can use its own
+/// provider, e.g. UUIDs, with [`ServeServerConfig::with_request_id_provider`](crate::service::ServeServerConfig::with_request_id_provider).
 pub trait RequestIdProvider: Send + Sync + 'static {
     fn next_request_id(&self) -> RequestId;
 }
@@ -230,6 +291,24 @@ impl ProgressTokenProvider for AtomicU32Provider {
     }
 }
 
+/// Count the request ids in a `u64`, sent as strings, e.g. `"42"`.
+///
+/// The ids of [`AtomicU32RequestIdProvider`] are json numbers, which the clients parsing numbers
+/// as doubles, e.g. in JavaScript, only read exactly up to 2^53. Strings are read exactly by any
+/// client, whatever the count, at the cost of allocating and formatting them: about 50ns more
+/// per request than the numbers, see the `request_id` bench.
+#[derive(Debug, Default)]
+pub struct AtomicU64RequestIdProvider {
+    id: AtomicU64,
+}
+
+impl RequestIdProvider for AtomicU64RequestIdProvider {
+    fn next_request_id(&self) -> RequestId {
+        let id = self.id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        RequestId::String(id.to_string().into())
+    }
+}
+
 type Responder<T> = tokio::sync::oneshot::Sender<T>;
 
 /// A handle to a remote request
@@ -267,7 +346,7 @@ impl<R: ServiceRole> RequestHandle<R> {
                         method: crate::model::CancelledNotificationMethod,
                         extensions: Default::default(),
                     };
-                    let _ = self.peer.send_notification(notification.into()).await;
+                    let _ = self.peer.send_notification(notification).await;
                     error
                 }
             }
@@ -286,7 +365,7 @@ impl<R: ServiceRole> RequestHandle<R> {
             method: crate::model::CancelledNotificationMethod,
             extensions: Default::default(),
         };
-        self.peer.send_notification(notification.into()).await?;
+        self.peer.send_notification(notification).await?;
         Ok(())
     }
 }
@@ -315,6 +394,124 @@ pub struct Peer<R: ServiceRole> {
     request_id_provider: Arc<dyn RequestIdProvider>,
     progress_token_provider: Arc<dyn ProgressTokenProvider>,
     info: Arc<tokio::sync::OnceCell<R::PeerInfo>>,
+    notification_handlers: Arc<NotificationHandlers<R::PeerNot>>,
+    /// The round-trip times of the last pings, oldest first
+    ping_rtts: Arc<std::sync::Mutex<VecDeque<Duration>>>,
+}
+
+/// Log a message as json rather than with its `Debug` output, so the secrets it holds can be
+/// found and redacted by the `redaction` feature
+struct AsJson<'a, T>(&'a T);
+
+impl<T: serde::Serialize> std::fmt::Display for AsJson<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string(self.0) {
+            Ok(json) => f.write_str(&json),
+            Err(error) => write!(f, "<not serializable: {error}>"),
+        }
+    }
+}
+
+type NotificationHandlerFn<N> = Arc<dyn Fn(&N) + Send + Sync>;
+
+/// The callbacks subscribed to the notifications of the peer
+pub(crate) struct NotificationHandlers<N> {
+    next_id: std::sync::atomic::AtomicU64,
+    handlers: std::sync::RwLock<HashMap<u64, NotificationHandlerFn<N>>>,
+    /// Set when the service stopped, no notification will be dispatched anymore
+    closed: std::sync::atomic::AtomicBool,
+}
+
+impl<N> Default for NotificationHandlers<N> {
+    fn default() -> Self {
+        Self {
+            next_id: Default::default(),
+            handlers: Default::default(),
+            closed: Default::default(),
+        }
+    }
+}
+
+impl<N: Send + Sync + 'static> NotificationHandlers<N> {
+    pub(crate) fn subscribe(
+        self: &Arc<Self>,
+        handler: impl Fn(&N) + Send + Sync + 'static,
+    ) -> NotificationHandle {
+        let id = self
+            .next_id
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut handlers = self
+            .handlers
+            .write()
+            .expect("notification handlers poisoned");
+        // checked under the lock, so a handler is never kept after `close`
+        if !self.closed.load(std::sync::atomic::Ordering::Acquire) {
+            handlers.insert(id, Arc::new(handler));
+        }
+        drop(handlers);
+        let handlers = Arc::downgrade(self);
+        NotificationHandle {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(handlers) = handlers.upgrade() {
+                    handlers
+                        .handlers
+                        .write()
+                        .expect("notification handlers poisoned")
+                        .remove(&id);
+                }
+            })),
+        }
+    }
+
+    /// Drop every handler once the service stopped, so whatever they capture is released, e.g.
+    /// the sender of a stream of notifications which then ends
+    fn close(&self) {
+        let mut handlers = self
+            .handlers
+            .write()
+            .expect("notification handlers poisoned");
+        self.closed
+            .store(true, std::sync::atomic::Ordering::Release);
+        handlers.clear();
+    }
+
+    fn dispatch(&self, notification: &N) {
+        // don't hold the lock while calling, a handler may subscribe or cancel
+        let handlers: Vec<_> = self
+            .handlers
+            .read()
+            .expect("notification handlers poisoned")
+            .values()
+            .cloned()
+            .collect();
+        for handler in handlers {
+            handler(notification);
+        }
+    }
+}
+
+/// A subscription to the notifications of a peer, see [`Peer::on_notification`].
+///
+/// Dropping the handle keeps the subscription, call [`NotificationHandle::cancel`] to end it.
+pub struct NotificationHandle {
+    unsubscribe: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl std::fmt::Debug for NotificationHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationHandle")
+            .field("active", &self.unsubscribe.is_some())
+            .finish()
+    }
+}
+
+impl NotificationHandle {
+    /// Remove the handler, it's not called for the notifications received after
+    pub fn cancel(mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe()
+        }
+    }
 }
 
 impl<R: ServiceRole> std::fmt::Debug for Peer<R> {
@@ -331,6 +528,7 @@ type ProxyOutbound<R> = mpsc::Receiver<PeerSinkMessage<R>>;
 #[derive(Debug, Default)]
 pub struct PeerRequestOptions {
     pub timeout: Option<Duration>,
+    /// Metadata merged into the `_meta` of the request, e.g. built with [`Meta::builder`]
     pub meta: Option<Meta>,
 }
 
@@ -342,6 +540,8 @@ impl PeerRequestOptions {
 
 impl<R: ServiceRole> Peer<R> {
     const CLIENT_CHANNEL_BUFFER_SIZE: usize = 1024;
+    /// The count of pings kept for the [`Peer::latency_stats`]
+    pub const PING_HISTORY: usize = 100;
     pub(crate) fn new(
         request_id_provider: Arc<dyn RequestIdProvider>,
         peer_info: Option<R::PeerInfo>,
@@ -353,21 +553,103 @@ impl<R: ServiceRole> Peer<R> {
                 request_id_provider,
                 progress_token_provider: Arc::new(AtomicU32ProgressTokenProvider::default()),
                 info: Arc::new(tokio::sync::OnceCell::new_with(peer_info)),
+                notification_handlers: Default::default(),
+                ping_rtts: Default::default(),
             },
             rx,
         )
     }
-    pub async fn send_notification(&self, notification: R::Not) -> Result<(), ServiceError> {
+    /// Call a handler for every notification of type `N` received from the peer, in addition to
+    /// the notification handlers of the service.
+    ///
+    /// `N` is a notification, e.g. [`ToolListChangedNotification`](crate::model::ToolListChangedNotification),
+    /// or the whole [`ServerNotification`](crate::model::ServerNotification) enum for all of them.
+    /// The handlers are called on the task receiving the messages, so they should return quickly,
+    /// and spawn a task for anything longer.
+    pub fn on_notification<N>(
+        &self,
+        handler: impl Fn(N) + Send + Sync + 'static,
+    ) -> NotificationHandle
+    where
+        N: TryFrom<R::PeerNot>,
+    {
+        self.notification_handlers
+            .subscribe(move |notification: &R::PeerNot| {
+                if let Ok(notification) = N::try_from(notification.clone()) {
+                    handler(notification)
+                }
+            })
+    }
+    /// Send any notification of the role, e.g. from a layer forwarding notifications it doesn't
+    /// know, and wait until it's written to the transport.
+    pub async fn send_notification(
+        &self,
+        notification: impl Into<R::Not>,
+    ) -> Result<(), ServiceError> {
         let (responder, receiver) = tokio::sync::oneshot::channel();
         self.tx
             .send(PeerSinkMessage::Notification {
-                notification,
+                notification: notification.into(),
                 responder,
             })
             .await
             .map_err(|_m| ServiceError::TransportClosed)?;
         receiver.await.map_err(|_e| ServiceError::TransportClosed)?
     }
+    /// Queue a notification without waiting, failing with [`ServiceError::QueueFull`] instead of
+    /// waiting for room in the queue.
+    ///
+    /// The notification is only queued, an error writing it to the transport is not returned.
+    pub fn try_send_notification(
+        &self,
+        notification: impl Into<R::Not>,
+    ) -> Result<(), ServiceError> {
+        let (responder, _receiver) = tokio::sync::oneshot::channel();
+        self.tx
+            .try_send(PeerSinkMessage::Notification {
+                notification: notification.into(),
+                responder,
+            })
+            .map_err(|error| match error {
+                mpsc::error::TrySendError::Full(_) => ServiceError::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => ServiceError::TransportClosed,
+            })
+    }
+    /// Send a ping, and record its round-trip time
+    pub(crate) async fn send_ping(&self, ping: R::Req) -> Result<Duration, ServiceError> {
+        let sent_at = Instant::now();
+        self.send_request(ping).await?;
+        let rtt = sent_at.elapsed();
+        let mut rtts = self.ping_rtts.lock().expect("ping rtts poisoned");
+        if rtts.len() == Self::PING_HISTORY {
+            rtts.pop_front();
+        }
+        rtts.push_back(rtt);
+        Ok(rtt)
+    }
+    /// The round-trip times of the last [`Peer::PING_HISTORY`] pings sent with `ping`, `None`
+    /// before the first one
+    pub fn latency_stats(&self) -> Option<LatencyStats> {
+        let mut rtts: Vec<_> = self
+            .ping_rtts
+            .lock()
+            .expect("ping rtts poisoned")
+            .iter()
+            .copied()
+            .collect();
+        rtts.sort();
+        let (min, max) = (*rtts.first()?, *rtts.last()?);
+        let mean = rtts.iter().sum::<Duration>() / rtts.len() as u32;
+        // the nearest rank
+        let p99 = rtts[(rtts.len() * 99).div_ceil(100) - 1];
+        Some(LatencyStats {
+            min,
+            max,
+            mean,
+            p99,
+            samples: rtts.len(),
+        })
+    }
     pub async fn send_request(&self, request: R::Req) -> Result<R::PeerResp, ServiceError> {
         self.send_request_with_option(request, PeerRequestOptions::no_options())
             .await?
@@ -389,13 +671,20 @@ impl<R: ServiceRole> Peer<R> {
         options: PeerRequestOptions,
     ) -> Result<RequestHandle<R>, ServiceError> {
         let id = self.request_id_provider.next_request_id();
-        let progress_token = self.progress_token_provider.next_progress_token();
-        request
-            .get_meta_mut()
-            .set_progress_token(progress_token.clone());
         if let Some(meta) = options.meta.clone() {
             request.get_meta_mut().extend(meta);
         }
+        // the token given by the caller is kept, a new one is only drawn for the other requests
+        let progress_token = match request.get_meta().get_progress_token() {
+            Some(progress_token) => progress_token,
+            None => {
+                let progress_token = self.progress_token_provider.next_progress_token();
+                request
+                    .get_meta_mut()
+                    .set_progress_token(progress_token.clone());
+                progress_token
+            }
+        };
         let (responder, receiver) = tokio::sync::oneshot::channel();
         self.tx
             .send(PeerSinkMessage::Request {
@@ -434,6 +723,7 @@ impl<R: ServiceRole> Peer<R> {
 pub struct RunningService<R: ServiceRole, S: Service<R>> {
     service: Arc<S>,
     peer: Peer<R>,
+    stats: ServiceStats,
     handle: tokio::task::JoinHandle<QuitReason>,
     cancellation_token: CancellationToken,
     dg: DropGuard,
@@ -455,6 +745,12 @@ impl<R: ServiceRole, S: Service<R>> RunningService<R, S> {
     pub fn service(&self) -> &S {
         self.service.as_ref()
     }
+    /// The counters of the messages handled by the service, which keep counting until it stops,
+    /// and can still be read after [`RunningService::waiting`]
+    #[inline]
+    pub fn stats(&self) -> ServiceStats {
+        self.stats.clone()
+    }
     #[inline]
     pub fn cancellation_token(&self) -> RunningServiceCancellationToken {
         RunningServiceCancellationToken(self.cancellation_token.clone())
@@ -479,6 +775,74 @@ impl RunningServiceCancellationToken {
     }
 }
 
+/// The round-trip times of the last pings sent to a peer, see [`Peer::latency_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    /// The 99th percentile
+    pub p99: Duration,
+    /// The count of pings the stats are computed from
+    pub samples: usize,
+}
+
+/// Counters of the messages handled by a running service, see [`RunningService::stats`]
+#[derive(Debug, Clone)]
+pub struct ServiceStats {
+    counters: Arc<StatsCounters>,
+}
+
+#[derive(Debug)]
+struct StatsCounters {
+    started_at: Instant,
+    requests_handled: AtomicU64,
+    notifications_received: AtomicU64,
+    errors_sent: AtomicU64,
+}
+
+impl ServiceStats {
+    fn new() -> Self {
+        Self {
+            counters: Arc::new(StatsCounters {
+                started_at: Instant::now(),
+                requests_handled: AtomicU64::new(0),
+                notifications_received: AtomicU64::new(0),
+                errors_sent: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The requests of the peer answered so far, including with an error
+    pub fn requests_handled(&self) -> u64 {
+        self.counters
+            .requests_handled
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn notifications_received(&self) -> u64 {
+        self.counters
+            .notifications_received
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// The requests of the peer answered with an error
+    pub fn errors_sent(&self) -> u64 {
+        self.counters
+            .errors_sent
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// How long the service has been running
+    pub fn uptime(&self) -> Duration {
+        self.counters.started_at.elapsed()
+    }
+
+    fn count(counter: &AtomicU64) {
+        counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug)]
 pub enum QuitReason {
     Cancelled,
@@ -487,6 +851,9 @@ pub enum QuitReason {
 }
 
 /// Request execution context
+///
+/// Cloning a context copies its [`Meta`] and [`Extensions`]. To hand the same context to several
+/// concurrent tasks of a handler, share it behind an [`Arc`] instead.
 #[derive(Debug, Clone)]
 pub struct RequestContext<R: ServiceRole> {
     /// this token will be cancelled when the [`CancelledNotification`] is received.
@@ -498,13 +865,80 @@ pub struct RequestContext<R: ServiceRole> {
     pub peer: Peer<R>,
 }
 
-/// Request execution context
+/// Inserted in the [`Extensions`] of a [`RequestContext`], to run something only once the
+/// response of the request is sent, e.g. the notifications following it.
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseSent(tokio::sync::watch::Receiver<bool>);
+
+impl ResponseSent {
+    /// Wait for the response to be sent, returns `false` if it never will be, e.g. the transport
+    /// failed to send it or the service is closed.
+    #[cfg_attr(not(feature = "resource-streaming"), allow(dead_code))]
+    pub(crate) async fn wait(mut self) -> bool {
+        self.0.wait_for(|sent| *sent).await.is_ok()
+    }
+}
+
+/// The id of a session of a server serving several clients, e.g. over streamable HTTP
+pub type SessionId = Arc<str>;
+
+/// Notification execution context
 #[derive(Debug, Clone)]
 pub struct NotificationContext<R: ServiceRole> {
     pub meta: Meta,
     pub extensions: Extensions,
     /// An interface to fetch the remote client or server
     pub peer: Peer<R>,
+    arrived_at: Instant,
+    source_id: Option<SessionId>,
+}
+
+impl<R: ServiceRole> NotificationContext<R> {
+    /// A context for a notification received now, taking its meta and extensions
+    pub(crate) fn received(peer: Peer<R>, meta: Meta, extensions: Extensions) -> Self {
+        let source_id = source_id(&extensions);
+        Self {
+            meta,
+            extensions,
+            peer,
+            arrived_at: Instant::now(),
+            source_id,
+        }
+    }
+
+    /// When the notification was received, to measure how long it waited to be handled
+    pub fn arrived_at(&self) -> Instant {
+        self.arrived_at
+    }
+
+    /// The session the notification was sent in, when served over a session-based transport, i.e.
+    /// the streamable HTTP or SSE server
+    pub fn source_id(&self) -> Option<&SessionId> {
+        self.source_id.as_ref()
+    }
+}
+
+/// Read the session id from the http request of a message, set by the session-based transports
+#[cfg_attr(not(feature = "server-side-http"), allow(unused_variables))]
+fn source_id(extensions: &Extensions) -> Option<SessionId> {
+    #[cfg(feature = "server-side-http")]
+    if let Some(parts) = extensions.get::<http::request::Parts>() {
+        use crate::transport::common::http_header::HEADER_SESSION_ID;
+        // a streamable HTTP session is named by a header, an SSE one by a query parameter
+        let header = parts
+            .headers
+            .get(HEADER_SESSION_ID)
+            .and_then(|value| value.to_str().ok());
+        let query = || {
+            parts
+                .uri
+                .query()?
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("sessionId="))
+        };
+        return header.or_else(query).map(SessionId::from);
+    }
+    None
 }
 
 /// Use this function to skip initialization process
@@ -565,6 +999,7 @@ where
     let mut local_responder_pool =
         HashMap::<RequestId, Responder<Result<R::PeerResp, ServiceError>>>::new();
     let mut local_ct_pool = HashMap::<RequestId, CancellationToken>::new();
+    let mut response_sent_pool = HashMap::<RequestId, tokio::sync::watch::Sender<bool>>::new();
     let shared_service = Arc::new(service);
     // for return
     let service = shared_service.clone();
@@ -573,6 +1008,8 @@ where
     // let mut stream = std::pin::pin!(stream);
     let serve_loop_ct = ct.child_token();
     let peer_return: Peer<R> = peer.clone();
+    let stats = ServiceStats::new();
+    let stats_return = stats.clone();
     let current_span = tracing::Span::current();
     let handle = tokio::spawn(async move {
         let mut transport = transport.into_transport();
@@ -679,20 +1116,19 @@ where
                 }
                 // response and error
                 Event::ToSink(m) => {
-                    if let Some(id) = match &m {
-                        JsonRpcMessage::Response(response) => Some(&response.id),
-                        JsonRpcMessage::Error(error) => Some(&error.id),
-                        _ => None,
-                    } {
+                    if let Some(id) = m.id() {
                         if let Some(ct) = local_ct_pool.remove(id) {
                             ct.cancel();
                         }
+                        let response_sent = response_sent_pool.remove(id);
                         let send = transport.send(m);
                         let current_span = tracing::Span::current();
                         tokio::spawn(async move {
                             let send_result = send.await;
                             if let Err(error) = send_result {
                                 tracing::error!(%error, "fail to response message");
+                            } else if let Some(response_sent) = response_sent {
+                                let _ = response_sent.send(true);
                             }
                         }.instrument(current_span));
                     }
@@ -739,7 +1175,7 @@ where
                     mut request,
                     ..
                 })) => {
-                    tracing::debug!(%id, ?request, "received request");
+                    tracing::debug!(%id, request = %AsJson(&request), "received request");
                     {
                         let service = shared_service.clone();
                         let sink = sink_proxy_tx.clone();
@@ -752,6 +1188,9 @@ where
                         // swap meta firstly, otherwise progress token will be lost
                         std::mem::swap(&mut meta, request.get_meta_mut());
                         std::mem::swap(&mut extensions, request.extensions_mut());
+                        let (response_sent_tx, response_sent_rx) = tokio::sync::watch::channel(false);
+                        response_sent_pool.insert(id.clone(), response_sent_tx);
+                        extensions.insert(ResponseSent(response_sent_rx));
                         let context = RequestContext {
                             ct: context_ct,
                             id: id.clone(),
@@ -760,17 +1199,20 @@ where
                             extensions,
                         };
                         let current_span = tracing::Span::current();
+                        let stats = stats.clone();
                         tokio::spawn(async move {
                             let result = service
                                 .handle_request(request, context)
                                 .await;
+                            ServiceStats::count(&stats.counters.requests_handled);
                             let response = match result {
                                 Ok(result) => {
-                                    tracing::debug!(%id, ?result, "response message");
+                                    tracing::debug!(%id, result = %AsJson(&result), "response message");
                                     JsonRpcMessage::response(result, id)
                                 }
                                 Err(error) => {
                                     tracing::warn!(%id, ?error, "response error");
+                                    ServiceStats::count(&stats.counters.errors_sent);
                                     JsonRpcMessage::error(error, id)
                                 }
                             };
@@ -782,7 +1224,8 @@ where
                     notification,
                     ..
                 })) => {
-                    tracing::info!(?notification, "received notification");
+                    tracing::info!(notification = %AsJson(&notification), "received notification");
+                    ServiceStats::count(&stats.counters.notifications_received);
                     // catch cancelled notification
                     let mut notification = match notification.try_into() {
                         Ok::<CancelledNotification, _>(cancelled) => {
@@ -794,6 +1237,7 @@ where
                         }
                         Err(notification) => notification,
                     };
+                    peer.notification_handlers.dispatch(&notification);
                     {
                         let service = shared_service.clone();
                         let mut extensions = Extensions::new();
@@ -801,11 +1245,7 @@ where
                         // avoid clone
                         std::mem::swap(&mut extensions, notification.extensions_mut());
                         std::mem::swap(&mut meta, notification.get_meta_mut());
-                        let context = NotificationContext {
-                            peer: peer.clone(),
-                            meta,
-                            extensions,
-                        };
+                        let context = NotificationContext::received(peer.clone(), meta, extensions);
                         let current_span = tracing::Span::current();
                         tokio::spawn(async move {
                             let result = service.handle_notification(notification, context).await;
@@ -837,6 +1277,7 @@ where
                 }
             }
         };
+        peer.notification_handlers.close();
         let sink_close_result = transport.close().await;
         if let Err(e) = sink_close_result {
             tracing::error!(%e, "fail to close sink");
@@ -847,6 +1288,7 @@ where
     RunningService {
         service,
         peer: peer_return,
+        stats: stats_return,
         handle,
         cancellation_token: ct.clone(),
         dg: ct.drop_guard(),
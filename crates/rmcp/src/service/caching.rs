@@ -0,0 +1,181 @@
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::{NotificationHandle, Peer, PeerRequestOptions, RoleClient, ServiceError};
+use crate::model::{
+    ClientRequest, ListToolsRequest, ListToolsResult, Meta, PaginatedRequestParam, ServerResult,
+    ToolListChangedNotification,
+};
+
+/// The first page of the tools, by the page size it was requested with
+type CachedTools = Arc<RwLock<HashMap<Option<u32>, (Instant, ListToolsResult)>>>;
+
+/// Cache the tools listed by [`Peer<RoleClient>::list_tools`] for a time to live.
+///
+/// The cache is cleared as soon as the server sends a `notifications/tools/list_changed`, or
+/// by [`CachingPeer::invalidate_tools`]. Only the first page is cached, once per page size, the
+/// requests with a cursor are always sent. The other methods of the peer, like `call_tool`, are reached through
+/// `Deref` and are not cached.
+///
+/// ```rust,ignore
+/// let tools = CachingPeer::new(client.peer().clone()).with_ttl(Duration::from_secs(300));
+/// let listed = tools.list_tools(None).await?;
+/// tools.call_tool(CallToolRequestParam { name: "sum".into(), arguments: None }).await?;
+/// ```
+#[derive(Clone)]
+pub struct CachingPeer {
+    peer: Peer<RoleClient>,
+    ttl: Duration,
+    tools: CachedTools,
+    /// Incremented by every invalidation, so a list requested before it isn't cached after it
+    generation: Arc<AtomicU64>,
+    _listener: Arc<ListenerGuard>,
+}
+
+/// Cancel the invalidation handler once every clone of the caching peer is dropped
+struct ListenerGuard(Option<NotificationHandle>);
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.take() {
+            handle.cancel();
+        }
+    }
+}
+
+impl std::fmt::Debug for CachingPeer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachingPeer")
+            .field("peer", &self.peer)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachingPeer {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+    pub fn new(peer: Peer<RoleClient>) -> Self {
+        let tools = CachedTools::default();
+        let generation = Arc::new(AtomicU64::new(0));
+        let handle = peer.on_notification({
+            let tools = tools.clone();
+            let generation = generation.clone();
+            move |_: ToolListChangedNotification| invalidate(&tools, &generation)
+        });
+        Self {
+            peer,
+            ttl: Self::DEFAULT_TTL,
+            tools,
+            generation,
+            _listener: Arc::new(ListenerGuard(Some(handle))),
+        }
+    }
+
+    /// How long the listed tools are cached, 60 seconds by default
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn peer(&self) -> &Peer<RoleClient> {
+        &self.peer
+    }
+
+    /// List the tools, from the cache if they were listed less than the time to live ago
+    pub async fn list_tools(
+        &self,
+        params: Option<PaginatedRequestParam>,
+    ) -> Result<ListToolsResult, ServiceError> {
+        self.list_tools_with_page_size(params, None).await
+    }
+
+    /// List the tools by pages of `page_size`, requested in the `_meta` of the request, see
+    /// [`Meta::get_page_size`].
+    ///
+    /// The first page is cached for each page size, a page of another size is never returned.
+    pub async fn list_tools_with_page_size(
+        &self,
+        params: Option<PaginatedRequestParam>,
+        page_size: Option<u32>,
+    ) -> Result<ListToolsResult, ServiceError> {
+        if params
+            .as_ref()
+            .is_some_and(|params| params.cursor.is_some())
+        {
+            return self.request_tools(params, page_size).await;
+        }
+        if let Some((listed_at, tools)) = self
+            .tools
+            .read()
+            .expect("tools cache poisoned")
+            .get(&page_size)
+        {
+            if listed_at.elapsed() < self.ttl {
+                return Ok(tools.clone());
+            }
+        }
+        let generation = self.generation.load(Ordering::Acquire);
+        let tools = self.request_tools(params, page_size).await?;
+        let mut cached = self.tools.write().expect("tools cache poisoned");
+        // the list may be outdated if it changed while it was requested
+        if self.generation.load(Ordering::Acquire) == generation {
+            cached.insert(page_size, (Instant::now(), tools.clone()));
+        }
+        Ok(tools)
+    }
+
+    async fn request_tools(
+        &self,
+        params: Option<PaginatedRequestParam>,
+        page_size: Option<u32>,
+    ) -> Result<ListToolsResult, ServiceError> {
+        let Some(page_size) = page_size else {
+            return self.peer.list_tools(params).await;
+        };
+        let request = ClientRequest::ListToolsRequest(ListToolsRequest {
+            params,
+            ..Default::default()
+        });
+        let options = PeerRequestOptions {
+            meta: Some(Meta::builder().page_size(page_size).build()),
+            ..Default::default()
+        };
+        match self
+            .peer
+            .send_request_with_option(request, options)
+            .await?
+            .await_response()
+            .await?
+        {
+            ServerResult::ListToolsResult(result) => Ok(result),
+            _ => Err(ServiceError::UnexpectedResponse),
+        }
+    }
+
+    /// Clear the cached tools, the next [`CachingPeer::list_tools`] asks the server
+    pub fn invalidate_tools(&self) {
+        invalidate(&self.tools, &self.generation);
+    }
+}
+
+fn invalidate(tools: &CachedTools, generation: &AtomicU64) {
+    let mut tools = tools.write().expect("tools cache poisoned");
+    generation.fetch_add(1, Ordering::AcqRel);
+    tools.clear();
+}
+
+impl Deref for CachingPeer {
+    type Target = Peer<RoleClient>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.peer
+    }
+}
@@ -0,0 +1,405 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+
+use super::{Peer, RoleServer, ServiceError};
+use crate::model::{CreateMessageRequestParam, CreateMessageResult};
+
+/// The state of the circuit of a [`CircuitBreakerSampling`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// The calls are sent
+    Closed,
+    /// The calls fail with [`ServiceError::Unavailable`] without being sent
+    Open,
+    /// A single call is sent to probe the peer, the circuit closes if it succeeds
+    HalfOpen,
+}
+
+/// When the circuit of a [`CircuitBreakerSampling`] opens and recovers
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    /// Open the circuit after this many consecutive failures
+    pub failure_threshold: u32,
+    /// The number of the last calls the failure rate is computed over, the rate is only checked
+    /// once there were that many calls
+    pub window: usize,
+    /// Open the circuit when the failure rate over the window is greater than this
+    pub failure_rate: f64,
+    /// How long the circuit stays open before a probe is allowed
+    pub recovery_timeout: Duration,
+    /// Fail the calls not answered in time with [`ServiceError::Timeout`], so a peer which never
+    /// answers opens the circuit too
+    pub call_timeout: Option<Duration>,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: 20,
+            failure_rate: 0.5,
+            recovery_timeout: Duration::from_secs(30),
+            call_timeout: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+struct Breaker {
+    config: CircuitBreakerConfig,
+    consecutive_failures: u32,
+    /// The outcomes of the last calls, `true` for a failure
+    outcomes: VecDeque<bool>,
+    opened_at: Instant,
+    probing: bool,
+    state: watch::Sender<CircuitState>,
+}
+
+impl Breaker {
+    fn state(&self) -> CircuitState {
+        *self.state.borrow()
+    }
+
+    fn set_state(&self, state: CircuitState) {
+        self.state.send_if_modified(|current| {
+            let modified = *current != state;
+            *current = state;
+            modified
+        });
+    }
+
+    /// Whether a call may be sent, it's then the probe if the circuit isn't closed
+    fn try_acquire(&mut self) -> Result<(), ServiceError> {
+        match self.state() {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open if self.opened_at.elapsed() >= self.config.recovery_timeout => {
+                self.set_state(CircuitState::HalfOpen);
+                self.probing = true;
+                Ok(())
+            }
+            CircuitState::HalfOpen if !self.probing => {
+                self.probing = true;
+                Ok(())
+            }
+            CircuitState::Open | CircuitState::HalfOpen => Err(ServiceError::Unavailable),
+        }
+    }
+
+    fn record(&mut self, failed: bool) {
+        match self.state() {
+            CircuitState::Closed => {
+                self.outcomes.push_back(failed);
+                if self.outcomes.len() > self.config.window {
+                    self.outcomes.pop_front();
+                }
+                self.consecutive_failures = if failed {
+                    self.consecutive_failures + 1
+                } else {
+                    0
+                };
+                let failures = self.outcomes.iter().filter(|failed| **failed).count();
+                let rate_exceeded = self.outcomes.len() >= self.config.window
+                    && failures as f64 / self.outcomes.len() as f64 > self.config.failure_rate;
+                if self.consecutive_failures >= self.config.failure_threshold || rate_exceeded {
+                    self.open();
+                }
+            }
+            CircuitState::HalfOpen => {
+                self.probing = false;
+                if failed {
+                    self.open();
+                } else {
+                    self.consecutive_failures = 0;
+                    self.outcomes.clear();
+                    self.set_state(CircuitState::Closed);
+                }
+            }
+            // a call sent before the circuit opened
+            CircuitState::Open => {}
+        }
+    }
+
+    fn open(&mut self) {
+        self.opened_at = Instant::now();
+        self.set_state(CircuitState::Open);
+    }
+}
+
+/// A call allowed by the breaker, the probe is released if the call is dropped before its end
+struct Permit {
+    breaker: Arc<Mutex<Breaker>>,
+    recorded: bool,
+}
+
+impl Permit {
+    fn record(mut self, failed: bool) {
+        self.recorded = true;
+        self.breaker
+            .lock()
+            .expect("circuit breaker poisoned")
+            .record(failed);
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if !self.recorded {
+            let mut breaker = self.breaker.lock().expect("circuit breaker poisoned");
+            if breaker.state() == CircuitState::HalfOpen {
+                breaker.probing = false;
+            }
+        }
+    }
+}
+
+/// Stop sending `sampling/createMessage` requests to a client whose LLM keeps failing or doesn't
+/// answer, instead of waiting for every one of them.
+///
+/// The circuit opens after [`failure_threshold`](CircuitBreakerConfig::failure_threshold)
+/// consecutive failures, or when more than the
+/// [`failure_rate`](CircuitBreakerConfig::failure_rate) of the calls of the window failed. The
+/// calls then fail with [`ServiceError::Unavailable`] right away, until the
+/// [`recovery_timeout`](CircuitBreakerConfig::recovery_timeout) is over and a single call is sent
+/// to probe the client. Every error counts as a failure, including the errors returned by the
+/// client.
+///
+/// It wraps a [`Peer<RoleServer>`], or any tower service sending the requests with the `tower`
+/// feature, see [`CircuitBreakerSamplingLayer`].
+///
+/// ```rust,ignore
+/// let sampling = CircuitBreakerSampling::new(context.peer.clone(), CircuitBreakerConfig::default());
+/// let mut state = sampling.state();
+/// match sampling.create_message(params).await {
+///     Err(ServiceError::Unavailable) => { /* fall back */ }
+///     result => { /* ... */ }
+/// }
+/// ```
+pub struct CircuitBreakerSampling<S = Peer<RoleServer>> {
+    inner: S,
+    breaker: Arc<Mutex<Breaker>>,
+    state: watch::Receiver<CircuitState>,
+}
+
+impl<S: Clone> Clone for CircuitBreakerSampling<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            breaker: self.breaker.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for CircuitBreakerSampling<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakerSampling")
+            .field("state", &*self.state.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> CircuitBreakerSampling<S> {
+    pub fn new(inner: S, config: CircuitBreakerConfig) -> Self {
+        let (sender, state) = watch::channel(CircuitState::Closed);
+        Self {
+            inner,
+            breaker: Arc::new(Mutex::new(Breaker {
+                config,
+                consecutive_failures: 0,
+                outcomes: VecDeque::with_capacity(config.window),
+                opened_at: Instant::now(),
+                probing: false,
+                state: sender,
+            })),
+            state,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Watch the state of the circuit, e.g. to report the availability of the client's LLM
+    pub fn state(&self) -> watch::Receiver<CircuitState> {
+        self.state.clone()
+    }
+
+    /// Send a call if the circuit allows it, and record its outcome.
+    ///
+    /// The call is only made once the circuit allowed it, the future of an eager service may
+    /// already be sending the request.
+    async fn guarded<T, F>(
+        breaker: Arc<Mutex<Breaker>>,
+        call: impl FnOnce() -> F,
+    ) -> Result<T, ServiceError>
+    where
+        F: Future<Output = Result<T, ServiceError>>,
+    {
+        let call_timeout = {
+            let mut guard = breaker.lock().expect("circuit breaker poisoned");
+            guard.try_acquire()?;
+            guard.config.call_timeout
+        };
+        let permit = Permit {
+            breaker,
+            recorded: false,
+        };
+        let call = call();
+        let result = match call_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .unwrap_or(Err(ServiceError::Timeout { timeout })),
+            None => call.await,
+        };
+        permit.record(result.is_err());
+        result
+    }
+}
+
+impl CircuitBreakerSampling<Peer<RoleServer>> {
+    /// Send a `sampling/createMessage` request, unless the circuit is open
+    pub async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+    ) -> Result<CreateMessageResult, ServiceError> {
+        Self::guarded(self.breaker.clone(), || self.inner.create_message(params)).await
+    }
+}
+
+/// The tower [`Layer`](tower_layer::Layer) of [`CircuitBreakerSampling`], the services it wraps
+/// send a [`CreateMessageRequestParam`], like a [`Peer<RoleServer>`].
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CircuitBreakerSamplingLayer {
+    config: CircuitBreakerConfig,
+}
+
+#[cfg(feature = "tower")]
+impl CircuitBreakerSamplingLayer {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> tower_layer::Layer<S> for CircuitBreakerSamplingLayer {
+    type Service = CircuitBreakerSampling<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreakerSampling::new(inner, self.config)
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> tower_service::Service<CreateMessageRequestParam> for CircuitBreakerSampling<S>
+where
+    S: tower_service::Service<
+            CreateMessageRequestParam,
+            Response = CreateMessageResult,
+            Error = ServiceError,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = CreateMessageResult;
+    type Error = ServiceError;
+    type Future = futures::future::BoxFuture<'static, Result<CreateMessageResult, ServiceError>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, params: CreateMessageRequestParam) -> Self::Future {
+        // the ready service is the one to call, a fresh clone takes its place
+        let inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+        Box::pin(Self::guarded(self.breaker.clone(), move || {
+            inner.call(params)
+        }))
+    }
+}
+
+#[cfg(feature = "tower")]
+impl tower_service::Service<CreateMessageRequestParam> for Peer<RoleServer> {
+    type Response = CreateMessageResult;
+    type Error = ServiceError;
+    type Future = futures::future::BoxFuture<'static, Result<CreateMessageResult, ServiceError>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, params: CreateMessageRequestParam) -> Self::Future {
+        let peer = self.clone();
+        Box::pin(async move { peer.create_message(params).await })
+    }
+}
+
+#[cfg(all(test, feature = "tower"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tower_service::Service;
+
+    use super::*;
+
+    /// A failing service which counts its requests when it's called, not when its future is
+    /// polled
+    #[derive(Debug, Clone, Default)]
+    struct Eager {
+        sent: Arc<AtomicUsize>,
+    }
+
+    impl Service<CreateMessageRequestParam> for Eager {
+        type Response = CreateMessageResult;
+        type Error = ServiceError;
+        type Future = futures::future::Ready<Result<CreateMessageResult, ServiceError>>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _params: CreateMessageRequestParam) -> Self::Future {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            futures::future::ready(Err(ServiceError::TransportClosed))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_doesnt_call_the_inner_service() {
+        let eager = Eager::default();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            recovery_timeout: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut sampling = CircuitBreakerSampling::new(eager.clone(), config);
+        let params = || {
+            CreateMessageRequestParam::builder()
+                .user("Hello")
+                .max_tokens(16)
+                .build()
+        };
+        for _ in 0..2 {
+            assert!(sampling.call(params()).await.is_err());
+        }
+        assert_eq!(*sampling.state().borrow(), CircuitState::Open);
+        let result = sampling.call(params()).await;
+        assert!(matches!(result, Err(ServiceError::Unavailable)));
+        assert_eq!(eager.sent.load(Ordering::SeqCst), 2);
+    }
+}
@@ -14,9 +14,10 @@ use crate::{
         ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
         ListToolsResult, PaginatedRequestParam, ProgressNotification, ProgressNotificationParam,
         ReadResourceRequest, ReadResourceRequestParam, ReadResourceResult, Reference, RequestId,
+        ResourceUpdatedNotification, ResourceUpdatedNotificationParam,
         RootsListChangedNotification, ServerInfo, ServerJsonRpcMessage, ServerNotification,
-        ServerRequest, ServerResult, SetLevelRequest, SetLevelRequestParam, SubscribeRequest,
-        SubscribeRequestParam, UnsubscribeRequest, UnsubscribeRequestParam,
+        ServerRequest, ServerResult, ServerSpec, SetLevelRequest, SetLevelRequestParam,
+        SubscribeRequest, SubscribeRequestParam, UnsubscribeRequest, UnsubscribeRequestParam,
     },
     transport::DynamicTransportError,
 };
@@ -92,6 +93,10 @@ where
             ServerJsonRpcMessage::Response(JsonRpcResponse { id, result, .. }) => {
                 break Ok((result, id));
             }
+            // The server refused to initialize, e.g. for an unsupported protocol version
+            ServerJsonRpcMessage::Error(_) => {
+                break Err(ClientInitializeError::ExpectedInitResponse(Some(message)));
+            }
             // Server could send logging messages before handshake
             ServerJsonRpcMessage::Notification(mut notification) => {
                 let ServerNotification::LoggingMessageNotification(logging) =
@@ -101,16 +106,12 @@ where
                     continue;
                 };
 
-                let mut context = NotificationContext {
-                    peer: peer.clone(),
-                    meta: Meta::default(),
-                    extensions: Extensions::default(),
-                };
-
-                if let Some(meta) = logging.extensions.get_mut::<Meta>() {
-                    std::mem::swap(&mut context.meta, meta);
+                let mut meta = Meta::default();
+                if let Some(logging_meta) = logging.extensions.get_mut::<Meta>() {
+                    std::mem::swap(&mut meta, logging_meta);
                 }
-                std::mem::swap(&mut context.extensions, &mut logging.extensions);
+                let extensions = std::mem::take(&mut logging.extensions);
+                let context = NotificationContext::received(peer.clone(), meta, extensions);
 
                 if let Err(error) = service
                     .handle_notification(notification.notification, context)
@@ -164,6 +165,18 @@ impl<S: Service<RoleClient>> ServiceExt<RoleClient> for S {
     }
 }
 
+/// Initialize a client session over the transport, and serve it in the background.
+///
+/// The `initialize` request carries the [`Service::get_info`] of the service, which is
+/// [`ClientInfo::default`] for `()`. A [`ClientInfo`] is also a service, pass one to report the
+/// name and version of your application instead:
+/// ```rust,ignore
+/// let info = ClientInfo {
+///     client_info: Implementation::new("my-app", env!("CARGO_PKG_VERSION")),
+///     ..Default::default()
+/// };
+/// let client = serve_client(info, transport).await?;
+/// ```
 pub async fn serve_client<S, T, E, A>(
     service: S,
     transport: T,
@@ -362,6 +375,103 @@ impl Peer<RoleClient> {
 }
 
 impl Peer<RoleClient> {
+    /// Subscribe to the updates of a resource.
+    ///
+    /// The returned stream yields the `notifications/resources/updated` of the resource, until
+    /// the connection is closed. The resource is unsubscribed when the stream is dropped.
+    ///
+    /// ```rust,ignore
+    /// let mut updates = client.subscribe_resource("file:///log.txt").await?;
+    /// while let Some(update) = updates.next().await {
+    ///     let content = client.read_resource(ReadResourceRequestParam { uri: update.uri }).await?;
+    /// }
+    /// ```
+    pub async fn subscribe_resource(
+        &self,
+        uri: impl Into<String>,
+    ) -> Result<ResourceSubscription, ServiceError> {
+        let uri = uri.into();
+        // listen first, an update may be sent right after the subscription
+        let (tx, updates) = tokio::sync::mpsc::unbounded_channel();
+        let handle = self.on_notification({
+            let uri = uri.clone();
+            move |notification: ResourceUpdatedNotification| {
+                if notification.params.uri == uri {
+                    let _ = tx.send(notification.params);
+                }
+            }
+        });
+        if let Err(error) = self
+            .subscribe(SubscribeRequestParam { uri: uri.clone() })
+            .await
+        {
+            handle.cancel();
+            return Err(error);
+        }
+        Ok(ResourceSubscription {
+            uri,
+            updates,
+            active: Some((self.clone(), handle)),
+        })
+    }
+
+    /// Ping the server, and return the round-trip time, see [`Peer::latency_stats`]
+    pub async fn ping(&self) -> Result<Duration, ServiceError> {
+        self.send_ping(ClientRequest::PingRequest(Default::default()))
+            .await
+    }
+
+    /// Call a tool, passing the progress notifications of the call to `on_progress` until the
+    /// result is received.
+    ///
+    /// The request is sent with a progress token, and only the notifications of this token are
+    /// passed on. They are all passed before this returns, as the server sends them before
+    /// the result.
+    ///
+    /// ```rust,ignore
+    /// let result = client
+    ///     .call_tool_with_progress(params, |progress| {
+    ///         tracing::info!("{}/{:?}", progress.progress, progress.total)
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn call_tool_with_progress(
+        &self,
+        params: CallToolRequestParam,
+        on_progress: impl Fn(ProgressNotificationParam) + Send + Sync + 'static,
+    ) -> Result<CallToolResult, ServiceError> {
+        // listen first, the server may notify right after receiving the request
+        let progress_token = self.progress_token_provider.next_progress_token();
+        let handle = self.on_notification({
+            let progress_token = progress_token.clone();
+            move |notification: ProgressNotification| {
+                if notification.params.progress_token == progress_token {
+                    on_progress(notification.params)
+                }
+            }
+        });
+        let mut meta = Meta::new();
+        meta.set_progress_token(progress_token);
+        let request = ClientRequest::CallToolRequest(CallToolRequest {
+            method: Default::default(),
+            params,
+            extensions: Default::default(),
+        });
+        let options = PeerRequestOptions {
+            meta: Some(meta),
+            ..Default::default()
+        };
+        let result = match self.send_request_with_option(request, options).await {
+            Ok(request) => request.await_response().await,
+            Err(error) => Err(error),
+        };
+        handle.cancel();
+        match result? {
+            ServerResult::CallToolResult(result) => Ok(result),
+            _ => Err(ServiceError::UnexpectedResponse),
+        }
+    }
+
     /// A wrapper method for [`Peer<RoleClient>::list_tools`].
     ///
     /// This function will call [`Peer<RoleClient>::list_tools`] multiple times until all tools are listed.
@@ -419,6 +529,41 @@ impl Peer<RoleClient> {
         Ok(resources)
     }
 
+    /// Fetch the tools, prompts and resources of the server in parallel, see [`ServerSpec`].
+    ///
+    /// Only the capabilities advertised by the server are listed, the others are left empty.
+    pub async fn fetch_server_spec(&self) -> Result<ServerSpec, ServiceError> {
+        let capabilities = self
+            .peer_info()
+            .map(|info| info.capabilities.clone())
+            .unwrap_or_default();
+        let (tools, prompts, resources) = futures::try_join!(
+            async {
+                match capabilities.tools {
+                    Some(_) => self.list_all_tools().await,
+                    None => Ok(Vec::new()),
+                }
+            },
+            async {
+                match capabilities.prompts {
+                    Some(_) => self.list_all_prompts().await,
+                    None => Ok(Vec::new()),
+                }
+            },
+            async {
+                match capabilities.resources {
+                    Some(_) => self.list_all_resources().await,
+                    None => Ok(Vec::new()),
+                }
+            },
+        )?;
+        Ok(ServerSpec {
+            tools,
+            prompts,
+            resources,
+        })
+    }
+
     /// A wrapper method for [`Peer<RoleClient>::list_resource_templates`].
     ///
     /// This function will call [`Peer<RoleClient>::list_resource_templates`] multiple times until all resource templates are listed.
@@ -532,3 +677,61 @@ impl Peer<RoleClient> {
         Ok(completion.values)
     }
 }
+
+/// The updates of a resource, see [`Peer::subscribe_resource`].
+///
+/// Dropping the subscription sends a `resources/unsubscribe` request in the background, call
+/// [`ResourceSubscription::unsubscribe`] to wait for it instead.
+#[derive(Debug)]
+pub struct ResourceSubscription {
+    uri: String,
+    updates: tokio::sync::mpsc::UnboundedReceiver<ResourceUpdatedNotificationParam>,
+    active: Option<(Peer<RoleClient>, NotificationHandle)>,
+}
+
+impl ResourceSubscription {
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub async fn unsubscribe(mut self) -> Result<(), ServiceError> {
+        let Some((peer, handle)) = self.active.take() else {
+            return Ok(());
+        };
+        handle.cancel();
+        peer.unsubscribe(UnsubscribeRequestParam {
+            uri: std::mem::take(&mut self.uri),
+        })
+        .await
+    }
+}
+
+impl futures::Stream for ResourceSubscription {
+    type Item = ResourceUpdatedNotificationParam;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.updates.poll_recv(cx)
+    }
+}
+
+impl Drop for ResourceSubscription {
+    fn drop(&mut self) {
+        let Some((peer, handle)) = self.active.take() else {
+            return;
+        };
+        handle.cancel();
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(uri = %self.uri, "no runtime to unsubscribe the resource");
+            return;
+        };
+        let uri = std::mem::take(&mut self.uri);
+        runtime.spawn(async move {
+            if let Err(error) = peer.unsubscribe(UnsubscribeRequestParam { uri }).await {
+                tracing::warn!(%error, "failed to unsubscribe the resource");
+            }
+        });
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{Peer, ServiceError, ServiceRole};
+use crate::model::{
+    ResourceUpdatedNotification, ResourceUpdatedNotificationMethod,
+    ResourceUpdatedNotificationParam,
+};
+
+struct PendingUpdate {
+    param: ResourceUpdatedNotificationParam,
+    generation: u64,
+}
+
+#[derive(Default)]
+struct DebounceState {
+    pending: HashMap<String, PendingUpdate>,
+    next_generation: u64,
+}
+
+/// Coalesce bursts of [`ResourceUpdatedNotification`] per resource uri.
+///
+/// The first notification for an uri opens a window of `debounce_window`, every notification
+/// for the same uri arriving inside that window replaces the queued one, and only the most recent
+/// one is sent when the window ends. Different uris are debounced independently.
+///
+/// ```rust,ignore
+/// let notifier = DebounceNotifier::new(peer, Duration::from_millis(200));
+/// notifier.notify(ResourceUpdatedNotificationParam { uri: "file:///a.txt".into() });
+/// ```
+pub struct DebounceNotifier<R: ServiceRole> {
+    peer: Peer<R>,
+    debounce_window: Duration,
+    state: Arc<Mutex<DebounceState>>,
+}
+
+impl<R: ServiceRole> Clone for DebounceNotifier<R> {
+    fn clone(&self) -> Self {
+        Self {
+            peer: self.peer.clone(),
+            debounce_window: self.debounce_window,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<R: ServiceRole> std::fmt::Debug for DebounceNotifier<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebounceNotifier")
+            .field("peer", &self.peer)
+            .field("debounce_window", &self.debounce_window)
+            .finish()
+    }
+}
+
+impl<R> DebounceNotifier<R>
+where
+    R: ServiceRole,
+    R::Not: From<ResourceUpdatedNotification>,
+{
+    pub fn new(peer: Peer<R>, debounce_window: Duration) -> Self {
+        Self {
+            peer,
+            debounce_window,
+            state: Default::default(),
+        }
+    }
+
+    pub fn peer(&self) -> &Peer<R> {
+        &self.peer
+    }
+
+    pub fn debounce_window(&self) -> Duration {
+        self.debounce_window
+    }
+
+    /// Queue a notification, it will be sent at the end of the current window of its uri.
+    ///
+    /// This must be called inside a tokio runtime.
+    pub fn notify(&self, param: ResourceUpdatedNotificationParam) {
+        let uri = param.uri.clone();
+        let mut state = self.state.lock().expect("debounce state poisoned");
+        if let Some(pending) = state.pending.get_mut(&uri) {
+            // a window is already open for this uri, just keep the latest one
+            pending.param = param;
+            return;
+        }
+        let generation = state.next_generation;
+        state.next_generation = state.next_generation.wrapping_add(1);
+        state
+            .pending
+            .insert(uri.clone(), PendingUpdate { param, generation });
+        drop(state);
+        let notifier = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(notifier.debounce_window).await;
+            let param = {
+                let mut state = notifier.state.lock().expect("debounce state poisoned");
+                match state.pending.get(&uri) {
+                    // the entry may have been flushed, and a new window may have been opened since
+                    Some(pending) if pending.generation == generation => {
+                        state.pending.remove(&uri).map(|pending| pending.param)
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(param) = param {
+                if let Err(error) = notifier.send(param).await {
+                    tracing::warn!(%uri, %error, "failed to send debounced resource updated notification");
+                }
+            }
+        });
+    }
+
+    /// Send all queued notifications immediately, without waiting for their windows to end.
+    pub async fn flush(&self) -> Result<(), ServiceError> {
+        let pending = {
+            let mut state = self.state.lock().expect("debounce state poisoned");
+            std::mem::take(&mut state.pending)
+        };
+        for (_, pending) in pending {
+            self.send(pending.param).await?;
+        }
+        Ok(())
+    }
+
+    /// The count of uris which have a notification waiting to be sent.
+    pub fn pending_count(&self) -> usize {
+        self.state
+            .lock()
+            .expect("debounce state poisoned")
+            .pending
+            .len()
+    }
+
+    async fn send(&self, param: ResourceUpdatedNotificationParam) -> Result<(), ServiceError> {
+        let notification = ResourceUpdatedNotification {
+            method: ResourceUpdatedNotificationMethod,
+            params: param,
+            extensions: Default::default(),
+        };
+        self.peer.send_notification(notification).await
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::{
+        RoleServer,
+        model::ServerNotification,
+        service::{AtomicU32RequestIdProvider, PeerSinkMessage},
+    };
+
+    fn spawn_peer() -> (
+        Peer<RoleServer>,
+        tokio::task::JoinHandle<Vec<ResourceUpdatedNotificationParam>>,
+    ) {
+        let (peer, mut rx) =
+            Peer::<RoleServer>::new(Arc::new(AtomicU32RequestIdProvider::default()), None);
+        let sink = tokio::spawn(async move {
+            let mut received = vec![];
+            while let Some(message) = rx.recv().await {
+                if let PeerSinkMessage::Notification {
+                    notification: ServerNotification::ResourceUpdatedNotification(notification),
+                    responder,
+                } = message
+                {
+                    received.push(notification.params);
+                    let _ = responder.send(Ok(()));
+                }
+            }
+            received
+        });
+        (peer, sink)
+    }
+
+    fn updated(uri: &str) -> ResourceUpdatedNotificationParam {
+        ResourceUpdatedNotificationParam { uri: uri.into() }
+    }
+
+    #[tokio::test]
+    async fn test_rapid_notifications_are_coalesced() {
+        let (peer, sink) = spawn_peer();
+        let notifier = DebounceNotifier::new(peer, Duration::from_millis(100));
+        for _ in 0..100 {
+            notifier.notify(updated("file:///a.txt"));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(notifier.pending_count(), 0);
+        drop(notifier);
+        let received = sink.await.unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].uri, "file:///a.txt");
+    }
+
+    #[tokio::test]
+    async fn test_uris_are_tracked_independently() {
+        let (peer, sink) = spawn_peer();
+        let notifier = DebounceNotifier::new(peer, Duration::from_millis(100));
+        for _ in 0..10 {
+            notifier.notify(updated("file:///a.txt"));
+            notifier.notify(updated("file:///b.txt"));
+        }
+        assert_eq!(notifier.pending_count(), 2);
+        notifier.flush().await.unwrap();
+        assert_eq!(notifier.pending_count(), 0);
+        // the timers of the flushed windows must not send again
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        drop(notifier);
+        let mut uris: Vec<_> = sink
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|param| param.uri)
+            .collect();
+        uris.sort();
+        assert_eq!(uris, vec!["file:///a.txt", "file:///b.txt"]);
+    }
+}
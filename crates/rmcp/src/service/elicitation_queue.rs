@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::oneshot;
+
+use super::{ElicitationError, ElicitationSafe, Peer, RoleServer};
+
+/// Serialize the elicitations sent to a client.
+///
+/// Most clients present one elicitation at a time, and can't handle a second `elicitation/create`
+/// request while the user is answering the first. Handlers eliciting through a shared queue wait
+/// for the pending elicitations to be answered before sending theirs, in the order they called.
+///
+/// ```rust,ignore
+/// let queue = ElicitationQueue::new(context.peer.clone());
+/// // clones share the queue
+/// let profile: Option<UserProfile> = queue.clone().elicit("Please enter your profile").await?;
+/// ```
+#[derive(Clone)]
+pub struct ElicitationQueue {
+    peer: Peer<RoleServer>,
+    /// Fires when the last queued elicitation is done
+    last: Arc<Mutex<Option<oneshot::Receiver<()>>>>,
+}
+
+impl std::fmt::Debug for ElicitationQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElicitationQueue")
+            .field("peer", &self.peer)
+            .finish()
+    }
+}
+
+/// A place in the queue, the next elicitation starts when it's dropped
+struct Turn {
+    previous: Option<oneshot::Receiver<()>>,
+    done: Option<oneshot::Sender<()>>,
+}
+
+impl Turn {
+    async fn wait(&mut self) {
+        if let Some(previous) = self.previous.as_mut() {
+            // a dropped sender also means the previous turn is over
+            let _ = previous.await;
+        }
+        self.previous = None;
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        // cancelled while waiting, so the next turn must still wait for the previous one
+        if let (Some(previous), Some(done)) = (self.previous.take(), self.done.take()) {
+            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                handle.spawn(async move {
+                    let _ = previous.await;
+                    drop(done);
+                });
+            }
+        }
+    }
+}
+
+impl ElicitationQueue {
+    pub fn new(peer: Peer<RoleServer>) -> Self {
+        Self {
+            peer,
+            last: Default::default(),
+        }
+    }
+
+    pub fn peer(&self) -> &Peer<RoleServer> {
+        &self.peer
+    }
+
+    fn enqueue(&self) -> Turn {
+        let (done, finished) = oneshot::channel();
+        let previous = self
+            .last
+            .lock()
+            .expect("elicitation queue poisoned")
+            .replace(finished);
+        Turn {
+            previous,
+            done: Some(done),
+        }
+    }
+
+    /// Same as [`Peer::elicit`], once the pending elicitations are answered
+    pub async fn elicit<T>(&self, message: impl Into<String>) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.elicit_with_timeout(message, None).await
+    }
+
+    /// Same as [`Peer::elicit_with_timeout`], once the pending elicitations are answered.
+    ///
+    /// The timeout only starts when the elicitation is sent, not while waiting in the queue.
+    pub async fn elicit_with_timeout<T>(
+        &self,
+        message: impl Into<String>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        let mut turn = self.enqueue();
+        turn.wait().await;
+        let result = self.peer.elicit_with_timeout(message, timeout).await;
+        drop(turn);
+        result
+    }
+}
@@ -0,0 +1,167 @@
+//! Middlewares wrapping a [`Service`], see [`ServiceExt::with_timeout`] and
+//! [`ServiceExt::with_tracing`].
+//!
+//! They are also tower [`Layer`](tower_layer::Layer)s with the `tower` feature, so they can be
+//! stacked with any other layer by [`ServiceExt::with_layer`].
+use std::time::Duration;
+
+use tracing::Instrument;
+
+use super::{NotificationContext, RequestContext, Service, ServiceRole};
+use crate::{ErrorData as McpError, model::HasMethod};
+
+/// Fail the requests the inner service doesn't handle in time, see [`ServiceExt::with_timeout`].
+///
+/// [`ServiceExt::with_timeout`]: super::ServiceExt::with_timeout
+#[derive(Debug, Clone)]
+pub struct TimeoutService<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<S> TimeoutService<S> {
+    pub fn new(inner: S, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl<R: ServiceRole, S: Service<R>> Service<R> for TimeoutService<S> {
+    async fn handle_request(
+        &self,
+        request: R::PeerReq,
+        context: RequestContext<R>,
+    ) -> Result<R::Resp, McpError> {
+        // the handler is dropped on timeout, cancel the work it spawned too
+        let ct = context.ct.clone();
+        match tokio::time::timeout(self.timeout, self.inner.handle_request(request, context)).await
+        {
+            Ok(result) => result,
+            Err(_) => {
+                ct.cancel();
+                Err(McpError::internal_error(
+                    format!("request timed out after {:?}", self.timeout),
+                    None,
+                ))
+            }
+        }
+    }
+
+    fn handle_notification(
+        &self,
+        notification: R::PeerNot,
+        context: NotificationContext<R>,
+    ) -> impl Future<Output = Result<(), McpError>> + Send + '_ {
+        self.inner.handle_notification(notification, context)
+    }
+
+    fn get_info(&self) -> R::Info {
+        self.inner.get_info()
+    }
+}
+
+/// Handle every request and notification in a span, and log the failed requests, see
+/// [`ServiceExt::with_tracing`].
+///
+/// [`ServiceExt::with_tracing`]: super::ServiceExt::with_tracing
+#[derive(Debug, Clone)]
+pub struct TracingService<S> {
+    inner: S,
+}
+
+impl<S> TracingService<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<R, S> Service<R> for TracingService<S>
+where
+    R: ServiceRole,
+    R::PeerReq: HasMethod,
+    S: Service<R>,
+{
+    async fn handle_request(
+        &self,
+        request: R::PeerReq,
+        context: RequestContext<R>,
+    ) -> Result<R::Resp, McpError> {
+        let span = tracing::info_span!("mcp_request", method = request.method(), id = %context.id);
+        async {
+            let start = std::time::Instant::now();
+            let result = self.inner.handle_request(request, context).await;
+            let elapsed = start.elapsed();
+            match &result {
+                Ok(_) => tracing::debug!(?elapsed, "request handled"),
+                Err(error) => {
+                    tracing::warn!(?elapsed, code = error.code.0, %error.message, "request failed")
+                }
+            }
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
+    fn handle_notification(
+        &self,
+        notification: R::PeerNot,
+        context: NotificationContext<R>,
+    ) -> impl Future<Output = Result<(), McpError>> + Send + '_ {
+        self.inner
+            .handle_notification(notification, context)
+            .instrument(tracing::info_span!("mcp_notification"))
+    }
+
+    fn get_info(&self) -> R::Info {
+        self.inner.get_info()
+    }
+}
+
+/// The tower [`Layer`](tower_layer::Layer) of [`TimeoutService`]
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+#[cfg(feature = "tower")]
+impl TimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<S> tower_layer::Layer<S> for TimeoutLayer {
+    type Service = TimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimeoutService::new(inner, self.timeout)
+    }
+}
+
+/// The tower [`Layer`](tower_layer::Layer) of [`TracingService`]
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingLayer;
+
+#[cfg(feature = "tower")]
+impl<S> tower_layer::Layer<S> for TracingLayer {
+    type Service = TracingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TracingService::new(inner)
+    }
+}
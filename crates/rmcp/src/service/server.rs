@@ -1,5 +1,9 @@
 use std::borrow::Cow;
 
+#[cfg(feature = "resource-streaming")]
+use base64::engine::{Engine, general_purpose::STANDARD as BASE64_STANDARD};
+#[cfg(feature = "resource-streaming")]
+use futures::StreamExt;
 use thiserror::Error;
 
 use super::*;
@@ -11,12 +15,14 @@ use crate::{
     model::{
         CancelledNotification, CancelledNotificationParam, ClientInfo, ClientJsonRpcMessage,
         ClientNotification, ClientRequest, ClientResult, CreateMessageRequest,
-        CreateMessageRequestParam, CreateMessageResult, ErrorData, ListRootsRequest,
-        ListRootsResult, LoggingMessageNotification, LoggingMessageNotificationParam,
-        ProgressNotification, ProgressNotificationParam, PromptListChangedNotification,
-        ProtocolVersion, ResourceListChangedNotification, ResourceUpdatedNotification,
-        ResourceUpdatedNotificationParam, ServerInfo, ServerNotification, ServerRequest,
-        ServerResult, ToolListChangedNotification,
+        CreateMessageRequestParam, CreateMessageResult, ErrorData, InitializeRequestParam,
+        ListRootsRequest, ListRootsResult, LoggingMessageNotification,
+        LoggingMessageNotificationParam, ProgressNotification, ProgressNotificationParam,
+        PromptListChangedNotification, ProtocolVersion, ResourceChunkEndNotification,
+        ResourceChunkEndNotificationParam, ResourceChunkNotification,
+        ResourceChunkNotificationParam, ResourceListChangedNotification,
+        ResourceUpdatedNotification, ResourceUpdatedNotificationParam, ServerInfo,
+        ServerNotification, ServerRequest, ServerResult, ToolListChangedNotification,
     },
     transport::DynamicTransportError,
 };
@@ -58,7 +64,10 @@ pub enum ServerInitializeError {
     #[error("initialize failed: {0}")]
     InitializeFailed(ErrorData),
 
-    #[error("unsupported protocol version: {0}")]
+    #[error(
+        "unsupported protocol version: {0}, the latest version supported is {latest}",
+        latest = ProtocolVersion::LATEST
+    )]
     UnsupportedProtocolVersion(ProtocolVersion),
 
     #[error("Send message error {error}, when {context}")]
@@ -168,24 +177,249 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     tokio::select! {
-        result = serve_server_with_ct_inner(service, transport.into_transport(), ct.clone()) => { result }
+        result = serve_server_with_ct_inner(
+            |_: &InitializeRequestParam| std::future::ready(Ok(service)),
+            transport.into_transport(),
+            ServeServerConfig::default(),
+            ct.clone(),
+        ) => { result }
         _ = ct.cancelled() => {
             Err(ServerInitializeError::Cancelled)
         }
     }
 }
 
-async fn serve_server_with_ct_inner<S, T>(
+/// The protocol versions a server accepts from its clients, see [`serve_server_with_config`].
+///
+/// By default, like with [`serve_server`], a server accepts any version. A client of a version
+/// newer than the ones of the server is answered with the version of the server, and decides
+/// whether it can go on with it. The clients out of a range of versions are only refused once it
+/// is set with [`ServeServerConfig::with_protocol_versions`].
+#[derive(Clone, Default)]
+pub struct ServeServerConfig {
+    /// The oldest version accepted, any one if `None`, the default
+    pub min_protocol_version: Option<ProtocolVersion>,
+    /// The newest version accepted, any one if `None`, the default.
+    ///
+    /// When set, the unknown versions newer than [`ProtocolVersion::LATEST`] are refused too.
+    pub max_protocol_version: Option<ProtocolVersion>,
+    /// The ids of the requests sent to the client, default is [`AtomicU32RequestIdProvider`]
+    pub request_id_provider: Option<Arc<dyn RequestIdProvider>>,
+}
+
+impl std::fmt::Debug for ServeServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServeServerConfig")
+            .field("min_protocol_version", &self.min_protocol_version)
+            .field("max_protocol_version", &self.max_protocol_version)
+            .field("request_id_provider", &self.request_id_provider.is_some())
+            .finish()
+    }
+}
+
+impl ServeServerConfig {
+    /// Only accept the clients of these versions, e.g.
+    /// `ProtocolVersion::V_2025_03_26..=ProtocolVersion::V_2025_06_18`, the other ones are refused
+    /// with [`ServerInitializeError::UnsupportedProtocolVersion`].
+    ///
+    /// # Panics
+    ///
+    /// If no version of the range can be accepted, e.g. if it's empty, or if it only holds unknown
+    /// versions newer than [`ProtocolVersion::LATEST`]: the server could never be initialized.
+    pub fn with_protocol_versions(
+        mut self,
+        versions: std::ops::RangeInclusive<ProtocolVersion>,
+    ) -> Self {
+        let (min, max) = versions.into_inner();
+        assert!(
+            accepts_any(&min, &max),
+            "no protocol version can be accepted from {min} to {max}"
+        );
+        self.min_protocol_version = Some(min);
+        self.max_protocol_version = Some(max);
+        self
+    }
+
+    /// Generate the ids of the requests sent to the client with this provider, e.g.
+    /// [`AtomicU64RequestIdProvider`](crate::service::AtomicU64RequestIdProvider)
+    pub fn with_request_id_provider(mut self, provider: impl RequestIdProvider) -> Self {
+        self.request_id_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Whether a client of this version is accepted.
+    ///
+    /// Once a maximum version is set, the unknown versions newer than [`ProtocolVersion::LATEST`]
+    /// are refused whatever it is, as there is no telling what they are compatible with.
+    pub fn accepts(&self, version: &ProtocolVersion) -> bool {
+        self.min_protocol_version
+            .as_ref()
+            .is_none_or(|min| version >= min)
+            && self.max_protocol_version.as_ref().is_none_or(|max| {
+                version <= max && (version.is_supported() || *version < ProtocolVersion::LATEST)
+            })
+    }
+}
+
+/// Whether a client of some version is accepted from `min` to `max`, the range may only hold
+/// unknown versions
+fn accepts_any(min: &ProtocolVersion, max: &ProtocolVersion) -> bool {
+    let config = ServeServerConfig {
+        min_protocol_version: Some(min.clone()),
+        max_protocol_version: Some(max.clone()),
+        request_id_provider: None,
+    };
+    // the minimum is accepted if it's older than the latest one, whether it's known or not
+    config.accepts(min)
+        || ProtocolVersion::KNOWN
+            .iter()
+            .any(|version| config.accepts(version))
+}
+
+/// Serve a server, refusing the clients whose protocol version is out of the range of the config
+/// with [`ServerInitializeError::UnsupportedProtocolVersion`]
+pub async fn serve_server_with_config<S, T, E, A>(
     service: S,
     transport: T,
+    config: ServeServerConfig,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    tokio::select! {
+        result = serve_server_with_ct_inner(
+            |_: &InitializeRequestParam| std::future::ready(Ok(service)),
+            transport.into_transport(),
+            config,
+            ct.clone(),
+        ) => { result }
+        _ = ct.cancelled() => {
+            Err(ServerInitializeError::Cancelled)
+        }
+    }
+}
+
+/// Create the service once the client asked to initialize, and serve it.
+///
+/// The factory is given the `initialize` request params, e.g. to check the client capabilities,
+/// and can open the resources of the service, e.g. a database connection. If it fails, the error
+/// is sent as the response to the `initialize` request and the session is over, without any
+/// [`ServerHandler::initialize`](crate::ServerHandler::initialize) override:
+/// ```rust,ignore
+/// let server = serve_server_lazy(
+///     |params: &InitializeRequestParam| {
+///         let sampling = params.capabilities.sampling.is_some();
+///         async move {
+///             if !sampling {
+///                 return Err(ErrorData::invalid_request("sampling is required", None));
+///             }
+///             Ok(MyServer::connect(&database_url).await?)
+///         }
+///     },
+///     stdio(),
+/// )
+/// .await?;
+/// ```
+pub async fn serve_server_lazy<S, F, Fut, T, E, A>(
+    factory: F,
+    transport: T,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    F: FnOnce(&InitializeRequestParam) -> Fut,
+    Fut: Future<Output = Result<S, ErrorData>>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    serve_server_lazy_with_ct(factory, transport, CancellationToken::new()).await
+}
+
+/// See [`serve_server_lazy`]
+pub async fn serve_server_lazy_with_ct<S, F, Fut, T, E, A>(
+    factory: F,
+    transport: T,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    F: FnOnce(&InitializeRequestParam) -> Fut,
+    Fut: Future<Output = Result<S, ErrorData>>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    serve_server_lazy_with_config(factory, transport, ServeServerConfig::default(), ct).await
+}
+
+/// See [`serve_server_lazy`] and [`serve_server_with_config`], the protocol version of the client
+/// is checked before the factory is called
+pub async fn serve_server_lazy_with_config<S, F, Fut, T, E, A>(
+    factory: F,
+    transport: T,
+    config: ServeServerConfig,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    F: FnOnce(&InitializeRequestParam) -> Fut,
+    Fut: Future<Output = Result<S, ErrorData>>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    tokio::select! {
+        result = serve_server_with_ct_inner(
+            factory,
+            transport.into_transport(),
+            config,
+            ct.clone(),
+        ) => { result }
+        _ = ct.cancelled() => {
+            Err(ServerInitializeError::Cancelled)
+        }
+    }
+}
+
+/// Check the protocol version of a client before creating its service, see
+/// [`ServeServerConfig::accepts`]
+pub(crate) fn check_protocol_version(
+    config: &ServeServerConfig,
+    version: &ProtocolVersion,
+) -> Result<(), ErrorData> {
+    if config.accepts(version) {
+        return Ok(());
+    }
+    let supported = match (&config.min_protocol_version, &config.max_protocol_version) {
+        (Some(min), Some(max)) => format!("the versions supported are {min} to {max}"),
+        (Some(min), None) => format!("the oldest version supported is {min}"),
+        (None, Some(max)) => format!("the newest version supported is {max}"),
+        // any version is accepted
+        (None, None) => return Ok(()),
+    };
+    Err(ErrorData::invalid_params(
+        format!("unsupported protocol version {version}, {supported}"),
+        None,
+    ))
+}
+
+async fn serve_server_with_ct_inner<S, F, Fut, T>(
+    factory: F,
+    transport: T,
+    config: ServeServerConfig,
     ct: CancellationToken,
 ) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
 where
     S: Service<RoleServer>,
+    F: FnOnce(&InitializeRequestParam) -> Fut,
+    Fut: Future<Output = Result<S, ErrorData>>,
     T: Transport<RoleServer> + 'static,
 {
     let mut transport = transport.into_transport();
-    let id_provider = <Arc<AtomicU32RequestIdProvider>>::default();
+    let id_provider = config
+        .request_id_provider
+        .clone()
+        .unwrap_or_else(|| Arc::new(AtomicU32RequestIdProvider::default()));
 
     // Get initialize request
     let (request, id) = expect_request(&mut transport, "initialized request").await?;
@@ -195,6 +429,30 @@ where
             ClientJsonRpcMessage::request(request, id),
         )));
     };
+    // checked first, so the service isn't created for a client it can't serve
+    if let Err(error) = check_protocol_version(&config, &peer_info.params.protocol_version) {
+        transport
+            .send(ServerJsonRpcMessage::error(error, id))
+            .await
+            .map_err(|error| {
+                ServerInitializeError::transport::<T>(error, "sending error response")
+            })?;
+        return Err(ServerInitializeError::UnsupportedProtocolVersion(
+            peer_info.params.protocol_version.clone(),
+        ));
+    }
+    let service = match factory(&peer_info.params).await {
+        Ok(service) => service,
+        Err(e) => {
+            transport
+                .send(ServerJsonRpcMessage::error(e.clone(), id))
+                .await
+                .map_err(|error| {
+                    ServerInitializeError::transport::<T>(error, "sending error response")
+                })?;
+            return Err(ServerInitializeError::InitializeFailed(e));
+        }
+    };
     let (peer, peer_rx) = Peer::new(id_provider, Some(peer_info.params.clone()));
     let context = RequestContext {
         ct: ct.child_token(),
@@ -220,16 +478,12 @@ where
             return Err(ServerInitializeError::InitializeFailed(e));
         }
     };
-    let peer_protocol_version = peer_info.params.protocol_version.clone();
-    let protocol_version = match peer_protocol_version
-        .partial_cmp(&init_response.protocol_version)
-        .ok_or(ServerInitializeError::UnsupportedProtocolVersion(
-            peer_protocol_version,
-        ))? {
-        std::cmp::Ordering::Less => peer_info.params.protocol_version.clone(),
-        _ => init_response.protocol_version,
-    };
-    init_response.protocol_version = protocol_version;
+    let peer_protocol_version = &peer_info.params.protocol_version;
+    if peer_protocol_version.is_supported()
+        && *peer_protocol_version < init_response.protocol_version
+    {
+        init_response.protocol_version = peer_protocol_version.clone();
+    }
     transport
         .send(ServerJsonRpcMessage::response(
             ServerResult::InitializeResult(init_response),
@@ -247,11 +501,11 @@ where
             Some(ClientJsonRpcMessage::notification(notification)),
         ));
     };
-    let context = NotificationContext {
-        meta: notification.get_meta().clone(),
-        extensions: notification.extensions().clone(),
-        peer: peer.clone(),
-    };
+    let context = NotificationContext::received(
+        peer.clone(),
+        notification.get_meta().clone(),
+        notification.extensions().clone(),
+    );
     let _ = service.handle_notification(notification, context).await;
     // Continue processing service
     Ok(serve_inner(service, transport, peer, peer_rx, ct))
@@ -384,6 +638,11 @@ macro_rules! method {
 }
 
 impl Peer<RoleServer> {
+    /// Ping the client, and return the round-trip time, see [`Peer::latency_stats`]
+    pub async fn ping(&self) -> Result<Duration, ServiceError> {
+        self.send_ping(ServerRequest::PingRequest(Default::default()))
+            .await
+    }
     pub async fn create_message(
         &self,
         params: CreateMessageRequestParam,
@@ -413,6 +672,58 @@ impl Peer<RoleServer> {
     method!(peer_not notify_resource_list_changed ResourceListChangedNotification);
     method!(peer_not notify_tool_list_changed ToolListChangedNotification);
     method!(peer_not notify_prompt_list_changed PromptListChangedNotification);
+    method!(peer_not notify_resource_chunk ResourceChunkNotification(ResourceChunkNotificationParam));
+    method!(peer_not notify_resource_chunk_end ResourceChunkEndNotification(ResourceChunkEndNotificationParam));
+
+    /// Check if the client declared streaming resources capability during initialization.
+    pub fn supports_streaming_resources(&self) -> bool {
+        self.peer_info()
+            .is_some_and(|client_info| client_info.capabilities.streaming_resources.is_some())
+    }
+
+    /// Send a streaming resource as a sequence of `notifications/resources/chunk`, terminated
+    /// by a `notifications/resources/chunk_end`, for the `resources/read` request `request_id`.
+    ///
+    /// The notifications should only be sent once the response of the request is, so the client
+    /// knows the stream token before receiving its chunks.
+    ///
+    /// If the stream yields an error, it's reported in the end notification and the stream is dropped.
+    #[cfg(feature = "resource-streaming")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "resource-streaming")))]
+    pub async fn send_resource_stream(
+        &self,
+        request_id: RequestId,
+        stream_token: String,
+        mut stream: crate::handler::server::streaming::ResourceChunkStream,
+    ) -> Result<(), ServiceError> {
+        let mut index = 0;
+        let mut error = None;
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(data) => {
+                    self.notify_resource_chunk(ResourceChunkNotificationParam {
+                        request_id: request_id.clone(),
+                        stream_token: stream_token.clone(),
+                        index,
+                        data: BASE64_STANDARD.encode(&data),
+                    })
+                    .await?;
+                    index += 1;
+                }
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        self.notify_resource_chunk_end(ResourceChunkEndNotificationParam {
+            request_id,
+            stream_token,
+            total_chunks: index,
+            error,
+        })
+        .await
+    }
 }
 
 // =============================================================================
@@ -456,6 +767,17 @@ pub enum ElicitationError {
     CapabilityNotSupported,
 }
 
+#[cfg(feature = "elicitation")]
+impl ElicitationError {
+    /// A [`ElicitationError::ParseError`] for content which is valid json but not acceptable
+    fn invalid_content(message: impl std::fmt::Display, data: serde_json::Value) -> Self {
+        ElicitationError::ParseError {
+            error: <serde_json::Error as serde::de::Error>::custom(message),
+            data,
+        }
+    }
+}
+
 /// Marker trait to ensure that elicitation types generate object-type JSON schemas.
 ///
 /// This trait provides compile-time safety to ensure that types used with
@@ -481,6 +803,11 @@ pub trait ElicitationSafe: schemars::JsonSchema {}
 /// This macro automatically implements the `ElicitationSafe` trait for struct types
 /// that should be used with `elicit<T>()` methods.
 ///
+/// Primitives, strings, sequences, tuples and `Option`s are rejected at compile time, as their
+/// schemas are never objects. This check can only see the type as written, so a newtype struct
+/// or an enum of unit variants passes it, use [`assert_elicitation_safe`] in a test to check the
+/// actual schema.
+///
 /// # Example
 ///
 /// ```rust
@@ -499,16 +826,139 @@ pub trait ElicitationSafe: schemars::JsonSchema {}
 /// // Now safe to use in async context:
 /// // let profile: UserProfile = server.elicit("Enter profile").await?;
 /// ```
+///
+/// ```compile_fail
+/// rmcp::elicit_safe!(String);
+/// ```
 #[cfg(feature = "elicitation")]
 #[macro_export]
 macro_rules! elicit_safe {
-    ($($t:ty),* $(,)?) => {
-        $(
-            impl $crate::service::ElicitationSafe for $t {}
-        )*
+    (@reject $($t:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "`",
+            ::core::stringify!($($t)*),
+            "` doesn't generate an object schema and can't be elicited, wrap it in a struct"
+        ));
+    };
+    () => {};
+    (bool $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject bool); };
+    (char $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject char); };
+    (str $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject str); };
+    (String $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject String); };
+    (i8 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject i8); };
+    (i16 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject i16); };
+    (i32 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject i32); };
+    (i64 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject i64); };
+    (i128 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject i128); };
+    (isize $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject isize); };
+    (u8 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject u8); };
+    (u16 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject u16); };
+    (u32 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject u32); };
+    (u64 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject u64); };
+    (u128 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject u128); };
+    (usize $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject usize); };
+    (f32 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject f32); };
+    (f64 $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject f64); };
+    (Vec<$inner:ty> $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject Vec<$inner>); };
+    (Option<$inner:ty> $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject Option<$inner>); };
+    (($($inner:tt)*) $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject ($($inner)*)); };
+    ([$($inner:tt)*] $(, $($rest:tt)*)?) => { $crate::elicit_safe!(@reject [$($inner)*]); };
+    ($t:ty $(, $($rest:tt)*)?) => {
+        impl $crate::service::ElicitationSafe for $t {}
+        $crate::elicit_safe!($($($rest)*)?);
     };
 }
 
+/// Check that the schema generated for an elicitation type is an object, panicking otherwise.
+///
+/// [`elicit_safe!`](crate::elicit_safe) only rejects the types it recognizes by name, call this
+/// from a test to check what the type really generates:
+///
+/// ```rust
+/// # use schemars::JsonSchema;
+/// #[derive(JsonSchema)]
+/// struct UserProfile {
+///     name: String,
+/// }
+/// rmcp::elicit_safe!(UserProfile);
+///
+/// #[test]
+/// fn user_profile_is_elicitation_safe() {
+///     rmcp::service::assert_elicitation_safe::<UserProfile>();
+/// }
+/// # rmcp::service::assert_elicitation_safe::<UserProfile>();
+/// ```
+#[cfg(feature = "elicitation")]
+#[track_caller]
+pub fn assert_elicitation_safe<T: ElicitationSafe>() {
+    let schema = schemars::schema_for!(T);
+    let instance_type = schema.get("type");
+    if instance_type != Some(&serde_json::Value::from("object")) {
+        panic!(
+            "`{}` is marked with `elicit_safe!` but its schema is not an object (type: {}), \
+             elicitation requests need an object schema, wrap the value in a struct",
+            T::schema_name(),
+            instance_type.map_or_else(|| "none".to_owned(), ToString::to_string),
+        );
+    }
+}
+
+/// Validates the accepted content of an elicitation against its requested schema.
+///
+/// Clients are expected to validate the user input, but may not, see
+/// [`ElicitationCapability::schema_validation`](crate::model::ElicitationCapability::schema_validation).
+/// The `format` keyword is asserted, so an `"email"` field must hold an email address.
+///
+/// ```rust
+/// # use rmcp::service::ElicitationValidator;
+/// # use serde_json::json;
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "email": { "type": "string", "format": "email" } },
+///     "required": ["email"]
+/// });
+/// let validator = ElicitationValidator::new(schema.as_object().unwrap());
+/// assert!(validator.validate(&json!({ "email": "alice@example.com" })).is_ok());
+/// assert!(validator.validate(&json!({ "email": "alice" })).is_err());
+/// ```
+#[cfg(all(feature = "elicitation", feature = "schema-validation"))]
+#[derive(Debug, Clone)]
+pub struct ElicitationValidator {
+    validator: crate::model::SchemaValidator,
+}
+
+#[cfg(all(feature = "elicitation", feature = "schema-validation"))]
+impl ElicitationValidator {
+    pub fn new(requested_schema: &crate::model::JsonObject) -> Self {
+        Self {
+            validator: crate::model::SchemaValidator::new(
+                serde_json::Value::Object(requested_schema.clone()),
+                true,
+            ),
+        }
+    }
+
+    /// Validate the content of an accepted elicitation.
+    ///
+    /// A mismatch is reported as [`ElicitationError::ParseError`], carrying the content.
+    pub fn validate(&self, content: &serde_json::Value) -> Result<(), ElicitationError> {
+        self.validator.validate(content).map_err(|errors| {
+            let message = errors
+                .iter()
+                .map(|e| {
+                    if e.path.is_empty() {
+                        e.message.clone()
+                    } else {
+                        format!("{}: {}", e.path, e.message)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            ElicitationError::invalid_content(message, content.clone())
+        })
+    }
+}
+
 #[cfg(feature = "elicitation")]
 impl Peer<RoleServer> {
     /// Check if the client supports elicitation capability
@@ -668,6 +1118,159 @@ impl Peer<RoleServer> {
         message: impl Into<String>,
         timeout: Option<std::time::Duration>,
     ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.elicit_inner(message.into(), timeout, false).await
+    }
+
+    /// Request typed data from the user, and validate the response against the schema.
+    ///
+    /// Same as `elicit()`, but the accepted content is checked with an [`ElicitationValidator`]
+    /// before being parsed, rather than trusting the client to have validated the user input.
+    /// Constraints which don't show in the Rust type, like the `format` of a string, are enforced.
+    ///
+    /// **Requires the `elicitation` and `schema-validation` features to be enabled.**
+    ///
+    /// # Returns
+    /// Same as `elicit()`, a response not matching the schema is an
+    /// `Err(ElicitationError::ParseError { .. })`
+    #[cfg(all(
+        feature = "schemars",
+        feature = "elicitation",
+        feature = "schema-validation"
+    ))]
+    pub async fn elicit_validated<T>(
+        &self,
+        message: impl Into<String>,
+    ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.elicit_validated_with_timeout(message, None).await
+    }
+
+    /// Same as `elicit_validated()` with a custom timeout, see `elicit_with_timeout()`.
+    #[cfg(all(
+        feature = "schemars",
+        feature = "elicitation",
+        feature = "schema-validation"
+    ))]
+    pub async fn elicit_validated_with_timeout<T>(
+        &self,
+        message: impl Into<String>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.elicit_inner(message.into(), timeout, true).await
+    }
+
+    /// Ask the user to choose one of the options, and return the index of the chosen one.
+    ///
+    /// The options are listed in the message and in the description of the requested `choice`
+    /// field, an integer in `0..options.len()`.
+    ///
+    /// # Returns
+    /// * `Ok(Some(index))` if the user chose an option
+    /// * `Ok(None)` if there is no option to choose from, the request isn't sent then
+    /// * `Err(ElicitationError::ParseError { .. })` if the response isn't one of the indexes
+    /// * other errors as `elicit()`
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use rmcp::*;
+    /// # async fn example(peer: Peer<RoleServer>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let regions = ["eu-west-1", "us-east-1", "ap-south-1"];
+    /// if let Some(region) = peer.elicit_choice_value("Where to deploy?", &regions).await? {
+    ///     println!("Deploying to {region}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn elicit_choice<T: std::fmt::Display>(
+        &self,
+        message: impl Into<String>,
+        options: &[T],
+    ) -> Result<Option<usize>, ElicitationError> {
+        const FIELD: &str = "choice";
+        if options.is_empty() {
+            return Ok(None);
+        }
+        if !self.supports_elicitation() {
+            return Err(ElicitationError::CapabilityNotSupported);
+        }
+
+        let listing = options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| format!("{index}: {option}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                FIELD: {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": options.len() - 1,
+                    "description": format!("The number of the chosen option:\n{listing}"),
+                }
+            },
+            "required": [FIELD],
+        });
+        let serde_json::Value::Object(requested_schema) = schema else {
+            unreachable!("the schema is an object")
+        };
+        let response = self
+            .create_elicitation(CreateElicitationRequestParam {
+                message: format!("{}\n{listing}", message.into()),
+                requested_schema,
+            })
+            .await?;
+
+        match response.action {
+            crate::model::ElicitationAction::Accept => {
+                let value = response.content.ok_or(ElicitationError::NoContent)?;
+                match value.get(FIELD).and_then(serde_json::Value::as_u64) {
+                    Some(index) if index < options.len() as u64 => Ok(Some(index as usize)),
+                    _ => Err(ElicitationError::invalid_content(
+                        format!("expected `{FIELD}` in 0..{}", options.len()),
+                        value,
+                    )),
+                }
+            }
+            crate::model::ElicitationAction::Decline => Err(ElicitationError::UserDeclined),
+            crate::model::ElicitationAction::Cancel => Err(ElicitationError::UserCancelled),
+        }
+    }
+
+    /// Same as [`Peer::elicit_choice`], returning the chosen option
+    pub async fn elicit_choice_value<T: std::fmt::Display + Clone>(
+        &self,
+        message: impl Into<String>,
+        options: &[T],
+    ) -> Result<Option<T>, ElicitationError> {
+        let index = self.elicit_choice(message, options).await?;
+        Ok(index.map(|index| options[index].clone()))
+    }
+
+    /// Elicit through this peer with a default timeout, see [`ElicitationPeer`].
+    pub fn with_elicitation_timeout(&self, default: std::time::Duration) -> ElicitationPeer {
+        ElicitationPeer {
+            peer: self.clone(),
+            default_timeout: default,
+        }
+    }
+
+    #[cfg(all(feature = "schemars", feature = "elicitation"))]
+    async fn elicit_inner<T>(
+        &self,
+        message: String,
+        timeout: Option<std::time::Duration>,
+        validate: bool,
+    ) -> Result<Option<T>, ElicitationError>
     where
         T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
     {
@@ -678,11 +1281,15 @@ impl Peer<RoleServer> {
 
         // Generate schema automatically from type
         let schema = crate::handler::server::tool::schema_for_type::<T>();
+        #[cfg(feature = "schema-validation")]
+        let validator = validate.then(|| ElicitationValidator::new(&schema));
+        #[cfg(not(feature = "schema-validation"))]
+        let _ = validate;
 
         let response = self
             .create_elicitation_with_timeout(
                 CreateElicitationRequestParam {
-                    message: message.into(),
+                    message,
                     requested_schema: schema,
                 },
                 timeout,
@@ -692,6 +1299,10 @@ impl Peer<RoleServer> {
         match response.action {
             crate::model::ElicitationAction::Accept => {
                 if let Some(value) = response.content {
+                    #[cfg(feature = "schema-validation")]
+                    if let Some(validator) = &validator {
+                        validator.validate(&value)?;
+                    }
                     match serde_json::from_value::<T>(value.clone()) {
                         Ok(parsed) => Ok(Some(parsed)),
                         Err(error) => Err(ElicitationError::ParseError { error, data: value }),
@@ -705,3 +1316,77 @@ impl Peer<RoleServer> {
         }
     }
 }
+
+/// A [`Peer<RoleServer>`] whose elicitations time out after a default duration.
+///
+/// `Peer::elicit` waits for the user as long as it takes, a server not willing to wait forever
+/// for a client may use this instead:
+///
+/// ```rust,ignore
+/// let peer = context.peer.with_elicitation_timeout(Duration::from_secs(60));
+/// // fails with `ElicitationError::Service(ServiceError::Timeout { .. })` after a minute
+/// let profile: Option<UserProfile> = peer.elicit("Please enter your profile").await?;
+/// ```
+#[cfg(feature = "elicitation")]
+#[derive(Debug, Clone)]
+pub struct ElicitationPeer {
+    peer: Peer<RoleServer>,
+    default_timeout: std::time::Duration,
+}
+
+#[cfg(feature = "elicitation")]
+impl ElicitationPeer {
+    pub fn peer(&self) -> &Peer<RoleServer> {
+        &self.peer
+    }
+
+    pub fn default_timeout(&self) -> std::time::Duration {
+        self.default_timeout
+    }
+
+    /// Same as [`Peer::elicit`], with the default timeout
+    #[cfg(feature = "schemars")]
+    pub async fn elicit<T>(&self, message: impl Into<String>) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.elicit_with_timeout(message, None).await
+    }
+
+    /// Same as [`Peer::elicit_with_timeout`], falling back to the default timeout if `timeout`
+    /// is `None`
+    #[cfg(feature = "schemars")]
+    pub async fn elicit_with_timeout<T>(
+        &self,
+        message: impl Into<String>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.peer
+            .elicit_with_timeout(message, Some(timeout.unwrap_or(self.default_timeout)))
+            .await
+    }
+
+    /// Same as [`Peer::elicit_validated`], with the default timeout
+    #[cfg(all(feature = "schemars", feature = "schema-validation"))]
+    pub async fn elicit_validated<T>(
+        &self,
+        message: impl Into<String>,
+    ) -> Result<Option<T>, ElicitationError>
+    where
+        T: ElicitationSafe + for<'de> serde::Deserialize<'de>,
+    {
+        self.peer
+            .elicit_validated_with_timeout(message, Some(self.default_timeout))
+            .await
+    }
+}
+
+#[cfg(feature = "elicitation")]
+impl From<ElicitationPeer> for Peer<RoleServer> {
+    fn from(value: ElicitationPeer) -> Self {
+        value.peer
+    }
+}
@@ -68,6 +68,7 @@ use std::{borrow::Cow, sync::Arc};
 
 use crate::service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage};
 
+pub mod sequence;
 pub mod sink_stream;
 
 #[cfg(feature = "transport-async-rw")]
@@ -86,7 +87,7 @@ pub use worker::WorkerTransport;
 pub mod child_process;
 #[cfg(feature = "transport-child-process")]
 #[cfg_attr(docsrs, doc(cfg(feature = "transport-child-process")))]
-pub use child_process::{ConfigureCommandExt, TokioChildProcess};
+pub use child_process::{ChildProcessConfig, ConfigureCommandExt, TokioChildProcess};
 
 #[cfg(feature = "transport-io")]
 #[cfg_attr(docsrs, doc(cfg(feature = "transport-io")))]
@@ -109,9 +110,13 @@ pub mod sse_server;
 #[cfg_attr(docsrs, doc(cfg(feature = "transport-sse-server")))]
 pub use sse_server::SseServer;
 
-#[cfg(feature = "auth")]
-#[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
+#[cfg(any(feature = "auth", feature = "jwt-auth"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "auth", feature = "jwt-auth"))))]
 pub mod auth;
+
+#[cfg(feature = "redaction")]
+#[cfg_attr(docsrs, doc(cfg(feature = "redaction")))]
+pub mod redact;
 #[cfg(feature = "auth")]
 #[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
 pub use auth::{AuthError, AuthorizationManager, AuthorizationSession, AuthorizedHttpClient};
@@ -277,4 +282,24 @@ impl DynamicTransportError {
     pub fn is<T: Transport<R> + 'static, R: ServiceRole>(&self) -> bool {
         self.error.is::<T::Error>() && self.transport_type_id == std::any::TypeId::of::<T>()
     }
+    /// Get the error as `E`, whatever the transport.
+    ///
+    /// E.g. the error of the transports over a stream is a [`std::io::Error`], so its
+    /// [`kind`](std::io::Error::kind) can be inspected without naming the transport type.
+    pub fn downcast_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref::<E>()
+    }
+    /// Take the error as `E`, whatever the transport, see [`DynamicTransportError::downcast_ref`]
+    pub fn downcast_error<E: std::error::Error + 'static>(self) -> Result<Box<E>, Self> {
+        let Self {
+            transport_name,
+            transport_type_id,
+            error,
+        } = self;
+        error.downcast::<E>().map_err(|error| Self {
+            transport_name,
+            transport_type_id,
+            error,
+        })
+    }
 }
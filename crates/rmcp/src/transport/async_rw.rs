@@ -65,6 +65,64 @@ where
         ))));
         Self { read, write }
     }
+
+    /// Split the transport into its read and write halves, to read the next messages while a
+    /// message is being written, like [`tokio::io::split`].
+    pub fn split(
+        self,
+    ) -> (
+        AsyncRwTransportReader<Role, R>,
+        AsyncRwTransportWriter<Role, W>,
+    ) {
+        (
+            AsyncRwTransportReader { read: self.read },
+            AsyncRwTransportWriter { write: self.write },
+        )
+    }
+}
+
+/// The read half of an [`AsyncRwTransport`], a stream of the received messages
+pub struct AsyncRwTransportReader<Role: ServiceRole, R: AsyncRead> {
+    read: FramedRead<R, JsonRpcMessageCodec<RxJsonRpcMessage<Role>>>,
+}
+
+impl<Role: ServiceRole, R> futures::Stream for AsyncRwTransportReader<Role, R>
+where
+    R: AsyncRead + Unpin,
+{
+    type Item = Result<RxJsonRpcMessage<Role>, JsonRpcMessageCodecError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.read.poll_next_unpin(cx)
+    }
+}
+
+/// The write half of an [`AsyncRwTransport`]
+pub struct AsyncRwTransportWriter<Role: ServiceRole, W: AsyncWrite> {
+    write: Arc<Mutex<Option<TransportWriter<Role, W>>>>,
+}
+
+impl<Role: ServiceRole, W> AsyncRwTransportWriter<Role, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub async fn write_message(&mut self, message: TxJsonRpcMessage<Role>) -> std::io::Result<()> {
+        match self.write.lock().await.as_mut() {
+            Some(write) => write.send(message).await.map_err(Into::into),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "Transport is closed",
+            )),
+        }
+    }
+
+    pub async fn close(&mut self) -> std::io::Result<()> {
+        drop(self.write.lock().await.take());
+        Ok(())
+    }
 }
 
 #[cfg(feature = "client")]
@@ -422,6 +480,87 @@ mod test {
     ) -> impl Sink<T, Error = std::io::Error> {
         FramedWrite::new(writer, JsonRpcMessageCodec::<T>::default()).sink_map_err(Into::into)
     }
+    #[cfg(all(feature = "client", feature = "server"))]
+    #[tokio::test]
+    async fn test_split_halves_are_independent() {
+        use crate::{
+            RoleClient, RoleServer,
+            model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
+        };
+
+        let (server_read, client_write) = tokio::io::duplex(64);
+        let (client_read, server_write) = tokio::io::duplex(64);
+        let (mut reader, mut writer) =
+            AsyncRwTransport::<RoleServer, _, _>::new(server_read, server_write).split();
+        let mut client = AsyncRwTransport::<RoleClient, _, _>::new(client_read, client_write);
+
+        // a response larger than the pipe, only written once the client reads it
+        let response = ServerJsonRpcMessage::response(
+            crate::model::ServerResult::empty(()),
+            crate::model::NumberOrString::String("x".repeat(1024).into()),
+        );
+        let write = tokio::spawn(async move { writer.write_message(response).await });
+        client
+            .send(ClientJsonRpcMessage::notification(
+                crate::model::ClientNotification::InitializedNotification(Default::default()),
+            ))
+            .await
+            .unwrap();
+        // the next message is read while the response is still being written
+        let received = reader.next().await.unwrap().unwrap();
+        assert!(matches!(received, ClientJsonRpcMessage::Notification(_)));
+        assert!(!write.is_finished());
+
+        assert!(matches!(
+            client.receive().await,
+            Some(ServerJsonRpcMessage::Response(_))
+        ));
+        write.await.unwrap().unwrap();
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_dynamic_transport_error_downcast() {
+        use crate::{RoleClient, transport::DynamicTransportError};
+        type Transport =
+            AsyncRwTransport<RoleClient, tokio::io::DuplexStream, tokio::io::DuplexStream>;
+
+        let error = DynamicTransportError::new::<Transport, RoleClient>(std::io::Error::new(
+            std::io::ErrorKind::BrokenPipe,
+            "peer gone",
+        ));
+        assert_eq!(
+            error.downcast_ref::<std::io::Error>().map(|e| e.kind()),
+            Some(std::io::ErrorKind::BrokenPipe)
+        );
+        assert!(error.downcast_ref::<std::fmt::Error>().is_none());
+        let error = error.downcast_error::<std::fmt::Error>().unwrap_err();
+        assert!(error.is::<Transport, RoleClient>());
+        let io = error.downcast_error::<std::io::Error>().unwrap();
+        assert_eq!(io.to_string(), "peer gone");
+    }
+
+    #[tokio::test]
+    async fn test_decode_utf8_split_across_reads() {
+        use tokio::io::AsyncWriteExt;
+
+        let line =
+            "{\"jsonrpc\":\"2.0\",\"method\":\"echo\",\"params\":[\"\u{1F980}\"],\"id\":1}\n";
+        let crab = line.find('\u{1F980}').unwrap();
+        let (read, mut write) = tokio::io::duplex(1024);
+        let mut stream = from_async_read::<serde_json::Value, _>(read);
+
+        // the 4 bytes character is cut in the middle, the first read ends with half of it
+        write.write_all(&line.as_bytes()[..crab + 2]).await.unwrap();
+        write.flush().await.unwrap();
+        let next = tokio::time::timeout(std::time::Duration::from_millis(50), stream.next()).await;
+        assert!(next.is_err(), "no message before the end of the line");
+        write.write_all(&line.as_bytes()[crab + 2..]).await.unwrap();
+
+        let item = stream.next().await.unwrap();
+        assert_eq!(item["params"][0], "\u{1F980}");
+    }
+
     #[tokio::test]
     async fn test_decode() {
         use futures::StreamExt;
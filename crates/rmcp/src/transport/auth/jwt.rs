@@ -0,0 +1,281 @@
+//! Authenticate the http requests bearing a JSON Web Token, see [`JwtAuthLayer`].
+//!
+//! The layer wraps any http service, e.g. the router of an [`SseServer`] or a
+//! [`StreamableHttpService`]:
+//! ```rust,ignore
+//! use rmcp::transport::auth::jwt::{Algorithm, DecodingKey, JwtAuthLayer};
+//! let auth = JwtAuthLayer::new(DecodingKey::from_rsa_pem(public_key)?, Algorithm::RS256)
+//!     .with_issuer("https://auth.example.com")
+//!     .with_audience("my-mcp-server");
+//! let router = axum::Router::new()
+//!     .nest_service("/mcp", streamable_http_service)
+//!     .layer(auth);
+//! ```
+//!
+//! The [`AuthClaims`] of a valid token are inserted into the extensions of the http request, so
+//! the handlers can read them from the [`http::request::Parts`] of the message. With the
+//! streamable http server, they are also given as an
+//! [`AuthContext`](crate::transport::streamable_http_server::auth::AuthContext):
+//! ```rust,ignore
+//! use rmcp::{
+//!     handler::server::tool::Extension,
+//!     transport::{auth::jwt::AuthClaims, streamable_http_server::auth::AuthContext},
+//! };
+//! async fn my_tool(Extension(auth): Extension<AuthContext>) {
+//!     let claims = AuthClaims::from_context(&auth).expect("set by JwtAuthLayer");
+//!     tracing::info!("called by {:?} of {:?}", claims.sub, claims.iss)
+//! }
+//! ```
+//!
+//! [`SseServer`]: crate::transport::SseServer
+//! [`StreamableHttpService`]: crate::transport::StreamableHttpService
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use base64::Engine;
+use futures::future::{Either, Ready};
+use http::{HeaderMap, Request, Response, StatusCode};
+pub use jsonwebtoken::{Algorithm, DecodingKey, errors::Error};
+use jsonwebtoken::{Validation, errors::ErrorKind};
+use serde::{Deserialize, Serialize};
+
+use crate::model::JsonObject;
+
+/// The `aud` claim, a single audience or a list of them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Audience {
+    pub fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::Single(single) => single == audience,
+            Audience::Multiple(multiple) => multiple.iter().any(|a| a == audience),
+        }
+    }
+}
+
+/// The claims of a token, the registered ones and any other in `extra`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AuthClaims {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+    /// Expiration time, in seconds since the unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    /// Not before time, in seconds since the unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    /// Issued at time, in seconds since the unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    /// The granted scopes, separated by spaces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonObject,
+}
+
+#[cfg(feature = "transport-streamable-http-server")]
+impl AuthClaims {
+    /// Read the claims stored by [`JwtAuthLayer`] in an
+    /// [`AuthContext`](crate::transport::streamable_http_server::auth::AuthContext)
+    pub fn from_context(
+        context: &crate::transport::streamable_http_server::auth::AuthContext,
+    ) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(serde_json::Value::Object(context.claims.clone()))
+    }
+
+    pub fn into_context(self) -> crate::transport::streamable_http_server::auth::AuthContext {
+        let scopes = self
+            .scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        let subject = self.sub.clone();
+        let claims = match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(claims)) => claims,
+            _ => JsonObject::new(),
+        };
+        crate::transport::streamable_http_server::auth::AuthContext {
+            subject,
+            scopes,
+            claims,
+        }
+    }
+}
+
+/// The header fields `jsonwebtoken` doesn't check
+#[derive(Deserialize)]
+struct CriticalHeader {
+    /// Extensions the token requires to be understood, none are supported
+    #[serde(default)]
+    crit: Option<Vec<String>>,
+}
+
+/// A tower layer authenticating the requests bearing a JSON Web Token in their `Authorization`
+/// header.
+///
+/// A token is valid if it's signed by the key with the algorithm, not expired, and issued by the
+/// issuer to the audience when they are set. Tokens without an expiration time are refused. The
+/// other requests are answered with `401 Unauthorized`.
+#[derive(Clone)]
+pub struct JwtAuthLayer {
+    key: Arc<DecodingKey>,
+    validation: Validation,
+}
+
+impl std::fmt::Debug for JwtAuthLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // don't leak the secret into logs
+        f.debug_struct("JwtAuthLayer")
+            .field("validation", &self.validation)
+            .finish()
+    }
+}
+
+impl JwtAuthLayer {
+    /// Default tolerance for the clock skew between the issuer and the server
+    pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+    /// Accept the tokens signed by `key` with `algorithm`.
+    ///
+    /// A token can't pick another algorithm, e.g. `none`, or `HS256` with a public key as the
+    /// secret.
+    pub fn new(key: DecodingKey, algorithm: Algorithm) -> Self {
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = Self::DEFAULT_LEEWAY.as_secs();
+        validation.validate_nbf = true;
+        // the audience is only checked when one is expected
+        validation.validate_aud = false;
+        Self {
+            key: Arc::new(key),
+            validation,
+        }
+    }
+
+    /// Only accept the tokens issued to this audience
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.validation.set_audience(&[audience.into()]);
+        self.validation.validate_aud = true;
+        self.validation.required_spec_claims.insert("aud".into());
+        self
+    }
+
+    /// Only accept the tokens issued by this issuer
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.validation.set_issuer(&[issuer.into()]);
+        self.validation.required_spec_claims.insert("iss".into());
+        self
+    }
+
+    /// Set the tolerance for the clock skew when checking `exp` and `nbf`
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.validation.leeway = leeway.as_secs();
+        self
+    }
+
+    /// Check a token and decode its claims
+    pub fn verify(&self, token: &str) -> Result<AuthClaims, Error> {
+        let claims = jsonwebtoken::decode::<AuthClaims>(token, &self.key, &self.validation)?.claims;
+        let header = token.split('.').next().unwrap_or_default();
+        let critical = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(header)
+            .ok()
+            .and_then(|header| serde_json::from_slice::<CriticalHeader>(&header).ok());
+        if critical.is_none_or(|header| header.crit.is_some()) {
+            return Err(ErrorKind::InvalidToken.into());
+        }
+        // a token expires at `exp`, not a second after it
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let leeway = self.validation.leeway as i64;
+        if claims
+            .exp
+            .is_some_and(|exp| exp.saturating_add(leeway) <= now)
+        {
+            return Err(ErrorKind::ExpiredSignature.into());
+        }
+        Ok(claims)
+    }
+
+    fn authenticate(&self, headers: &HeaderMap) -> Result<AuthClaims, Error> {
+        let token = headers
+            .get(http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim())
+            .ok_or(ErrorKind::InvalidToken)?;
+        self.verify(token)
+    }
+}
+
+impl<S> tower_layer::Layer<S> for JwtAuthLayer {
+    type Service = JwtAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JwtAuthService {
+            inner,
+            auth: self.clone(),
+        }
+    }
+}
+
+/// The service of a [`JwtAuthLayer`]
+#[derive(Debug, Clone)]
+pub struct JwtAuthService<S> {
+    inner: S,
+    auth: JwtAuthLayer,
+}
+
+impl<S, B, ResBody> tower_service::Service<Request<B>> for JwtAuthService<S>
+where
+    S: tower_service::Service<Request<B>, Response = Response<ResBody>>,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Either<S::Future, Ready<Result<Response<ResBody>, S::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        match self.auth.authenticate(request.headers()) {
+            Ok(claims) => {
+                #[cfg(feature = "transport-streamable-http-server")]
+                request
+                    .extensions_mut()
+                    .insert(claims.clone().into_context());
+                request.extensions_mut().insert(claims);
+                Either::Left(self.inner.call(request))
+            }
+            Err(error) => {
+                tracing::debug!(%error, "unauthorized request");
+                let mut response = Response::new(ResBody::default());
+                *response.status_mut() = StatusCode::UNAUTHORIZED;
+                response.headers_mut().insert(
+                    http::header::WWW_AUTHENTICATE,
+                    http::HeaderValue::from_static("Bearer"),
+                );
+                Either::Right(futures::future::ready(Ok(response)))
+            }
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use std::process::Stdio;
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::PathBuf,
+    process::{ExitStatus, Stdio},
+    time::Duration,
+};
 
 use futures::future::Future;
 use process_wrap::tokio::{TokioChildWrapper, TokioCommandWrap};
@@ -11,6 +17,10 @@ use super::{RxJsonRpcMessage, Transport, TxJsonRpcMessage, async_rw::AsyncRwTran
 use crate::RoleClient;
 
 const MAX_WAIT_ON_DROP_SECS: u64 = 3;
+/// How often the child process is checked while waiting for a message
+const CHILD_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long the output of an exited child is still read, its pipe may be held open by a grandchild
+const EXITED_CHILD_READ_TIMEOUT: Duration = Duration::from_millis(100);
 /// The parts of a child process.
 type ChildProcessParts = (
     Box<dyn TokioChildWrapper>,
@@ -38,9 +48,19 @@ fn child_process(mut child: Box<dyn TokioChildWrapper>) -> std::io::Result<Child
 
 pub struct TokioChildProcess {
     child: ChildWithCleanup,
+    status: ChildStatus,
     transport: AsyncRwTransport<RoleClient, ChildStdout, ChildStdin>,
 }
 
+/// The status of the child process of a [`TokioChildProcess`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildStatus {
+    Running,
+    Exited(ExitStatus),
+    /// The child was taken by [`TokioChildProcess::into_inner`], or its status can't be read
+    Unknown,
+}
+
 pub struct ChildWithCleanup {
     inner: Option<Box<dyn TokioChildWrapper>>,
 }
@@ -102,6 +122,42 @@ impl TokioChildProcess {
         self.child.inner.as_ref()?.id()
     }
 
+    /// Check whether the child process is still running.
+    ///
+    /// The transport also watches the child while receiving: once it exits, the messages it
+    /// wrote are still received, then the transport is closed.
+    pub fn child_status(&mut self) -> ChildStatus {
+        poll_child_status(&mut self.child, &mut self.status)
+    }
+
+    async fn receive_message(&mut self) -> Option<RxJsonRpcMessage<RoleClient>> {
+        if !matches!(
+            poll_child_status(&mut self.child, &mut self.status),
+            ChildStatus::Exited(_)
+        ) {
+            let mut poll = tokio::time::interval(CHILD_STATUS_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    message = self.transport.receive() => return message,
+                    _ = poll.tick() => {
+                        if let ChildStatus::Exited(status) =
+                            poll_child_status(&mut self.child, &mut self.status)
+                        {
+                            if !status.success() {
+                                tracing::warn!(%status, "child process exited unexpectedly");
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        tokio::time::timeout(EXITED_CHILD_READ_TIMEOUT, self.transport.receive())
+            .await
+            .ok()
+            .flatten()
+    }
+
     /// Gracefully shutdown the child process
     ///
     /// This will first close the transport to the child process (the server),
@@ -150,6 +206,24 @@ impl TokioChildProcess {
     }
 }
 
+fn poll_child_status(child: &mut ChildWithCleanup, status: &mut ChildStatus) -> ChildStatus {
+    if let ChildStatus::Exited(_) = status {
+        return *status;
+    }
+    let Some(inner) = child.inner.as_mut() else {
+        return ChildStatus::Unknown;
+    };
+    *status = match inner.try_wait() {
+        Ok(Some(exit_status)) => ChildStatus::Exited(exit_status),
+        Ok(None) => ChildStatus::Running,
+        Err(e) => {
+            tracing::warn!("Error checking child process status: {e}");
+            ChildStatus::Unknown
+        }
+    };
+    *status
+}
+
 /// Builder for `TokioChildProcess` allowing custom `Stdio` configuration.
 pub struct TokioChildProcessBuilder {
     cmd: TokioCommandWrap,
@@ -197,6 +271,7 @@ impl TokioChildProcessBuilder {
         let transport = AsyncRwTransport::new(stdout, stdin);
         let proc = TokioChildProcess {
             child: ChildWithCleanup { inner: Some(child) },
+            status: ChildStatus::Running,
             transport,
         };
         Ok((proc, stderr_opt))
@@ -214,7 +289,7 @@ impl Transport<RoleClient> for TokioChildProcess {
     }
 
     fn receive(&mut self) -> impl Future<Output = Option<RxJsonRpcMessage<RoleClient>>> + Send {
-        self.transport.receive()
+        self.receive_message()
     }
 
     fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
@@ -222,6 +297,114 @@ impl Transport<RoleClient> for TokioChildProcess {
     }
 }
 
+/// A child process to spawn, for configurations built from data rather than code.
+///
+/// ```rust,no_run
+/// use rmcp::transport::{TokioChildProcess, child_process::ChildProcessConfig};
+///
+/// # fn example() -> std::io::Result<()> {
+/// let config = ChildProcessConfig::builder("uvx")
+///     .arg("mcp-server-git")
+///     .env("GIT_DIR", "/repo/.git")
+///     .clear_env(true)
+///     .working_dir("/repo")
+///     .build();
+/// let transport = TokioChildProcess::new(config)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChildProcessConfig {
+    pub command: OsString,
+    pub args: Vec<OsString>,
+    /// Variables set for the child, on top of the inherited environment unless `clear_env`
+    pub env_vars: HashMap<OsString, OsString>,
+    /// Start the child with only the variables of `env_vars`, e.g. to sandbox a tool
+    pub clear_env: bool,
+    /// The working directory of the child, the current one if not set
+    pub working_dir: Option<PathBuf>,
+}
+
+impl ChildProcessConfig {
+    pub fn builder(command: impl Into<OsString>) -> ChildProcessConfigBuilder {
+        ChildProcessConfigBuilder {
+            config: ChildProcessConfig {
+                command: command.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<ChildProcessConfig> for tokio::process::Command {
+    fn from(config: ChildProcessConfig) -> Self {
+        let mut command = tokio::process::Command::new(config.command);
+        command.args(config.args);
+        if config.clear_env {
+            command.env_clear();
+        }
+        command.envs(config.env_vars);
+        if let Some(working_dir) = config.working_dir {
+            command.current_dir(working_dir);
+        }
+        command
+    }
+}
+
+impl From<ChildProcessConfig> for TokioCommandWrap {
+    fn from(config: ChildProcessConfig) -> Self {
+        tokio::process::Command::from(config).into()
+    }
+}
+
+/// Builder for [`ChildProcessConfig`]
+#[derive(Debug, Clone)]
+pub struct ChildProcessConfigBuilder {
+    config: ChildProcessConfig,
+}
+
+impl ChildProcessConfigBuilder {
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.config.args.push(arg.into());
+        self
+    }
+
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        self.config.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.config.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn envs(
+        mut self,
+        vars: impl IntoIterator<Item = (impl Into<OsString>, impl Into<OsString>)>,
+    ) -> Self {
+        self.config.env_vars.extend(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    pub fn clear_env(mut self, clear_env: bool) -> Self {
+        self.config.clear_env = clear_env;
+        self
+    }
+
+    pub fn working_dir(mut self, working_dir: impl Into<PathBuf>) -> Self {
+        self.config.working_dir = Some(working_dir.into());
+        self
+    }
+
+    pub fn build(self) -> ChildProcessConfig {
+        self.config
+    }
+}
+
 pub trait ConfigureCommandExt {
     fn configure(self, f: impl FnOnce(&mut Self)) -> Self;
 }
@@ -306,4 +489,52 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_child_process_config() {
+        let working_dir = std::env::temp_dir().canonicalize().unwrap();
+        let config = ChildProcessConfig::builder("/bin/sh")
+            .args(["-c", "pwd; echo \"$MCP_TEST_VAR|$HOME\""])
+            .env("MCP_TEST_VAR", "value")
+            .working_dir(&working_dir)
+            .build();
+        let output = Command::from(config.clone()).output().await.unwrap();
+        let output = String::from_utf8(output.stdout).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), working_dir.to_str());
+        let inherited_home = std::env::var("HOME").unwrap_or_default();
+        assert_eq!(
+            lines.next(),
+            Some(format!("value|{inherited_home}").as_str())
+        );
+
+        let config = ChildProcessConfig {
+            clear_env: true,
+            ..config
+        };
+        let output = Command::from(config).output().await.unwrap();
+        let output = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(output.lines().nth(1), Some("value|"));
+    }
+
+    #[tokio::test]
+    async fn test_child_exit_closes_transport() {
+        // the backgrounded sleep keeps stdout open, the exit of the child must close the transport
+        let mut child_process = TokioChildProcess::new(Command::new("sh").configure(|cmd| {
+            cmd.args([
+                "-c",
+                r#"(sleep 5 &); echo '{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}'; sleep 0.1; exit 3"#,
+            ]);
+        }))
+        .unwrap();
+        assert_eq!(child_process.child_status(), ChildStatus::Running);
+        let started = std::time::Instant::now();
+        assert!(child_process.receive().await.is_some());
+        assert!(child_process.receive().await.is_none());
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        let ChildStatus::Exited(status) = child_process.child_status() else {
+            panic!("child process should have exited");
+        };
+        assert_eq!(status.code(), Some(3));
+    }
 }
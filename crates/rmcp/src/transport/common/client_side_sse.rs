@@ -131,6 +131,12 @@ impl<R: SseStreamReconnect> SseAutoReconnectStream<R> {
             state: SseAutoReconnectStreamState::Connected { stream },
         }
     }
+
+    /// The id of the last event received, sent as `Last-Event-ID` when reconnecting
+    #[cfg(any(feature = "transport-sse-client", test))]
+    pub(crate) fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
 }
 
 impl<E: std::error::Error + Send> SseAutoReconnectStream<NeverReconnect<E>> {
@@ -271,3 +277,58 @@ where
         self.poll_next(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    fn sse_message(id: &str) -> Result<Sse, SseError> {
+        Ok(Sse::default()
+            .id(id)
+            .data(r#"{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}"#))
+    }
+
+    /// Serves a new stream on each reconnection, and records the last event ids it was given
+    struct ReplayingConnector {
+        last_event_ids: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+    }
+
+    impl SseStreamReconnect for ReplayingConnector {
+        type Error = std::io::Error;
+        type Future = futures::future::Ready<Result<BoxedSseResponse, Self::Error>>;
+        fn retry_connection(&mut self, last_event_id: Option<&str>) -> Self::Future {
+            self.last_event_ids
+                .lock()
+                .unwrap()
+                .push(last_event_id.map(ToOwned::to_owned));
+            futures::future::ready(Ok(futures::stream::iter([sse_message("3")]).boxed()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_with_last_event_id() {
+        let last_event_ids = Arc::new(std::sync::Mutex::new(vec![]));
+        let dropped = futures::stream::iter([
+            sse_message("1"),
+            sse_message("2"),
+            Err(SseError::Body("connection reset".into())),
+        ])
+        .boxed();
+        let mut stream = Box::pin(SseAutoReconnectStream::new(
+            dropped,
+            ReplayingConnector {
+                last_event_ids: last_event_ids.clone(),
+            },
+            Arc::new(FixedInterval::default()),
+        ));
+        assert_eq!(stream.last_event_id(), None);
+        for expected in ["1", "2", "3"] {
+            stream.next().await.unwrap().unwrap();
+            assert_eq!(stream.last_event_id(), Some(expected));
+        }
+        assert!(stream.next().await.is_none());
+        assert_eq!(*last_event_ids.lock().unwrap(), vec![Some("2".to_owned())]);
+    }
+}
@@ -106,13 +106,212 @@ impl SseClientTransport<reqwest::Client> {
     pub async fn start(
         uri: impl Into<Arc<str>>,
     ) -> Result<Self, SseTransportError<reqwest::Error>> {
-        SseClientTransport::start_with_client(
-            reqwest::Client::default(),
-            SseClientConfig {
-                sse_endpoint: uri.into(),
-                ..Default::default()
-            },
-        )
+        Self::start_with_config(SseClientConfig {
+            sse_endpoint: uri.into(),
+            ..Default::default()
+        })
         .await
     }
+
+    /// Creates a new transport with a reqwest client built from the config, using its
+    /// [`proxy`](SseClientConfig::proxy) if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rmcp::transport::{SseClientTransport, sse_client::SseClientConfig};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let transport = SseClientTransport::start_with_config(SseClientConfig {
+    ///     sse_endpoint: "https://example.com/sse".into(),
+    ///     ..SseClientConfig::from_env()
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn start_with_config(
+        config: SseClientConfig,
+    ) -> Result<Self, SseTransportError<reqwest::Error>> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = config.proxy.clone() {
+            builder = builder.proxy(proxy);
+        }
+        SseClientTransport::start_with_client(builder.build()?, config).await
+    }
+}
+
+impl SseClientConfig {
+    /// A default config with the proxy read from the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY`
+    /// environment variables, or their lowercase versions.
+    ///
+    /// `HTTP_PROXY` is ignored when `REQUEST_METHOD` is set, since it may come from the `Proxy`
+    /// header of a request in a CGI environment.
+    pub fn from_env() -> Self {
+        Self {
+            proxy: proxy_from_vars(|name| std::env::var(name).ok()),
+            ..Default::default()
+        }
+    }
+}
+
+fn proxy_from_vars(var: impl Fn(&str) -> Option<String>) -> Option<reqwest::Proxy> {
+    let read = |upper: &str, lower: &str| {
+        var(upper)
+            .or_else(|| var(lower))
+            .filter(|value| !value.trim().is_empty())
+    };
+    let http = if var("REQUEST_METHOD").is_some() {
+        var("http_proxy")
+    } else {
+        read("HTTP_PROXY", "http_proxy")
+    };
+    let http = http.and_then(|value| parse_proxy_url("HTTP_PROXY", &value));
+    let https =
+        read("HTTPS_PROXY", "https_proxy").and_then(|value| parse_proxy_url("HTTPS_PROXY", &value));
+    if http.is_none() && https.is_none() {
+        return None;
+    }
+    let no_proxy = read("NO_PROXY", "no_proxy").unwrap_or_default();
+    // reqwest doesn't apply `Proxy::no_proxy` to custom proxies, so it's matched here
+    Some(reqwest::Proxy::custom(move |url| {
+        if url
+            .host_str()
+            .is_some_and(|host| is_no_proxy(&no_proxy, host))
+        {
+            return None;
+        }
+        match url.scheme() {
+            "http" => http.clone(),
+            "https" => https.clone(),
+            _ => None,
+        }
+    }))
+}
+
+/// Check if the host is excluded by a `NO_PROXY` list, e.g. `localhost,.internal,10.0.0.1`.
+///
+/// An entry matches the host itself and its subdomains, `*` matches every host.
+fn is_no_proxy(no_proxy: &str, host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('.'))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            entry == "*"
+                || host.eq_ignore_ascii_case(entry)
+                || host.len().checked_sub(entry.len() + 1).is_some_and(|dot| {
+                    host.as_bytes()[dot] == b'.' && host[dot + 1..].eq_ignore_ascii_case(entry)
+                })
+        })
+}
+
+fn parse_proxy_url(name: &str, value: &str) -> Option<reqwest::Url> {
+    let value = value.trim();
+    // a proxy is commonly given as `host:port`
+    let url = if value.contains("://") {
+        reqwest::Url::parse(value)
+    } else {
+        reqwest::Url::parse(&format!("http://{value}"))
+    };
+    url.inspect_err(|error| tracing::warn!(%error, "ignore invalid {name}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// Accept a connection and answer with `403 Forbidden`, returning the request head
+    async fn first_request_head(listener: &tokio::net::TcpListener) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = vec![];
+        let mut buffer = [0u8; 1024];
+        while !head.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).await.unwrap();
+            assert!(read > 0, "connection closed");
+            head.extend_from_slice(&buffer[..read]);
+        }
+        stream
+            .write_all(b"HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8(head).unwrap()
+    }
+
+    fn client(vars: &[(&str, String)]) -> reqwest::Client {
+        let vars: HashMap<_, _> = vars.iter().cloned().collect();
+        let proxy = proxy_from_vars(|name| vars.get(name).cloned()).expect("proxy is configured");
+        reqwest::Client::builder().proxy(proxy).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_proxy_from_vars() {
+        let proxy = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let direct = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = proxy.local_addr().unwrap();
+        let client = client(&[
+            ("http_proxy", proxy_addr.to_string()),
+            ("HTTPS_PROXY", format!("http://{proxy_addr}")),
+            ("NO_PROXY", "127.0.0.1".to_owned()),
+        ]);
+
+        let request = tokio::spawn(client.get("https://mcp.example.com/sse").send());
+        let head = first_request_head(&proxy).await;
+        assert!(head.starts_with("CONNECT mcp.example.com:443 "), "{head}");
+        assert!(request.await.unwrap().is_err());
+
+        let request = tokio::spawn(client.get("http://mcp.example.com/sse").send());
+        let head = first_request_head(&proxy).await;
+        assert!(
+            head.starts_with("GET http://mcp.example.com/sse "),
+            "{head}"
+        );
+        let _ = request.await.unwrap();
+
+        let direct_url = format!("http://{}/sse", direct.local_addr().unwrap());
+        let request = tokio::spawn(client.get(direct_url).send());
+        let head = first_request_head(&direct).await;
+        assert!(head.starts_with("GET /sse "), "{head}");
+        let _ = request.await.unwrap();
+    }
+
+    #[test]
+    fn test_no_proxy() {
+        let no_proxy = "localhost, .internal,10.0.0.1,::1";
+        for host in [
+            "localhost",
+            "mcp.internal",
+            "a.b.Internal",
+            "10.0.0.1",
+            "[::1]",
+        ] {
+            assert!(is_no_proxy(no_proxy, host), "{host}");
+        }
+        for host in [
+            "example.com",
+            "notinternal",
+            "10.0.0.10",
+            "localhost.example.com",
+        ] {
+            assert!(!is_no_proxy(no_proxy, host), "{host}");
+        }
+        assert!(is_no_proxy("*", "example.com"));
+    }
+
+    #[test]
+    fn test_http_proxy_ignored_in_cgi() {
+        let vars = |name: &str| match name {
+            "HTTP_PROXY" => Some("http://127.0.0.1:1".to_owned()),
+            "REQUEST_METHOD" => Some("GET".to_owned()),
+            _ => None,
+        };
+        assert!(proxy_from_vars(vars).is_none());
+        assert!(proxy_from_vars(|_| None).is_none());
+    }
 }
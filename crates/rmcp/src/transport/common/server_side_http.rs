@@ -9,8 +9,7 @@ use sse_stream::{KeepAlive, Sse, SseBody};
 
 use super::http_header::EVENT_STREAM_MIME_TYPE;
 use crate::model::{ClientJsonRpcMessage, ServerJsonRpcMessage};
-
-pub type SessionId = Arc<str>;
+pub use crate::service::SessionId;
 
 pub fn session_id() -> SessionId {
     uuid::Uuid::new_v4().to_string().into()
@@ -62,20 +61,30 @@ pub struct ServerSseMessage {
     pub message: Arc<ServerJsonRpcMessage>,
 }
 
+/// The comment sent on idle sse streams to keep proxies from closing them, comments are ignored
+/// by sse clients.
+pub(crate) const SSE_HEARTBEAT_COMMENT: &str = "heartbeat";
+
 pub(crate) fn sse_stream_response(
     stream: impl futures::Stream<Item = ServerSseMessage> + Send + Sync + 'static,
     keep_alive: Option<Duration>,
+    event_type: Option<String>,
 ) -> Response<BoxBody<Bytes, Infallible>> {
     use futures::StreamExt;
-    let stream = SseBody::new(stream.map(|message| {
+    let stream = SseBody::new(stream.map(move |message| {
         let data = serde_json::to_string(&message.message).expect("valid message");
         let mut sse = Sse::default().data(data);
         sse.id = message.event_id;
+        sse.event = event_type.clone();
         Result::<Sse, Infallible>::Ok(sse)
     }));
     let stream = match keep_alive {
         Some(duration) => stream
-            .with_keep_alive::<TokioTimer>(KeepAlive::new().interval(duration))
+            .with_keep_alive::<TokioTimer>(
+                KeepAlive::new()
+                    .interval(duration)
+                    .comment(SSE_HEARTBEAT_COMMENT),
+            )
             .boxed(),
         None => stream.boxed(),
     };
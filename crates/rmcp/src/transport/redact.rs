@@ -0,0 +1,265 @@
+//! Redaction of secrets in the logs.
+//!
+//! The messages logged by rmcp, e.g. the requests at `debug` level, contain the tool call
+//! arguments, which may hold passwords or tokens. A [`RedactingWriter`] replaces the values of the
+//! sensitive fields of the json in the logs with `[REDACTED]` before the formatted logs are
+//! written:
+//! ```rust
+//! use rmcp::transport::redact::Redactor;
+//!
+//! tracing_subscriber::fmt()
+//!     .with_writer(Redactor::default().writer(std::io::stderr))
+//!     .init();
+//! ```
+//! rmcp logs the messages as json, log your own values as json too, e.g. with
+//! `tracing::info!(arguments = %json, ..)` for a `serde_json::Value`, their `Debug` output isn't
+//! redacted.
+use std::{borrow::Cow, collections::HashSet, io, sync::Arc};
+
+use serde_json::Value;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The text replacing the redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Redact the values of the json fields with one of the sensitive names
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    /// Lowercase, the names are compared in any case
+    keys: Arc<HashSet<String>>,
+}
+
+impl Default for Redactor {
+    /// Redact the fields of the [`DEFAULT_KEYS`](Self::DEFAULT_KEYS)
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_KEYS.iter().copied())
+    }
+}
+
+impl Redactor {
+    /// The names of the usual fields of passwords, secrets, tokens, keys and credentials
+    pub const DEFAULT_KEYS: &[&str] = &[
+        "password",
+        "passwd",
+        "secret",
+        "client_secret",
+        "token",
+        "access_token",
+        "refresh_token",
+        "id_token",
+        "accessToken",
+        "refreshToken",
+        "api_key",
+        "apiKey",
+        "private_key",
+        "privateKey",
+        "authorization",
+        "credential",
+        "credentials",
+    ];
+
+    /// Redact the fields with one of these names, in any case, and only those
+    pub fn new(keys: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            keys: Arc::new(
+                keys.into_iter()
+                    .map(|key| key.as_ref().to_ascii_lowercase())
+                    .collect(),
+            ),
+        }
+    }
+
+    fn is_sensitive(&self, name: &str) -> bool {
+        self.keys.contains(&name.to_ascii_lowercase())
+    }
+
+    /// Replace the whole values of the sensitive fields of a json value with [`REDACTED`], at
+    /// any depth, including the json documents held by strings.
+    pub fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (name, value) in object.iter_mut() {
+                    if self.is_sensitive(name) {
+                        *value = Value::String(REDACTED.to_owned());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            Value::String(text) if text.starts_with(['{', '[']) => {
+                if let Ok(mut inner) = serde_json::from_str::<Value>(text) {
+                    self.redact_value(&mut inner);
+                    *text = inner.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Redact the json objects and arrays of a text, e.g. a formatted log line. The redacted json
+    /// is written back compact, its fields may be reordered, and the rest of the text is kept as
+    /// it is.
+    pub fn redact<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut redacted = String::new();
+        // the end of the text already copied to `redacted`
+        let mut copied = 0;
+        let mut position = 0;
+        while let Some(start) = text[position..]
+            .find(['{', '['])
+            .map(|start| position + start)
+        {
+            let mut values =
+                serde_json::Deserializer::from_str(&text[start..]).into_iter::<Value>();
+            let Some(Ok(mut value)) = values.next() else {
+                position = start + 1;
+                continue;
+            };
+            let end = start + values.byte_offset();
+            let original = value.clone();
+            self.redact_value(&mut value);
+            if value != original {
+                redacted.push_str(&text[copied..start]);
+                redacted.push_str(&value.to_string());
+                copied = end;
+            }
+            position = end;
+        }
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+        redacted.push_str(&text[copied..]);
+        Cow::Owned(redacted)
+    }
+
+    /// Wrap a [`MakeWriter`], e.g. `std::io::stderr`, to redact the logs written to it
+    pub fn writer<M>(&self, make_writer: M) -> RedactingWriter<M> {
+        RedactingWriter {
+            redactor: self.clone(),
+            inner: make_writer,
+        }
+    }
+}
+
+/// A [`MakeWriter`] redacting the logs written by a `tracing_subscriber` layer, see
+/// [`Redactor::writer`]
+#[derive(Debug, Clone)]
+pub struct RedactingWriter<M> {
+    redactor: Redactor,
+    inner: M,
+}
+
+impl<'a, M> MakeWriter<'a> for RedactingWriter<M>
+where
+    M: MakeWriter<'a>,
+{
+    type Writer = Redacting<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        Redacting {
+            redactor: self.redactor.clone(),
+            inner: self.inner.make_writer(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        Redacting {
+            redactor: self.redactor.clone(),
+            inner: self.inner.make_writer_for(meta),
+        }
+    }
+}
+
+/// The writer of a [`RedactingWriter`]
+///
+/// The formatting layers write an event at once, so a field is never split between two writes.
+#[derive(Debug)]
+pub struct Redacting<W> {
+    redactor: Redactor,
+    inner: W,
+}
+
+impl<W: io::Write> io::Write for Redacting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.inner
+            .write_all(self.redactor.redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_redact_text() {
+        let redactor = Redactor::default();
+        assert_eq!(
+            redactor.redact(r#"{"user":"alice","password":"hunter2"}"#),
+            r#"{"password":"[REDACTED]","user":"alice"}"#
+        );
+        // the whole value is redacted, whatever its type
+        assert_eq!(
+            redactor.redact(r#"call {"apiKey": {"id": 1, "value": "sk-1"}, "token": ["a", "b"]}"#),
+            r#"call {"apiKey":"[REDACTED]","token":"[REDACTED]"}"#
+        );
+        // json held by a string
+        assert_eq!(
+            redactor.redact(r#"{"json":"{\"access_token\":\"abc\"}"}"#),
+            r#"{"json":"{\"access_token\":\"[REDACTED]\"}"}"#
+        );
+        // the names are matched exactly
+        let text = r#"{"monkey":"george","keyboard":"qwerty"}"#;
+        assert!(matches!(redactor.redact(text), Cow::Borrowed(_)));
+        assert_eq!(redactor.redact("not {json] at all"), "not {json] at all");
+
+        let custom = Redactor::new(["ssn"]);
+        assert_eq!(
+            custom.redact(r#"{"ssn":"123","password":"x"}"#),
+            r#"{"password":"x","ssn":"[REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn test_redacted_logs() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(Redactor::default().writer({
+                let captured = captured.clone();
+                move || captured.clone()
+            }))
+            .with_ansi(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let arguments = json!({ "username": "alice", "password": "hunter2" });
+            tracing::info!(%arguments, "call tool");
+        });
+        let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("alice"), "{logs}");
+        assert!(logs.contains(REDACTED), "{logs}");
+        assert!(!logs.contains("hunter2"), "{logs}");
+    }
+}
@@ -0,0 +1,387 @@
+//! Ordering of the messages of transports which may reorder them, e.g. SSE streams under load.
+//!
+//! A [`SequencedTransport`] numbers the requests and notifications it sends with a `_seq` field
+//! in their `_meta`, and delivers the ones it receives in the order of their numbers:
+//! - a message received ahead of its turn is buffered until the missing ones arrive
+//! - a message received twice is dropped
+//! - a message ahead by the window or more means the missing ones are lost, the
+//!   [`SequenceError::Gap`] is logged and the transport is closed
+//! - if the inner transport closes while messages wait for a missing one, they are delivered in
+//!   order, and the [`SequenceError::Missing`] is logged before the transport closes
+//!
+//! Responses are matched to their request by id and have no `_meta`, so they aren't numbered. A
+//! message without number, e.g. from a peer which doesn't wrap its transport, is delivered as it
+//! comes. So the responses are not ordered relative to the requests and notifications: a response
+//! may be delivered before a notification sent ahead of it, e.g. a progress notification of its
+//! request, while that notification waits for a missing one.
+//!
+//! ```rust,ignore
+//! let transport = SequencedTransport::new(
+//!     transport,
+//!     SequenceConfig::default().sequence_window(64),
+//! );
+//! let client = ().serve(transport).await?;
+//! ```
+use std::collections::{BTreeMap, VecDeque};
+
+use super::Transport;
+use crate::{
+    model::{GetMeta, JsonRpcMessage, Meta},
+    service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage},
+};
+
+/// The field of `_meta` carrying the number of a message
+pub const SEQUENCE_FIELD: &str = "_seq";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceConfig {
+    /// If false, the messages are sent and received as they are
+    pub enabled: bool,
+    /// The number of messages which may be buffered while waiting for a missing one
+    pub window: usize,
+}
+
+impl SequenceConfig {
+    pub const DEFAULT_WINDOW: usize = 32;
+
+    pub fn enable_sequence_tracking(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn sequence_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+impl Default for SequenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SequenceError {
+    #[error(
+        "received message {received} while waiting for message {expected}, beyond the window of {window} messages"
+    )]
+    Gap {
+        expected: u64,
+        received: u64,
+        window: usize,
+    },
+    #[error(
+        "the transport closed while waiting for message {expected}, the messages from {received} were delivered after the gap"
+    )]
+    Missing { expected: u64, received: u64 },
+}
+
+/// Number the messages sent, and deliver the messages received in order, see the
+/// [module](self) documentation.
+pub struct SequencedTransport<R: ServiceRole, T> {
+    inner: T,
+    config: SequenceConfig,
+    next_sent: u64,
+    next_received: u64,
+    /// The messages received ahead of their turn, by number
+    pending: BTreeMap<u64, RxJsonRpcMessage<R>>,
+    /// The messages whose turn came, to be delivered
+    ready: VecDeque<RxJsonRpcMessage<R>>,
+    gap: Option<SequenceError>,
+}
+
+impl<R: ServiceRole, T> SequencedTransport<R, T> {
+    pub fn new(inner: T, config: SequenceConfig) -> Self {
+        Self {
+            inner,
+            config,
+            next_sent: 0,
+            next_received: 0,
+            pending: BTreeMap::new(),
+            ready: VecDeque::new(),
+            gap: None,
+        }
+    }
+
+    /// The gap which closed the transport, if any
+    pub fn gap(&self) -> Option<&SequenceError> {
+        self.gap.as_ref()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Deliver the messages waiting for a missing one, once no message can be received anymore
+    fn flush_pending(&mut self) {
+        let Some(&received) = self.pending.keys().next() else {
+            return;
+        };
+        let error = SequenceError::Missing {
+            expected: self.next_received,
+            received,
+        };
+        tracing::error!(%error, "closing the transport");
+        self.gap = Some(error);
+        self.ready
+            .extend(std::mem::take(&mut self.pending).into_values());
+    }
+}
+
+fn meta_mut<Req: GetMeta, Resp, Not: GetMeta>(
+    message: &mut JsonRpcMessage<Req, Resp, Not>,
+) -> Option<&mut Meta> {
+    match message {
+        JsonRpcMessage::Request(request) => Some(request.request.get_meta_mut()),
+        JsonRpcMessage::Notification(notification) => {
+            Some(notification.notification.get_meta_mut())
+        }
+        JsonRpcMessage::Response(_) | JsonRpcMessage::Error(_) => None,
+    }
+}
+
+/// Remove the number of a message
+fn take_sequence<Req: GetMeta, Resp, Not: GetMeta>(
+    message: &mut JsonRpcMessage<Req, Resp, Not>,
+) -> Option<u64> {
+    let numbered = match &*message {
+        JsonRpcMessage::Request(request) => request.request.get_meta().contains_key(SEQUENCE_FIELD),
+        JsonRpcMessage::Notification(notification) => notification
+            .notification
+            .get_meta()
+            .contains_key(SEQUENCE_FIELD),
+        JsonRpcMessage::Response(_) | JsonRpcMessage::Error(_) => false,
+    };
+    if !numbered {
+        return None;
+    }
+    meta_mut(message)?.remove(SEQUENCE_FIELD)?.as_u64()
+}
+
+impl<R, T> Transport<R> for SequencedTransport<R, T>
+where
+    R: ServiceRole,
+    R::Not: GetMeta,
+    T: Transport<R>,
+{
+    type Error = T::Error;
+
+    fn send(
+        &mut self,
+        mut item: TxJsonRpcMessage<R>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        if self.config.enabled {
+            if let Some(meta) = meta_mut(&mut item) {
+                meta.insert(SEQUENCE_FIELD.into(), self.next_sent.into());
+                self.next_sent += 1;
+            }
+        }
+        self.inner.send(item)
+    }
+
+    async fn receive(&mut self) -> Option<RxJsonRpcMessage<R>> {
+        loop {
+            if let Some(message) = self.ready.pop_front() {
+                return Some(message);
+            }
+            if self.gap.is_some() {
+                return None;
+            }
+            let Some(mut message) = self.inner.receive().await else {
+                self.flush_pending();
+                return self.ready.pop_front();
+            };
+            if !self.config.enabled {
+                return Some(message);
+            }
+            let Some(sequence) = take_sequence(&mut message) else {
+                return Some(message);
+            };
+            if sequence < self.next_received || self.pending.contains_key(&sequence) {
+                tracing::debug!(sequence, "dropping a duplicated message");
+                continue;
+            }
+            if sequence - self.next_received >= self.config.window as u64 {
+                let error = SequenceError::Gap {
+                    expected: self.next_received,
+                    received: sequence,
+                    window: self.config.window,
+                };
+                tracing::error!(%error, "closing the transport");
+                self.gap = Some(error);
+                return None;
+            }
+            self.pending.insert(sequence, message);
+            while let Some(message) = self.pending.remove(&self.next_received) {
+                self.ready.push_back(message);
+                self.next_received += 1;
+            }
+        }
+    }
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.close()
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use futures::{StreamExt, channel::mpsc};
+
+    use super::*;
+    use crate::{
+        RoleClient,
+        model::{
+            ClientNotification, ClientRequest, PingRequest, ProgressNotification,
+            ProgressNotificationParam, ProgressToken, ServerJsonRpcMessage, ServerNotification,
+        },
+        transport::sink_stream::SinkStreamTransport,
+    };
+
+    fn progress(progress: f64, sequence: Option<u64>) -> ServerJsonRpcMessage {
+        let mut notification = ServerNotification::ProgressNotification(ProgressNotification {
+            method: Default::default(),
+            params: ProgressNotificationParam {
+                progress_token: ProgressToken(crate::model::NumberOrString::Number(0)),
+                progress,
+                total: None,
+                message: None,
+            },
+            extensions: Default::default(),
+        });
+        if let Some(sequence) = sequence {
+            notification
+                .get_meta_mut()
+                .insert(SEQUENCE_FIELD.into(), sequence.into());
+        }
+        ServerJsonRpcMessage::notification(notification)
+    }
+
+    fn progress_of(message: ServerJsonRpcMessage) -> f64 {
+        match message {
+            JsonRpcMessage::Notification(notification) => match notification.notification {
+                notification if notification.get_meta().contains_key(SEQUENCE_FIELD) => {
+                    panic!("the number of {notification:?} is not removed")
+                }
+                ServerNotification::ProgressNotification(notification) => {
+                    notification.params.progress
+                }
+                notification => panic!("unexpected notification {notification:?}"),
+            },
+            message => panic!("unexpected message {message:?}"),
+        }
+    }
+
+    fn transport(
+        messages: Vec<ServerJsonRpcMessage>,
+        config: SequenceConfig,
+    ) -> (
+        SequencedTransport<RoleClient, impl Transport<RoleClient>>,
+        mpsc::UnboundedReceiver<crate::model::ClientJsonRpcMessage>,
+    ) {
+        let (sink, sent) = mpsc::unbounded();
+        let inner = SinkStreamTransport::new(sink, futures::stream::iter(messages));
+        (SequencedTransport::new(inner, config), sent)
+    }
+
+    async fn receive_all(
+        transport: &mut SequencedTransport<RoleClient, impl Transport<RoleClient>>,
+    ) -> Vec<f64> {
+        let mut received = Vec::new();
+        while let Some(message) = transport.receive().await {
+            received.push(progress_of(message));
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_numbers_sent_requests_and_notifications() {
+        let (mut transport, mut sent) = transport(vec![], SequenceConfig::default());
+        for _ in 0..2 {
+            transport
+                .send(crate::model::ClientJsonRpcMessage::request(
+                    ClientRequest::PingRequest(PingRequest::default()),
+                    crate::model::NumberOrString::Number(1),
+                ))
+                .await
+                .unwrap();
+        }
+        transport
+            .send(crate::model::ClientJsonRpcMessage::notification(
+                ClientNotification::InitializedNotification(Default::default()),
+            ))
+            .await
+            .unwrap();
+        for expected in 0..3u64 {
+            let mut message = sent.next().await.unwrap();
+            assert_eq!(take_sequence(&mut message), Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reorders_and_drops_duplicates() {
+        let messages = vec![
+            progress(1.0, Some(1)),
+            progress(0.0, Some(0)),
+            progress(0.5, None),
+            progress(0.0, Some(0)),
+            progress(3.0, Some(3)),
+            progress(2.0, Some(2)),
+            progress(3.0, Some(3)),
+        ];
+        let (mut transport, _sent) = transport(messages, SequenceConfig::default());
+        assert_eq!(receive_all(&mut transport).await, [0.0, 1.0, 0.5, 2.0, 3.0]);
+        assert_eq!(transport.gap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_gap_beyond_window_closes() {
+        let messages = vec![
+            progress(0.0, Some(0)),
+            progress(2.0, Some(2)),
+            progress(5.0, Some(5)),
+            progress(1.0, Some(1)),
+        ];
+        let (mut transport, _sent) =
+            transport(messages, SequenceConfig::default().sequence_window(4));
+        assert_eq!(receive_all(&mut transport).await, [0.0]);
+        assert_eq!(
+            transport.gap(),
+            Some(&SequenceError::Gap {
+                expected: 1,
+                received: 5,
+                window: 4
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_close_delivers_pending_and_reports_gap() {
+        let messages = vec![
+            progress(0.0, Some(0)),
+            progress(3.0, Some(3)),
+            progress(2.0, Some(2)),
+        ];
+        let (mut transport, _sent) = transport(messages, SequenceConfig::default());
+        assert_eq!(receive_all(&mut transport).await, [0.0, 2.0, 3.0]);
+        assert_eq!(
+            transport.gap(),
+            Some(&SequenceError::Missing {
+                expected: 1,
+                received: 2
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_passes_through() {
+        let messages = vec![progress(1.0, None), progress(0.0, None)];
+        let config = SequenceConfig::default().enable_sequence_tracking(false);
+        let (mut transport, _sent) = transport(messages, config);
+        assert_eq!(receive_all(&mut transport).await, [1.0, 0.0]);
+    }
+}
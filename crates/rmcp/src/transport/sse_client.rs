@@ -187,6 +187,14 @@ impl<C: SseClient + std::fmt::Debug> std::fmt::Debug for SseClientTransport<C> {
 }
 
 impl<C: SseClient> SseClientTransport<C> {
+    /// The id of the last event received from the server.
+    ///
+    /// When the connection drops, it's sent back as `Last-Event-ID` on reconnection so the server
+    /// can replay the events missed in between.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.stream.as_ref()?.last_event_id()
+    }
+
     pub async fn start_with_client(
         client: C,
         config: SseClientConfig,
@@ -279,6 +287,11 @@ pub struct SseClientConfig {
     pub retry_policy: Arc<dyn SseRetryPolicy>,
     /// if this is settled, the client will use this endpoint to send message and skip get the endpoint event
     pub use_message_endpoint: Option<String>,
+    /// the proxy of the reqwest client created by [`SseClientTransport::start_with_config`],
+    /// see [`SseClientConfig::from_env`]
+    #[cfg(feature = "transport-sse-client-reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "transport-sse-client-reqwest")))]
+    pub proxy: Option<reqwest::Proxy>,
 }
 
 impl Default for SseClientConfig {
@@ -287,6 +300,8 @@ impl Default for SseClientConfig {
             sse_endpoint: "".into(),
             retry_policy: Arc::new(super::common::client_side_sse::FixedInterval::default()),
             use_message_endpoint: None,
+            #[cfg(feature = "transport-sse-client-reqwest")]
+            proxy: None,
         }
     }
 }
@@ -1,7 +1,22 @@
+#[cfg(feature = "transport-streamable-http-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
+pub mod auth;
+#[cfg(feature = "transport-streamable-http-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
+pub mod cors;
+#[cfg(feature = "transport-streamable-http-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
+pub mod registry;
 pub mod session;
 #[cfg(feature = "transport-streamable-http-server")]
 #[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
 pub mod tower;
+#[cfg(feature = "transport-streamable-http-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
+pub use cors::{CorsConfig, CorsConfigError};
+#[cfg(feature = "transport-streamable-http-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
+pub use registry::SessionRegistry;
 pub use session::{SessionId, SessionManager};
 #[cfg(feature = "transport-streamable-http-server")]
 #[cfg_attr(docsrs, doc(cfg(feature = "transport-streamable-http-server")))]
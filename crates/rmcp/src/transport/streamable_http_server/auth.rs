@@ -0,0 +1,152 @@
+//! Authentication of the http requests of [`StreamableHttpService`](super::StreamableHttpService).
+//!
+//! The [`AuthContext`] of an authenticated request is injected into the extensions of the
+//! mcp message, so handlers can get it from [`crate::service::RequestContext`]:
+//! ```rust
+//! use rmcp::{
+//!     handler::server::tool::Extension,
+//!     transport::streamable_http_server::auth::AuthContext,
+//! };
+//! async fn my_tool(Extension(auth): Extension<AuthContext>) {
+//!     tracing::info!("called by {:?}", auth.subject)
+//! }
+//! ```
+use std::collections::HashMap;
+
+use futures::future::BoxFuture;
+use http::HeaderMap;
+use thiserror::Error;
+
+use crate::model::JsonObject;
+
+/// The identity of an authenticated client
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthContext {
+    /// Who the client is, e.g. the subject of a token
+    pub subject: Option<String>,
+    pub scopes: Vec<String>,
+    /// Any other information about the client
+    pub claims: JsonObject,
+}
+
+impl AuthContext {
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: Some(subject.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("Missing credentials")]
+    MissingCredentials,
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+    #[error("Authentication failed: {0}")]
+    Other(String),
+}
+
+/// Authenticate the http requests before they reach the session manager.
+///
+/// A request failing to authenticate is answered with `401 Unauthorized`.
+pub trait AuthHandler: Send + Sync + 'static {
+    fn authenticate<'a>(
+        &'a self,
+        headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, Result<AuthContext, AuthError>>;
+
+    /// The value of the `WWW-Authenticate` header of the `401 Unauthorized` responses
+    fn challenge(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Authenticate requests by a static set of bearer tokens
+#[derive(Clone, Default)]
+pub struct BearerTokenAuth {
+    tokens: HashMap<String, AuthContext>,
+}
+
+impl std::fmt::Debug for BearerTokenAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // don't leak the tokens into logs
+        f.debug_struct("BearerTokenAuth")
+            .field("tokens", &self.tokens.len())
+            .finish()
+    }
+}
+
+impl BearerTokenAuth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept a token, requests bearing it are authenticated as `context`
+    pub fn with_token(mut self, token: impl Into<String>, context: AuthContext) -> Self {
+        self.tokens.insert(token.into(), context);
+        self
+    }
+
+    fn find(&self, token: &str) -> Option<&AuthContext> {
+        // compare with every token in constant time, so the timing doesn't tell how close a guess is
+        self.tokens
+            .iter()
+            .fold(None, |found, (candidate, context)| {
+                if constant_time_eq(candidate.as_bytes(), token.as_bytes()) {
+                    Some(context)
+                } else {
+                    found
+                }
+            })
+    }
+}
+
+impl AuthHandler for BearerTokenAuth {
+    fn authenticate<'a>(
+        &'a self,
+        headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, Result<AuthContext, AuthError>> {
+        let result = bearer_token(headers).and_then(|token| {
+            self.find(token)
+                .cloned()
+                .ok_or(AuthError::InvalidCredentials)
+        });
+        Box::pin(std::future::ready(result))
+    }
+
+    fn challenge(&self) -> Option<&str> {
+        Some("Bearer")
+    }
+}
+
+/// The token of a `Bearer` authorization header
+pub(crate) fn bearer_token(headers: &HeaderMap) -> Result<&str, AuthError> {
+    let value = headers
+        .get(http::header::AUTHORIZATION)
+        .ok_or(AuthError::MissingCredentials)?
+        .to_str()
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let (scheme, token) = value.split_once(' ').ok_or(AuthError::InvalidCredentials)?;
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return Err(AuthError::InvalidCredentials);
+    }
+    Ok(token.trim())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::transport::common::http_header::{HEADER_LAST_EVENT_ID, HEADER_SESSION_ID};
+
+/// The CORS headers of the streamable http server, so browsers can call it from other origins.
+///
+/// No CORS header is sent without allowed origin, which is the default.
+///
+/// # Example
+/// ```rust
+/// # use rmcp::transport::{StreamableHttpServerConfig, streamable_http_server::CorsConfig};
+/// let config = StreamableHttpServerConfig {
+///     cors: CorsConfig::allow_origins(vec!["https://inspector.example.com"]),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    /// The origins allowed to call the server, `*` allows any origin
+    pub allowed_origins: Vec<HeaderValue>,
+    /// Allow the browser to send cookies and authorization headers
+    pub allow_credentials: bool,
+    /// How long the browser may cache the response of a preflight request
+    pub max_age: Duration,
+}
+
+impl CorsConfig {
+    pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(3600);
+
+    /// Allow any origin, without credentials, e.g. for development.
+    pub fn allow_all() -> Self {
+        Self {
+            allowed_origins: vec![HeaderValue::from_static("*")],
+            allow_credentials: false,
+            max_age: Self::DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Allow the given origins, without credentials. The origins which aren't valid header values
+    /// are skipped.
+    pub fn allow_origins(origins: Vec<&str>) -> Self {
+        let allowed_origins = origins
+            .into_iter()
+            .filter_map(|origin| match HeaderValue::from_str(origin) {
+                Ok(origin) => Some(origin),
+                Err(_) => {
+                    tracing::warn!(origin, "skipping invalid CORS origin");
+                    None
+                }
+            })
+            .collect();
+        Self {
+            allowed_origins,
+            allow_credentials: false,
+            max_age: Self::DEFAULT_MAX_AGE,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.allowed_origins.is_empty()
+    }
+
+    /// The layer adding the CORS headers, `None` without allowed origin.
+    ///
+    /// Credentials can't be allowed with the `*` origin, any website could then make
+    /// authenticated calls to the server and read their responses, so the configuration is
+    /// rejected like tower-http does.
+    pub fn layer(&self) -> Result<Option<CorsLayer>, CorsConfigError> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let any_origin = self.allowed_origins.iter().any(|origin| origin == "*");
+        let allow_origin = match (any_origin, self.allow_credentials) {
+            (true, true) => return Err(CorsConfigError::WildcardWithCredentials),
+            (true, false) => AllowOrigin::any(),
+            (false, _) => AllowOrigin::list(self.allowed_origins.iter().cloned()),
+        };
+        Ok(Some(
+            CorsLayer::new()
+                .allow_origin(allow_origin)
+                .allow_methods([Method::GET, Method::POST, Method::DELETE])
+                .allow_headers([
+                    header::ACCEPT,
+                    header::AUTHORIZATION,
+                    header::CONTENT_TYPE,
+                    header_name(HEADER_LAST_EVENT_ID),
+                    header_name(HEADER_SESSION_ID),
+                    header_name("Mcp-Protocol-Version"),
+                ])
+                .expose_headers([header_name(HEADER_SESSION_ID)])
+                .allow_credentials(self.allow_credentials)
+                .max_age(self.max_age),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum CorsConfigError {
+    #[error("credentials can't be allowed with the `*` origin, list the allowed origins instead")]
+    WildcardWithCredentials,
+}
+
+fn header_name(name: &'static str) -> HeaderName {
+    HeaderName::from_bytes(name.as_bytes()).expect("valid header name")
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use tokio::sync::RwLock;
+
+use super::session::SessionId;
+use crate::{RoleServer, model::ServerNotification, service::Peer};
+
+/// The live sessions of a [`StreamableHttpService`](super::StreamableHttpService).
+///
+/// A session is registered once its service is initialized, and removed when the service quits.
+///
+/// ```rust,ignore
+/// let service = StreamableHttpService::new(factory, session_manager, config);
+/// let registry = service.registry();
+/// // later, e.g. after a configuration reload
+/// registry
+///     .broadcast(ServerNotification::ToolListChangedNotification(Default::default()))
+///     .await;
+/// ```
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    peers: RwLock<HashMap<SessionId, Peer<RoleServer>>>,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) async fn register(&self, id: SessionId, peer: Peer<RoleServer>) {
+        self.peers.write().await.insert(id, peer);
+    }
+
+    pub(crate) async fn unregister(&self, id: &SessionId) {
+        self.peers.write().await.remove(id);
+    }
+
+    /// The ids of the live sessions
+    pub async fn sessions(&self) -> Vec<SessionId> {
+        self.peers.read().await.keys().cloned().collect()
+    }
+
+    /// The count of live sessions
+    pub async fn len(&self) -> usize {
+        self.peers.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.peers.read().await.is_empty()
+    }
+
+    /// Get the peer of a session, to talk to this client only
+    pub async fn peer(&self, id: &SessionId) -> Option<Peer<RoleServer>> {
+        self.peers.read().await.get(id).cloned()
+    }
+
+    /// Send a notification to every live session, and return the count of sessions it was
+    /// delivered to.
+    ///
+    /// A session failing to receive the notification is skipped, it's usually closing.
+    pub async fn broadcast(&self, notification: ServerNotification) -> usize {
+        // don't hold the lock while sending, a slow session would block the registration of others
+        let peers: Vec<_> = self
+            .peers
+            .read()
+            .await
+            .iter()
+            .map(|(id, peer)| (id.clone(), peer.clone()))
+            .collect();
+        let results = futures::future::join_all(
+            peers
+                .iter()
+                .map(|(_, peer)| peer.send_notification(notification.clone())),
+        )
+        .await;
+        let mut delivered = 0;
+        for ((id, _), result) in peers.iter().zip(results) {
+            match result {
+                Ok(()) => delivered += 1,
+                Err(error) => {
+                    tracing::warn!(session_id = %id, %error, "failed to broadcast notification")
+                }
+            }
+        }
+        delivered
+    }
+}
@@ -1,4 +1,7 @@
+use std::net::IpAddr;
+
 use futures::Stream;
+use thiserror::Error;
 
 pub use crate::transport::common::server_side_http::SessionId;
 use crate::{
@@ -10,6 +13,17 @@ use crate::{
 pub mod local;
 pub mod never;
 
+/// Error of [`SessionManager::create_session_for`]
+#[derive(Debug, Error)]
+pub enum CreateSessionError<E> {
+    #[error("Too many sessions for client {0}")]
+    TooManySessionsForClient(IpAddr),
+    #[error("Too many sessions")]
+    TooManySessions,
+    #[error(transparent)]
+    Session(E),
+}
+
 pub trait SessionManager: Send + Sync + 'static {
     type Error: std::error::Error + Send + 'static;
     type Transport: crate::transport::Transport<RoleServer>;
@@ -17,6 +31,22 @@ pub trait SessionManager: Send + Sync + 'static {
     fn create_session(
         &self,
     ) -> impl Future<Output = Result<(SessionId, Self::Transport), Self::Error>> + Send;
+    /// Create a new session for a client, `client_ip` is the ip of the http peer if it's known.
+    ///
+    /// Override this to limit the count of sessions, the default implementation ignores the
+    /// client and calls [`SessionManager::create_session`].
+    fn create_session_for(
+        &self,
+        client_ip: Option<IpAddr>,
+    ) -> impl Future<Output = Result<(SessionId, Self::Transport), CreateSessionError<Self::Error>>> + Send
+    {
+        let _ = client_ip;
+        async move {
+            self.create_session()
+                .await
+                .map_err(CreateSessionError::Session)
+        }
+    }
     fn initialize_session(
         &self,
         id: &SessionId,
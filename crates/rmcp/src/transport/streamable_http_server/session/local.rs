@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
+    net::IpAddr,
     num::ParseIntError,
     sync::Arc,
     time::Duration,
@@ -8,7 +9,7 @@ use std::{
 use futures::Stream;
 use thiserror::Error;
 use tokio::sync::{
-    mpsc::{Receiver, Sender},
+    mpsc::{Receiver, Sender, error::TrySendError},
     oneshot,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -17,14 +18,15 @@ use tracing::instrument;
 use crate::{
     RoleServer,
     model::{
-        CancelledNotificationParam, ClientJsonRpcMessage, ClientNotification, ClientRequest,
-        JsonRpcNotification, JsonRpcRequest, Notification, ProgressNotificationParam,
-        ProgressToken, RequestId, ServerJsonRpcMessage, ServerNotification,
+        CancelledNotificationMethod, CancelledNotificationParam, ClientJsonRpcMessage,
+        ClientNotification, ClientRequest, JsonRpcNotification, JsonRpcRequest, Notification,
+        ProgressNotificationParam, ProgressToken, RequestId, ServerJsonRpcMessage,
+        ServerNotification,
     },
     transport::{
         WorkerTransport,
         common::server_side_http::{SessionId, session_id},
-        worker::{Worker, WorkerContext, WorkerQuitReason, WorkerSendRequest},
+        worker::{BackpressurePolicy, Worker, WorkerContext, WorkerQuitReason, WorkerSendRequest},
     },
 };
 
@@ -42,14 +44,48 @@ pub enum LocalSessionManagerError {
     SessionError(#[from] SessionError),
     #[error("Invalid event id: {0}")]
     InvalidEventId(#[from] EventIdParseError),
+    #[error("Too many sessions for client {0}")]
+    TooManySessionsForClient(IpAddr),
+    #[error("Too many sessions")]
+    TooManySessions,
 }
 impl SessionManager for LocalSessionManager {
     type Error = LocalSessionManagerError;
     type Transport = WorkerTransport<LocalSessionWorker>;
     async fn create_session(&self) -> Result<(SessionId, Self::Transport), Self::Error> {
+        self.create_session_for(None).await.map_err(|e| match e {
+            CreateSessionError::TooManySessionsForClient(ip) => {
+                LocalSessionManagerError::TooManySessionsForClient(ip)
+            }
+            CreateSessionError::TooManySessions => LocalSessionManagerError::TooManySessions,
+            CreateSessionError::Session(e) => e,
+        })
+    }
+    async fn create_session_for(
+        &self,
+        client_ip: Option<IpAddr>,
+    ) -> Result<(SessionId, Self::Transport), CreateSessionError<Self::Error>> {
+        let mut sessions = self.sessions.write().await;
+        if let Some(max_total_sessions) = self.session_config.max_total_sessions {
+            if sessions.len() >= max_total_sessions {
+                return Err(CreateSessionError::TooManySessions);
+            }
+        }
+        if let (Some(ip), Some(max_sessions_per_ip)) =
+            (client_ip, self.session_config.max_sessions_per_ip)
+        {
+            let count = sessions
+                .values()
+                .filter(|handle| handle.client_ip == Some(ip))
+                .count();
+            if count >= max_sessions_per_ip {
+                return Err(CreateSessionError::TooManySessionsForClient(ip));
+            }
+        }
         let id = session_id();
-        let (handle, worker) = create_local_session(id.clone(), self.session_config.clone());
-        self.sessions.write().await.insert(id.clone(), handle);
+        let (mut handle, worker) = create_local_session(id.clone(), self.session_config.clone());
+        handle.client_ip = client_ip;
+        sessions.insert(id.clone(), handle);
         Ok((id, WorkerTransport::spawn(worker)))
     }
     async fn initialize_session(
@@ -130,13 +166,47 @@ impl SessionManager for LocalSessionManager {
     }
 }
 
-/// `<index>/request_id>`
+/// The id of a sse event, telling the stream and the position in the stream of the event.
+///
+/// It's displayed as `<index>/<request_id>`, or `<index>` for the common stream. See
+/// [`EventId::compact`] for the format sent to the clients.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EventId {
     http_request_id: Option<HttpRequestId>,
     index: usize,
 }
 
+impl EventId {
+    /// Display the event id in the format sent to the clients: a base64url string when the event
+    /// belongs to a request stream, or a plain decimal for the common stream.
+    ///
+    /// Unlike `<index>/<request_id>`, it's a single token, which some sse clients expect.
+    pub fn compact(&self) -> DisplayCompact<'_> {
+        DisplayCompact(self)
+    }
+
+    /// The event id of a request stream whose id is a uuid, see [`RequestIdGenerator::Uuid4`]
+    pub fn from_uuid(session_id: uuid::Uuid, index: usize) -> Self {
+        EventId {
+            http_request_id: Some(session_id.as_u128()),
+            index,
+        }
+    }
+
+    fn from_compact_bytes(bytes: &[u8]) -> Option<Self> {
+        let (index, request_id) = bytes.split_at_checked(8)?;
+        let http_request_id = match request_id.len() {
+            8 => u128::from(u64::from_be_bytes(request_id.try_into().ok()?)),
+            16 => u128::from_be_bytes(request_id.try_into().ok()?),
+            _ => return None,
+        };
+        Some(EventId {
+            index: usize::try_from(u64::from_be_bytes(index.try_into().ok()?)).ok()?,
+            http_request_id: Some(http_request_id),
+        })
+    }
+}
+
 impl std::fmt::Display for EventId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.index)?;
@@ -147,6 +217,35 @@ impl std::fmt::Display for EventId {
     }
 }
 
+/// Compact display of an [`EventId`], returned by [`EventId::compact`]
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayCompact<'a>(&'a EventId);
+
+impl std::fmt::Display for DisplayCompact<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+        match self.0.http_request_id {
+            Some(http_request_id) => {
+                // the sequential ids fit in 8 bytes, the uuids take 16
+                let mut bytes = [0u8; 24];
+                bytes[..8].copy_from_slice(&(self.0.index as u64).to_be_bytes());
+                let len = match u64::try_from(http_request_id) {
+                    Ok(http_request_id) => {
+                        bytes[8..16].copy_from_slice(&http_request_id.to_be_bytes());
+                        16
+                    }
+                    Err(_) => {
+                        bytes[8..].copy_from_slice(&http_request_id.to_be_bytes());
+                        24
+                    }
+                };
+                write!(f, "{}", URL_SAFE_NO_PAD.encode(&bytes[..len]))
+            }
+            None => write!(f, "{}", self.0.index),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Error)]
 pub enum EventIdParseError {
     #[error("Invalid index: {0}")]
@@ -157,44 +256,93 @@ pub enum EventIdParseError {
     InvalidRequestIdType,
     #[error("Missing request id")]
     MissingRequestId,
+    #[error("Invalid compact event id")]
+    InvalidCompact,
 }
 
+/// Parse both the `<index>/<request_id>` format and the [compact](EventId::compact) one.
 impl std::str::FromStr for EventId {
     type Err = EventIdParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((index, request_id)) = s.split_once("/") {
             let index = usize::from_str(index).map_err(EventIdParseError::InvalidIndex)?;
-            let request_id = u64::from_str(request_id).map_err(EventIdParseError::InvalidIndex)?;
-            Ok(EventId {
+            let request_id =
+                u128::from_str(request_id).map_err(EventIdParseError::InvalidNumericRequestId)?;
+            return Ok(EventId {
                 http_request_id: Some(request_id),
                 index,
-            })
-        } else {
-            let index = usize::from_str(s).map_err(EventIdParseError::InvalidIndex)?;
-            Ok(EventId {
+            });
+        }
+        match usize::from_str(s) {
+            Ok(index) => Ok(EventId {
                 http_request_id: None,
                 index,
-            })
+            }),
+            // the compact format is 22 or 32 chars long, a decimal index can't be that long
+            Err(error)
+                if s.len() != 22 && s.len() != 32 && s.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Err(EventIdParseError::InvalidIndex(error))
+            }
+            Err(_) => {
+                use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+                URL_SAFE_NO_PAD
+                    .decode(s)
+                    .ok()
+                    .and_then(|bytes| EventId::from_compact_bytes(&bytes))
+                    .ok_or(EventIdParseError::InvalidCompact)
+            }
         }
     }
 }
 
-use super::{ServerSseMessage, SessionManager};
+use super::{CreateSessionError, ServerSseMessage, SessionManager};
+
+/// The messages kept to be replayed when a client resumes a stream, by event index
+struct MessageCache(VecDeque<(usize, ServerSseMessage)>);
+
+impl MessageCache {
+    fn new(capacity: usize) -> Self {
+        Self(VecDeque::with_capacity(capacity))
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn insert(&mut self, index: usize, message: ServerSseMessage, capacity: usize) {
+        if self.0.len() >= capacity {
+            self.0.pop_front();
+        }
+        self.0.push_back((index, message));
+    }
+
+    /// The cached messages from `index`, in the order they were sent.
+    fn since(&self, index: usize) -> Vec<ServerSseMessage> {
+        self.0
+            .iter()
+            .filter(|(i, _)| *i >= index)
+            .map(|(_, message)| message.clone())
+            .collect()
+    }
+}
 
 struct CachedTx {
     tx: Sender<ServerSseMessage>,
-    cache: VecDeque<ServerSseMessage>,
+    cache: MessageCache,
     http_request_id: Option<HttpRequestId>,
     capacity: usize,
+    next_index: usize,
 }
 
 impl CachedTx {
     fn new(tx: Sender<ServerSseMessage>, http_request_id: Option<HttpRequestId>) -> Self {
         Self {
-            cache: VecDeque::with_capacity(tx.capacity()),
+            cache: MessageCache::new(tx.capacity()),
             capacity: tx.capacity(),
             tx,
             http_request_id,
+            next_index: 0,
         }
     }
     fn new_common(tx: Sender<ServerSseMessage>) -> Self {
@@ -202,29 +350,17 @@ impl CachedTx {
     }
 
     async fn send(&mut self, message: ServerJsonRpcMessage) {
-        let index = self.cache.back().map_or(0, |m| {
-            m.event_id
-                .as_deref()
-                .unwrap_or_default()
-                .parse::<EventId>()
-                .expect("valid event id")
-                .index
-                + 1
-        });
+        let index = self.next_index;
+        self.next_index += 1;
         let event_id = EventId {
             http_request_id: self.http_request_id,
             index,
         };
         let message = ServerSseMessage {
-            event_id: Some(event_id.to_string()),
+            event_id: Some(event_id.compact().to_string()),
             message: Arc::new(message),
         };
-        if self.cache.len() >= self.capacity {
-            self.cache.pop_front();
-            self.cache.push_back(message.clone());
-        } else {
-            self.cache.push_back(message.clone());
-        }
+        self.cache.insert(index, message.clone(), self.capacity);
         let _ = self.tx.send(message).await.inspect_err(|e| {
             let event_id = &e.0.event_id;
             tracing::trace!(?event_id, "trying to send message in a closed session")
@@ -232,24 +368,19 @@ impl CachedTx {
     }
 
     async fn sync(&mut self, index: usize) -> Result<(), SessionError> {
-        let Some(front) = self.cache.front() else {
+        if self.cache.len() == 0 {
             return Ok(());
-        };
-        let front_event_id = front
-            .event_id
-            .as_deref()
-            .unwrap_or_default()
-            .parse::<EventId>()?;
-        let sync_index = index.saturating_sub(front_event_id.index);
-        if sync_index > self.cache.len() {
+        }
+        if index >= self.next_index {
             // invalid index
             return Err(SessionError::InvalidEventId);
         }
-        for message in self.cache.iter().skip(sync_index) {
+        // the client already received the event of the index
+        for message in self.cache.since(index + 1) {
             let send_result = self.tx.send(message.clone()).await;
             if send_result.is_err() {
                 let event_id: EventId = message.event_id.as_deref().unwrap_or_default().parse()?;
-                return Err(SessionError::ChannelClosed(Some(event_id.index as u64)));
+                return Err(SessionError::ChannelClosed(Some(event_id.index as u128)));
             }
         }
         Ok(())
@@ -261,7 +392,7 @@ struct HttpRequestWise {
     tx: CachedTx,
 }
 
-type HttpRequestId = u64;
+type HttpRequestId = u128;
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 enum ResourceKey {
     McpRequestId(RequestId),
@@ -282,6 +413,39 @@ impl LocalSessionWorker {
     pub fn id(&self) -> &SessionId {
         &self.id
     }
+
+    /// Cancel the requests which are still in flight, the http streams waiting for them are gone
+    /// with the session.
+    async fn cancel_pending_requests(&mut self, context: &mut WorkerContext<Self>, reason: &str) {
+        let pending: Vec<RequestId> = self
+            .resource_router
+            .keys()
+            .filter_map(|resource| match resource {
+                ResourceKey::McpRequestId(request_id) => Some(request_id.clone()),
+                ResourceKey::ProgressToken(_) => None,
+            })
+            .collect();
+        for request_id in pending {
+            tracing::debug!(%request_id, reason, "cancel pending request");
+            let notification = ClientNotification::CancelledNotification(Notification {
+                method: CancelledNotificationMethod,
+                params: CancelledNotificationParam {
+                    request_id: request_id.clone(),
+                    reason: Some(reason.to_owned()),
+                },
+                extensions: Default::default(),
+            });
+            self.unregister_resource(&ResourceKey::McpRequestId(request_id));
+            if context
+                .send_to_handler(ClientJsonRpcMessage::notification(notification))
+                .await
+                .is_err()
+            {
+                // the handler is gone, there is no one to notify
+                break;
+            }
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -375,9 +539,14 @@ impl LocalSessionWorker {
         }
     }
     fn next_http_request_id(&mut self) -> HttpRequestId {
-        let id = self.next_http_request_id;
-        self.next_http_request_id = self.next_http_request_id.wrapping_add(1);
-        id
+        match self.session_config.request_id_generator {
+            RequestIdGenerator::Sequential => {
+                let id = self.next_http_request_id;
+                self.next_http_request_id = self.next_http_request_id.wrapping_add(1);
+                id
+            }
+            RequestIdGenerator::Uuid4 => uuid::Uuid::new_v4().as_u128(),
+        }
     }
     async fn establish_request_wise_channel(
         &mut self,
@@ -562,8 +731,10 @@ pub enum SessionQuitReason {
 #[derive(Debug, Clone)]
 pub struct LocalSessionHandle {
     id: SessionId,
+    client_ip: Option<IpAddr>,
     // after all event_tx drop, inner task will be terminated
     event_tx: Sender<SessionEvent>,
+    backpressure: BackpressurePolicy,
 }
 
 impl LocalSessionHandle {
@@ -572,6 +743,11 @@ impl LocalSessionHandle {
         &self.id
     }
 
+    /// Get the ip of the client which created the session, if it's known
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
     /// Close the session
     pub async fn close(&self) -> Result<(), SessionError> {
         self.event_tx
@@ -587,11 +763,18 @@ impl LocalSessionHandle {
         message: ClientJsonRpcMessage,
         http_request_id: Option<HttpRequestId>,
     ) -> Result<(), SessionError> {
+        let event = SessionEvent::ClientMessage {
+            message,
+            http_request_id,
+        };
+        if self.backpressure == BackpressurePolicy::Error {
+            return self.event_tx.try_send(event).map_err(|e| match e {
+                TrySendError::Full(_) => SessionError::ChannelClosed(http_request_id),
+                TrySendError::Closed(_) => SessionError::SessionServiceTerminated,
+            });
+        }
         self.event_tx
-            .send(SessionEvent::ClientMessage {
-                message,
-                http_request_id,
-            })
+            .send(event)
             .await
             .map_err(|_| SessionError::SessionServiceTerminated)?;
         Ok(())
@@ -720,6 +903,13 @@ impl Worker for LocalSessionWorker {
             channel_buffer_capacity: self.session_config.channel_capacity,
         }
     }
+    fn backpressure(&self) -> BackpressurePolicy {
+        match self.session_config.backpressure {
+            // the handle refuses the messages instead, so the session isn't closed
+            BackpressurePolicy::Error => BackpressurePolicy::Block,
+            policy => policy,
+        }
+    }
     #[instrument(name = "streamable_http_session", skip_all, fields(id = self.id.as_ref()))]
     async fn run(
         mut self,
@@ -759,9 +949,30 @@ impl Worker for LocalSessionWorker {
             .send(Ok(()))
             .map_err(|_| WorkerQuitReason::HandlerTerminated)?;
         let ct = context.cancellation_token.clone();
-        let keep_alive = self.session_config.keep_alive.unwrap_or(Duration::MAX);
+        let keep_alive = self.session_config.keep_alive;
+        let idle_timeout = self.session_config.idle_timeout;
+        let mut last_activity = tokio::time::Instant::now();
+        let mut last_client_message = last_activity;
+        let mut idle_check = idle_timeout
+            .map(|timeout| tokio::time::interval_at(last_client_message + timeout, timeout));
         loop {
-            let keep_alive_timeout = tokio::time::sleep(keep_alive);
+            let keep_alive_deadline =
+                keep_alive.map(|keep_alive| (keep_alive, last_activity + keep_alive));
+            let keep_alive_timeout = async move {
+                match keep_alive_deadline {
+                    Some((keep_alive, deadline)) => {
+                        tokio::time::sleep_until(deadline).await;
+                        keep_alive
+                    }
+                    None => std::future::pending().await,
+                }
+            };
+            let idle_tick = async {
+                match &mut idle_check {
+                    Some(interval) => interval.tick().await,
+                    None => std::future::pending().await,
+                }
+            };
             let event = tokio::select! {
                 event = self.event_rx.recv() => {
                     if let Some(event) = event {
@@ -776,10 +987,26 @@ impl Worker for LocalSessionWorker {
                 _ = ct.cancelled() => {
                     return Err(WorkerQuitReason::Cancelled)
                 }
-                _ = keep_alive_timeout => {
+                keep_alive = keep_alive_timeout => {
                     return Err(WorkerQuitReason::fatal(LocalSessionWorkerError::KeepAliveTimeout(keep_alive), "poll next session event"))
                 }
+                _ = idle_tick => {
+                    let (Some(idle_timeout), Some(interval)) = (idle_timeout, &mut idle_check) else {
+                        continue;
+                    };
+                    if last_client_message.elapsed() < idle_timeout {
+                        interval.reset_at(last_client_message + idle_timeout);
+                        continue;
+                    }
+                    tracing::info!(session_id = ?self.id, "close idle session");
+                    InnerEvent::FromHttpService(SessionEvent::Close)
+                }
             };
+            // the idle checks are not an activity, they `continue` before
+            last_activity = tokio::time::Instant::now();
+            if let InnerEvent::FromHttpService(SessionEvent::ClientMessage { .. }) = &event {
+                last_client_message = last_activity;
+            }
             match event {
                 InnerEvent::FromHandler(WorkerSendRequest { message, responder }) => {
                     // catch response
@@ -846,6 +1073,8 @@ impl Worker for LocalSessionWorker {
                     let _ = responder.send(handle_result);
                 }
                 InnerEvent::FromHttpService(SessionEvent::Close) => {
+                    self.cancel_pending_requests(&mut context, "session closed")
+                        .await;
                     return Err(WorkerQuitReason::TransportClosed);
                 }
                 _ => {
@@ -860,12 +1089,108 @@ impl Worker for LocalSessionWorker {
 pub struct SessionConfig {
     /// the capacity of the channel for the session. Default is 16.
     pub channel_capacity: usize,
-    /// if set, the session will be closed after this duration of inactivity.
+    /// if set, the session is closed after this duration without any activity: a message of the
+    /// client or of the server, or an http request on the session, e.g. opening a stream. The
+    /// worker quits with [`LocalSessionWorkerError::KeepAliveTimeout`]. Default is None.
     pub keep_alive: Option<Duration>,
+    /// if set, the session is closed when no client message is received for this duration, even
+    /// if the server keeps sending messages to it, e.g. the notifications of a client which went
+    /// away without deleting its session. The session is closed as if the client deleted it.
+    /// Default is None.
+    pub idle_timeout: Option<Duration>,
+    /// the maximum count of sessions created by a same client ip. Default is 10.
+    ///
+    /// The client ip is only known when the http server provides it, see
+    /// [`StreamableHttpService`](crate::transport::StreamableHttpService).
+    ///
+    /// A session counts until it's closed, and neither `keep_alive` nor `idle_timeout` is set by
+    /// default: the sessions of the clients going away without deleting them are never closed,
+    /// and end up refusing any new session from their ip. Set `idle_timeout` along with this
+    /// limit, unless the clients always delete their sessions.
+    pub max_sessions_per_ip: Option<usize>,
+    /// the maximum count of sessions of the manager. Default is 1000.
+    pub max_total_sessions: Option<usize>,
+    /// what to do with the client messages when the server handler is not keeping up. Default is
+    /// [`BackpressurePolicy::Block`].
+    ///
+    /// With [`BackpressurePolicy::Drop`] the notifications the handler can't take are dropped and
+    /// the requests are answered with an error, and with
+    /// [`BackpressurePolicy::Error`] the messages pushed to a full session are refused with
+    /// [`SessionError::ChannelClosed`].
+    pub backpressure: BackpressurePolicy,
+    /// how the ids of the request streams, carried by their event ids, are generated. Default is
+    /// [`RequestIdGenerator::Sequential`].
+    pub request_id_generator: RequestIdGenerator,
+}
+
+/// How a session generates the ids of its request streams
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestIdGenerator {
+    /// Count the streams from 0, the ids are only unique within the session
+    #[default]
+    Sequential,
+    /// Use random uuids, so the event ids don't collide when several server instances share the
+    /// state of the sessions, and can be correlated by a distributed tracing
+    Uuid4,
 }
 
 impl SessionConfig {
     pub const DEFAULT_CHANNEL_CAPACITY: usize = 16;
+    pub const DEFAULT_MAX_SESSIONS_PER_IP: usize = 10;
+    pub const DEFAULT_MAX_TOTAL_SESSIONS: usize = 1000;
+
+    pub fn builder() -> SessionConfigBuilder {
+        SessionConfigBuilder::default()
+    }
+}
+
+/// Build a [`SessionConfig`], the fields not set keep their default
+#[derive(Debug, Clone, Default)]
+pub struct SessionConfigBuilder {
+    config: SessionConfig,
+}
+
+impl SessionConfigBuilder {
+    pub fn channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.config.channel_capacity = channel_capacity;
+        self
+    }
+
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.config.keep_alive = Some(keep_alive);
+        self
+    }
+
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.config.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Limit the sessions of a same client ip, `None` for no limit
+    pub fn max_sessions_per_ip(mut self, max_sessions_per_ip: Option<usize>) -> Self {
+        self.config.max_sessions_per_ip = max_sessions_per_ip;
+        self
+    }
+
+    /// Limit the sessions of the manager, `None` for no limit
+    pub fn max_total_sessions(mut self, max_total_sessions: Option<usize>) -> Self {
+        self.config.max_total_sessions = max_total_sessions;
+        self
+    }
+
+    pub fn backpressure(mut self, backpressure: BackpressurePolicy) -> Self {
+        self.config.backpressure = backpressure;
+        self
+    }
+
+    pub fn request_id_generator(mut self, request_id_generator: RequestIdGenerator) -> Self {
+        self.config.request_id_generator = request_id_generator;
+        self
+    }
+
+    pub fn build(self) -> SessionConfig {
+        self.config
+    }
 }
 
 impl Default for SessionConfig {
@@ -873,6 +1198,11 @@ impl Default for SessionConfig {
         Self {
             channel_capacity: Self::DEFAULT_CHANNEL_CAPACITY,
             keep_alive: None,
+            idle_timeout: None,
+            max_sessions_per_ip: Some(Self::DEFAULT_MAX_SESSIONS_PER_IP),
+            max_total_sessions: Some(Self::DEFAULT_MAX_TOTAL_SESSIONS),
+            backpressure: BackpressurePolicy::default(),
+            request_id_generator: RequestIdGenerator::default(),
         }
     }
 }
@@ -894,6 +1224,8 @@ pub fn create_local_session(
     let handle = LocalSessionHandle {
         event_tx,
         id: id.clone(),
+        client_ip: None,
+        backpressure: config.backpressure,
     };
     let session_worker = LocalSessionWorker {
         next_http_request_id: 0,
@@ -906,3 +1238,209 @@ pub fn create_local_session(
     };
     (handle, session_worker)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_id_round_trip() {
+        // xorshift, a fixed seed keeps failures reproducible
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let edge_cases = [0, 1, 9, 10, u32::MAX as u64, usize::MAX as u64, u64::MAX];
+        let mut event_ids = vec![];
+        for index in edge_cases {
+            event_ids.push(EventId {
+                http_request_id: None,
+                index: index as usize,
+            });
+            for request_id in edge_cases {
+                event_ids.push(EventId {
+                    http_request_id: Some(request_id.into()),
+                    index: index as usize,
+                });
+            }
+            event_ids.push(EventId::from_uuid(uuid::Uuid::max(), index as usize));
+            event_ids.push(EventId::from_uuid(uuid::Uuid::new_v4(), index as usize));
+        }
+        for _ in 0..10_000 {
+            let value = next();
+            // also cover small values, the common case
+            let index = (next() >> (value % 64)) as usize;
+            let request_id = u128::from(next() >> ((value >> 8) % 64));
+            let http_request_id = (value & 1 == 0).then_some(request_id);
+            event_ids.push(EventId {
+                http_request_id,
+                index,
+            });
+        }
+        for event_id in event_ids {
+            let displayed = event_id.to_string();
+            assert_eq!(
+                displayed.parse::<EventId>().unwrap(),
+                event_id,
+                "{displayed}"
+            );
+            let compact = event_id.compact().to_string();
+            assert_eq!(compact.parse::<EventId>().unwrap(), event_id, "{compact}");
+            if event_id.http_request_id.is_none() {
+                assert!(compact.bytes().all(|b| b.is_ascii_digit()), "{compact}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_event_id_parse_error() {
+        for invalid in [
+            "",
+            "a",
+            "1/",
+            "/1",
+            "1/a",
+            "-1",
+            "AAAAAAAAAAAAAAAAAAAAA",
+            "AAAA!AAAAAAAAAAAAAAAAA",
+        ] {
+            assert!(invalid.parse::<EventId>().is_err(), "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_replay() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let mut cached_tx = CachedTx::new(tx, Some(7));
+        for _ in 0..6 {
+            cached_tx
+                .send(ServerJsonRpcMessage::notification(
+                    ServerNotification::ToolListChangedNotification(Default::default()),
+                ))
+                .await;
+            rx.recv().await.unwrap();
+        }
+        assert_eq!(cached_tx.cache.len(), 4);
+
+        // resume on a new channel, from an evicted message up to the last one
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        cached_tx.tx = tx;
+        cached_tx.sync(0).await.unwrap();
+        drop(cached_tx);
+        let mut replayed = vec![];
+        while let Some(message) = rx.recv().await {
+            let event_id: EventId = message.event_id.unwrap().parse().unwrap();
+            assert_eq!(event_id.http_request_id, Some(7));
+            replayed.push(event_id.index);
+        }
+        assert_eq!(replayed, vec![2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_cache_sync_invalid_index() {
+        let (tx, _rx) = tokio::sync::mpsc::channel(4);
+        let mut cached_tx = CachedTx::new_common(tx);
+        cached_tx
+            .send(ServerJsonRpcMessage::notification(
+                ServerNotification::ToolListChangedNotification(Default::default()),
+            ))
+            .await;
+        assert!(cached_tx.sync(0).await.is_ok());
+        assert!(matches!(
+            cached_tx.sync(1).await,
+            Err(SessionError::InvalidEventId)
+        ));
+    }
+
+    fn initialized_notification() -> ClientJsonRpcMessage {
+        ClientJsonRpcMessage::notification(ClientNotification::InitializedNotification(
+            Default::default(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_error_refuses_messages() {
+        let config = SessionConfig {
+            channel_capacity: 1,
+            backpressure: BackpressurePolicy::Error,
+            ..Default::default()
+        };
+        // the worker isn't run, so the session is never drained
+        let (handle, _worker) = create_local_session("session", config);
+        assert!(
+            handle
+                .push_message(initialized_notification(), None)
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            handle
+                .push_message(initialized_notification(), Some(3))
+                .await,
+            Err(SessionError::ChannelClosed(Some(3)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_drop_discards_notifications_and_answers_requests() {
+        use crate::{
+            model::{ClientRequest, ServerResult},
+            transport::Transport,
+        };
+        let config = SessionConfig {
+            channel_capacity: 1,
+            backpressure: BackpressurePolicy::Drop,
+            ..Default::default()
+        };
+        let (handle, worker) = create_local_session("session", config);
+        let mut transport = WorkerTransport::spawn(worker);
+        let initialize = tokio::spawn({
+            let handle = handle.clone();
+            async move {
+                let request = ClientJsonRpcMessage::request(
+                    ClientRequest::PingRequest(Default::default()),
+                    RequestId::Number(1),
+                );
+                handle.initialize(request).await
+            }
+        });
+        assert!(transport.receive().await.is_some());
+        transport
+            .send(ServerJsonRpcMessage::response(
+                ServerResult::empty(()),
+                RequestId::Number(1),
+            ))
+            .await
+            .unwrap();
+        initialize.await.unwrap().unwrap();
+
+        for _ in 0..3 {
+            handle
+                .push_message(initialized_notification(), None)
+                .await
+                .unwrap();
+        }
+        // a dropped request is answered on its stream
+        let mut channel = handle.establish_request_wise_channel().await.unwrap();
+        let request = ClientJsonRpcMessage::request(
+            ClientRequest::PingRequest(Default::default()),
+            RequestId::Number(2),
+        );
+        handle
+            .push_message(request, channel.http_request_id)
+            .await
+            .unwrap();
+        let answer = channel.inner.recv().await.unwrap();
+        let (error, id) = answer.message.as_ref().clone().into_error().unwrap();
+        assert_eq!(id, RequestId::Number(2));
+        assert_eq!(error.code, crate::model::ErrorCode::RATE_LIMITED);
+
+        assert!(transport.receive().await.is_some());
+        let next =
+            tokio::time::timeout(std::time::Duration::from_millis(50), transport.receive()).await;
+        assert!(next.is_err(), "only the first message is kept");
+    }
+}
@@ -1,4 +1,10 @@
-use std::{convert::Infallible, fmt::Display, sync::Arc, time::Duration};
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
 
 use bytes::Bytes;
 use futures::{StreamExt, future::BoxFuture};
@@ -6,13 +12,21 @@ use http::{Method, Request, Response, header::ALLOW};
 use http_body::Body;
 use http_body_util::{BodyExt, Full, combinators::BoxBody};
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tower_http::cors::CorsLayer;
 
-use super::session::SessionManager;
+use super::{
+    auth::{AuthContext, AuthHandler},
+    cors::CorsConfig,
+    registry::SessionRegistry,
+    session::{CreateSessionError, SessionManager},
+};
 use crate::{
     RoleServer,
-    model::{ClientJsonRpcMessage, ClientRequest, GetExtensions},
-    serve_server,
-    service::serve_directly,
+    model::{ClientJsonRpcMessage, ClientRequest, GetExtensions, ServerJsonRpcMessage},
+    service::{
+        ServeServerConfig, check_protocol_version, serve_directly, serve_server_with_config,
+    },
     transport::{
         OneshotTransport, TransportAdapterIdentity,
         common::{
@@ -20,19 +34,44 @@ use crate::{
                 EVENT_STREAM_MIME_TYPE, HEADER_LAST_EVENT_ID, HEADER_SESSION_ID, JSON_MIME_TYPE,
             },
             server_side_http::{
-                BoxResponse, ServerSseMessage, accepted_response, expect_json,
+                BoxResponse, ServerSseMessage, SessionId, accepted_response, expect_json,
                 internal_error_response, sse_stream_response, unexpected_message_response,
             },
         },
     },
 };
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct StreamableHttpServerConfig {
-    /// The ping message duration for SSE connections.
+    /// The interval of the `: heartbeat` comments sent on idle SSE streams, which keep reverse
+    /// proxies from closing them. Default is 15 seconds.
     pub sse_keep_alive: Option<Duration>,
     /// If true, the server will create a session for each request and keep it alive.
     pub stateful_mode: bool,
+    /// If set, every http request must be authenticated by this handler, see [`AuthHandler`].
+    pub auth_handler: Option<Arc<dyn AuthHandler>>,
+    /// If set, the SSE events are sent with the type `{prefix}-{session_id}`, or `{prefix}` in
+    /// stateless mode, so the messages of several sessions can be told apart by the client.
+    pub sse_event_type_prefix: Option<String>,
+    /// The CORS headers sent to browsers, none by default, see [`CorsConfig`]. Its layer is
+    /// built by [`StreamableHttpService::new`], later changes aren't applied.
+    pub cors: CorsConfig,
+    /// How the sessions are served, e.g. the protocol versions accepted from the clients, which
+    /// are checked before a session is created. Any version is accepted by default.
+    pub serve_config: ServeServerConfig,
+}
+
+impl std::fmt::Debug for StreamableHttpServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamableHttpServerConfig")
+            .field("sse_keep_alive", &self.sse_keep_alive)
+            .field("stateful_mode", &self.stateful_mode)
+            .field("auth_handler", &self.auth_handler.is_some())
+            .field("sse_event_type_prefix", &self.sse_event_type_prefix)
+            .field("cors", &self.cors)
+            .field("serve_config", &self.serve_config)
+            .finish()
+    }
 }
 
 impl Default for StreamableHttpServerConfig {
@@ -40,6 +79,10 @@ impl Default for StreamableHttpServerConfig {
         Self {
             sse_keep_alive: Some(Duration::from_secs(15)),
             stateful_mode: true,
+            auth_handler: None,
+            sse_event_type_prefix: None,
+            cors: CorsConfig::default(),
+            serve_config: ServeServerConfig::default(),
         }
     }
 }
@@ -57,10 +100,54 @@ impl Default for StreamableHttpServerConfig {
 ///     tracing::info!("http parts:{parts:?}")
 /// }
 /// ```
+///
+/// ## Session limits
+///
+/// The session manager may limit the count of sessions per client ip, the service responds with
+/// `429 Too Many Requests` when a client exceeds its limit, and with `503 Service Unavailable`
+/// when the global limit is reached. The client ip is read from a [`SocketAddr`] request
+/// extension, or from axum's `ConnectInfo<SocketAddr>` when the `axum` feature is enabled and the
+/// app is served with `into_make_service_with_connect_info::<SocketAddr>()`.
+///
+/// ## SSE event types
+///
+/// The SSE events have no type by default, so a browser receives them as `message` events.
+/// With [`StreamableHttpServerConfig::sse_event_type_prefix`], the events of a session get the
+/// type `{prefix}-{session_id}`, and a client shared by several sessions listens to each one:
+/// ```js
+/// const source = new EventSource(url);
+/// source.addEventListener(`${prefix}-${sessionId}`, (event) => {
+///     const message = JSON.parse(event.data);
+///     console.log(sessionId, message);
+/// });
+/// ```
+///
+/// ## Graceful shutdown
+///
+/// [`StreamableHttpService::drain`] closes the sessions and waits for them, e.g. before a pod
+/// stops on `SIGTERM`. Once draining, the new sessions are refused with
+/// `503 Service Unavailable`. With axum, [`StreamableHttpService::drain_on_signal`] drains the
+/// sessions before the server stops:
+/// ```rust,ignore
+/// let service = StreamableHttpService::new(factory, session_manager, config);
+/// let shutdown = service.drain_on_signal(
+///     async { tokio::signal::ctrl_c().await.unwrap() },
+///     Duration::from_secs(10),
+/// );
+/// let router = axum::Router::new().nest_service("/mcp", service);
+/// axum::serve(listener, router).with_graceful_shutdown(shutdown).await?;
+/// ```
 pub struct StreamableHttpService<S, M = super::session::local::LocalSessionManager> {
     pub config: StreamableHttpServerConfig,
     session_manager: Arc<M>,
     service_factory: Arc<dyn Fn() -> Result<S, std::io::Error> + Send + Sync>,
+    registry: Arc<SessionRegistry>,
+    /// The parent of the cancellation tokens of the sessions, cancelled when draining
+    drain_ct: CancellationToken,
+    /// The tasks serving the sessions, and the requests in stateless mode
+    session_tasks: TaskTracker,
+    /// The layer of [`StreamableHttpServerConfig::cors`], built once by [`StreamableHttpService::new`]
+    cors: Option<CorsLayer>,
 }
 
 impl<S, M> Clone for StreamableHttpService<S, M> {
@@ -69,6 +156,10 @@ impl<S, M> Clone for StreamableHttpService<S, M> {
             config: self.config.clone(),
             session_manager: self.session_manager.clone(),
             service_factory: self.service_factory.clone(),
+            registry: self.registry.clone(),
+            drain_ct: self.drain_ct.clone(),
+            session_tasks: self.session_tasks.clone(),
+            cors: self.cors.clone(),
         }
     }
 }
@@ -86,6 +177,46 @@ where
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
     fn call(&mut self, req: http::Request<RequestBody>) -> Self::Future {
         let service = self.clone();
+        // the cors layer answers the preflight requests, before they are authenticated
+        if let Some(cors) = &self.cors {
+            let mut service = tower_layer::Layer::layer(cors, HandleService(service));
+            return Box::pin(async move {
+                futures::future::poll_fn(|cx| {
+                    tower_service::Service::<Request<RequestBody>>::poll_ready(&mut service, cx)
+                })
+                .await?;
+                service.call(req).await
+            });
+        }
+        Box::pin(async move {
+            let response = service.handle(req).await;
+            Ok(response)
+        })
+    }
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+/// The service without cors layer, wrapped by the layer of [`StreamableHttpServerConfig::cors`]
+struct HandleService<S, M>(StreamableHttpService<S, M>);
+
+impl<RequestBody, S, M> tower_service::Service<Request<RequestBody>> for HandleService<S, M>
+where
+    RequestBody: Body + Send + 'static,
+    S: crate::Service<RoleServer>,
+    M: SessionManager,
+    RequestBody::Error: Display,
+    RequestBody::Data: Send + 'static,
+{
+    type Response = BoxResponse;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    fn call(&mut self, req: http::Request<RequestBody>) -> Self::Future {
+        let service = self.0.clone();
         Box::pin(async move {
             let response = service.handle(req).await;
             Ok(response)
@@ -104,25 +235,85 @@ where
     S: crate::Service<RoleServer> + Send + 'static,
     M: SessionManager,
 {
+    /// # Panics
+    ///
+    /// If the [CORS configuration](StreamableHttpServerConfig::cors) is invalid, see
+    /// [`CorsConfig::layer`].
     pub fn new(
         service_factory: impl Fn() -> Result<S, std::io::Error> + Send + Sync + 'static,
         session_manager: Arc<M>,
         config: StreamableHttpServerConfig,
     ) -> Self {
+        let cors = config.cors.layer().expect("invalid CORS configuration");
         Self {
+            cors,
             config,
             session_manager,
             service_factory: Arc::new(service_factory),
+            registry: Default::default(),
+            drain_ct: CancellationToken::new(),
+            session_tasks: TaskTracker::new(),
+        }
+    }
+    /// The live sessions of this service, which can be used to notify all the clients
+    pub fn registry(&self) -> Arc<SessionRegistry> {
+        self.registry.clone()
+    }
+    /// Refuse the new sessions, cancel the live ones, and wait up to `drain_timeout` for them to
+    /// be closed.
+    ///
+    /// In stateless mode, the new requests are refused and the ones in flight are answered before
+    /// the drain completes.
+    ///
+    /// Returns whether all the sessions were closed in time.
+    pub async fn drain(&self, drain_timeout: Duration) -> bool {
+        drain_sessions(&self.drain_ct, &self.session_tasks, drain_timeout).await
+    }
+    /// Wait for the signal, then [drain](Self::drain) the sessions.
+    ///
+    /// The returned future is meant for axum's `with_graceful_shutdown`, so the server stops
+    /// accepting connections once the sessions are closed.
+    pub fn drain_on_signal(
+        &self,
+        signal: impl Future<Output = ()> + Send + 'static,
+        drain_timeout: Duration,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let drain_ct = self.drain_ct.clone();
+        let session_tasks = self.session_tasks.clone();
+        async move {
+            signal.await;
+            tracing::info!("draining the sessions");
+            if !drain_sessions(&drain_ct, &session_tasks, drain_timeout).await {
+                tracing::warn!(
+                    remaining = session_tasks.len(),
+                    "sessions still open after the drain timeout"
+                );
+            }
         }
     }
     fn get_service(&self) -> Result<S, std::io::Error> {
         (self.service_factory)()
     }
-    pub async fn handle<B>(&self, request: Request<B>) -> Response<BoxBody<Bytes, Infallible>>
+    fn sse_event_type(&self, session_id: Option<&SessionId>) -> Option<String> {
+        let prefix = self.config.sse_event_type_prefix.as_ref()?;
+        Some(match session_id {
+            Some(session_id) => format!("{prefix}-{session_id}"),
+            None => prefix.clone(),
+        })
+    }
+    pub async fn handle<B>(&self, mut request: Request<B>) -> Response<BoxBody<Bytes, Infallible>>
     where
         B: Body + Send + 'static,
         B::Error: Display,
     {
+        if let Some(auth_handler) = &self.config.auth_handler {
+            match auth_handler.authenticate(request.headers()).await {
+                Ok(auth_context) => {
+                    request.extensions_mut().insert(auth_context);
+                }
+                Err(error) => return unauthorized_response(auth_handler.as_ref(), error),
+            }
+        }
         let method = request.method().clone();
         let allowed_methods = match self.config.stateful_mode {
             true => "GET, POST, DELETE",
@@ -209,7 +400,11 @@ where
                 .resume(&session_id, last_event_id)
                 .await
                 .map_err(internal_error_response("resume session"))?;
-            Ok(sse_stream_response(stream, self.config.sse_keep_alive))
+            Ok(sse_stream_response(
+                stream,
+                self.config.sse_keep_alive,
+                self.sse_event_type(Some(&session_id)),
+            ))
         } else {
             // create standalone stream
             let stream = self
@@ -217,7 +412,11 @@ where
                 .create_standalone_stream(&session_id)
                 .await
                 .map_err(internal_error_response("create standalone stream"))?;
-            Ok(sse_stream_response(stream, self.config.sse_keep_alive))
+            Ok(sse_stream_response(
+                stream,
+                self.config.sse_keep_alive,
+                self.sse_event_type(Some(&session_id)),
+            ))
         }
     }
 
@@ -290,9 +489,11 @@ where
                 // inject request part to extensions
                 match &mut message {
                     ClientJsonRpcMessage::Request(req) => {
+                        inject_auth_context(req.request.extensions_mut(), &part);
                         req.request.extensions_mut().insert(part);
                     }
                     ClientJsonRpcMessage::Notification(not) => {
+                        inject_auth_context(not.notification.extensions_mut(), &part);
                         not.notification.extensions_mut().insert(part);
                     }
                     _ => {
@@ -307,7 +508,11 @@ where
                             .create_stream(&session_id, message)
                             .await
                             .map_err(internal_error_response("get session"))?;
-                        Ok(sse_stream_response(stream, self.config.sse_keep_alive))
+                        Ok(sse_stream_response(
+                            stream,
+                            self.config.sse_keep_alive,
+                            self.sse_event_type(Some(&session_id)),
+                        ))
                     }
                     ClientJsonRpcMessage::Notification(_)
                     | ClientJsonRpcMessage::Response(_)
@@ -321,36 +526,78 @@ where
                     }
                 }
             } else {
-                let (session_id, transport) = self
-                    .session_manager
-                    .create_session()
-                    .await
-                    .map_err(internal_error_response("create session"))?;
-                if let ClientJsonRpcMessage::Request(req) = &mut message {
-                    if !matches!(req.request, ClientRequest::InitializeRequest(_)) {
-                        return Err(unexpected_message_response("initialize request"));
-                    }
-                    // inject request part to extensions
-                    req.request.extensions_mut().insert(part);
-                } else {
+                if self.drain_ct.is_cancelled() {
+                    return Err(draining_response());
+                }
+                // the request is checked before a session is created, so the rejected requests
+                // don't count against the session limits
+                let ClientJsonRpcMessage::Request(req) = &mut message else {
+                    return Err(unexpected_message_response("initialize request"));
+                };
+                let ClientRequest::InitializeRequest(initialize) = &req.request else {
                     return Err(unexpected_message_response("initialize request"));
+                };
+                // nor is the service created for a client of an unsupported version
+                if let Err(error) = check_protocol_version(
+                    &self.config.serve_config,
+                    &initialize.params.protocol_version,
+                ) {
+                    let message = ServerJsonRpcMessage::error(error, req.id.clone());
+                    return Err(sse_stream_response(
+                        futures::stream::once(async move {
+                            ServerSseMessage {
+                                event_id: None,
+                                message: message.into(),
+                            }
+                        }),
+                        self.config.sse_keep_alive,
+                        self.sse_event_type(None),
+                    ));
                 }
+                let client_ip = client_ip(&part);
+                // inject request part to extensions
+                inject_auth_context(req.request.extensions_mut(), &part);
+                req.request.extensions_mut().insert(part);
                 let service = self
                     .get_service()
                     .map_err(internal_error_response("get service"))?;
+                let (session_id, transport) = self
+                    .session_manager
+                    .create_session_for(client_ip)
+                    .await
+                    .map_err(create_session_error_response)?;
+                let session_id_header = match session_id.parse::<http::HeaderValue>() {
+                    Ok(header) => header,
+                    Err(error) => {
+                        let _ = self.session_manager.close_session(&session_id).await;
+                        return Err(internal_error_response("create session id header")(error));
+                    }
+                };
                 // spawn a task to serve the session
-                tokio::spawn({
+                self.session_tasks.spawn({
                     let session_manager = self.session_manager.clone();
+                    let registry = self.registry.clone();
                     let session_id = session_id.clone();
+                    let ct = self.drain_ct.child_token();
+                    let serve_config = self.config.serve_config.clone();
                     async move {
-                        let service = serve_server::<S, M::Transport, _, TransportAdapterIdentity>(
-                            service, transport,
+                        let service = serve_server_with_config::<
+                            S,
+                            M::Transport,
+                            _,
+                            TransportAdapterIdentity,
+                        >(
+                            service, transport, serve_config, ct
                         )
                         .await;
                         match service {
                             Ok(service) => {
                                 // on service created
+                                registry
+                                    .register(session_id.clone(), service.peer().clone())
+                                    .await;
                                 let _ = service.waiting().await;
+                                registry.unregister(&session_id).await;
                             }
                             Err(e) => {
                                 tracing::error!("Failed to create service: {e}");
@@ -365,11 +612,17 @@ where
                     }
                 });
                 // get initialize response
-                let response = self
+                let response = match self
                     .session_manager
                     .initialize_session(&session_id, message)
                     .await
-                    .map_err(internal_error_response("create stream"))?;
+                {
+                    Ok(response) => response,
+                    Err(error) => {
+                        let _ = self.session_manager.close_session(&session_id).await;
+                        return Err(internal_error_response("create stream")(error));
+                    }
+                };
                 let mut response = sse_stream_response(
                     futures::stream::once({
                         async move {
@@ -380,27 +633,30 @@ where
                         }
                     }),
                     self.config.sse_keep_alive,
+                    self.sse_event_type(Some(&session_id)),
                 );
 
-                response.headers_mut().insert(
-                    HEADER_SESSION_ID,
-                    session_id
-                        .parse()
-                        .map_err(internal_error_response("create session id header"))?,
-                );
+                response
+                    .headers_mut()
+                    .insert(HEADER_SESSION_ID, session_id_header);
                 Ok(response)
             }
         } else {
+            if self.drain_ct.is_cancelled() {
+                return Err(draining_response());
+            }
             let service = self
                 .get_service()
                 .map_err(internal_error_response("get service"))?;
             match message {
                 ClientJsonRpcMessage::Request(mut request) => {
+                    inject_auth_context(request.request.extensions_mut(), &part);
                     request.request.extensions_mut().insert(part);
                     let (transport, receiver) =
                         OneshotTransport::<RoleServer>::new(ClientJsonRpcMessage::Request(request));
                     let service = serve_directly(service, transport, None);
-                    tokio::spawn(async move {
+                    // tracked like the sessions, so a drain waits for the response to be sent
+                    self.session_tasks.spawn(async move {
                         // on service created
                         let _ = service.waiting().await;
                     });
@@ -413,6 +669,7 @@ where
                             }
                         }),
                         self.config.sse_keep_alive,
+                        self.sse_event_type(None),
                     ))
                 }
                 ClientJsonRpcMessage::Notification(_notification) => {
@@ -451,3 +708,72 @@ where
         Ok(accepted_response())
     }
 }
+
+/// Get the ip of the http peer from the request extensions
+fn client_ip(part: &http::request::Parts) -> Option<IpAddr> {
+    #[cfg(feature = "axum")]
+    if let Some(axum::extract::ConnectInfo(addr)) = part
+        .extensions
+        .get::<axum::extract::ConnectInfo<SocketAddr>>()
+    {
+        return Some(addr.ip());
+    }
+    part.extensions.get::<SocketAddr>().map(SocketAddr::ip)
+}
+
+async fn drain_sessions(
+    drain_ct: &CancellationToken,
+    session_tasks: &TaskTracker,
+    drain_timeout: Duration,
+) -> bool {
+    drain_ct.cancel();
+    session_tasks.close();
+    tokio::time::timeout(drain_timeout, session_tasks.wait())
+        .await
+        .is_ok()
+}
+
+fn draining_response() -> BoxResponse {
+    Response::builder()
+        .status(http::StatusCode::SERVICE_UNAVAILABLE)
+        .body(Full::new(Bytes::from("Service Unavailable: server is shutting down")).boxed())
+        .expect("valid response")
+}
+
+fn create_session_error_response<E: Display>(error: CreateSessionError<E>) -> BoxResponse {
+    let status = match &error {
+        CreateSessionError::TooManySessionsForClient(_) => http::StatusCode::TOO_MANY_REQUESTS,
+        CreateSessionError::TooManySessions => http::StatusCode::SERVICE_UNAVAILABLE,
+        CreateSessionError::Session(error) => {
+            return internal_error_response("create session")(error);
+        }
+    };
+    tracing::warn!("Refuse to create session: {error}");
+    Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::from(error.to_string())).boxed())
+        .expect("valid response")
+}
+
+fn inject_auth_context(extensions: &mut crate::model::Extensions, part: &http::request::Parts) {
+    if let Some(auth_context) = part.extensions.get::<AuthContext>() {
+        extensions.insert(auth_context.clone());
+    }
+}
+
+fn unauthorized_response(auth_handler: &dyn AuthHandler, error: impl Display) -> BoxResponse {
+    tracing::debug!("Refuse unauthenticated request: {error}");
+    let mut response = Response::builder()
+        .status(http::StatusCode::UNAUTHORIZED)
+        .header(http::header::CONTENT_TYPE, JSON_MIME_TYPE);
+    if let Some(challenge) = auth_handler.challenge() {
+        response = response.header(http::header::WWW_AUTHENTICATE, challenge);
+    }
+    let body = serde_json::json!({
+        "error": "unauthorized",
+        "error_description": error.to_string(),
+    });
+    response
+        .body(Full::new(Bytes::from(body.to_string())).boxed())
+        .expect("valid response")
+}
@@ -4,7 +4,10 @@ use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Level};
 
 use super::{IntoTransport, Transport};
-use crate::service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage};
+use crate::{
+    model::ErrorData,
+    service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage},
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum WorkerQuitReason<E> {
@@ -50,6 +53,11 @@ pub trait Worker: Sized + Send + 'static {
     fn config(&self) -> WorkerConfig {
         WorkerConfig::default()
     }
+    /// What [`WorkerContext::send_to_handler`] does when the channel to the handler is full.
+    /// Default is [`BackpressurePolicy::Block`].
+    fn backpressure(&self) -> BackpressurePolicy {
+        BackpressurePolicy::default()
+    }
 }
 
 pub struct WorkerSendRequest<W: Worker> {
@@ -78,6 +86,22 @@ impl Default for WorkerConfig {
         }
     }
 }
+
+/// What to do with a message received by the transport when the handler is not keeping up, i.e.
+/// its channel is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Wait for the handler, and stop receiving in the meantime
+    #[default]
+    Block,
+    /// Drop the notifications, and answer the requests with a
+    /// [rate limited](crate::model::ErrorCode::RATE_LIMITED) error so the peer isn't left
+    /// waiting
+    Drop,
+    /// Fail at once
+    Error,
+}
+
 pub enum WorkerAdapter {}
 
 impl<W: Worker> IntoTransport<W::Role, W::Error, WorkerAdapter> for W {
@@ -95,6 +119,7 @@ impl<W: Worker> WorkerTransport<W> {
     }
     pub fn spawn_with_ct(worker: W, transport_task_ct: CancellationToken) -> Self {
         let config = worker.config();
+        let backpressure = worker.backpressure();
         let worker_name = config.name;
         let (to_transport_tx, from_handler_rx) =
             tokio::sync::mpsc::channel::<WorkerSendRequest<W>>(config.channel_buffer_capacity);
@@ -102,8 +127,10 @@ impl<W: Worker> WorkerTransport<W> {
             tokio::sync::mpsc::channel::<RxJsonRpcMessage<W::Role>>(config.channel_buffer_capacity);
         let context = WorkerContext {
             to_handler_tx,
+            to_transport_tx: to_transport_tx.downgrade(),
             from_handler_rx,
             cancellation_token: transport_task_ct.clone(),
+            backpressure,
         };
 
         let join_handle = tokio::spawn(async move {
@@ -151,17 +178,67 @@ pub struct WorkerContext<W: Worker> {
     pub to_handler_tx: tokio::sync::mpsc::Sender<RxJsonRpcMessage<W::Role>>,
     pub from_handler_rx: tokio::sync::mpsc::Receiver<WorkerSendRequest<W>>,
     pub cancellation_token: CancellationToken,
+    /// Where the answers to the dropped requests are queued, as if the handler sent them. It's
+    /// weak so the handler still terminates the worker by dropping its sender.
+    to_transport_tx: tokio::sync::mpsc::WeakSender<WorkerSendRequest<W>>,
+    backpressure: BackpressurePolicy,
 }
 
 impl<W: Worker> WorkerContext<W> {
+    /// Send a message to the handler, following the [`BackpressurePolicy`] if its channel is full.
+    ///
+    /// With [`BackpressurePolicy::Error`], a full channel is a fatal [`Worker::err_closed`] error.
+    /// With [`BackpressurePolicy::Drop`], a dropped request is answered with an error, which the
+    /// worker receives from [`WorkerContext::recv_from_handler`] like any response.
     pub async fn send_to_handler(
         &mut self,
         item: RxJsonRpcMessage<W::Role>,
     ) -> Result<(), WorkerQuitReason<W::Error>> {
-        self.to_handler_tx
-            .send(item)
-            .await
-            .map_err(|_| WorkerQuitReason::HandlerTerminated)
+        use tokio::sync::mpsc::error::TrySendError;
+        if self.backpressure == BackpressurePolicy::Block {
+            return self
+                .to_handler_tx
+                .send(item)
+                .await
+                .map_err(|_| WorkerQuitReason::HandlerTerminated);
+        }
+        match self.to_handler_tx.try_send(item) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Closed(_)) => Err(WorkerQuitReason::HandlerTerminated),
+            Err(TrySendError::Full(_)) if self.backpressure == BackpressurePolicy::Error => Err(
+                WorkerQuitReason::fatal(W::err_closed(), "send to full handler channel"),
+            ),
+            Err(TrySendError::Full(item)) => {
+                self.drop_message(item);
+                Ok(())
+            }
+        }
+    }
+
+    fn drop_message(&self, item: RxJsonRpcMessage<W::Role>) {
+        let Some((method, id)) = item
+            .request_method()
+            .map(str::to_owned)
+            .zip(item.id().cloned())
+        else {
+            // don't log the content, it may be sensitive
+            tracing::warn!("handler channel full, message dropped");
+            return;
+        };
+        tracing::warn!(%method, %id, "handler channel full, request dropped");
+        let Some(to_transport_tx) = self.to_transport_tx.upgrade() else {
+            return;
+        };
+        let error = ErrorData::rate_limited("too many pending messages, request dropped", None);
+        let (responder, _) = tokio::sync::oneshot::channel();
+        let answer = WorkerSendRequest {
+            message: TxJsonRpcMessage::<W::Role>::error(error, id),
+            responder,
+        };
+        // the worker may be the one draining this channel, so don't wait for it here
+        tokio::spawn(async move {
+            let _ = to_transport_tx.send(answer).await;
+        });
     }
 
     pub async fn recv_from_handler(
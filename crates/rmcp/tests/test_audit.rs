@@ -0,0 +1,173 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    handler::server::audit::{AuditRecord, AuditService, FileAuditSink},
+    model::{CallToolRequestParam, CallToolResult, Content, NumberOrString, PingRequest},
+    service::RequestContext,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+mod common;
+use common::calculator::Calculator;
+
+fn call(name: &'static str, arguments: serde_json::Value) -> CallToolRequestParam {
+    CallToolRequestParam {
+        name: name.into(),
+        arguments: arguments.as_object().cloned(),
+    }
+}
+
+#[tokio::test]
+async fn test_every_request_is_audited() -> anyhow::Result<()> {
+    let records = Arc::new(Mutex::new(Vec::<AuditRecord>::new()));
+    let server = AuditService::new(Calculator::new(), {
+        let records = records.clone();
+        move |record| {
+            records.lock().unwrap().push(record);
+            std::future::ready(())
+        }
+    });
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(server.serve(server_transport), ().serve(client_transport));
+    let (server, client) = (server?, client?);
+
+    client.list_tools(Default::default()).await?;
+    // the calculator doesn't route its tools, so the calls fail
+    assert!(
+        client
+            .call_tool(call("sum", json!({ "a": 1, "b": 2 })))
+            .await
+            .is_err()
+    );
+    client.cancel().await?;
+    server.cancel().await?;
+
+    let records = records.lock().unwrap();
+    let methods: Vec<_> = records.iter().map(|r| r.method.as_str()).collect();
+    assert_eq!(methods, ["initialize", "tools/list", "tools/call"]);
+    assert!(records[0].success);
+    assert!(records[1].success);
+    assert!(!records[2].success);
+    assert!(records.iter().all(|record| record.principal.is_none()));
+    // the ids of the client requests
+    assert!(matches!(records[1].request_id, NumberOrString::Number(_)));
+    assert_ne!(records[1].request_id, records[2].request_id);
+
+    let params = json!({ "name": "sum", "arguments": { "a": 1, "b": 2 } });
+    let expected: [u8; 32] = Sha256::digest(params.to_string().as_bytes()).into();
+    assert_eq!(records[2].arguments_hash, expected);
+    assert_ne!(records[1].arguments_hash, records[2].arguments_hash);
+    Ok(())
+}
+
+/// Never answers a ping
+struct Stuck;
+
+impl ServerHandler for Stuck {
+    async fn ping(&self, _context: RequestContext<RoleServer>) -> Result<(), rmcp::ErrorData> {
+        std::future::pending().await
+    }
+}
+
+#[tokio::test]
+async fn test_dropped_request_is_audited() -> anyhow::Result<()> {
+    let (records_tx, mut records) = tokio::sync::mpsc::unbounded_channel();
+    // the timeout drops the audited handling of the ping
+    let server = AuditService::new(Stuck, move |record| {
+        let _ = records_tx.send(record);
+        std::future::ready(())
+    })
+    .with_timeout(Duration::from_millis(50));
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(server.serve(server_transport), ().serve(client_transport));
+    let (server, client) = (server?, client?);
+
+    assert!(
+        client
+            .send_request(PingRequest::default().into())
+            .await
+            .is_err()
+    );
+    let initialize = records.recv().await.unwrap();
+    assert!(initialize.success);
+    let ping = tokio::time::timeout(Duration::from_secs(1), records.recv())
+        .await?
+        .unwrap();
+    assert_eq!(ping.method, "ping");
+    assert!(!ping.success);
+
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(())
+}
+
+/// Every tool fails
+struct FailingTools;
+
+impl ServerHandler for FailingTools {
+    async fn call_tool(
+        &self,
+        _request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        Ok(CallToolResult::error(vec![Content::text("failed")]))
+    }
+}
+
+#[tokio::test]
+async fn test_failed_tool_call_is_audited_as_failure() -> anyhow::Result<()> {
+    let (records_tx, mut records) = tokio::sync::mpsc::unbounded_channel();
+    let server = AuditService::new(FailingTools, move |record| {
+        let _ = records_tx.send(record);
+        std::future::ready(())
+    });
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(server.serve(server_transport), ().serve(client_transport));
+    let (server, client) = (server?, client?);
+
+    let result = client.call_tool(call("fail", json!({}))).await?;
+    assert_eq!(result.is_error, Some(true));
+    let initialize = records.recv().await.unwrap();
+    assert!(initialize.success);
+    let call = records.recv().await.unwrap();
+    assert_eq!(call.method, "tools/call");
+    assert!(!call.success);
+
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_audit_sink() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("rmcp-audit-{}.ndjson", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let server = AuditService::new(Calculator::new(), FileAuditSink::open(&path)?);
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(server.serve(server_transport), ().serve(client_transport));
+    let (server, client) = (server?, client?);
+    client
+        .call_tool(call("sum", json!({ "a": 1, "password": "hunter2" })))
+        .await
+        .ok();
+    client.cancel().await?;
+    server.cancel().await?;
+
+    let content = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    assert!(!content.contains("hunter2"));
+    let lines: Vec<serde_json::Value> = content
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["method"], "tools/call");
+    assert_eq!(lines[1]["arguments_hash"].as_str().unwrap().len(), 64);
+    assert!(lines[1]["timestamp"].is_string());
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use rmcp::{
+    RoleClient, RoleServer, ServerHandler,
+    model::{
+        CallToolRequestParam, CallToolResult, Content, JsonObject, ListToolsResult,
+        PaginatedRequestParam, Tool, ToolListChangedNotification,
+    },
+    service::{CachingPeer, RequestContext, RunningService},
+};
+use rmcp_test_macros::mcp_integration_test;
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, Default)]
+struct Counted {
+    list_calls: Arc<AtomicUsize>,
+}
+
+impl Counted {
+    fn list_calls(&self) -> usize {
+        self.list_calls.load(Ordering::SeqCst)
+    }
+}
+
+impl ServerHandler for Counted {
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        let calls = self.list_calls.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(ListToolsResult::with_all_items(vec![Tool::new(
+            format!("tool_{calls}"),
+            "",
+            JsonObject::new(),
+        )]))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        Ok(CallToolResult::success(vec![Content::text(request.name)]))
+    }
+}
+
+fn tool_name(result: &ListToolsResult) -> &str {
+    &result.tools[0].name
+}
+
+#[mcp_integration_test(server = Counted::default())]
+async fn test_list_tools_is_cached(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, Counted>,
+) -> anyhow::Result<()> {
+    let handler = server.service();
+    let tools = CachingPeer::new(client.peer().clone());
+
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_1");
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_1");
+    assert_eq!(handler.list_calls(), 1);
+    // the calls aren't cached
+    tools
+        .call_tool(CallToolRequestParam {
+            name: "tool_1".into(),
+            arguments: None,
+        })
+        .await?;
+    tools
+        .call_tool(CallToolRequestParam {
+            name: "tool_1".into(),
+            arguments: None,
+        })
+        .await?;
+
+    tools.invalidate_tools();
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_2");
+
+    // the notification handlers are all called before the test task is woken up
+    let changed = Arc::new(Notify::new());
+    let handle = client.on_notification({
+        let changed = changed.clone();
+        move |_: ToolListChangedNotification| changed.notify_one()
+    });
+    server.peer().notify_tool_list_changed().await?;
+    tokio::time::timeout(Duration::from_secs(5), changed.notified()).await?;
+    handle.cancel();
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_3");
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_3");
+    assert_eq!(handler.list_calls(), 3);
+    Ok(())
+}
+
+#[mcp_integration_test(server = Counted::default())]
+async fn test_cached_tools_expire(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, Counted>,
+) -> anyhow::Result<()> {
+    let handler = server.service();
+    let tools = CachingPeer::new(client.peer().clone()).with_ttl(Duration::from_millis(50));
+
+    tools.list_tools(None).await?;
+    tools.list_tools(None).await?;
+    assert_eq!(handler.list_calls(), 1);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_2");
+    // a page after the first one is always requested
+    tools
+        .list_tools(Some(PaginatedRequestParam {
+            cursor: Some("next".into()),
+        }))
+        .await?;
+    assert_eq!(handler.list_calls(), 3);
+    Ok(())
+}
+
+#[mcp_integration_test(server = Counted::default())]
+async fn test_cached_tools_by_page_size(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, Counted>,
+) -> anyhow::Result<()> {
+    let handler = server.service();
+    let tools = CachingPeer::new(client.peer().clone());
+
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_1");
+    // a page of another size isn't the cached one
+    assert_eq!(
+        tool_name(&tools.list_tools_with_page_size(None, Some(1)).await?),
+        "tool_2"
+    );
+    assert_eq!(
+        tool_name(&tools.list_tools_with_page_size(None, Some(1)).await?),
+        "tool_2"
+    );
+    assert_eq!(
+        tool_name(&tools.list_tools_with_page_size(None, Some(2)).await?),
+        "tool_3"
+    );
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_1");
+    assert_eq!(handler.list_calls(), 3);
+
+    // all the sizes are invalidated at once
+    tools.invalidate_tools();
+    assert_eq!(
+        tool_name(&tools.list_tools_with_page_size(None, Some(1)).await?),
+        "tool_4"
+    );
+    assert_eq!(tool_name(&tools.list_tools(None).await?), "tool_5");
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use rmcp::{
+    RoleClient, RoleServer, ServerHandler,
+    model::{ClientInfo, Implementation},
+    service::Peer,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+struct Server;
+
+impl ServerHandler for Server {}
+
+fn embedding_app() -> ClientInfo {
+    ClientInfo {
+        client_info: Implementation::new("embedding-app", "4.2.0"),
+        ..Default::default()
+    }
+}
+
+#[mcp_integration_test(server = Server, client = embedding_app())]
+async fn test_custom_client_info(_client: Peer<RoleClient>, server: Peer<RoleServer>) {
+    let peer_info = server.peer_info().expect("initialized");
+    assert_eq!(peer_info.client_info.name, "embedding-app");
+    assert_eq!(peer_info.client_info.version, "4.2.0");
+}
@@ -139,6 +139,15 @@ fn test_reference_convenience_methods() {
         Some("file://path/to/resource")
     );
     assert_eq!(resource_ref.as_prompt_name(), None);
+
+    let tool_ref = Reference::for_tool("convert");
+    assert_eq!(tool_ref.reference_type(), "ref/tool");
+    assert_eq!(tool_ref.as_tool_name(), Some("convert"));
+    assert_eq!(tool_ref.as_prompt_name(), None);
+    assert_eq!(
+        serde_json::to_value(&tool_ref).unwrap(),
+        json!({ "type": "ref/tool", "name": "convert" })
+    );
 }
 
 #[test]
@@ -216,3 +225,63 @@ fn test_mcp_schema_compliance() {
     // Verify type tag is correct
     assert_eq!(parsed["ref"]["type"].as_str().unwrap(), "ref/resource");
 }
+
+#[test]
+fn test_completion_ranker_ordering() {
+    use rmcp::handler::server::completion::CompletionRanker;
+
+    let candidates = [
+        "JavaScript",
+        "Java",
+        "Rust",
+        "TypeScript",
+        "Python",
+        "Memory Safety",
+    ];
+    let ranker = CompletionRanker::new();
+
+    let values = |input: &str| -> Vec<String> {
+        ranker
+            .rank(input, &candidates)
+            .into_iter()
+            .map(|candidate| candidate.value)
+            .collect()
+    };
+    // exact match first, then prefix matches, closest first
+    assert_eq!(values("java"), vec!["Java", "JavaScript"]);
+    assert_eq!(values("script"), vec!["JavaScript", "TypeScript"]);
+    assert_eq!(values("ms"), vec!["Memory Safety"]);
+    assert_eq!(values("rs")[0], "Rust");
+    // typos are caught by edit distance
+    assert_eq!(values("pyhton"), vec!["Python"]);
+    assert!(values("zzz").is_empty());
+    assert_eq!(values("").len(), candidates.len());
+
+    let ranked = ranker.rank("java", &candidates);
+    assert_eq!(ranked[0].score, 1.0);
+    assert!(ranked[0].score > ranked[1].score);
+}
+
+#[test]
+fn test_completion_info_from_ranked() {
+    use rmcp::handler::server::completion::CompletionRanker;
+
+    let candidates: Vec<String> = (0..150).map(|i| format!("item{i}")).collect();
+    let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+    let ranked = CompletionRanker::new().rank("item", &candidates);
+
+    let info = CompletionInfo::from_ranked(ranked.clone(), 5);
+    assert_eq!(info.values.len(), 5);
+    assert_eq!(info.total, Some(150));
+    assert_eq!(info.has_more, Some(true));
+
+    // capped to the protocol maximum
+    let info = CompletionInfo::from_ranked(ranked, 500);
+    assert_eq!(info.values.len(), CompletionInfo::MAX_VALUES);
+    assert!(info.validate().is_ok());
+
+    let info =
+        CompletionInfo::from_ranked(CompletionRanker::new().rank("item1", &candidates[..3]), 10);
+    assert_eq!(info.values[0], "item1");
+    assert_eq!(info.has_more, Some(false));
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use rmcp::{
+    RoleClient, RoleServer, ServerHandler,
+    handler::server::completion::CachedCompletionService,
+    model::{
+        ArgumentInfo, CompleteRequestParam, CompleteResult, CompletionContext, CompletionInfo,
+        Reference,
+    },
+    service::{RequestContext, RunningService},
+};
+use rmcp_test_macros::mcp_integration_test;
+
+#[derive(Debug, Default)]
+struct Languages {
+    calls: AtomicUsize,
+}
+
+impl ServerHandler for Languages {
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, rmcp::ErrorData> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let values = ["python", "rust"]
+            .into_iter()
+            .filter(|value| value.starts_with(&request.argument.value))
+            .map(String::from)
+            .collect();
+        Ok(CompleteResult {
+            completion: CompletionInfo {
+                values,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+fn request(reference: Reference, argument: &str, value: &str) -> CompleteRequestParam {
+    CompleteRequestParam {
+        r#ref: reference,
+        argument: ArgumentInfo {
+            name: argument.into(),
+            value: value.into(),
+        },
+        context: None,
+    }
+}
+
+#[mcp_integration_test(server = CachedCompletionService::new(Languages::default()))]
+async fn test_completions_are_cached(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, CachedCompletionService<Languages>>,
+) -> anyhow::Result<()> {
+    let calls = || server.service().inner().calls.load(Ordering::SeqCst);
+
+    let result = client
+        .complete(request(Reference::for_prompt("code"), "language", "p"))
+        .await?;
+    assert_eq!(result.completion.values, ["python"]);
+    client
+        .complete(request(Reference::for_prompt("code"), "language", "p"))
+        .await?;
+    assert_eq!(calls(), 1);
+    // the value is part of the key, the completion of another prefix is another completion
+    let result = client
+        .complete(request(Reference::for_prompt("code"), "language", "r"))
+        .await?;
+    assert_eq!(result.completion.values, ["rust"]);
+    assert_eq!(calls(), 2);
+    // so are the reference, the argument and the context
+    client
+        .complete(request(Reference::for_prompt("code"), "framework", "p"))
+        .await?;
+    client
+        .complete(request(
+            Reference::for_resource("code://{language}"),
+            "language",
+            "p",
+        ))
+        .await?;
+    client
+        .complete(CompleteRequestParam {
+            context: Some(CompletionContext::with_arguments(HashMap::from([(
+                "framework".to_owned(),
+                "django".to_owned(),
+            )]))),
+            ..request(Reference::for_prompt("code"), "language", "p")
+        })
+        .await?;
+    assert_eq!(calls(), 5);
+
+    server.service().invalidate(&Reference::for_prompt("code"));
+    client
+        .complete(request(Reference::for_prompt("code"), "language", "p"))
+        .await?;
+    client
+        .complete(request(
+            Reference::for_resource("code://{language}"),
+            "language",
+            "p",
+        ))
+        .await?;
+    assert_eq!(calls(), 6);
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = CachedCompletionService::new(Languages::default())
+        .with_ttl(Duration::from_millis(50))
+)]
+async fn test_cached_completions_expire(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, CachedCompletionService<Languages>>,
+) -> anyhow::Result<()> {
+    let complete = || client.complete(request(Reference::for_prompt("code"), "language", ""));
+    complete().await?;
+    complete().await?;
+    assert_eq!(server.service().inner().calls.load(Ordering::SeqCst), 1);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    complete().await?;
+    assert_eq!(server.service().inner().calls.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = CachedCompletionService::new(Languages::default()).with_capacity(2)
+)]
+async fn test_cache_is_bounded(
+    client: &RunningService<RoleClient, ()>,
+    server: &RunningService<RoleServer, CachedCompletionService<Languages>>,
+) -> anyhow::Result<()> {
+    let calls = || server.service().inner().calls.load(Ordering::SeqCst);
+
+    let complete =
+        |value: &str| client.complete(request(Reference::for_prompt("code"), "language", value));
+    for value in ["", "p", "r"] {
+        complete(value).await?;
+    }
+    assert_eq!(calls(), 3);
+    // the oldest completion was evicted, the newest ones are kept
+    complete("r").await?;
+    complete("p").await?;
+    assert_eq!(calls(), 3);
+    complete("").await?;
+    assert_eq!(calls(), 4);
+    Ok(())
+}
+
+#[cfg(feature = "tower")]
+#[tokio::test]
+async fn test_layer_shares_the_cache() -> anyhow::Result<()> {
+    use rmcp::{ServiceExt, handler::server::completion::CachedCompletionLayer};
+    use tower_layer::Layer;
+
+    let cache = CachedCompletionLayer::new();
+    let mut sessions = vec![];
+    for _ in 0..2 {
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let (server, client) = tokio::join!(
+            cache.layer(Languages::default()).serve(server_transport),
+            ().serve(client_transport)
+        );
+        sessions.push((server?, client?));
+    }
+    let calls = |sessions: &[(
+        RunningService<RoleServer, CachedCompletionService<Languages>>,
+        _,
+    )]| {
+        sessions
+            .iter()
+            .map(|(server, _)| server.service().inner().calls.load(Ordering::SeqCst))
+            .sum::<usize>()
+    };
+
+    for (_, client) in &sessions {
+        client
+            .complete(request(Reference::for_prompt("code"), "language", "p"))
+            .await?;
+    }
+    // the second session got the completion of the first one
+    assert_eq!(calls(&sessions), 1);
+
+    cache.invalidate(&Reference::for_prompt("code"));
+    sessions[1]
+        .1
+        .complete(request(Reference::for_prompt("code"), "language", "p"))
+        .await?;
+    assert_eq!(calls(&sessions), 2);
+
+    for (server, client) in sessions {
+        client.cancel().await?;
+        server.cancel().await?;
+    }
+    Ok(())
+}
@@ -24,6 +24,31 @@ pub struct ChatRequest {
     pub messages: Vec<ChatMessage>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Address {
+    pub street: String,
+    pub zip: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ShipRequest {
+    pub from: Address,
+    pub to: Address,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Paging {
+    pub page: u32,
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchRequest {
+    pub query: String,
+    #[serde(flatten)]
+    pub paging: Paging,
+}
+
 #[derive(Clone, Default)]
 pub struct Demo;
 
@@ -41,6 +66,18 @@ impl Demo {
         let content = Content::json(chat_request.0)?;
         Ok(CallToolResult::success(vec![content]))
     }
+
+    #[tool(description = "Ship a parcel")]
+    async fn ship(&self, request: Parameters<ShipRequest>) -> Result<CallToolResult, McpError> {
+        let content = Content::json(request.0)?;
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Search")]
+    async fn search(&self, request: Parameters<SearchRequest>) -> Result<CallToolResult, McpError> {
+        let content = Content::json(request.0)?;
+        Ok(CallToolResult::success(vec![content]))
+    }
 }
 
 #[test]
@@ -64,3 +101,51 @@ fn test_complex_schema() {
     assert_eq!(enum_number, 4);
     println!("{}", serde_json::to_string_pretty(&input_schema).unwrap());
 }
+
+#[test]
+fn test_shared_type_is_a_definition() {
+    let input_schema = Demo::ship_tool_attr().input_schema;
+    let properties = input_schema["properties"].as_object().unwrap();
+    assert_eq!(properties["from"]["$ref"], "#/definitions/Address");
+    assert_eq!(properties["to"]["$ref"], "#/definitions/Address");
+    let definitions = input_schema["definitions"].as_object().unwrap();
+    assert_eq!(definitions.keys().collect::<Vec<_>>(), ["Address"]);
+}
+
+#[cfg(feature = "schema-validation")]
+#[test]
+fn test_validate_input_with_shared_type() {
+    let tool = Demo::ship_tool_attr();
+    let args = |value: serde_json::Value| value.as_object().cloned().unwrap();
+    let valid = args(serde_json::json!({
+        "from": { "street": "1 Main St", "zip": null },
+        "to": { "street": "2 Main St", "zip": "10001" }
+    }));
+    assert_eq!(tool.validate_input(&valid), Ok(()));
+    let invalid = args(serde_json::json!({
+        "from": { "street": "1 Main St" },
+        "to": { "zip": "10001" }
+    }));
+    let errors = tool.validate_input(&invalid).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "/to");
+}
+
+#[test]
+fn test_flattened_fields_are_direct_properties() {
+    let input_schema = Demo::search_tool_attr().input_schema;
+    assert_eq!(input_schema["type"], "object");
+    let properties = input_schema["properties"].as_object().unwrap();
+    let mut names = properties.keys().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["page", "per_page", "query"]);
+    let mut required = input_schema["required"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|name| name.as_str().unwrap())
+        .collect::<Vec<_>>();
+    required.sort();
+    assert_eq!(required, ["page", "query"]);
+    assert!(input_schema.get("definitions").is_none());
+}
@@ -0,0 +1,33 @@
+//cargo test --test test_elicit_safe --features "server elicitation"
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct UserProfile {
+    #[allow(dead_code)]
+    name: String,
+}
+
+/// Not recognized by the macro, only the schema tells it's a string
+#[derive(Debug, Deserialize, JsonSchema)]
+struct UserName(#[allow(dead_code)] String);
+
+rmcp::elicit_safe!(UserProfile, UserName,);
+
+#[test]
+fn test_object_schema_is_elicitation_safe() {
+    rmcp::service::assert_elicitation_safe::<UserProfile>();
+}
+
+#[test]
+#[should_panic(expected = "is marked with `elicit_safe!` but its schema is not an object")]
+fn test_newtype_is_not_elicitation_safe() {
+    rmcp::service::assert_elicitation_safe::<UserName>();
+}
+
+#[test]
+fn test_primitives_are_rejected_at_compile_time() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/elicit_safe_*.rs");
+}
@@ -470,6 +470,8 @@ async fn test_elicitation_structured_schemas() {
 // Typed elicitation tests using the API with schemars
 #[cfg(feature = "schemars")]
 mod typed_elicitation_tests {
+    use rmcp_test_macros::mcp_integration_test;
+
     use super::*;
 
     /// Simple user confirmation with reason
@@ -528,6 +530,67 @@ mod typed_elicitation_tests {
     // Mark types as safe for elicitation (they generate object schemas)
     rmcp::elicit_safe!(UserConfirmation, UserProfile, UserPreferences);
 
+    /// Confirms every elicitation, with a reason
+    struct ConfirmingClient;
+
+    impl rmcp::ClientHandler for ConfirmingClient {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_elicitation().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn create_elicitation(
+            &self,
+            request: CreateElicitationRequestParam,
+            _context: RequestContext<RoleClient>,
+        ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
+            Ok(CreateElicitationResult {
+                action: ElicitationAction::Accept,
+                content: Some(json!({ "confirmed": true, "reason": request.message })),
+            })
+        }
+    }
+
+    struct Server;
+
+    impl rmcp::ServerHandler for Server {}
+
+    /// Test a typed elicitation answered by a client handler
+    #[mcp_integration_test(server = Server, client = ConfirmingClient)]
+    async fn test_elicitation_answered_by_client_handler(
+        _client: Peer<RoleClient>,
+        server: Peer<RoleServer>,
+    ) -> anyhow::Result<()> {
+        let confirmation = server.elicit::<UserConfirmation>("Proceed?").await?;
+        assert_eq!(
+            confirmation,
+            Some(UserConfirmation {
+                confirmed: true,
+                reason: Some("Proceed?".into()),
+            })
+        );
+        Ok(())
+    }
+
+    /// Test that a client handler without elicitation support cancels the requests
+    #[mcp_integration_test(server = Server)]
+    async fn test_elicitation_cancelled_by_default(
+        _client: Peer<RoleClient>,
+        server: Peer<RoleServer>,
+    ) -> anyhow::Result<()> {
+        let result = server
+            .create_elicitation(CreateElicitationRequestParam {
+                message: "Proceed?".into(),
+                requested_schema: Default::default(),
+            })
+            .await?;
+        assert_eq!(result.action, ElicitationAction::Cancel);
+        assert_eq!(result.content, None);
+        Ok(())
+    }
+
     /// Test automatic schema generation for simple types
     #[tokio::test]
     async fn test_typed_elicitation_simple_schema() {
@@ -1473,3 +1536,458 @@ async fn test_elicitation_examples_compile() {
         _assert_safe::<UserProfile>();
     }
 }
+
+#[cfg(all(feature = "schemars", feature = "schema-validation"))]
+mod validation {
+    use std::sync::Mutex;
+
+    use rmcp::{ClientHandler, ServerHandler, model::*, service::*};
+    use rmcp_test_macros::mcp_integration_test;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Contact {
+        #[schemars(email)]
+        email: String,
+    }
+
+    rmcp::elicit_safe!(Contact);
+
+    fn email_schema() -> JsonObject {
+        json!({
+            "type": "object",
+            "properties": {
+                "email": { "type": "string", "format": "email" }
+            },
+            "required": ["email"]
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    #[test]
+    fn test_validator_requires_email_format() {
+        let validator = ElicitationValidator::new(&email_schema());
+        assert!(
+            validator
+                .validate(&json!({ "email": "alice@example.com" }))
+                .is_ok()
+        );
+
+        let malformed = json!({ "email": "not an email" });
+        match validator.validate(&malformed) {
+            Err(ElicitationError::ParseError { error, data }) => {
+                assert!(error.to_string().contains("/email"), "{error}");
+                assert_eq!(data, malformed);
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+
+        assert!(matches!(
+            validator.validate(&json!({})),
+            Err(ElicitationError::ParseError { .. })
+        ));
+    }
+
+    #[derive(Clone)]
+    struct Server;
+
+    impl ServerHandler for Server {}
+
+    /// A client answering every elicitation with the same content, without validating it
+    struct RawClient {
+        content: serde_json::Value,
+        requested_schema: Mutex<Option<JsonObject>>,
+    }
+
+    impl RawClient {
+        fn new(content: serde_json::Value) -> Self {
+            Self {
+                content,
+                requested_schema: Default::default(),
+            }
+        }
+    }
+
+    impl ClientHandler for RawClient {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_elicitation().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn create_elicitation(
+            &self,
+            request: CreateElicitationRequestParam,
+            _context: RequestContext<RoleClient>,
+        ) -> Result<CreateElicitationResult, ErrorData> {
+            *self.requested_schema.lock().unwrap() = Some(request.requested_schema);
+            Ok(CreateElicitationResult {
+                action: ElicitationAction::Accept,
+                content: Some(self.content.clone()),
+            })
+        }
+    }
+
+    #[mcp_integration_test(
+        server = Server,
+        client = RawClient::new(json!({ "email": "not an email" }))
+    )]
+    async fn test_elicit_accepts_malformed_response(
+        client: &RunningService<RoleClient, RawClient>,
+        server: &RunningService<RoleServer, Server>,
+    ) -> anyhow::Result<()> {
+        // the response deserializes into the type, only the schema catches it
+        let contact = server.elicit::<Contact>("What's your email?").await?;
+        assert_eq!(contact.unwrap().email, "not an email");
+        let requested_schema = client.service().requested_schema.lock().unwrap().take();
+        assert_eq!(
+            requested_schema.unwrap()["properties"]["email"]["format"],
+            json!("email")
+        );
+        Ok(())
+    }
+
+    #[mcp_integration_test(
+        server = Server,
+        client = RawClient::new(json!({ "email": "not an email" }))
+    )]
+    async fn test_elicit_validated_rejects_malformed_response(
+        _client: Peer<RoleClient>,
+        server: Peer<RoleServer>,
+    ) -> anyhow::Result<()> {
+        match server
+            .elicit_validated::<Contact>("What's your email?")
+            .await
+        {
+            Err(ElicitationError::ParseError { data, .. }) => {
+                assert_eq!(data, json!({ "email": "not an email" }))
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[mcp_integration_test(
+        server = Server,
+        client = RawClient::new(json!({ "email": "alice@example.com" }))
+    )]
+    async fn test_elicit_validated_accepts_valid_response(
+        _client: Peer<RoleClient>,
+        server: Peer<RoleServer>,
+    ) -> anyhow::Result<()> {
+        let contact = server
+            .elicit_validated::<Contact>("What's your email?")
+            .await?;
+        assert_eq!(contact.unwrap().email, "alice@example.com");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "schemars")]
+mod queue {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use rmcp::{ClientHandler, ServerHandler, model::*, service::*};
+    use rmcp_test_macros::mcp_integration_test;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Answer {
+        answer: String,
+    }
+
+    rmcp::elicit_safe!(Answer);
+
+    #[derive(Clone)]
+    struct Server;
+
+    impl ServerHandler for Server {}
+
+    /// A client taking its time to answer, and recording how many elicitations it had at once
+    #[derive(Default)]
+    struct SlowClient {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        answered: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl ClientHandler for SlowClient {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_elicitation().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn create_elicitation(
+            &self,
+            request: CreateElicitationRequestParam,
+            _context: RequestContext<RoleClient>,
+        ) -> Result<CreateElicitationResult, ErrorData> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            self.answered.lock().unwrap().push(request.message.clone());
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(CreateElicitationResult {
+                action: ElicitationAction::Accept,
+                content: Some(json!({ "answer": request.message })),
+            })
+        }
+    }
+
+    #[mcp_integration_test(server = Server, client = SlowClient::default())]
+    async fn test_queue_serializes_concurrent_elicitations(
+        client: &RunningService<RoleClient, SlowClient>,
+        server: &RunningService<RoleServer, Server>,
+    ) -> anyhow::Result<()> {
+        let client = client.service();
+
+        let queue = ElicitationQueue::new(server.peer().clone());
+        let first = queue.clone();
+        let first = tokio::spawn(async move { first.elicit::<Answer>("first").await });
+        // make sure the first one is queued first
+        tokio::task::yield_now().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let second = queue.elicit::<Answer>("second").await?;
+
+        assert_eq!(first.await??.unwrap().answer, "first");
+        assert_eq!(second.unwrap().answer, "second");
+        assert_eq!(client.max_in_flight.load(Ordering::SeqCst), 1);
+        assert_eq!(*client.answered.lock().unwrap(), vec!["first", "second"]);
+
+        // without the queue, the client gets both at once
+        let peer = server.peer().clone();
+        let (first, second) = tokio::join!(
+            peer.elicit::<Answer>("first"),
+            peer.elicit::<Answer>("second")
+        );
+        first?;
+        second?;
+        assert_eq!(client.max_in_flight.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[mcp_integration_test(server = Server, client = SlowClient::default())]
+    async fn test_queue_survives_cancelled_waiter(
+        client: &RunningService<RoleClient, SlowClient>,
+        server: &RunningService<RoleServer, Server>,
+    ) -> anyhow::Result<()> {
+        let client = client.service();
+
+        let queue = ElicitationQueue::new(server.peer().clone());
+        let first = queue.clone();
+        let first = tokio::spawn(async move { first.elicit::<Answer>("first").await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        // gives up while waiting for the first one
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            queue.elicit::<Answer>("cancelled"),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        let third = queue.elicit::<Answer>("third").await?;
+
+        assert_eq!(first.await??.unwrap().answer, "first");
+        assert_eq!(third.unwrap().answer, "third");
+        assert_eq!(client.max_in_flight.load(Ordering::SeqCst), 1);
+        assert_eq!(*client.answered.lock().unwrap(), vec!["first", "third"]);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "schemars")]
+mod default_timeout {
+    use std::time::Duration;
+
+    use rmcp::{ClientHandler, ServerHandler, model::*, service::*};
+    use rmcp_test_macros::mcp_integration_test;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Answer {
+        #[allow(dead_code)]
+        answer: String,
+    }
+
+    rmcp::elicit_safe!(Answer);
+
+    #[derive(Clone)]
+    struct Server;
+
+    impl ServerHandler for Server {}
+
+    /// A client whose user never answers
+    #[derive(Clone)]
+    struct AbsentClient;
+
+    impl ClientHandler for AbsentClient {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_elicitation().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn create_elicitation(
+            &self,
+            _request: CreateElicitationRequestParam,
+            _context: RequestContext<RoleClient>,
+        ) -> Result<CreateElicitationResult, ErrorData> {
+            std::future::pending().await
+        }
+    }
+
+    #[mcp_integration_test(server = Server, client = AbsentClient)]
+    async fn test_default_elicitation_timeout(
+        _client: Peer<RoleClient>,
+        server: Peer<RoleServer>,
+    ) -> anyhow::Result<()> {
+        let peer = server.with_elicitation_timeout(Duration::from_millis(100));
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            peer.elicit::<Answer>("Anyone there?"),
+        )
+        .await?;
+        assert!(matches!(
+            result,
+            Err(ElicitationError::Service(ServiceError::Timeout { timeout }))
+                if timeout == Duration::from_millis(100)
+        ));
+
+        // an explicit timeout takes precedence
+        let result = peer
+            .elicit_with_timeout::<Answer>("Anyone there?", Some(Duration::from_millis(10)))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElicitationError::Service(ServiceError::Timeout { timeout }))
+                if timeout == Duration::from_millis(10)
+        ));
+        Ok(())
+    }
+}
+
+mod choice {
+    use std::sync::Mutex;
+
+    use rmcp::{ClientHandler, ServerHandler, model::*, service::*};
+    use rmcp_test_macros::mcp_integration_test;
+    use serde_json::json;
+
+    #[derive(Clone)]
+    struct Server;
+
+    impl ServerHandler for Server {}
+
+    /// A client whose user always picks the same option
+    struct Chooser {
+        choice: serde_json::Value,
+        request: Mutex<Option<CreateElicitationRequestParam>>,
+    }
+
+    impl Chooser {
+        fn new(choice: serde_json::Value) -> Self {
+            Self {
+                choice,
+                request: Default::default(),
+            }
+        }
+    }
+
+    impl ClientHandler for Chooser {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo {
+                capabilities: ClientCapabilities::builder().enable_elicitation().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn create_elicitation(
+            &self,
+            request: CreateElicitationRequestParam,
+            _context: RequestContext<RoleClient>,
+        ) -> Result<CreateElicitationResult, ErrorData> {
+            *self.request.lock().unwrap() = Some(request);
+            Ok(CreateElicitationResult {
+                action: ElicitationAction::Accept,
+                content: Some(json!({ "choice": self.choice })),
+            })
+        }
+    }
+
+    #[mcp_integration_test(server = Server, client = Chooser::new(json!(2)))]
+    async fn test_elicit_choice(
+        client: &RunningService<RoleClient, Chooser>,
+        server: &RunningService<RoleServer, Server>,
+    ) -> anyhow::Result<()> {
+        let options = ["red", "green", "blue", "yellow"];
+
+        let index = server
+            .peer()
+            .elicit_choice("Pick a color", &options)
+            .await?;
+        assert_eq!(index, Some(2));
+        let value = server
+            .peer()
+            .elicit_choice_value("Pick a color", &options)
+            .await?;
+        assert_eq!(value, Some("blue"));
+
+        let request = client.service().request.lock().unwrap().take().unwrap();
+        assert!(request.message.starts_with("Pick a color"));
+        assert!(request.message.contains("2: blue"));
+        assert_eq!(
+            serde_json::Value::Object(request.requested_schema),
+            json!({
+                "type": "object",
+                "properties": {
+                    "choice": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 3,
+                        "description": "The number of the chosen option:\n0: red\n1: green\n2: blue\n3: yellow",
+                    }
+                },
+                "required": ["choice"],
+            })
+        );
+        Ok(())
+    }
+
+    #[mcp_integration_test(server = Server, client = Chooser::new(json!(4)))]
+    async fn test_elicit_choice_out_of_range(
+        client: &RunningService<RoleClient, Chooser>,
+        server: &RunningService<RoleServer, Server>,
+    ) -> anyhow::Result<()> {
+        let result = server
+            .peer()
+            .elicit_choice("Pick a color", &["red", "green"])
+            .await;
+        assert!(matches!(
+            result,
+            Err(ElicitationError::ParseError { data, .. }) if data == json!({ "choice": 4 })
+        ));
+        assert!(client.service().request.lock().unwrap().take().is_some());
+
+        // nothing to choose from, nothing is asked
+        let result = server
+            .peer()
+            .elicit_choice("Pick a color", &[] as &[&str])
+            .await;
+        assert_eq!(result?, None);
+        assert!(client.service().request.lock().unwrap().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use bytes::Bytes;
+use http_body_util::Full;
+use jsonwebtoken::{EncodingKey, Header};
+use rmcp::transport::{
+    StreamableHttpServerConfig,
+    auth::jwt::{Algorithm, AuthClaims, DecodingKey, JwtAuthLayer},
+    streamable_http_server::{session::local::LocalSessionManager, tower::StreamableHttpService},
+};
+use serde_json::{Value, json};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const ISSUER_PRIVATE_KEY: &[u8] = include_bytes!("test_jwt_auth/issuer_private.pem");
+const ISSUER_PUBLIC_KEY: &[u8] = include_bytes!("test_jwt_auth/issuer_public.pem");
+const OTHER_PRIVATE_KEY: &[u8] = include_bytes!("test_jwt_auth/other_private.pem");
+
+fn sign_with(private_key: &[u8], claims: Value) -> String {
+    let key = EncodingKey::from_rsa_pem(private_key).unwrap();
+    jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &key).unwrap()
+}
+
+fn sign(claims: Value) -> String {
+    sign_with(ISSUER_PRIVATE_KEY, claims)
+}
+
+/// A token with any header, signed by the issuer
+fn sign_with_header(header: Value, claims: Value) -> String {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let signed = format!(
+        "{}.{}",
+        engine.encode(header.to_string()),
+        engine.encode(claims.to_string())
+    );
+    let key = EncodingKey::from_rsa_pem(ISSUER_PRIVATE_KEY).unwrap();
+    let signature = jsonwebtoken::crypto::sign(signed.as_bytes(), &key, Algorithm::RS256).unwrap();
+    format!("{signed}.{signature}")
+}
+
+/// A token with an empty signature
+fn unsigned(header: Value, claims: Value) -> String {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    format!(
+        "{}.{}.",
+        engine.encode(header.to_string()),
+        engine.encode(claims.to_string())
+    )
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn valid_claims() -> Value {
+    json!({
+        "sub": "alice",
+        "iss": "https://auth.example.com",
+        "aud": ["other", "mcp"],
+        "exp": now() + 600,
+        "scope": "tools:read tools:call",
+        "tenant": "acme",
+    })
+}
+
+fn auth() -> JwtAuthLayer {
+    JwtAuthLayer::new(
+        DecodingKey::from_rsa_pem(ISSUER_PUBLIC_KEY).unwrap(),
+        Algorithm::RS256,
+    )
+    .with_issuer("https://auth.example.com")
+    .with_audience("mcp")
+}
+
+#[test]
+fn test_valid_token_is_decoded() {
+    let claims = auth().verify(&sign(valid_claims())).unwrap();
+    assert_eq!(claims.sub.as_deref(), Some("alice"));
+    assert_eq!(claims.iss.as_deref(), Some("https://auth.example.com"));
+    assert!(claims.aud.as_ref().unwrap().contains("mcp"));
+    assert_eq!(claims.extra["tenant"], "acme");
+
+    let context = claims.clone().into_context();
+    assert_eq!(context.subject.as_deref(), Some("alice"));
+    assert!(context.has_scope("tools:call"));
+    assert_eq!(AuthClaims::from_context(&context).unwrap(), claims);
+}
+
+#[test]
+fn test_invalid_tokens_are_refused() {
+    let auth = auth();
+    let with = |patch: Value| {
+        let mut claims = valid_claims();
+        for (key, value) in patch.as_object().unwrap() {
+            if value.is_null() {
+                claims.as_object_mut().unwrap().remove(key);
+            } else {
+                claims[key] = value.clone();
+            }
+        }
+        sign(claims)
+    };
+
+    let refused = [
+        // signed by another key
+        sign_with(OTHER_PRIVATE_KEY, valid_claims()),
+        with(json!({ "exp": now() - 3600 })),
+        with(json!({ "exp": null })),
+        with(json!({ "nbf": now() + 3600 })),
+        with(json!({ "iss": "https://evil.example.com" })),
+        with(json!({ "iss": null })),
+        with(json!({ "aud": "other" })),
+        with(json!({ "aud": null })),
+        // the algorithm is the one of the layer, not the one of the token
+        sign_with_header(json!({ "alg": "none" }), valid_claims()),
+        sign_with_header(json!({ "alg": "RS512" }), valid_claims()),
+        unsigned(json!({ "alg": "none" }), valid_claims()),
+        unsigned(json!({ "alg": "None" }), valid_claims()),
+        // an HS256 token using the public key of the issuer as the secret
+        jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &valid_claims(),
+            &EncodingKey::from_secret(ISSUER_PUBLIC_KEY),
+        )
+        .unwrap(),
+        // an extension the token requires to be understood
+        sign_with_header(json!({ "alg": "RS256", "crit": ["exp"] }), valid_claims()),
+        "not.a.token".to_owned(),
+    ];
+    for token in refused {
+        assert!(auth.verify(&token).is_err(), "{token}");
+    }
+
+    // edited claims break the signature
+    let token = sign(valid_claims());
+    let mut parts: Vec<_> = token.split('.').map(str::to_owned).collect();
+    parts[1] = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(json!({ "sub": "mallory", "exp": now() + 600 }).to_string());
+    assert!(auth.verify(&parts.join(".")).is_err());
+
+    // within the leeway for the clock skew
+    assert!(auth.verify(&with(json!({ "exp": now() - 10 }))).is_ok());
+    // the audience is only checked when one is expected
+    let any_audience = JwtAuthLayer::new(
+        DecodingKey::from_rsa_pem(ISSUER_PUBLIC_KEY).unwrap(),
+        Algorithm::RS256,
+    );
+    assert!(
+        any_audience
+            .verify(&with(json!({ "aud": "other" })))
+            .is_ok()
+    );
+}
+
+#[derive(Clone)]
+struct Server;
+
+impl rmcp::ServerHandler for Server {}
+
+fn request(authorization: &str) -> http::Request<Full<Bytes>> {
+    http::Request::post("/mcp")
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .header("Authorization", authorization)
+        .body(Full::new(Bytes::from(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.1.0"}}}"#,
+        )))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_layer_guards_the_service() {
+    let mut service = auth().layer(StreamableHttpService::<Server, LocalSessionManager>::new(
+        || Ok(Server),
+        Default::default(),
+        StreamableHttpServerConfig {
+            stateful_mode: false,
+            ..Default::default()
+        },
+    ));
+
+    for authorization in ["Bearer expired.or.forged", "Basic YWxpY2U6c2VjcmV0"] {
+        let response = service.call(request(authorization)).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()["WWW-Authenticate"], "Bearer");
+    }
+
+    let token = sign(valid_claims());
+    let response = service
+        .call(request(&format!("Bearer {token}")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use rmcp::{
+    Peer, RoleClient, RoleServer, ServerHandler,
+    model::{
+        JsonObject, ListToolsResult, PaginatedRequestParam, ServerCapabilities, ServerInfo, Tool,
+    },
+    service::RequestContext,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+/// Lists the given tools
+struct ToolsServer(Vec<&'static str>);
+
+impl ServerHandler for ToolsServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        Ok(ListToolsResult::with_all_items(
+            self.0
+                .iter()
+                .map(|name| Tool::new(*name, "", Arc::new(JsonObject::new())))
+                .collect(),
+        ))
+    }
+}
+
+#[mcp_integration_test(server = ToolsServer(vec!["search", "read"]))]
+async fn test_merge_tools_from_peers(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    // the same server twice, so all its tools are listed by both
+    let peers = [client.clone(), client];
+
+    let results = ListToolsResult::merge_from_peers(&peers).await;
+    let indexes: Vec<_> = results.iter().map(|(index, _)| *index).collect();
+    assert_eq!(indexes, [0, 1]);
+    let results = results
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect::<Result<Vec<_>, _>>()?;
+    let merged = ListToolsResult::merge_all(results)?;
+    let names: Vec<_> = merged.tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(names, ["0_search", "0_read", "1_search", "1_read"]);
+    Ok(())
+}
@@ -557,3 +557,217 @@ async fn test_context_inclusion() -> anyhow::Result<()> {
     server_handle.await??;
     Ok(())
 }
+
+#[derive(Clone)]
+struct VersionedClient(ProtocolVersion);
+
+impl rmcp::ClientHandler for VersionedClient {
+    fn get_info(&self) -> ClientInfo {
+        ClientInfo {
+            protocol_version: self.0.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_protocol_version_negotiation() -> anyhow::Result<()> {
+    let version =
+        |s: &str| serde_json::from_value::<ProtocolVersion>(serde_json::json!(s)).unwrap();
+    let cases = [
+        (ProtocolVersion::V_2024_11_05, ProtocolVersion::V_2024_11_05),
+        (ProtocolVersion::V_2025_06_18, ProtocolVersion::LATEST),
+        // unknown, but not newer than what we support
+        (version("2024-01-01"), ProtocolVersion::LATEST),
+        (version("draft"), ProtocolVersion::LATEST),
+    ];
+    for (requested, negotiated) in cases {
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let (server, client) = tokio::join!(
+            TestServer::new().serve(server_transport),
+            VersionedClient(requested.clone()).serve(client_transport)
+        );
+        let (server, client) = (server?, client?);
+        assert_eq!(
+            client.peer_info().unwrap().protocol_version,
+            negotiated,
+            "requested {requested}"
+        );
+        client.cancel().await?;
+        server.cancel().await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_future_protocol_version_is_negotiated_down() -> anyhow::Result<()> {
+    let future = serde_json::from_value::<ProtocolVersion>(serde_json::json!("2099-01-01"))?;
+    assert!(!future.is_supported());
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        TestServer::new().serve(server_transport),
+        VersionedClient(future).serve(client_transport)
+    );
+    let _server = server?;
+    let client = client?;
+    assert_eq!(
+        client.peer_info().map(|info| info.protocol_version.clone()),
+        Some(ProtocolVersion::LATEST)
+    );
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_future_protocol_version_is_rejected_when_bounded() -> anyhow::Result<()> {
+    let future = serde_json::from_value::<ProtocolVersion>(serde_json::json!("2099-01-01"))?;
+    let config = rmcp::service::ServeServerConfig::default()
+        .with_protocol_versions(ProtocolVersion::V_2024_11_05..=ProtocolVersion::LATEST);
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        rmcp::serve_server_with_config(
+            TestServer::new(),
+            server_transport,
+            config,
+            Default::default()
+        ),
+        VersionedClient(future.clone()).serve(client_transport)
+    );
+    match server {
+        Err(rmcp::service::ServerInitializeError::UnsupportedProtocolVersion(version)) => {
+            assert_eq!(version, future)
+        }
+        other => panic!(
+            "expected an unsupported version error, got {:?}",
+            other.err()
+        ),
+    }
+    let error = client.err().expect("the client must not be initialized");
+    assert!(
+        error
+            .to_string()
+            .contains("unsupported protocol version 2099-01-01"),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_protocol_version_range() -> anyhow::Result<()> {
+    let config = rmcp::service::ServeServerConfig::default()
+        .with_protocol_versions(ProtocolVersion::V_2025_03_26..=ProtocolVersion::V_2025_06_18);
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        rmcp::serve_server_with_config(
+            TestServer::new(),
+            server_transport,
+            config.clone(),
+            Default::default()
+        ),
+        VersionedClient(ProtocolVersion::V_2024_11_05).serve(client_transport)
+    );
+    assert!(matches!(
+        server,
+        Err(rmcp::service::ServerInitializeError::UnsupportedProtocolVersion(version))
+            if version == ProtocolVersion::V_2024_11_05
+    ));
+    let error = client.err().expect("the client must not be initialized");
+    assert!(
+        error
+            .to_string()
+            .contains("the versions supported are 2025-03-26 to 2025-06-18"),
+        "{error}"
+    );
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        rmcp::serve_server_with_config(
+            TestServer::new(),
+            server_transport,
+            config,
+            Default::default()
+        ),
+        VersionedClient(ProtocolVersion::V_2025_03_26).serve(client_transport)
+    );
+    let (server, client) = (server?, client?);
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "no protocol version can be accepted from 2099-01-01 to 2099-12-31")]
+fn test_protocol_version_range_newer_than_latest() {
+    let version = |version| serde_json::from_value::<ProtocolVersion>(version).unwrap();
+    let _ = rmcp::service::ServeServerConfig::default().with_protocol_versions(
+        version(serde_json::json!("2099-01-01"))..=version(serde_json::json!("2099-12-31")),
+    );
+}
+
+#[test]
+#[should_panic(expected = "no protocol version can be accepted from 2025-06-18 to 2025-03-26")]
+fn test_protocol_version_range_empty() {
+    let _ = rmcp::service::ServeServerConfig::default()
+        .with_protocol_versions(ProtocolVersion::V_2025_06_18..=ProtocolVersion::V_2025_03_26);
+}
+
+#[test]
+fn test_protocol_version_range_of_known_versions() {
+    // newer than the latest version, but known
+    let config = rmcp::service::ServeServerConfig::default()
+        .with_protocol_versions(ProtocolVersion::V_2025_06_18..=ProtocolVersion::V_2025_06_18);
+    assert!(config.accepts(&ProtocolVersion::V_2025_06_18));
+}
+
+/// Answers the roots requests with their id
+#[derive(Debug, Clone, Default)]
+struct RequestIdClient;
+
+impl rmcp::ClientHandler for RequestIdClient {
+    async fn list_roots(
+        &self,
+        context: RequestContext<rmcp::RoleClient>,
+    ) -> Result<ListRootsResult, ErrorData> {
+        Ok(ListRootsResult {
+            roots: vec![Root {
+                uri: format!("file:///{}", context.id),
+                name: None,
+            }],
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_request_id_provider() -> anyhow::Result<()> {
+    let config = rmcp::service::ServeServerConfig::default()
+        .with_request_id_provider(rmcp::service::AtomicU64RequestIdProvider::default());
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        rmcp::serve_server_with_config(
+            TestServer::new(),
+            server_transport,
+            config,
+            Default::default()
+        ),
+        RequestIdClient.serve(client_transport)
+    );
+    let (server, client) = (server?, client?);
+
+    for expected in ["file:///0", "file:///1"] {
+        let roots = server.list_roots().await?;
+        assert_eq!(roots.roots[0].uri, expected);
+    }
+    let provider = rmcp::service::AtomicU64RequestIdProvider::default();
+    assert_eq!(
+        rmcp::service::RequestIdProvider::next_request_id(&provider),
+        NumberOrString::String("0".into())
+    );
+
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(())
+}
@@ -0,0 +1,340 @@
+//! Every model value must deserialize back from its json, whatever its optional fields are.
+//!
+//! The number of cases is set by `PROPTEST_CASES`, e.g. `PROPTEST_CASES=10000` in CI.
+use std::sync::Arc;
+
+use proptest::{collection::vec, option, prelude::*};
+use rmcp::model::*;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: Serialize + DeserializeOwned,
+{
+    let json = serde_json::to_string(value).expect("serializable");
+    serde_json::from_str(&json).unwrap_or_else(|error| panic!("{error}: {json}"))
+}
+
+fn text() -> impl Strategy<Value = String> {
+    ".{0,16}"
+}
+
+fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        text().prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 32, 4, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..4).prop_map(Value::Array),
+            proptest::collection::btree_map(text(), inner, 0..4)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+fn json_object() -> impl Strategy<Value = JsonObject> {
+    proptest::collection::btree_map(text(), json_value(), 0..4)
+        .prop_map(|map| map.into_iter().collect())
+}
+
+/// `Some(Value::Null)` is written as `null`, which reads back as `None`
+fn optional_json_value() -> impl Strategy<Value = Option<Value>> {
+    option::of(json_value().prop_filter("null is absent", |value| !value.is_null()))
+}
+
+/// A priority or temperature, in tenths, so it's exactly read back
+fn ratio() -> impl Strategy<Value = f32> {
+    (0u8..=10).prop_map(|tenths| f32::from(tenths) / 10.0)
+}
+
+fn call_tool_request_param() -> impl Strategy<Value = CallToolRequestParam> {
+    (text(), option::of(json_object())).prop_map(|(name, arguments)| CallToolRequestParam {
+        name: name.into(),
+        arguments,
+    })
+}
+
+fn content() -> impl Strategy<Value = Content> {
+    prop_oneof![
+        text().prop_map(Content::text),
+        (vec(any::<u8>(), 0..16), text())
+            .prop_map(|(data, mime_type)| Content::image_bytes(data, mime_type)),
+    ]
+}
+
+fn create_message_request_param() -> impl Strategy<Value = CreateMessageRequestParam> {
+    let message = (
+        prop_oneof![Just(Role::User), Just(Role::Assistant)],
+        content(),
+    )
+        .prop_map(|(role, content)| SamplingMessage { role, content });
+    let preferences = (
+        option::of(vec(option::of(text()), 0..3)),
+        option::of(ratio()),
+        option::of(ratio()),
+        option::of(ratio()),
+    )
+        .prop_map(|(hints, cost, speed, intelligence)| ModelPreferences {
+            hints: hints.map(|hints| hints.into_iter().map(|name| ModelHint { name }).collect()),
+            cost_priority: cost,
+            speed_priority: speed,
+            intelligence_priority: intelligence,
+        });
+    let include_context = prop_oneof![
+        Just(ContextInclusion::AllServers),
+        Just(ContextInclusion::None),
+        Just(ContextInclusion::ThisServer),
+    ];
+    (
+        vec(message, 0..4),
+        option::of(preferences),
+        option::of(text()),
+        option::of(include_context),
+        option::of(ratio()),
+        any::<u32>(),
+        option::of(vec(text(), 0..3)),
+        optional_json_value(),
+    )
+        .prop_map(
+            |(
+                messages,
+                model_preferences,
+                system_prompt,
+                include_context,
+                temperature,
+                max_tokens,
+                stop_sequences,
+                metadata,
+            )| CreateMessageRequestParam {
+                messages,
+                model_preferences,
+                system_prompt,
+                include_context,
+                temperature,
+                max_tokens,
+                stop_sequences,
+                metadata,
+            },
+        )
+}
+
+fn icons() -> impl Strategy<Value = Option<Vec<Icon>>> {
+    option::of(vec(
+        (text(), option::of(text()), option::of(text())).prop_map(|(src, mime_type, sizes)| Icon {
+            src,
+            mime_type,
+            sizes,
+        }),
+        0..2,
+    ))
+}
+
+fn tool() -> impl Strategy<Value = Tool> {
+    let annotations = (
+        option::of(text()),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+    )
+        .prop_map(
+            |(title, read_only, destructive, idempotent, open_world)| ToolAnnotations {
+                title,
+                read_only_hint: read_only,
+                destructive_hint: destructive,
+                idempotent_hint: idempotent,
+                open_world_hint: open_world,
+            },
+        );
+    (
+        text(),
+        option::of(text()),
+        option::of(text()),
+        json_object(),
+        option::of(json_object()),
+        option::of(annotations),
+        icons(),
+    )
+        .prop_map(
+            |(name, title, description, input_schema, output_schema, annotations, icons)| Tool {
+                name: name.into(),
+                title,
+                description: description.map(Into::into),
+                input_schema: Arc::new(input_schema),
+                output_schema: output_schema.map(Arc::new),
+                annotations,
+                icons,
+            },
+        )
+}
+
+fn list_tools_result() -> impl Strategy<Value = ListToolsResult> {
+    (option::of(text()), vec(tool(), 0..3)).prop_map(|(next_cursor, tools)| ListToolsResult {
+        next_cursor: next_cursor.map(Cursor::new),
+        tools,
+    })
+}
+
+fn error_data() -> impl Strategy<Value = ErrorData> {
+    (any::<i32>(), text(), optional_json_value()).prop_map(|(code, message, data)| ErrorData {
+        code: ErrorCode(code),
+        message: message.into(),
+        data,
+    })
+}
+
+fn initialize_result() -> impl Strategy<Value = InitializeResult> {
+    let protocol_version = prop_oneof![
+        Just(ProtocolVersion::V_2024_11_05),
+        Just(ProtocolVersion::V_2025_03_26),
+        Just(ProtocolVersion::V_2025_06_18),
+    ];
+    let capabilities = (
+        option::of(json_object()),
+        option::of(json_object()),
+        option::of(option::of(any::<bool>())),
+        option::of((option::of(any::<bool>()), option::of(any::<bool>()))),
+        option::of(option::of(any::<bool>())),
+    )
+        .prop_map(
+            |(logging, completions, prompts, resources, tools)| ServerCapabilities {
+                experimental: None,
+                logging,
+                completions,
+                prompts: prompts.map(|list_changed| PromptsCapability { list_changed }),
+                resources: resources.map(|(subscribe, list_changed)| ResourcesCapability {
+                    subscribe,
+                    list_changed,
+                }),
+                tools: tools.map(|list_changed| ToolsCapability { list_changed }),
+            },
+        );
+    let implementation = (
+        text(),
+        option::of(text()),
+        text(),
+        icons(),
+        option::of(text()),
+    )
+        .prop_map(
+            |(name, title, version, icons, website_url)| Implementation {
+                name,
+                title,
+                version,
+                icons,
+                website_url,
+            },
+        );
+    (
+        protocol_version,
+        capabilities,
+        implementation,
+        option::of(text()),
+    )
+        .prop_map(
+            |(protocol_version, capabilities, server_info, instructions)| InitializeResult {
+                protocol_version,
+                capabilities,
+                server_info,
+                instructions,
+            },
+        )
+}
+
+fn request_id() -> impl Strategy<Value = RequestId> {
+    prop_oneof![
+        any::<i64>().prop_map(NumberOrString::Number),
+        text().prop_map(|id| NumberOrString::String(id.into())),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_call_tool_request_param_round_trip(value in call_tool_request_param()) {
+        prop_assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn test_create_message_request_param_round_trip(value in create_message_request_param()) {
+        prop_assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn test_list_tools_result_round_trip(value in list_tools_result()) {
+        prop_assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn test_error_data_round_trip(value in error_data()) {
+        prop_assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn test_initialize_result_round_trip(value in initialize_result()) {
+        prop_assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn test_client_message_round_trip(
+        params in call_tool_request_param(),
+        id in request_id(),
+    ) {
+        let request = ClientJsonRpcMessage::request(
+            ClientRequest::CallToolRequest(Request::new(params.clone())),
+            id.clone(),
+        );
+        let (request, request_id) = round_trip(&request).into_request().expect("a request");
+        prop_assert_eq!(request_id, id);
+        let ClientRequest::CallToolRequest(request) = request else {
+            panic!("expected a tools/call request, got {request:?}");
+        };
+        prop_assert_eq!(request.params, params);
+
+        let notification = ClientJsonRpcMessage::notification(
+            ClientNotification::RootsListChangedNotification(Default::default()),
+        );
+        let notification = round_trip(&notification)
+            .into_notification()
+            .expect("a notification");
+        prop_assert!(matches!(
+            notification,
+            ClientNotification::RootsListChangedNotification(_)
+        ));
+    }
+
+    #[test]
+    fn test_server_message_round_trip(
+        result in list_tools_result(),
+        error in error_data(),
+        id in request_id(),
+    ) {
+        let response = ServerJsonRpcMessage::response(
+            ServerResult::ListToolsResult(result.clone()),
+            id.clone(),
+        );
+        let (response, response_id) = round_trip(&response).into_response().expect("a response");
+        prop_assert_eq!(response_id, id.clone());
+        let ServerResult::ListToolsResult(response) = response else {
+            panic!("expected a tools/list result, got {response:?}");
+        };
+        prop_assert_eq!(response, result);
+
+        let response = ServerJsonRpcMessage::error(error.clone(), id.clone());
+        prop_assert_eq!(round_trip(&response).into_error(), Some((error, id)));
+
+        let notification = ServerJsonRpcMessage::notification(
+            ServerNotification::ToolListChangedNotification(Default::default()),
+        );
+        let notification = round_trip(&notification)
+            .into_notification()
+            .expect("a notification");
+        prop_assert!(matches!(
+            notification,
+            ServerNotification::ToolListChangedNotification(_)
+        ));
+    }
+}
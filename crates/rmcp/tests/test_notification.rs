@@ -1,15 +1,28 @@
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
 
+use futures::StreamExt;
 use rmcp::{
-    ClientHandler, ServerHandler, ServiceExt,
+    ClientHandler, Peer, RoleClient, RoleServer, ServerHandler, ServiceExt,
     model::{
-        ResourceUpdatedNotificationParam, ServerCapabilities, ServerInfo, SubscribeRequestParam,
+        ResourceListChangedNotification, ResourceUpdatedNotificationParam, ServerCapabilities,
+        ServerInfo, ServerNotification, SubscribeRequestParam, ToolListChangedNotification,
     },
+    service::RunningService,
 };
-use tokio::sync::Notify;
+use rmcp_test_macros::mcp_integration_test;
+use tokio::sync::{Notify, mpsc, watch};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-pub struct Server {}
+/// Subscribing to this uri makes the server send a tool list change
+const TOOLS_URI: &str = "test://tools";
+
+#[derive(Default)]
+pub struct Server {
+    unsubscribed: Arc<Notify>,
+}
 
 impl ServerHandler for Server {
     fn get_info(&self) -> ServerInfo {
@@ -30,6 +43,11 @@ impl ServerHandler for Server {
     ) -> Result<(), rmcp::ErrorData> {
         let uri = request.uri;
         let peer = context.peer;
+        if uri == TOOLS_URI {
+            return peer.notify_tool_list_changed().await.map_err(|e| {
+                rmcp::ErrorData::internal_error(format!("failed to notify: {e}"), None)
+            });
+        }
 
         tokio::spawn(async move {
             let span = tracing::info_span!("subscribe", uri = %uri);
@@ -45,6 +63,15 @@ impl ServerHandler for Server {
 
         Ok(())
     }
+
+    async fn unsubscribe(
+        &self,
+        _request: rmcp::model::UnsubscribeRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        self.unsubscribed.notify_one();
+        Ok(())
+    }
 }
 
 pub struct Client {
@@ -74,7 +101,7 @@ async fn test_server_notification() -> anyhow::Result<()> {
         .try_init();
     let (server_transport, client_transport) = tokio::io::duplex(4096);
     tokio::spawn(async move {
-        let server = Server {}.serve(server_transport).await?;
+        let server = Server::default().serve(server_transport).await?;
         server.waiting().await?;
         anyhow::Ok(())
     });
@@ -93,3 +120,194 @@ async fn test_server_notification() -> anyhow::Result<()> {
     client.cancel().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_typed_notification_handlers() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let server = Server::default().serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let cancelled_calls = Arc::new(AtomicUsize::new(0));
+    let cancelled = client.on_notification::<ToolListChangedNotification>({
+        let cancelled_calls = cancelled_calls.clone();
+        move |_| {
+            cancelled_calls.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _kept = client.on_notification(move |notification: ToolListChangedNotification| {
+        let _ = tx.send(notification);
+    });
+
+    let trigger = SubscribeRequestParam {
+        uri: TOOLS_URI.to_owned(),
+    };
+    client.subscribe(trigger.clone()).await?;
+    rx.recv().await.expect("tool list change");
+    assert_eq!(cancelled_calls.load(Ordering::SeqCst), 1);
+
+    // the handlers are called in the same pass, so the cancelled one would have been called by now
+    cancelled.cancel();
+    client.subscribe(trigger).await?;
+    rx.recv().await.expect("tool list change");
+    assert_eq!(cancelled_calls.load(Ordering::SeqCst), 1);
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_subscription() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = Server::default();
+    let unsubscribed = server.unsubscribed.clone();
+    tokio::spawn(async move {
+        let server = server.serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let mut updates = client.subscribe_resource("test://test-resource").await?;
+    assert_eq!(updates.uri(), "test://test-resource");
+    let update = updates.next().await.expect("resource update");
+    assert_eq!(update.uri, "test://test-resource");
+
+    drop(updates);
+    unsubscribed.notified().await;
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resource_subscription_ends_with_connection() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = Server::default().serve(server_transport);
+    let (server, client) = tokio::join!(server, ().serve(client_transport));
+    let (server, client) = (server?, client?);
+
+    let mut updates = client.subscribe_resource("test://test-resource").await?;
+    updates.next().await.expect("resource update");
+
+    server.cancel().await?;
+    let end = tokio::time::timeout(std::time::Duration::from_secs(5), updates.next()).await?;
+    assert!(end.is_none());
+    Ok(())
+}
+
+#[mcp_integration_test(server = Server::default())]
+async fn test_send_any_notification(
+    client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> anyhow::Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _handle = client.on_notification(move |notification: ServerNotification| {
+        let _ = tx.send(notification);
+    });
+
+    // a layer forwarding a notification only knows its type
+    let forwarded = ToolListChangedNotification::default();
+    server.send_notification(forwarded).await?;
+    server.try_send_notification(ResourceListChangedNotification::default())?;
+
+    let received = rx.recv().await.expect("tool list change");
+    assert!(matches!(
+        received,
+        ServerNotification::ToolListChangedNotification(_)
+    ));
+    let received = rx.recv().await.expect("resource list change");
+    assert!(matches!(
+        received,
+        ServerNotification::ResourceListChangedNotification(_)
+    ));
+    Ok(())
+}
+
+/// Keeps the roots it's told about
+struct RootsServer {
+    roots: watch::Sender<Vec<String>>,
+}
+
+impl Default for RootsServer {
+    fn default() -> Self {
+        Self {
+            roots: watch::Sender::new(vec![]),
+        }
+    }
+}
+
+impl ServerHandler for RootsServer {
+    fn on_roots_list_changed(
+        &self,
+        context: rmcp::service::NotificationContext<rmcp::RoleServer>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        self.list_changed_roots(context)
+    }
+
+    async fn on_roots_changed(
+        &self,
+        roots: rmcp::model::ListRootsResult,
+        _context: rmcp::service::NotificationContext<rmcp::RoleServer>,
+    ) {
+        self.roots
+            .send_replace(roots.roots.into_iter().map(|root| root.uri).collect());
+    }
+}
+
+/// Lists a root per change notified
+#[derive(Default)]
+struct RootsClient {
+    changes: AtomicUsize,
+    listed: AtomicUsize,
+}
+
+impl ClientHandler for RootsClient {
+    async fn list_roots(
+        &self,
+        _context: rmcp::service::RequestContext<rmcp::RoleClient>,
+    ) -> Result<rmcp::model::ListRootsResult, rmcp::ErrorData> {
+        self.listed.fetch_add(1, Ordering::SeqCst);
+        let roots = (0..=self.changes.load(Ordering::SeqCst))
+            .map(|i| rmcp::model::Root {
+                uri: format!("file:///root{i}"),
+                name: None,
+            })
+            .collect();
+        Ok(rmcp::model::ListRootsResult { roots })
+    }
+}
+
+#[mcp_integration_test(server = RootsServer::default(), client = RootsClient::default())]
+async fn test_roots_listed_on_change(
+    client: &RunningService<RoleClient, RootsClient>,
+    server: &RunningService<RoleServer, RootsServer>,
+) -> anyhow::Result<()> {
+    let mut received = server.service().roots.subscribe();
+
+    client.notify_roots_list_changed().await?;
+    received
+        .wait_for(|roots| *roots == ["file:///root0"])
+        .await?;
+    client.service().changes.fetch_add(1, Ordering::SeqCst);
+    client.notify_roots_list_changed().await?;
+    received
+        .wait_for(|roots| *roots == ["file:///root0", "file:///root1"])
+        .await?;
+    Ok(())
+}
+
+#[mcp_integration_test(server = Server::default(), client = RootsClient::default())]
+async fn test_roots_not_listed_by_default(
+    client: &RunningService<RoleClient, RootsClient>,
+    _server: &RunningService<RoleServer, Server>,
+) -> anyhow::Result<()> {
+    client.notify_roots_list_changed().await?;
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(client.service().listed.load(Ordering::SeqCst), 0);
+    Ok(())
+}
@@ -1,11 +1,15 @@
 use futures::StreamExt;
 use rmcp::{
-    ClientHandler, Peer, RoleServer, ServerHandler, ServiceExt,
+    ClientHandler, Peer, RoleClient, RoleServer, ServerHandler, ServiceExt,
     handler::{client::progress::ProgressDispatcher, server::tool::ToolRouter},
-    model::{CallToolRequestParam, ClientRequest, Meta, ProgressNotificationParam, Request},
+    model::{
+        CallToolRequestParam, ClientRequest, Meta, NumberOrString, ProgressNotificationParam,
+        ProgressToken, Request,
+    },
     service::PeerRequestOptions,
     tool, tool_handler, tool_router,
 };
+use rmcp_test_macros::mcp_integration_test;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 pub struct MyClient {
@@ -81,6 +85,31 @@ impl MyServer {
         }
         Ok(())
     }
+
+    #[tool]
+    pub async fn three_steps(
+        meta: Meta,
+        client: Peer<RoleServer>,
+    ) -> Result<String, rmcp::ErrorData> {
+        let progress_token = meta
+            .get_progress_token()
+            .ok_or(rmcp::ErrorData::invalid_params(
+                "Progress token is required for this tool",
+                None,
+            ))?;
+        for step in 1..=3 {
+            client
+                .notify_progress(ProgressNotificationParam {
+                    progress_token: progress_token.clone(),
+                    progress: step as f64,
+                    total: Some(3.0),
+                    message: None,
+                })
+                .await
+                .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
+        }
+        Ok("done".into())
+    }
 }
 
 #[tool_handler]
@@ -130,3 +159,77 @@ async fn test_progress_subscriber() -> anyhow::Result<()> {
     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
     Ok(())
 }
+
+#[mcp_integration_test(server = MyServer::new())]
+async fn test_call_tool_with_progress(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let result = client
+        .call_tool_with_progress(
+            CallToolRequestParam {
+                name: "three_steps".into(),
+                arguments: None,
+            },
+            {
+                let progress = progress.clone();
+                move |param| progress.lock().unwrap().push(param.progress)
+            },
+        )
+        .await?;
+    assert_eq!(result.content[0].as_text().unwrap().text, "done");
+    assert_eq!(*progress.lock().unwrap(), vec![1.0, 2.0, 3.0]);
+
+    // the callback is removed with the call
+    client
+        .call_tool(CallToolRequestParam {
+            name: "three_steps".into(),
+            arguments: None,
+        })
+        .await?;
+    assert_eq!(progress.lock().unwrap().len(), 3);
+    Ok(())
+}
+
+#[mcp_integration_test(server = MyServer::new())]
+async fn test_request_keeps_the_progress_token_of_the_caller(
+    client: Peer<RoleClient>,
+) -> anyhow::Result<()> {
+    let ping = || async {
+        let handle = client
+            .send_cancellable_request(
+                ClientRequest::PingRequest(Default::default()),
+                PeerRequestOptions::no_options(),
+            )
+            .await?;
+        let token = handle.progress_token.clone();
+        handle.await_response().await?;
+        anyhow::Ok(token)
+    };
+
+    let before = ping().await?;
+    let progress_token = ProgressToken(NumberOrString::String("caller".into()));
+    let mut meta = Meta::new();
+    meta.set_progress_token(progress_token.clone());
+    let handle = client
+        .send_cancellable_request(
+            ClientRequest::CallToolRequest(Request::new(CallToolRequestParam {
+                name: "three_steps".into(),
+                arguments: None,
+            })),
+            PeerRequestOptions {
+                meta: Some(meta),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(handle.progress_token, progress_token);
+    handle.await_response().await?;
+
+    // no token was drawn for the request given one
+    let after = ping().await?;
+    let (NumberOrString::Number(before), NumberOrString::Number(after)) = (before.0, after.0)
+    else {
+        panic!("expected numeric tokens");
+    };
+    assert_eq!(after, before + 1);
+    Ok(())
+}
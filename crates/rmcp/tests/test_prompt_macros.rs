@@ -3,15 +3,16 @@
 use std::sync::Arc;
 
 use rmcp::{
-    ClientHandler, RoleServer, ServerHandler, ServiceExt,
+    ClientHandler, RoleClient, RoleServer, ServerHandler,
     handler::server::{router::prompt::PromptRouter, wrapper::Parameters},
     model::{
         ClientInfo, GetPromptRequestParam, GetPromptResult, ListPromptsResult,
         PaginatedRequestParam, PromptMessage, PromptMessageRole,
     },
     prompt, prompt_handler, prompt_router,
-    service::RequestContext,
+    service::{Peer, RequestContext},
 };
+use rmcp_test_macros::mcp_integration_test;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -310,21 +311,11 @@ impl ClientHandler for DummyClientHandler {
     }
 }
 
-#[tokio::test]
-async fn test_optional_i64_field_with_null_input() -> anyhow::Result<()> {
-    let (server_transport, client_transport) = tokio::io::duplex(4096);
-
-    // Server setup
-    let server = OptionalSchemaTester::new();
-    let server_handle = tokio::spawn(async move {
-        server.serve(server_transport).await?.waiting().await?;
-        anyhow::Ok(())
-    });
-
-    // Create a simple client handler that just forwards prompt calls
-    let client_handler = DummyClientHandler::default();
-    let client = client_handler.serve(client_transport).await?;
-
+#[mcp_integration_test(
+    server = OptionalSchemaTester::new(),
+    client = DummyClientHandler::default()
+)]
+async fn test_optional_i64_field_with_null_input(client: Peer<RoleClient>) -> anyhow::Result<()> {
     // Test null case
     let result = client
         .get_prompt(GetPromptRequestParam {
@@ -376,8 +367,94 @@ async fn test_optional_i64_field_with_null_input() -> anyhow::Result<()> {
         some_result_text, "Received count: 42",
         "Some case should return expected message"
     );
+    Ok(())
+}
+
+/// A server whose prompt declares a required argument, but doesn't read it
+#[derive(Debug, Clone)]
+pub struct RequiredArgumentServer {
+    prompt_router: PromptRouter<Self>,
+}
+
+impl RequiredArgumentServer {
+    fn new(prompt_router: PromptRouter<Self>) -> Self {
+        let prompt = rmcp::model::Prompt::new(
+            "greeting",
+            Some("Greet someone"),
+            Some(vec![
+                rmcp::model::PromptArgument::builder()
+                    .name("name")
+                    .required(true)
+                    .build(),
+            ]),
+        );
+        Self {
+            prompt_router: prompt_router.with_route(
+                rmcp::handler::server::router::prompt::PromptRoute::new_dyn(prompt, |_| {
+                    Box::pin(async {
+                        Ok(GetPromptResult {
+                            description: None,
+                            messages: vec![PromptMessage::new_text(
+                                PromptMessageRole::User,
+                                "Hello",
+                            )],
+                        })
+                    })
+                }),
+            ),
+        }
+    }
+}
+
+#[prompt_handler]
+impl ServerHandler for RequiredArgumentServer {}
+
+#[mcp_integration_test(
+    server = RequiredArgumentServer::new(PromptRouter::new()),
+    client = DummyClientHandler::default()
+)]
+async fn test_missing_required_prompt_argument(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    let error = client
+        .get_prompt(GetPromptRequestParam {
+            name: "greeting".into(),
+            arguments: None,
+        })
+        .await
+        .expect_err("the required argument is missing");
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected an mcp error, got {error:?}");
+    };
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert_eq!(error.data, Some(serde_json::json!({ "missing": ["name"] })));
 
-    client.cancel().await?;
-    server_handle.await??;
+    let result = client
+        .get_prompt(GetPromptRequestParam {
+            name: "greeting".into(),
+            arguments: Some(
+                serde_json::json!({ "name": "Alice" })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+        })
+        .await?;
+    assert_eq!(result.messages.len(), 1);
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = RequiredArgumentServer::new(PromptRouter::new().without_validation()),
+    client = DummyClientHandler::default()
+)]
+async fn test_missing_required_prompt_argument_without_validation(
+    client: Peer<RoleClient>,
+) -> anyhow::Result<()> {
+    let result = client
+        .get_prompt(GetPromptRequestParam {
+            name: "greeting".into(),
+            arguments: None,
+        })
+        .await?;
+    assert_eq!(result.messages.len(), 1);
     Ok(())
 }
@@ -0,0 +1,89 @@
+//cargo test --test test_resource_routers --features "server uri-templates"
+use std::collections::HashMap;
+
+use rmcp::{
+    handler::server::router::resource::ResourceRouter,
+    model::{
+        AnnotateAble, ErrorCode, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult,
+        ResourceContents,
+    },
+};
+
+fn template(uri_template: &str, name: &str) -> RawResourceTemplate {
+    RawResourceTemplate {
+        uri_template: uri_template.into(),
+        name: name.into(),
+        title: None,
+        description: None,
+        mime_type: None,
+    }
+}
+
+async fn echo_variables(
+    request: ReadResourceRequestParam,
+    variables: HashMap<String, String>,
+) -> Result<ReadResourceResult, rmcp::ErrorData> {
+    let mut variables = variables.into_iter().collect::<Vec<_>>();
+    variables.sort();
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(
+            format!("{variables:?}"),
+            request.uri,
+        )],
+    })
+}
+
+fn router() -> ResourceRouter {
+    ResourceRouter::new()
+        .with_route(
+            template("users://{id}/profile", "profile").no_annotation(),
+            echo_variables,
+        )
+        .with_route(
+            template("users://{id}/files/{path}", "file").no_annotation(),
+            echo_variables,
+        )
+}
+
+fn text(result: &ReadResourceResult) -> &str {
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => text,
+        contents => panic!("unexpected contents {contents:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_resource_router_routes_to_matching_template() {
+    let router = router();
+    assert_eq!(router.list_all().len(), 2);
+
+    let (_, variables) = router.route("users://42/files/notes/a%20b.txt").unwrap();
+    assert_eq!(variables["id"], "42");
+    assert_eq!(variables["path"], "notes/a b.txt");
+    assert!(router.route("groups://42/profile").is_none());
+
+    let result = router
+        .read(ReadResourceRequestParam {
+            uri: "users://42/profile".into(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(text(&result), r#"[("id", "42")]"#);
+
+    let uri = template("users://{id}/files/{path}", "file")
+        .expand(&HashMap::from([("id", "7"), ("path", "a.txt")]))
+        .unwrap();
+    let result = router.read(ReadResourceRequestParam { uri }).await.unwrap();
+    assert_eq!(text(&result), r#"[("id", "7"), ("path", "a.txt")]"#);
+}
+
+#[tokio::test]
+async fn test_resource_router_not_found() {
+    let error = router()
+        .read(ReadResourceRequestParam {
+            uri: "groups://42".into(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+}
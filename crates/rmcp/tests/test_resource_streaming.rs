@@ -0,0 +1,83 @@
+use bytes::Bytes;
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    handler::server::streaming::StreamingReadResourceResult,
+    model::{ReadResourceRequestParam, ReadResourceResult},
+    service::RequestContext,
+};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+struct LargeFile;
+
+impl ServerHandler for LargeFile {
+    async fn read_resource_streaming(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<StreamingReadResourceResult, rmcp::ErrorData> {
+        let chunks = ["ab", "cd"].map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())));
+        Ok(
+            StreamingReadResourceResult::new(ReadResourceResult { contents: vec![] }).with_stream(
+                request.uri,
+                None,
+                futures::stream::iter(chunks),
+            ),
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_chunks_follow_the_response() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = tokio::spawn(LargeFile.serve(server_transport));
+    let (read, mut write) = tokio::io::split(client_transport);
+    let mut lines = BufReader::new(read).lines();
+    let mut send = async |message: Value| write.write_all(format!("{message}\n").as_bytes()).await;
+
+    send(json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": { "streamingResources": {} },
+            "clientInfo": { "name": "raw", "version": "0" }
+        }
+    }))
+    .await?;
+    lines.next_line().await?;
+    send(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })).await?;
+    send(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "resources/read",
+        "params": { "uri": "file:///large.bin" }
+    }))
+    .await?;
+
+    let mut messages = vec![];
+    while let Some(line) = lines.next_line().await? {
+        let message: Value = serde_json::from_str(&line)?;
+        let end = message["method"] == "notifications/resources/chunk_end";
+        messages.push(message);
+        if end {
+            break;
+        }
+    }
+    // the client learns the stream token from the response, before any chunk
+    assert_eq!(messages.len(), 4);
+    assert_eq!(messages[0]["id"], 1);
+    let stream_token = &messages[0]["result"]["contents"][0]["streamToken"];
+    for (index, chunk) in messages[1..3].iter().enumerate() {
+        assert_eq!(chunk["method"], "notifications/resources/chunk");
+        assert_eq!(chunk["params"]["requestId"], 1);
+        assert_eq!(&chunk["params"]["streamToken"], stream_token);
+        assert_eq!(chunk["params"]["index"], index);
+    }
+    assert_eq!(messages[3]["params"]["requestId"], 1);
+    assert_eq!(messages[3]["params"]["totalChunks"], 2);
+
+    server.await??.cancel().await?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use rmcp::{
+    Peer, RoleClient, ServerHandler,
+    handler::server::router::Router,
+    model::{
+        ClientRequest, ErrorCode, ListToolsRequest, Meta, PaginatedRequestParam, ServerResult,
+    },
+    service::{PeerRequestOptions, ServiceError},
+    tool, tool_router,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+#[derive(Debug, Clone, Default)]
+struct Calculator;
+
+#[tool_router]
+impl Calculator {
+    #[tool(description = "Add")]
+    fn add(&self) -> String {
+        "add".into()
+    }
+
+    #[tool(description = "Subtract")]
+    fn subtract(&self) -> String {
+        "subtract".into()
+    }
+
+    #[tool(description = "Multiply")]
+    fn multiply(&self) -> String {
+        "multiply".into()
+    }
+}
+
+impl ServerHandler for Calculator {}
+
+fn router() -> Router<Calculator> {
+    Router::new(Calculator)
+        .with_tools(Calculator::tool_router().map.into_values())
+        .with_tool_page_size(2)
+}
+
+#[mcp_integration_test(server = router())]
+async fn test_router_lists_tools_by_pages(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    let first = client.list_tools(None).await?;
+    assert_eq!(first.tools.len(), 2);
+    let cursor = first.next_cursor.expect("a second page");
+    let second = client
+        .list_tools(Some(PaginatedRequestParam::default().with_cursor(cursor)))
+        .await?;
+    assert_eq!(second.tools.len(), 1);
+    assert_eq!(second.next_cursor, None);
+
+    // a client may request smaller pages in the `_meta` of the request, but not larger ones
+    let list_with_page_size = |page_size: u32| {
+        let client = &client;
+        async move {
+            let response = client
+                .send_request_with_option(
+                    ClientRequest::ListToolsRequest(ListToolsRequest::default()),
+                    PeerRequestOptions {
+                        meta: Some(Meta::builder().page_size(page_size).build()),
+                        ..Default::default()
+                    },
+                )
+                .await?
+                .await_response()
+                .await?;
+            let ServerResult::ListToolsResult(page) = response else {
+                panic!("unexpected response {response:?}");
+            };
+            anyhow::Ok(page)
+        }
+    };
+    assert_eq!(list_with_page_size(1).await?.tools.len(), 1);
+    let clamped = list_with_page_size(3).await?;
+    assert_eq!(clamped.tools.len(), 2);
+    assert!(clamped.next_cursor.is_some());
+    assert_eq!(client.list_all_tools().await?.len(), 3);
+
+    let error = client
+        .list_tools(Some(PaginatedRequestParam::default().with_cursor("42:2")))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ServiceError::McpError(error) if error.code == ErrorCode::INVALID_PARAMS)
+    );
+    Ok(())
+}
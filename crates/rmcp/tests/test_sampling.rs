@@ -65,6 +65,45 @@ async fn test_sampling_request_params() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_sampling_request_params_builder() -> Result<()> {
+    let params = CreateMessageRequestParam::builder()
+        .system_prompt("You are a helpful assistant.")
+        .user("What is the capital of France?")
+        .assistant("Paris.")
+        .user("And of Germany?")
+        .model_hint("claude")
+        .temperature(0.7)
+        .max_tokens(100)
+        .build();
+
+    assert_eq!(
+        params.messages,
+        vec![
+            SamplingMessage::user_text("What is the capital of France?"),
+            SamplingMessage::assistant_text("Paris."),
+            SamplingMessage::user_text("And of Germany?"),
+        ]
+    );
+    assert_eq!(
+        params.system_prompt.as_deref(),
+        Some("You are a helpful assistant.")
+    );
+    assert_eq!(params.max_tokens, 100);
+    assert_eq!(params.temperature, Some(0.7));
+    let hints = params.model_preferences.and_then(|p| p.hints).unwrap();
+    assert_eq!(hints[0].name.as_deref(), Some("claude"));
+
+    let default_params = CreateMessageRequestParam::builder().user("Hi").build();
+    assert_eq!(
+        default_params.max_tokens,
+        CreateMessageRequestParamBuilder::DEFAULT_MAX_TOKENS
+    );
+    assert!(default_params.model_preferences.is_none());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_sampling_result_structure() -> Result<()> {
     // Test sampling result structure
@@ -0,0 +1,180 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use anyhow::Result;
+use rmcp::{
+    ErrorData, Peer, RoleClient, RoleServer, ServerHandler,
+    handler::client::sampling::{ForwardingSamplingHandler, SamplingBackend},
+    model::*,
+    service::{
+        CircuitBreakerConfig, CircuitBreakerSampling, CircuitState, RunningService, ServiceError,
+    },
+};
+use rmcp_test_macros::mcp_integration_test;
+
+#[derive(Debug, Clone)]
+struct Sampler;
+
+impl ServerHandler for Sampler {}
+
+/// A client LLM failing while it's unhealthy, and never answering while it hangs
+#[derive(Debug, Default)]
+struct Backend {
+    calls: AtomicUsize,
+    unhealthy: AtomicBool,
+    hanging: AtomicBool,
+}
+
+impl Backend {
+    fn hanging() -> Self {
+        Self {
+            hanging: AtomicBool::new(true),
+            ..Default::default()
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn set_unhealthy(&self, unhealthy: bool) {
+        self.unhealthy.store(unhealthy, Ordering::SeqCst);
+    }
+}
+
+impl SamplingBackend for Backend {
+    async fn create_message(
+        &self,
+        _params: CreateMessageRequestParam,
+    ) -> Result<CreateMessageResult, ErrorData> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.hanging.load(Ordering::SeqCst) {
+            std::future::pending::<()>().await;
+        }
+        if self.unhealthy.load(Ordering::SeqCst) {
+            return Err(ErrorData::internal_error("overloaded", None));
+        }
+        Ok(CreateMessageResult {
+            model: "echo".into(),
+            stop_reason: None,
+            message: SamplingMessage::new(Role::Assistant, Content::text("Hi")),
+        })
+    }
+}
+
+type Client = RunningService<RoleClient, ForwardingSamplingHandler<Backend>>;
+
+fn params() -> CreateMessageRequestParam {
+    CreateMessageRequestParam::builder()
+        .user("Hello")
+        .max_tokens(16)
+        .build()
+}
+
+fn config() -> CircuitBreakerConfig {
+    CircuitBreakerConfig {
+        failure_threshold: 3,
+        window: 10,
+        failure_rate: 0.5,
+        recovery_timeout: Duration::from_millis(100),
+        call_timeout: Some(Duration::from_millis(100)),
+    }
+}
+
+#[mcp_integration_test(
+    server = Sampler,
+    client = ForwardingSamplingHandler::new(Backend::default())
+)]
+async fn test_circuit_opens_after_consecutive_failures_and_recovers(
+    client: &Client,
+    server: &RunningService<RoleServer, Sampler>,
+) -> Result<()> {
+    let backend = client.service().backend();
+    let sampling = CircuitBreakerSampling::new(server.peer().clone(), config());
+    let state = sampling.state();
+
+    sampling.create_message(params()).await?;
+    backend.set_unhealthy(true);
+    for _ in 0..3 {
+        assert!(matches!(
+            sampling.create_message(params()).await,
+            Err(ServiceError::McpError(_))
+        ));
+    }
+    assert_eq!(*state.borrow(), CircuitState::Open);
+    // the calls aren't sent while the circuit is open
+    assert!(matches!(
+        sampling.create_message(params()).await,
+        Err(ServiceError::Unavailable)
+    ));
+    assert_eq!(backend.calls(), 4);
+
+    // a failed probe opens the circuit again
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(sampling.create_message(params()).await.is_err());
+    assert_eq!(backend.calls(), 5);
+    assert_eq!(*state.borrow(), CircuitState::Open);
+    assert!(matches!(
+        sampling.create_message(params()).await,
+        Err(ServiceError::Unavailable)
+    ));
+
+    // a successful one closes it
+    backend.set_unhealthy(false);
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    sampling.create_message(params()).await?;
+    assert_eq!(*state.borrow(), CircuitState::Closed);
+    sampling.create_message(params()).await?;
+    assert_eq!(backend.calls(), 7);
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = Sampler,
+    client = ForwardingSamplingHandler::new(Backend::default())
+)]
+async fn test_circuit_opens_on_failure_rate(
+    client: &Client,
+    server: &RunningService<RoleServer, Sampler>,
+) -> Result<()> {
+    let backend = client.service().backend();
+    let sampling = CircuitBreakerSampling::new(server.peer().clone(), config());
+
+    // never 3 failures in a row, but 6 out of the last 10 calls
+    for unhealthy in [true, true, false, true, true, false, true, false, false] {
+        backend.set_unhealthy(unhealthy);
+        let _ = sampling.create_message(params()).await;
+        assert_eq!(*sampling.state().borrow(), CircuitState::Closed);
+    }
+    backend.set_unhealthy(true);
+    let _ = sampling.create_message(params()).await;
+    assert_eq!(*sampling.state().borrow(), CircuitState::Open);
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = Sampler,
+    client = ForwardingSamplingHandler::new(Backend::hanging())
+)]
+async fn test_hanging_calls_open_the_circuit(
+    _client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> Result<()> {
+    let sampling = CircuitBreakerSampling::new(server, config());
+    let mut state = sampling.state();
+
+    for _ in 0..3 {
+        assert!(matches!(
+            sampling.create_message(params()).await,
+            Err(ServiceError::Timeout { .. })
+        ));
+    }
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        state.wait_for(|s| *s == CircuitState::Open),
+    )
+    .await??;
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//cargo test --test test_sampling_forwarding --features "client server openai axum"
+use anyhow::Result;
+use axum::{
+    Json, Router,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use rmcp::{
+    ErrorData, Peer, RoleClient, RoleServer, ServerHandler,
+    handler::client::sampling::{ForwardingSamplingHandler, OpenAISamplingBackend},
+    model::*,
+};
+use rmcp_test_macros::mcp_integration_test;
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone)]
+struct Sampler;
+
+impl ServerHandler for Sampler {}
+
+fn params() -> CreateMessageRequestParam {
+    CreateMessageRequestParam::builder()
+        .system_prompt("You are a helpful assistant.")
+        .user("What is the capital of France?")
+        .max_tokens(64)
+        .build()
+}
+
+fn echo(
+    params: CreateMessageRequestParam,
+) -> impl Future<Output = Result<CreateMessageResult, ErrorData>> {
+    let question = params.messages[0].content.as_text().unwrap().text.clone();
+    std::future::ready(Ok(CreateMessageResult {
+        model: "echo".into(),
+        stop_reason: Some(CreateMessageResult::STOP_REASON_END_TURN.into()),
+        message: SamplingMessage::new(Role::Assistant, Content::text(question)),
+    }))
+}
+
+#[mcp_integration_test(server = Sampler, client = ForwardingSamplingHandler::new(echo))]
+async fn test_sampling_is_forwarded_to_closure(
+    _client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> Result<()> {
+    assert!(server.peer_info().unwrap().capabilities.sampling.is_some());
+
+    let result = server.create_message(params()).await?;
+    assert_eq!(result.model, "echo");
+    assert_eq!(
+        result.message.content.as_text().unwrap().text,
+        "What is the capital of France?"
+    );
+    Ok(())
+}
+
+/// A chat completions API answering with the authorization and the body of the request it got,
+/// failing with the status of the `fail` model
+async fn mock_openai() -> OpenAISamplingBackend {
+    let app = Router::new().route(
+        "/v1/chat/completions",
+        post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+            if body["model"] == "fail" {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": "upstream cluster eu-west-7 overloaded" })),
+                );
+            }
+            let received = json!({
+                "authorization": headers["authorization"].to_str().unwrap(),
+                "body": body,
+            });
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "id": "chatcmpl-1",
+                    "object": "chat.completion",
+                    "created": 0,
+                    "model": "gpt-test-2024",
+                    "choices": [{
+                        "index": 0,
+                        "message": { "role": "assistant", "content": received.to_string() },
+                        "finish_reason": "stop",
+                    }],
+                })),
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind the mock api");
+    let addr = listener.local_addr().expect("bound");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    OpenAISamplingBackend::new("sk-test")
+        .with_base_url(format!("http://{addr}/v1"))
+        .with_default_model("gpt-test")
+}
+
+/// The request received by the mock api, from its answer
+fn received(result: &CreateMessageResult) -> Value {
+    serde_json::from_str(&result.message.content.as_text().unwrap().text).unwrap()
+}
+
+#[mcp_integration_test(
+    server = Sampler,
+    client = ForwardingSamplingHandler::new(mock_openai().await)
+)]
+async fn test_openai_sampling_backend(
+    _client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> Result<()> {
+    let result = server.create_message(params()).await?;
+    assert_eq!(result.model, "gpt-test-2024");
+    assert_eq!(
+        result.stop_reason.as_deref(),
+        Some(CreateMessageResult::STOP_REASON_END_TURN)
+    );
+    assert_eq!(result.message.role, Role::Assistant);
+    assert_eq!(
+        received(&result),
+        json!({
+            "authorization": "Bearer sk-test",
+            "body": {
+                "model": "gpt-test",
+                "messages": [
+                    { "role": "system", "content": "You are a helpful assistant." },
+                    { "role": "user", "content": "What is the capital of France?" },
+                ],
+                "max_tokens": 64,
+            },
+        })
+    );
+    Ok(())
+}
+
+#[mcp_integration_test(
+    server = Sampler,
+    client = ForwardingSamplingHandler::new(
+        mock_openai().await.with_models(["gpt-4o", "gpt-4o-mini", "fail"])
+    )
+)]
+async fn test_openai_model_hints(
+    _client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> Result<()> {
+    let model_for = async |hints: &[&str]| -> Result<Value> {
+        let mut params = CreateMessageRequestParam::builder();
+        params.user("Hello").max_tokens(16);
+        for hint in hints {
+            params.model_hint(*hint);
+        }
+        let result = server.create_message(params.build()).await?;
+        Ok(received(&result)["body"]["model"].clone())
+    };
+
+    // a hint is a part of the name of a model, the first hint matching a model selects it
+    assert_eq!(
+        model_for(&["claude-3-sonnet", "mini"]).await?,
+        "gpt-4o-mini"
+    );
+    assert_eq!(model_for(&["4o"]).await?, "gpt-4o");
+    // a hint matching none of the models is never sent
+    assert_eq!(model_for(&["claude-3-sonnet"]).await?, "gpt-test");
+    assert_eq!(model_for(&[]).await?, "gpt-test");
+
+    // the body of a failed completion isn't sent to the server
+    let params = CreateMessageRequestParam::builder()
+        .user("Hello")
+        .max_tokens(16)
+        .model_hint("fail")
+        .build();
+    let Err(rmcp::service::ServiceError::McpError(error)) = server.create_message(params).await
+    else {
+        panic!("the completion should fail");
+    };
+    assert_eq!(error.data, None);
+    assert!(!error.message.contains("eu-west-7"));
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use rmcp::{
+    ErrorData, ServerHandler,
+    model::{ClientInfo, Implementation, InitializeRequestParam, ProtocolVersion},
+    serve_client, serve_server_lazy,
+    service::{ServeServerConfig, ServerInitializeError, serve_server_lazy_with_config},
+};
+
+struct Server {
+    client_name: String,
+}
+
+impl ServerHandler for Server {}
+
+fn client_info(name: &str) -> ClientInfo {
+    ClientInfo {
+        client_info: Implementation::new(name, "1.0.0"),
+        ..Default::default()
+    }
+}
+
+/// Only serve the clients named `trusted`
+fn factory(
+    params: &InitializeRequestParam,
+) -> impl Future<Output = Result<Server, ErrorData>> + use<> {
+    let client_name = params.client_info.name.clone();
+    async move {
+        if client_name != "trusted" {
+            return Err(ErrorData::invalid_request("untrusted client", None));
+        }
+        Ok(Server { client_name })
+    }
+}
+
+#[tokio::test]
+async fn test_lazy_server_accepts() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        serve_server_lazy(factory, server_transport),
+        serve_client(client_info("trusted"), client_transport)
+    );
+    let (server, client) = (server?, client?);
+    assert_eq!(server.service().client_name, "trusted");
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lazy_server_rejects() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        serve_server_lazy(factory, server_transport),
+        serve_client(client_info("stranger"), client_transport)
+    );
+    let Err(ServerInitializeError::InitializeFailed(error)) = server else {
+        panic!("expected the initialization to fail");
+    };
+    assert_eq!(error.message, "untrusted client");
+    let error = client.expect_err("the client is rejected").to_string();
+    assert!(error.contains("untrusted client"), "{error}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lazy_server_checks_version_first() -> anyhow::Result<()> {
+    let created = std::sync::atomic::AtomicBool::new(false);
+    let client = ClientInfo {
+        protocol_version: serde_json::from_value::<ProtocolVersion>("2099-01-01".into())?,
+        ..client_info("trusted")
+    };
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        serve_server_lazy_with_config(
+            |params: &InitializeRequestParam| {
+                created.store(true, std::sync::atomic::Ordering::SeqCst);
+                factory(params)
+            },
+            server_transport,
+            ServeServerConfig::default()
+                .with_protocol_versions(ProtocolVersion::V_2024_11_05..=ProtocolVersion::LATEST),
+            Default::default()
+        ),
+        serve_client(client, client_transport)
+    );
+    assert!(matches!(
+        server,
+        Err(ServerInitializeError::UnsupportedProtocolVersion(_))
+    ));
+    assert!(client.is_err());
+    assert!(!created.load(std::sync::atomic::Ordering::SeqCst));
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use rmcp::{
+    Peer, RoleClient, RoleServer, ServerHandler,
+    model::{
+        JsonObject, ListPromptsResult, ListToolsResult, PaginatedRequestParam, Prompt,
+        ServerCapabilities, ServerInfo, Tool,
+    },
+    service::RequestContext,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+/// Has tools and prompts, but no resources
+struct SpecServer;
+
+impl ServerHandler for SpecServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .build(),
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        Ok(ListToolsResult::with_all_items(vec![Tool::new(
+            "sum",
+            "Add two numbers",
+            Arc::new(JsonObject::new()),
+        )]))
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, rmcp::ErrorData> {
+        Ok(ListPromptsResult::with_all_items(vec![Prompt::new(
+            "greet",
+            Some("Greet someone"),
+            None,
+        )]))
+    }
+}
+
+#[mcp_integration_test(server = SpecServer)]
+async fn test_fetch_server_spec(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    // resources are not listed, as the server has no such capability
+    let spec = client.fetch_server_spec().await?;
+    assert_eq!(spec.tools.len(), 1);
+    assert_eq!(spec.prompts[0].name, "greet");
+    assert!(spec.resources.is_empty());
+    let schema = spec.to_json_schema();
+    assert_eq!(
+        schema["properties"]["tools"]["properties"]["sum"]["description"],
+        "Add two numbers"
+    );
+    Ok(())
+}
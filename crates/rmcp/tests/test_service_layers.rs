@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use rmcp::{
+    ServerHandler, ServiceExt,
+    model::{ErrorCode, ListToolsResult, PaginatedRequestParam, ServerCapabilities, ServerInfo},
+    service::{RequestContext, TimeoutLayer, TracingLayer},
+};
+
+/// Lists its tools after `delay`
+#[derive(Clone)]
+struct SlowServer {
+    delay: Duration,
+}
+
+impl ServerHandler for SlowServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<rmcp::RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        tokio::time::sleep(self.delay).await;
+        Ok(ListToolsResult::default())
+    }
+}
+
+async fn list_tools<S>(server: S) -> anyhow::Result<Result<ListToolsResult, rmcp::ServiceError>>
+where
+    S: rmcp::Service<rmcp::RoleServer>,
+{
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(server.serve(server_transport), ().serve(client_transport));
+    let (server, client) = (server?, client?);
+    let result = client.list_tools(None).await;
+    client.cancel().await?;
+    server.cancel().await?;
+    Ok(result)
+}
+
+#[tokio::test]
+async fn test_with_timeout() -> anyhow::Result<()> {
+    let slow = SlowServer {
+        delay: Duration::from_millis(500),
+    };
+    let result = list_tools(slow.clone().with_timeout(Duration::from_millis(20))).await?;
+    let Err(rmcp::ServiceError::McpError(error)) = result else {
+        panic!("expected a timeout, got {result:?}");
+    };
+    assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+    assert!(error.message.contains("timed out"), "{}", error.message);
+
+    let fast = SlowServer {
+        delay: Duration::ZERO,
+    };
+    assert!(
+        list_tools(fast.with_timeout(Duration::from_secs(5)))
+            .await?
+            .is_ok()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_layers() -> anyhow::Result<()> {
+    let server = SlowServer {
+        delay: Duration::from_millis(500),
+    }
+    .with_tracing()
+    .with_layer(TimeoutLayer::new(Duration::from_millis(20)))
+    .with_layer(TracingLayer);
+    assert!(list_tools(server).await?.is_err());
+    Ok(())
+}
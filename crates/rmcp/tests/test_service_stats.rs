@@ -0,0 +1,69 @@
+use rmcp::{
+    Peer, RoleClient, RoleServer, ServerHandler,
+    model::{CallToolRequestParam, ClientInfo},
+    service::RunningService,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+struct Server;
+
+impl ServerHandler for Server {}
+
+#[mcp_integration_test(server = Server, client = ClientInfo::default())]
+async fn test_service_stats(
+    client: &RunningService<RoleClient, ClientInfo>,
+    server: &RunningService<RoleServer, Server>,
+) -> anyhow::Result<()> {
+    let stats = server.stats();
+
+    client.list_tools(Default::default()).await?;
+    // the server has no tools, the call is answered with an error
+    assert!(
+        client
+            .call_tool(CallToolRequestParam {
+                name: "missing".into(),
+                arguments: None,
+            })
+            .await
+            .is_err()
+    );
+    client.notify_roots_list_changed().await?;
+    // the messages are handled in order, the notification is counted once the ping is answered
+    client.ping().await?;
+
+    // the initialize request and initialized notification are handled before the service runs
+    assert_eq!(stats.requests_handled(), 3);
+    assert_eq!(stats.errors_sent(), 1);
+    assert_eq!(stats.notifications_received(), 1);
+    assert!(stats.uptime() > std::time::Duration::ZERO);
+    Ok(())
+}
+
+#[mcp_integration_test(server = Server, client = ClientInfo::default())]
+async fn test_ping_latency(
+    client: Peer<RoleClient>,
+    server: Peer<RoleServer>,
+) -> anyhow::Result<()> {
+    assert_eq!(client.latency_stats(), None);
+
+    for _ in 0..10 {
+        client.ping().await?;
+    }
+    server.ping().await?;
+
+    let stats = client.latency_stats().expect("pinged");
+    assert_eq!(stats.samples, 10);
+    assert!(stats.min <= stats.mean && stats.mean <= stats.p99 && stats.p99 <= stats.max);
+    // in memory, a ping is a matter of microseconds
+    assert!(stats.min < std::time::Duration::from_millis(1), "{stats:?}");
+    assert_eq!(server.latency_stats().expect("pinged").samples, 1);
+
+    for _ in 0..Peer::<RoleClient>::PING_HISTORY {
+        client.ping().await?;
+    }
+    assert_eq!(
+        client.latency_stats().expect("pinged").samples,
+        Peer::<RoleClient>::PING_HISTORY
+    );
+    Ok(())
+}
@@ -0,0 +1,928 @@
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rmcp::{
+    model::ClientJsonRpcMessage,
+    serve_server,
+    transport::{
+        StreamableHttpServerConfig,
+        streamable_http_server::{
+            CorsConfig, CorsConfigError,
+            auth::{AuthContext, BearerTokenAuth},
+            session::{
+                SessionManager,
+                local::{LocalSessionManager, SessionConfig},
+            },
+            tower::StreamableHttpService,
+        },
+    },
+};
+use tokio_util::sync::CancellationToken;
+mod common;
+use common::calculator::Calculator;
+
+const INITIALIZE_REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.1.0"}}}"#;
+
+async fn serve(session_config: SessionConfig) -> anyhow::Result<(String, CancellationToken)> {
+    serve_with(session_config, None).await
+}
+
+async fn serve_with(
+    session_config: SessionConfig,
+    sse_keep_alive: Option<Duration>,
+) -> anyhow::Result<(String, CancellationToken)> {
+    let session_manager = LocalSessionManager {
+        session_config,
+        ..Default::default()
+    };
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            Arc::new(session_manager),
+            StreamableHttpServerConfig {
+                stateful_mode: true,
+                sse_keep_alive,
+                ..Default::default()
+            },
+        );
+    serve_service(service).await
+}
+
+async fn serve_service<S, M>(
+    service: StreamableHttpService<S, M>,
+) -> anyhow::Result<(String, CancellationToken)>
+where
+    S: rmcp::Service<rmcp::RoleServer> + Send + 'static,
+    M: SessionManager,
+{
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let tcp_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/mcp", tcp_listener.local_addr()?);
+    let ct = CancellationToken::new();
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(
+                tcp_listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+            .await;
+        }
+    });
+    Ok((url, ct))
+}
+
+async fn initialize(client: &reqwest::Client, url: &str) -> anyhow::Result<reqwest::StatusCode> {
+    let response = client
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .body(INITIALIZE_REQUEST)
+        .send()
+        .await?;
+    Ok(response.status())
+}
+
+#[tokio::test]
+async fn test_sessions_per_ip_limit() -> anyhow::Result<()> {
+    let (url, ct) = serve(SessionConfig::default()).await?;
+    let client = reqwest::Client::new();
+    for _ in 0..SessionConfig::DEFAULT_MAX_SESSIONS_PER_IP {
+        assert_eq!(initialize(&client, &url).await?, reqwest::StatusCode::OK);
+    }
+    assert_eq!(
+        initialize(&client, &url).await?,
+        reqwest::StatusCode::TOO_MANY_REQUESTS
+    );
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejected_initializes_dont_count_against_limits() -> anyhow::Result<()> {
+    let (url, ct) = serve(SessionConfig {
+        max_total_sessions: Some(2),
+        ..Default::default()
+    })
+    .await?;
+    let client = reqwest::Client::new();
+    let post = |body: &'static str| {
+        client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+    };
+    for _ in 0..SessionConfig::DEFAULT_MAX_SESSIONS_PER_IP + 1 {
+        let not_initialize = post(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#).await?;
+        assert!(not_initialize.status().is_client_error());
+        let malformed = post(r#"{"jsonrpc":"2.0","id":1,"method":"initialize""#).await?;
+        assert!(malformed.status().is_client_error());
+    }
+    assert_eq!(initialize(&client, &url).await?, reqwest::StatusCode::OK);
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_total_sessions_limit() -> anyhow::Result<()> {
+    let (url, ct) = serve(
+        SessionConfig::builder()
+            .max_sessions_per_ip(None)
+            .max_total_sessions(Some(2))
+            .build(),
+    )
+    .await?;
+    let client = reqwest::Client::new();
+    for _ in 0..2 {
+        assert_eq!(initialize(&client, &url).await?, reqwest::StatusCode::OK);
+    }
+    assert_eq!(
+        initialize(&client, &url).await?,
+        reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_idle_session_is_closed() -> anyhow::Result<()> {
+    let session_manager = LocalSessionManager {
+        session_config: SessionConfig {
+            idle_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (session_id, transport) = session_manager.create_session().await?;
+    let worker = tokio::spawn(async move {
+        let service = serve_server(Calculator::new(), transport).await?;
+        service.waiting().await?;
+        anyhow::Ok(())
+    });
+    let initialize: ClientJsonRpcMessage = serde_json::from_str(INITIALIZE_REQUEST)?;
+    session_manager
+        .initialize_session(&session_id, initialize)
+        .await?;
+    let initialized: ClientJsonRpcMessage =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)?;
+    session_manager
+        .accept_message(&session_id, initialized)
+        .await?;
+
+    tokio::time::timeout(Duration::from_secs(1), worker).await???;
+    Ok(())
+}
+
+/// Run an initialized session, the server sends a notification every 50ms until `pushes` is
+/// cancelled. The session lives as long as the returned manager.
+async fn session_with_server_pushes(
+    session_config: SessionConfig,
+    pushes: CancellationToken,
+) -> anyhow::Result<(
+    LocalSessionManager,
+    tokio::task::JoinHandle<anyhow::Result<()>>,
+)> {
+    let session_manager = LocalSessionManager {
+        session_config,
+        ..Default::default()
+    };
+    let (session_id, transport) = session_manager.create_session().await?;
+    let worker = tokio::spawn(async move {
+        let service = serve_server(Calculator::new(), transport).await?;
+        let peer = service.peer().clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(50));
+            while pushes.run_until_cancelled(interval.tick()).await.is_some() {
+                let _ = peer.notify_tool_list_changed().await;
+            }
+        });
+        service.waiting().await?;
+        anyhow::Ok(())
+    });
+    let initialize: ClientJsonRpcMessage = serde_json::from_str(INITIALIZE_REQUEST)?;
+    session_manager
+        .initialize_session(&session_id, initialize)
+        .await?;
+    let initialized: ClientJsonRpcMessage =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)?;
+    session_manager
+        .accept_message(&session_id, initialized)
+        .await?;
+    Ok((session_manager, worker))
+}
+
+#[tokio::test]
+async fn test_server_messages_keep_alive_but_not_idle() -> anyhow::Result<()> {
+    // the messages of the server are an activity for the keep alive
+    let pushes = CancellationToken::new();
+    let (_session_manager, mut worker) = session_with_server_pushes(
+        SessionConfig {
+            keep_alive: Some(Duration::from_millis(150)),
+            ..Default::default()
+        },
+        pushes.clone(),
+    )
+    .await?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!worker.is_finished());
+    pushes.cancel();
+    tokio::time::timeout(Duration::from_secs(1), &mut worker).await???;
+
+    // but not for the idle timeout, which only counts the client messages
+    let pushes = CancellationToken::new();
+    let (_session_manager, worker) = session_with_server_pushes(
+        SessionConfig {
+            keep_alive: Some(Duration::from_secs(60)),
+            idle_timeout: Some(Duration::from_millis(150)),
+            ..Default::default()
+        },
+        pushes.clone(),
+    )
+    .await?;
+    tokio::time::timeout(Duration::from_secs(1), worker).await???;
+    pushes.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sse_heartbeat() -> anyhow::Result<()> {
+    use futures::StreamExt;
+
+    let keep_alive = Duration::from_millis(200);
+    let (url, ct) = serve_with(SessionConfig::default(), Some(keep_alive)).await?;
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .body(INITIALIZE_REQUEST)
+        .send()
+        .await?;
+    let session_id = response
+        .headers()
+        .get("Mcp-Session-Id")
+        .expect("session id")
+        .to_str()?
+        .to_owned();
+    let mut stream = client
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("Mcp-Session-Id", session_id)
+        .send()
+        .await?
+        .bytes_stream();
+
+    // no mcp message is sent on the standalone stream, only heartbeats are expected
+    for _ in 0..2 {
+        let chunk = tokio::time::timeout(keep_alive * 2, stream.next())
+            .await?
+            .expect("stream is open")?;
+        assert_eq!(&chunk[..], b": heartbeat\n\n");
+    }
+    ct.cancel();
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct WhoAmI;
+
+impl rmcp::ServerHandler for WhoAmI {
+    async fn call_tool(
+        &self,
+        _request: rmcp::model::CallToolRequestParam,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let subject = context
+            .extensions
+            .get::<AuthContext>()
+            .and_then(|auth| auth.subject.clone())
+            .unwrap_or_default();
+        Ok(rmcp::model::CallToolResult::success(vec![
+            rmcp::model::Content::text(subject),
+        ]))
+    }
+}
+
+#[tokio::test]
+async fn test_bearer_token_auth() -> anyhow::Result<()> {
+    let service: StreamableHttpService<WhoAmI, LocalSessionManager> = StreamableHttpService::new(
+        || Ok(WhoAmI),
+        Default::default(),
+        StreamableHttpServerConfig {
+            stateful_mode: false,
+            auth_handler: Some(Arc::new(
+                BearerTokenAuth::new().with_token("secret", AuthContext::new("alice")),
+            )),
+            ..Default::default()
+        },
+    );
+    let (url, ct) = serve_service(service).await?;
+
+    let client = reqwest::Client::new();
+    let call = |token: Option<&str>| {
+        let mut request = client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"whoami"}}"#);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send()
+    };
+
+    let response = call(None).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()["WWW-Authenticate"], "Bearer");
+    let response = call(Some("guess")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = call(Some("secret")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body = response.text().await?;
+    assert!(body.contains(r#""text":"alice""#), "{body}");
+    ct.cancel();
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct ToolListWatcher {
+    changed: Arc<tokio::sync::Notify>,
+}
+
+impl rmcp::ClientHandler for ToolListWatcher {
+    async fn on_tool_list_changed(
+        &self,
+        _context: rmcp::service::NotificationContext<rmcp::RoleClient>,
+    ) {
+        self.changed.notify_one();
+    }
+}
+
+#[tokio::test]
+async fn test_broadcast_to_all_sessions() -> anyhow::Result<()> {
+    use rmcp::{ServiceExt, model::ServerNotification, transport::StreamableHttpClientTransport};
+
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            Default::default(),
+            Default::default(),
+        );
+    let registry = service.registry();
+    let (url, ct) = serve_service(service).await?;
+
+    let mut clients = vec![];
+    for _ in 0..3 {
+        let watcher = ToolListWatcher::default();
+        let changed = watcher.changed.clone();
+        let client = watcher
+            .serve(StreamableHttpClientTransport::from_uri(url.clone()))
+            .await?;
+        clients.push((client, changed));
+    }
+    // sessions are registered once the server side is initialized
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while registry.len().await < 3 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    let delivered = registry
+        .broadcast(ServerNotification::ToolListChangedNotification(
+            Default::default(),
+        ))
+        .await;
+    assert_eq!(delivered, 3);
+    for (_, changed) in &clients {
+        tokio::time::timeout(Duration::from_secs(1), changed.notified()).await?;
+    }
+
+    for (client, _) in clients {
+        client.cancel().await?;
+    }
+    ct.cancel();
+    Ok(())
+}
+
+/// Streams progress notifications, and holds the rest of them until released
+#[derive(Debug, Clone)]
+struct Streamer {
+    release: Arc<tokio::sync::Notify>,
+}
+
+impl rmcp::ServerHandler for Streamer {
+    async fn call_tool(
+        &self,
+        _request: rmcp::model::CallToolRequestParam,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let progress_token = context.meta.get_progress_token().expect("progress token");
+        for progress in 1..=5 {
+            if progress == 4 {
+                self.release.notified().await;
+            }
+            context
+                .peer
+                .notify_progress(rmcp::model::ProgressNotificationParam {
+                    progress_token: progress_token.clone(),
+                    progress: progress as f64,
+                    total: Some(5.0),
+                    message: None,
+                })
+                .await
+                .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
+        }
+        Ok(rmcp::model::CallToolResult::success(vec![]))
+    }
+}
+
+/// Read the next `count` sse events of the stream, as `(id, data)`
+async fn next_events(
+    stream: &mut (impl futures::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin),
+    buffer: &mut String,
+    count: usize,
+) -> anyhow::Result<Vec<(String, serde_json::Value)>> {
+    use futures::StreamExt;
+    let mut events = vec![];
+    while events.len() < count {
+        let Some(end) = buffer.find("\n\n") else {
+            let chunk = tokio::time::timeout(Duration::from_secs(1), stream.next())
+                .await?
+                .expect("stream is open")?;
+            buffer.push_str(std::str::from_utf8(&chunk)?);
+            continue;
+        };
+        let event: String = buffer.drain(..end + 2).collect();
+        let mut id = None;
+        let mut data = None;
+        for line in event.lines() {
+            if let Some(value) = line.strip_prefix("id:") {
+                id = Some(value.trim().to_owned());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data = Some(serde_json::from_str(value.trim())?);
+            }
+        }
+        if let (Some(id), Some(data)) = (id, data) {
+            events.push((id, data));
+        }
+    }
+    Ok(events)
+}
+
+#[tokio::test]
+async fn test_resume_replays_missed_events() -> anyhow::Result<()> {
+    let release = Arc::new(tokio::sync::Notify::new());
+    let service: StreamableHttpService<Streamer, LocalSessionManager> = StreamableHttpService::new(
+        {
+            let release = release.clone();
+            move || {
+                Ok(Streamer {
+                    release: release.clone(),
+                })
+            }
+        },
+        Default::default(),
+        Default::default(),
+    );
+    let (url, ct) = serve_service(service).await?;
+    let client = reqwest::Client::new();
+    let post = |session_id: Option<&str>, body: &'static str| {
+        let mut request = client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(body);
+        if let Some(session_id) = session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+        request.send()
+    };
+    let response = post(None, INITIALIZE_REQUEST).await?;
+    let session_id = response.headers()["Mcp-Session-Id"].to_str()?.to_owned();
+    post(
+        Some(&session_id),
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+    )
+    .await?;
+
+    let mut stream = post(
+        Some(&session_id),
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"stream","_meta":{"progressToken":"p"}}}"#,
+    )
+    .await?
+    .bytes_stream();
+    let mut buffer = String::new();
+    let received = next_events(&mut stream, &mut buffer, 2).await?;
+    assert_eq!(received[1].1["params"]["progress"], 2.0);
+    // the connection drops, the third progress notification is missed
+    drop(stream);
+
+    let mut stream = client
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("Mcp-Session-Id", &session_id)
+        .header("Last-Event-ID", &received[1].0)
+        .send()
+        .await?
+        .bytes_stream();
+    let mut buffer = String::new();
+    let replayed = next_events(&mut stream, &mut buffer, 1).await?;
+    assert_eq!(replayed[0].1["params"]["progress"], 3.0);
+
+    release.notify_one();
+    let rest = next_events(&mut stream, &mut buffer, 3).await?;
+    assert_eq!(rest[0].1["params"]["progress"], 4.0);
+    assert_eq!(rest[1].1["params"]["progress"], 5.0);
+    assert_eq!(rest[2].1["id"], 2);
+
+    ct.cancel();
+    Ok(())
+}
+
+/// Sends the session and arrival time of the roots list changes it's notified of
+#[derive(Debug, Clone)]
+struct RootsWatcher {
+    received: tokio::sync::mpsc::UnboundedSender<(Option<rmcp::service::SessionId>, Instant)>,
+}
+
+impl rmcp::ServerHandler for RootsWatcher {
+    async fn on_roots_list_changed(
+        &self,
+        context: rmcp::service::NotificationContext<rmcp::RoleServer>,
+    ) {
+        let _ = self
+            .received
+            .send((context.source_id().cloned(), context.arrived_at()));
+    }
+}
+
+#[tokio::test]
+async fn test_notification_source_and_arrival() -> anyhow::Result<()> {
+    use rmcp::{ServiceExt, transport::StreamableHttpClientTransport};
+
+    let (received, mut notifications) = tokio::sync::mpsc::unbounded_channel();
+    let service: StreamableHttpService<RootsWatcher, LocalSessionManager> =
+        StreamableHttpService::new(
+            move || {
+                Ok(RootsWatcher {
+                    received: received.clone(),
+                })
+            },
+            Default::default(),
+            Default::default(),
+        );
+    let registry = service.registry();
+    let (url, ct) = serve_service(service).await?;
+    let client = ().serve(StreamableHttpClientTransport::from_uri(url)).await?;
+
+    let sent_at = Instant::now();
+    client.notify_roots_list_changed().await?;
+    let (source_id, arrived_at) =
+        tokio::time::timeout(Duration::from_secs(1), notifications.recv())
+            .await?
+            .expect("notification received");
+    assert!(arrived_at >= sent_at);
+    assert!(arrived_at <= Instant::now());
+    let source_id = source_id.expect("sent in a session");
+    assert_eq!(registry.sessions().await, vec![source_id]);
+
+    client.cancel().await?;
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drain_closes_all_sessions() -> anyhow::Result<()> {
+    use rmcp::{ServiceExt, transport::StreamableHttpClientTransport};
+
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            Default::default(),
+            Default::default(),
+        );
+    let registry = service.registry();
+    let (url, ct) = serve_service(service.clone()).await?;
+
+    let mut clients = vec![];
+    for _ in 0..3 {
+        let client = ().serve(StreamableHttpClientTransport::from_uri(url.clone())).await?;
+        clients.push(client);
+    }
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while registry.len().await < 3 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    assert!(service.drain(Duration::from_secs(5)).await);
+    assert!(registry.is_empty().await);
+    let client = reqwest::Client::new();
+    assert_eq!(
+        initialize(&client, &url).await?,
+        reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    for client in clients {
+        client.cancel().await?;
+    }
+    ct.cancel();
+    Ok(())
+}
+
+/// Answers the pings after a while
+#[derive(Clone)]
+struct SlowPing;
+
+impl rmcp::ServerHandler for SlowPing {
+    async fn ping(
+        &self,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_drain_waits_for_stateless_requests() -> anyhow::Result<()> {
+    let service: StreamableHttpService<SlowPing, LocalSessionManager> = StreamableHttpService::new(
+        || Ok(SlowPing),
+        Default::default(),
+        StreamableHttpServerConfig {
+            stateful_mode: false,
+            sse_keep_alive: None,
+            ..Default::default()
+        },
+    );
+    let (url, ct) = serve_service(service.clone()).await?;
+    let client = reqwest::Client::new();
+    let ping = || {
+        client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#)
+            .send()
+    };
+
+    let response = ping().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let started = Instant::now();
+    assert!(service.drain(Duration::from_secs(5)).await);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    // the request in flight is answered
+    let body = tokio::time::timeout(Duration::from_secs(1), response.text()).await??;
+    assert!(body.contains(r#""result":{}"#), "{body}");
+
+    assert_eq!(
+        ping().await?.status(),
+        reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sse_event_type_prefix() -> anyhow::Result<()> {
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            Default::default(),
+            StreamableHttpServerConfig {
+                sse_keep_alive: None,
+                sse_event_type_prefix: Some("tenant".into()),
+                ..Default::default()
+            },
+        );
+    let (url, ct) = serve_service(service).await?;
+    let response = reqwest::Client::new()
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .body(INITIALIZE_REQUEST)
+        .send()
+        .await?;
+    let session_id = response
+        .headers()
+        .get("Mcp-Session-Id")
+        .expect("session id")
+        .to_str()?
+        .to_owned();
+    let body = response.text().await?;
+    assert!(
+        body.contains(&format!("event: tenant-{session_id}\n")),
+        "unexpected body: {body}"
+    );
+
+    ct.cancel();
+    Ok(())
+}
+
+async fn serve_with_cors(
+    cors: CorsConfig,
+    auth: bool,
+) -> anyhow::Result<(String, CancellationToken)> {
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            Default::default(),
+            StreamableHttpServerConfig {
+                cors,
+                auth_handler: auth.then(|| {
+                    Arc::new(BearerTokenAuth::new().with_token("secret", AuthContext::new("alice")))
+                        as _
+                }),
+                ..Default::default()
+            },
+        );
+    serve_service(service).await
+}
+
+fn preflight(client: &reqwest::Client, url: &str, origin: &str) -> reqwest::RequestBuilder {
+    client
+        .request(reqwest::Method::OPTIONS, url)
+        .header("Origin", origin)
+        .header("Access-Control-Request-Method", "POST")
+        .header(
+            "Access-Control-Request-Headers",
+            "content-type, mcp-session-id",
+        )
+}
+
+fn header<'a>(response: &'a reqwest::Response, name: &str) -> Option<&'a str> {
+    response
+        .headers()
+        .get(name)
+        .map(|value| value.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_cors_preflight() -> anyhow::Result<()> {
+    let origin = "https://app.example.com";
+    // the preflight is answered before the request is authenticated
+    let (url, ct) = serve_with_cors(CorsConfig::allow_origins(vec![origin]), true).await?;
+    let client = reqwest::Client::new();
+
+    let response = preflight(&client, &url, origin).send().await?;
+    assert!(response.status().is_success());
+    assert_eq!(
+        header(&response, "access-control-allow-origin"),
+        Some(origin)
+    );
+    assert!(
+        header(&response, "access-control-allow-methods")
+            .unwrap()
+            .contains("POST")
+    );
+    let allowed_headers = header(&response, "access-control-allow-headers").unwrap();
+    assert!(allowed_headers.contains("content-type"));
+    assert!(allowed_headers.contains("mcp-session-id"));
+    assert_eq!(header(&response, "access-control-max-age"), Some("3600"));
+    assert_eq!(header(&response, "access-control-allow-credentials"), None);
+
+    // another origin isn't allowed
+    let response = preflight(&client, &url, "https://evil.example.com")
+        .send()
+        .await?;
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    // the actual requests get the headers too, the session id being exposed to scripts
+    let response = client
+        .post(&url)
+        .header("Origin", origin)
+        .bearer_auth("secret")
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .body(INITIALIZE_REQUEST)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        header(&response, "access-control-allow-origin"),
+        Some(origin)
+    );
+    assert_eq!(
+        header(&response, "access-control-expose-headers"),
+        Some("mcp-session-id")
+    );
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cors_allow_all() -> anyhow::Result<()> {
+    let (url, ct) = serve_with_cors(CorsConfig::allow_all(), false).await?;
+    let client = reqwest::Client::new();
+    let origin = "http://localhost:5173";
+    let response = preflight(&client, &url, origin).send().await?;
+    assert!(response.status().is_success());
+    assert_eq!(header(&response, "access-control-allow-origin"), Some("*"));
+    assert_eq!(header(&response, "access-control-allow-credentials"), None);
+
+    // without cors, a preflight is an unsupported method
+    let (url, no_cors_ct) = serve_with_cors(CorsConfig::default(), false).await?;
+    let response = preflight(&client, &url, origin).send().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    ct.cancel();
+    no_cors_ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cors_credentials_for_listed_origins_only() -> anyhow::Result<()> {
+    let origin = "https://app.example.com";
+    let cors = CorsConfig {
+        allow_credentials: true,
+        ..CorsConfig::allow_origins(vec![origin])
+    };
+    let (url, ct) = serve_with_cors(cors, true).await?;
+    let client = reqwest::Client::new();
+    let credentialed = |origin: &str| {
+        client
+            .post(&url)
+            .header("Origin", origin)
+            .bearer_auth("secret")
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(INITIALIZE_REQUEST)
+    };
+
+    let response = credentialed(origin).send().await?;
+    assert_eq!(
+        header(&response, "access-control-allow-origin"),
+        Some(origin)
+    );
+    assert_eq!(
+        header(&response, "access-control-allow-credentials"),
+        Some("true")
+    );
+
+    // the browser of a foreign origin isn't allowed to read the response
+    let response = credentialed("https://evil.example.com").send().await?;
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    ct.cancel();
+    Ok(())
+}
+
+#[test]
+fn test_cors_wildcard_with_credentials_is_rejected() {
+    let cors = CorsConfig {
+        allow_credentials: true,
+        ..CorsConfig::allow_all()
+    };
+    assert_eq!(
+        cors.layer().unwrap_err(),
+        CorsConfigError::WildcardWithCredentials
+    );
+}
+
+#[tokio::test]
+async fn test_protocol_versions_refused_only_when_configured() -> anyhow::Result<()> {
+    const FUTURE_INITIALIZE_REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2099-01-01","capabilities":{},"clientInfo":{"name":"test","version":"0.1.0"}}}"#;
+    let serve_config = rmcp::service::ServeServerConfig::default().with_protocol_versions(
+        rmcp::model::ProtocolVersion::V_2024_11_05..=rmcp::model::ProtocolVersion::LATEST,
+    );
+    let client = reqwest::Client::new();
+    for (serve_config, refused) in [(Default::default(), false), (serve_config, true)] {
+        let service: StreamableHttpService<Calculator, LocalSessionManager> =
+            StreamableHttpService::new(
+                || Ok(Calculator::new()),
+                Default::default(),
+                StreamableHttpServerConfig {
+                    serve_config,
+                    ..Default::default()
+                },
+            );
+        let (url, ct) = serve_service(service).await?;
+        let response = client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(FUTURE_INITIALIZE_REQUEST)
+            .send()
+            .await?;
+        assert_eq!(response.headers().contains_key("mcp-session-id"), !refused);
+        let body = response.text().await?;
+        assert_eq!(
+            body.contains("unsupported protocol version 2099-01-01"),
+            refused,
+            "{body}"
+        );
+        ct.cancel();
+    }
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use rmcp::{
+    RoleServer, ServerHandler,
+    handler::server::{
+        completion::CachedCompletionService,
+        router::{
+            Router,
+            tool::{RoleBasedAcl, ToolRouter},
+        },
+        wrapper::Parameters,
+    },
+    model::{
+        ClientNotification, ClientRequest, ErrorCode, ServerCapabilities, ServerInfo, ServerResult,
+    },
+    schemars,
+    service::{NotificationContext, RequestContext},
+    tool, tool_handler, tool_router,
+    transport::{
+        StreamableHttpServerConfig,
+        streamable_http_server::{
+            auth::{AuthContext, BearerTokenAuth},
+            session::local::LocalSessionManager,
+            tower::StreamableHttpService,
+        },
+    },
+};
+use serde_json::{Value, json};
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct PathRequest {
+    path: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum DeleteMode {
+    Trash,
+    Purge,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct DeleteRequest {
+    path: String,
+    mode: Option<DeleteMode>,
+}
+
+#[derive(Debug, Clone)]
+struct Files {
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl Files {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router().with_acl(acl()),
+        }
+    }
+
+    #[tool(description = "Read a file")]
+    fn read(&self, Parameters(PathRequest { path }): Parameters<PathRequest>) -> String {
+        format!("content of {path}")
+    }
+
+    #[tool(description = "Delete a file")]
+    fn delete(&self, Parameters(request): Parameters<DeleteRequest>) -> String {
+        let path = request.path;
+        match request.mode {
+            Some(mode) => format!("deleted {path} ({mode:?})"),
+            None => format!("deleted {path}"),
+        }
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for Files {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+}
+
+fn acl() -> Arc<RoleBasedAcl> {
+    Arc::new(
+        RoleBasedAcl::new()
+            .with_role("reader", ["read"])
+            .with_role("admin", ["read", "delete"]),
+    )
+}
+
+fn with_roles(subject: &str, roles: Value) -> AuthContext {
+    let mut context = AuthContext::new(subject);
+    context.claims.insert("roles".into(), roles);
+    context
+}
+
+async fn send<S: rmcp::Service<RoleServer> + Send + 'static>(
+    service: &StreamableHttpService<S, LocalSessionManager>,
+    token: &str,
+    method: &str,
+    params: Value,
+) -> Value {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let request = http::Request::post("/mcp")
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Full::new(Bytes::from(body.to_string())))
+        .unwrap();
+    let response = service.handle(request).await;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    // the response is the data of the single event of the stream
+    let data = body
+        .lines()
+        .find_map(|line| line.strip_prefix("data:"))
+        .unwrap_or(&body);
+    serde_json::from_str(data.trim()).unwrap()
+}
+
+fn tool_names(response: &Value) -> Vec<&str> {
+    let mut names: Vec<_> = response["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    names
+}
+
+fn config() -> StreamableHttpServerConfig {
+    StreamableHttpServerConfig {
+        stateful_mode: false,
+        auth_handler: Some(Arc::new(
+            BearerTokenAuth::new()
+                .with_token("reader", with_roles("bob", json!("reader")))
+                .with_token("admin", with_roles("alice", json!(["reader", "admin"])))
+                .with_token("nobody", AuthContext::new("eve")),
+        )),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_role_based_tool_acl() {
+    let service: StreamableHttpService<Files, LocalSessionManager> =
+        StreamableHttpService::new(|| Ok(Files::new()), Default::default(), config());
+    let forbidden = ErrorCode::FORBIDDEN.0;
+
+    let list = send(&service, "admin", "tools/list", json!({})).await;
+    assert_eq!(tool_names(&list), ["delete", "read"]);
+    let list = send(&service, "reader", "tools/list", json!({})).await;
+    assert_eq!(tool_names(&list), ["read"]);
+    let list = send(&service, "nobody", "tools/list", json!({})).await;
+    assert!(tool_names(&list).is_empty());
+
+    let call = |name: &str| json!({ "name": name, "arguments": { "path": "/tmp/a" } });
+    let result = send(&service, "reader", "tools/call", call("read")).await;
+    assert_eq!(result["result"]["content"][0]["text"], "content of /tmp/a");
+    let result = send(&service, "reader", "tools/call", call("delete")).await;
+    assert_eq!(result["error"]["code"], forbidden);
+    assert_eq!(result["error"]["message"], "Forbidden");
+    // unknown tools are forbidden too, so their existence isn't leaked
+    let result = send(&service, "reader", "tools/call", call("format")).await;
+    assert_eq!(result["error"]["code"], forbidden);
+    let result = send(&service, "admin", "tools/call", call("delete")).await;
+    assert_eq!(result["result"]["content"][0]["text"], "deleted /tmp/a");
+}
+
+fn files_router() -> Router<Files> {
+    let mut router = Router::new(Files::new()).with_tools(Files::tool_router().map.into_values());
+    router.tool_router = router.tool_router.with_acl(acl());
+    router
+}
+
+fn complete(name: &str) -> Value {
+    json!({
+        "ref": { "type": "ref/tool", "name": name },
+        "argument": { "name": "mode", "value": "" }
+    })
+}
+
+#[tokio::test]
+async fn test_denied_tool_is_not_completed() {
+    let service: StreamableHttpService<Router<Files>, LocalSessionManager> =
+        StreamableHttpService::new(|| Ok(files_router()), Default::default(), config());
+
+    let result = send(&service, "admin", "completion/complete", complete("delete")).await;
+    assert_eq!(
+        result["result"]["completion"]["values"],
+        json!(["trash", "purge"])
+    );
+    // the denied tool answers like an unknown one, without the values of its schema
+    for name in ["delete", "format"] {
+        let result = send(&service, "reader", "completion/complete", complete(name)).await;
+        assert_eq!(result["error"]["code"], ErrorCode::FORBIDDEN.0, "{name}");
+        assert!(result.get("result").is_none());
+    }
+}
+
+/// The service of every request of a stateless server, so they share the completion cache
+#[derive(Clone)]
+struct SharedCache(Arc<CachedCompletionService<Router<Files>>>);
+
+impl rmcp::Service<RoleServer> for SharedCache {
+    async fn handle_request(
+        &self,
+        request: ClientRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ServerResult, rmcp::ErrorData> {
+        self.0.handle_request(request, context).await
+    }
+
+    async fn handle_notification(
+        &self,
+        notification: ClientNotification,
+        context: NotificationContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        self.0.handle_notification(notification, context).await
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.0.get_info()
+    }
+}
+
+#[tokio::test]
+async fn test_cached_completions_are_not_shared_by_clients() {
+    let cached = SharedCache(Arc::new(CachedCompletionService::new(files_router())));
+    let service: StreamableHttpService<SharedCache, LocalSessionManager> =
+        StreamableHttpService::new(move || Ok(cached.clone()), Default::default(), config());
+
+    let result = send(&service, "admin", "completion/complete", complete("delete")).await;
+    assert_eq!(
+        result["result"]["completion"]["values"],
+        json!(["trash", "purge"])
+    );
+    // the completion cached for the admin is not served to the reader
+    let result = send(
+        &service,
+        "reader",
+        "completion/complete",
+        complete("delete"),
+    )
+    .await;
+    assert_eq!(result["error"]["code"], ErrorCode::FORBIDDEN.0);
+}
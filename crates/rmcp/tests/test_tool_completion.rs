@@ -0,0 +1,90 @@
+use rmcp::{
+    Peer, RoleClient, RoleServer, ServerHandler,
+    handler::server::{router::Router, wrapper::Parameters},
+    model::{ArgumentInfo, CompleteRequestParam, CompleteResult, CompletionInfo, Reference},
+    schemars,
+    service::RequestContext,
+    tool, tool_router,
+};
+use rmcp_test_macros::mcp_integration_test;
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Unit {
+    Celsius,
+    Kelvin,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ConvertRequest {
+    unit: Unit,
+    value: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Converter;
+
+#[tool_router]
+impl Converter {
+    #[tool(description = "Convert a temperature")]
+    fn convert(&self, Parameters(request): Parameters<ConvertRequest>) -> String {
+        format!("{} {:?}", request.value, request.unit)
+    }
+}
+
+impl ServerHandler for Converter {
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, rmcp::ErrorData> {
+        if request.argument.value == "k" {
+            return Err(rmcp::ErrorData::internal_error(
+                "unit catalog unavailable",
+                None,
+            ));
+        }
+        // a unit unknown to the schema, and a duplicate
+        let values = match request.argument.name.as_str() {
+            "unit" => vec!["rankine".into(), "kelvin".into()],
+            _ => vec![],
+        };
+        Ok(CompleteResult {
+            completion: CompletionInfo {
+                values,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+fn router() -> Router<Converter> {
+    Router::new(Converter).with_tools(Converter::tool_router().map.into_values())
+}
+
+#[mcp_integration_test(server = router())]
+async fn test_router_completes_tool_arguments(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    let request = |name: &str, argument: &str, value: &str| CompleteRequestParam {
+        r#ref: Reference::for_tool(name),
+        argument: ArgumentInfo {
+            name: argument.into(),
+            value: value.into(),
+        },
+        context: None,
+    };
+    // the values of the service are merged after the ones of the schema
+    let result = client.complete(request("convert", "unit", "")).await?;
+    assert_eq!(
+        result.completion.values,
+        vec!["celsius", "kelvin", "rankine"]
+    );
+    let result = client.complete(request("convert", "unit", "ra")).await?;
+    assert_eq!(result.completion.values, vec!["rankine", "kelvin"]);
+    // the completion of the schema is kept when the service fails
+    let result = client.complete(request("convert", "unit", "k")).await?;
+    assert_eq!(result.completion.values, vec!["kelvin"]);
+    // without suggested values, the request goes to the service
+    let result = client.complete(request("convert", "value", "")).await?;
+    assert!(result.completion.values.is_empty());
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use rmcp::{
+    ClientHandler, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    handler::server::{
+        router::{fs_watch::FsWatchedToolRouter, tool::ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+    },
+    model::{
+        CallToolRequestParam, CallToolResult, ListToolsResult, PaginatedRequestParam,
+        ServerCapabilities, ServerInfo,
+    },
+    schemars,
+    service::{NotificationContext, RequestContext},
+    tool, tool_router,
+};
+use serde_json::json;
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct SumRequest {
+    a: i32,
+    b: i32,
+}
+
+#[derive(Clone)]
+struct Calculator {
+    tools: FsWatchedToolRouter<Self>,
+}
+
+#[tool_router]
+impl Calculator {
+    #[tool(description = "Calculate the sum of two numbers")]
+    fn sum(&self, Parameters(SumRequest { a, b }): Parameters<SumRequest>) -> String {
+        (a + b).to_string()
+    }
+}
+
+impl ServerHandler for Calculator {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+            ..Default::default()
+        }
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        self.tools
+            .call(ToolCallContext::new(self, request, context))
+            .await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        Ok(ListToolsResult::with_all_items(self.tools.list_all()))
+    }
+}
+
+#[derive(Clone, Default)]
+struct Watcher {
+    changed: Arc<tokio::sync::Notify>,
+}
+
+impl ClientHandler for Watcher {
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        self.changed.notify_one();
+    }
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rmcp-tool-hot-reload-{}-{:?}",
+        std::process::id(),
+        std::time::SystemTime::now()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_definition(dir: &std::path::Path, description: &str) {
+    let definition = json!({
+        "name": "sum",
+        "description": description,
+        "inputSchema": {
+            "type": "object",
+            "properties": { "a": { "type": "integer" }, "b": { "type": "integer" } },
+            "required": ["a", "b"]
+        }
+    });
+    std::fs::write(dir.join("sum.json"), definition.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn test_tool_definitions_are_reloaded() -> anyhow::Result<()> {
+    let dir = temp_dir();
+    write_definition(&dir, "Add two integers");
+    // a definition without route is skipped
+    std::fs::write(
+        dir.join("mul.json"),
+        json!({ "name": "mul", "inputSchema": {} }).to_string(),
+    )?;
+
+    let tools = FsWatchedToolRouter::new(Calculator::tool_router(), &dir)
+        .with_poll_interval(Duration::from_millis(20));
+    let watcher = Watcher::default();
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (server, client) = tokio::join!(
+        Calculator {
+            tools: tools.clone()
+        }
+        .serve(server_transport),
+        watcher.clone().serve(client_transport)
+    );
+    let (server, client) = (server?, client?);
+    tools.set_peer(server.peer().clone());
+    let watching = tools.watch();
+
+    let listed = client.list_tools(Default::default()).await?;
+    assert_eq!(listed.tools.len(), 1);
+    assert_eq!(
+        listed.get("sum").unwrap().description.as_deref(),
+        Some("Add two integers")
+    );
+
+    write_definition(&dir, "Add two integers, reloaded");
+    tokio::time::timeout(Duration::from_secs(5), watcher.changed.notified()).await?;
+    let listed = client.list_tools(Default::default()).await?;
+    assert_eq!(
+        listed.get("sum").unwrap().description.as_deref(),
+        Some("Add two integers, reloaded")
+    );
+    // the route keeps its handler
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "sum".into(),
+            arguments: json!({ "a": 1, "b": 2 }).as_object().cloned(),
+        })
+        .await?;
+    assert_eq!(result.content[0].as_text().unwrap().text, "3");
+
+    // without its file, a tool is back to its definition in code
+    std::fs::remove_file(dir.join("sum.json"))?;
+    tokio::time::timeout(Duration::from_secs(5), watcher.changed.notified()).await?;
+    let listed = client.list_tools(Default::default()).await?;
+    assert_eq!(
+        listed.get("sum").unwrap().description.as_deref(),
+        Some("Calculate the sum of two numbers")
+    );
+
+    // the changes are no longer loaded once the guard is dropped
+    drop(watching);
+    write_definition(&dir, "Add two integers, not reloaded");
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let listed = client.list_tools(Default::default()).await?;
+    assert_eq!(
+        listed.get("sum").unwrap().description.as_deref(),
+        Some("Calculate the sum of two numbers")
+    );
+
+    client.cancel().await?;
+    server.cancel().await?;
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_router_without_directory() {
+    let tools: FsWatchedToolRouter<Calculator> =
+        FsWatchedToolRouter::new(ToolRouter::new(), "/nonexistent/rmcp/tools");
+    assert!(tools.list_all().is_empty());
+}
@@ -7,11 +7,17 @@
 use std::sync::Arc;
 
 use rmcp::{
-    ClientHandler, ServerHandler, ServiceExt,
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolRequestParam, ClientInfo},
+    ClientHandler, RoleClient, ServerHandler,
+    handler::server::{
+        router::tool::ToolRouter,
+        tool::ToolError,
+        wrapper::{Json, Parameters},
+    },
+    model::{CallToolRequestParam, ClientInfo, ErrorCode},
+    service::Peer,
     tool, tool_handler, tool_router,
 };
+use rmcp_test_macros::mcp_integration_test;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -141,6 +147,53 @@ async fn test_tool_macros_with_generics() {
     assert_eq!(server.get_data().await, "mock data");
 }
 
+/// Value of the echo tool.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct EchoValue<T> {
+    /// The value.
+    pub value: T,
+}
+
+/// Server echoing a value of any type.
+#[derive(Debug, Clone)]
+pub struct EchoServer<T>(std::marker::PhantomData<T>);
+
+#[tool_router]
+impl<T> EchoServer<T>
+where
+    T: JsonSchema + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    #[tool(description = "Echo the value")]
+    async fn echo(&self, Parameters(value): Parameters<EchoValue<T>>) -> Json<EchoValue<T>> {
+        Json(value)
+    }
+}
+
+#[test]
+fn test_tool_attr_is_cached() {
+    let first = Server::get_weather_tool_attr();
+    let second = Server::get_weather_tool_attr();
+    assert_eq!(first, second);
+    assert!(Arc::ptr_eq(&first.input_schema, &second.input_schema));
+
+    // a tool of a generic impl is cached per instance of the generics
+    let text = EchoServer::<String>::echo_tool_attr();
+    let number = EchoServer::<i64>::echo_tool_attr();
+    assert_eq!(text.input_schema["properties"]["value"]["type"], "string");
+    assert_eq!(
+        number.input_schema["properties"]["value"]["type"],
+        "integer"
+    );
+    assert_eq!(
+        number.output_schema.unwrap()["properties"]["value"]["type"],
+        "integer"
+    );
+    assert_eq!(
+        EchoServer::<String>::tool_router().list_all(),
+        vec![EchoServer::<String>::echo_tool_attr()]
+    );
+}
+
 #[tokio::test]
 async fn test_tool_macros_with_optional_param() {
     let _attr = Server::get_weather_tool_attr();
@@ -292,21 +345,11 @@ impl ClientHandler for DummyClientHandler {
     }
 }
 
-#[tokio::test]
-async fn test_optional_i64_field_with_null_input() -> anyhow::Result<()> {
-    let (server_transport, client_transport) = tokio::io::duplex(4096);
-
-    // Server setup
-    let server = OptionalSchemaTester::new();
-    let server_handle = tokio::spawn(async move {
-        server.serve(server_transport).await?.waiting().await?;
-        anyhow::Ok(())
-    });
-
-    // Create a simple client handler that just forwards tool calls
-    let client_handler = DummyClientHandler::default();
-    let client = client_handler.serve(client_transport).await?;
-
+#[mcp_integration_test(
+    server = OptionalSchemaTester::new(),
+    client = DummyClientHandler::default()
+)]
+async fn test_optional_i64_field_with_null_input(client: Peer<RoleClient>) -> anyhow::Result<()> {
     // Test null case
     let result = client
         .call_tool(CallToolRequestParam {
@@ -362,8 +405,113 @@ async fn test_optional_i64_field_with_null_input() -> anyhow::Result<()> {
         some_result_text, "Received count: 42",
         "Some case should return expected message"
     );
+    Ok(())
+}
+
+/// Parameters of the divide tool.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct DivideRequest {
+    /// The dividend.
+    pub a: i64,
+    /// The divisor.
+    pub b: i64,
+}
+
+/// A server whose tool fails with a [`ToolError`].
+#[derive(Debug, Clone)]
+pub struct ToolErrorServer {
+    tool_router: ToolRouter<Self>,
+}
+
+impl Default for ToolErrorServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tool_router]
+impl ToolErrorServer {
+    /// Create a new server.
+    pub fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    /// Divide a by b.
+    #[tool]
+    async fn divide(
+        &self,
+        Parameters(DivideRequest { a, b }): Parameters<DivideRequest>,
+    ) -> Result<String, ToolError> {
+        if b == 0 {
+            return Err(ToolError::InvalidParams("`b` must not be zero".into()));
+        }
+        Ok((a / b).to_string())
+    }
+
+    /// Always unavailable.
+    #[tool]
+    async fn maintenance(&self) -> Result<String, ToolError> {
+        Err(ToolError::Unavailable)
+    }
 
-    client.cancel().await?;
-    server_handle.await??;
+    /// Always fails.
+    #[tool]
+    async fn query(&self) -> Result<String, ToolError> {
+        Err(ToolError::internal(
+            "connection to db.internal:5432 refused",
+        ))
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for ToolErrorServer {}
+
+#[mcp_integration_test(
+    server = ToolErrorServer::new(),
+    client = DummyClientHandler::default()
+)]
+async fn test_tool_error_return_type(client: Peer<RoleClient>) -> anyhow::Result<()> {
+    let divide = |b: i64| CallToolRequestParam {
+        name: "divide".into(),
+        arguments: serde_json::json!({ "a": 6, "b": b }).as_object().cloned(),
+    };
+    let result = client.call_tool(divide(3)).await?;
+    assert_eq!(result.is_error, Some(false));
+
+    let error = client.call_tool(divide(0)).await.unwrap_err();
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected an mcp error, got {error:?}");
+    };
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    assert_eq!(error.message, "invalid params: `b` must not be zero");
+
+    let error = client
+        .call_tool(CallToolRequestParam {
+            name: "maintenance".into(),
+            arguments: None,
+        })
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected an mcp error, got {error:?}");
+    };
+    assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+
+    // the detail of an internal error stays on the server
+    let error = client
+        .call_tool(CallToolRequestParam {
+            name: "query".into(),
+            arguments: None,
+        })
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected an mcp error, got {error:?}");
+    };
+    assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+    assert_eq!(error.message, "internal error");
+    assert_eq!(error.data, None);
     Ok(())
 }
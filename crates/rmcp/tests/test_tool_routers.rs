@@ -66,3 +66,106 @@ where
     H: CallToolHandler<S, A>,
 {
 }
+
+#[derive(Debug, schemars::JsonSchema, serde::Deserialize, serde::Serialize)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+#[derive(Debug, schemars::JsonSchema, serde::Deserialize, serde::Serialize)]
+pub struct Convert {
+    pub unit: Unit,
+    #[schemars(extend("examples" = ["Paris", "Tokyo"]), default = "default_city")]
+    pub city: String,
+    pub value: f64,
+}
+
+fn default_city() -> String {
+    "London".into()
+}
+
+#[rmcp::tool]
+async fn convert(Parameters(Convert { city, .. }): Parameters<Convert>) {
+    drop(city)
+}
+
+#[test]
+fn test_tool_router_complete_from_schema() {
+    use rmcp::model::{ArgumentInfo, CompleteRequestParam, Reference};
+
+    let router = ToolRouter::<TestHandler<()>>::new().with_route((convert_tool_attr(), convert));
+    let request = |name: &str, argument: &str, value: &str| CompleteRequestParam {
+        r#ref: Reference::for_tool(name),
+        argument: ArgumentInfo {
+            name: argument.into(),
+            value: value.into(),
+        },
+        context: None,
+    };
+
+    let completion = router.complete(&request("convert", "unit", "")).unwrap();
+    assert_eq!(completion.values, vec!["Celsius", "Fahrenheit", "Kelvin"]);
+    let completion = router.complete(&request("convert", "unit", "kel")).unwrap();
+    assert_eq!(completion.values, vec!["Kelvin"]);
+    let completion = router.complete(&request("convert", "city", "")).unwrap();
+    assert_eq!(completion.values, vec!["Paris", "Tokyo", "London"]);
+
+    assert!(router.complete(&request("convert", "value", "")).is_none());
+    assert!(router.complete(&request("unknown", "unit", "")).is_none());
+    let mut prompt_request = request("convert", "unit", "");
+    prompt_request.r#ref = Reference::for_prompt("convert");
+    assert!(router.complete(&prompt_request).is_none());
+
+    let completion = router.complete_argument("convert", "unit", "fahr");
+    assert_eq!(completion.values, vec!["Fahrenheit"]);
+    assert!(
+        router
+            .complete_argument("convert", "value", "")
+            .values
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_router_infers_capabilities() {
+    use rmcp::{Service, handler::server::router::Router, model::ToolsCapability};
+
+    let router = Router::new(TestHandler::<()>::default());
+    assert_eq!(router.capabilities().tools, None);
+
+    let router = router.with_tools(TestHandler::<()>::test_router_1().map.into_values());
+    let info = router.get_info();
+    assert_eq!(info.capabilities.tools, Some(ToolsCapability::default()));
+    assert_eq!(info.capabilities.prompts, None);
+}
+
+#[cfg(feature = "schema-validation")]
+#[test]
+fn test_route_validates_against_its_current_schema() {
+    use rmcp::handler::server::{
+        router::tool::{IntoToolRoute, ToolRoute},
+        tool::schema_for_type,
+    };
+
+    let mut route: ToolRoute<TestHandler> =
+        (async_function_tool_attr(), async_function).into_tool_route();
+    let fields = serde_json::json!({ "fields": { "key": "value" } });
+    let fields = fields.as_object().unwrap();
+    let sum = serde_json::json!({ "a": 1, "b": 2 });
+    let sum = sum.as_object().unwrap();
+    assert_eq!(route.validate_input(fields), Ok(()));
+    assert!(route.validate_input(sum).is_err());
+
+    let mut attr = route.attr.clone();
+    attr.input_schema = schema_for_type::<Sum>().into();
+    route.set_attr(attr);
+    assert_eq!(route.validate_input(sum), Ok(()));
+    assert!(route.validate_input(fields).is_err());
+
+    // the compiled schema isn't used once the one of the tool is replaced
+    route.attr.input_schema = schema_for_type::<Request>().into();
+    assert_eq!(route.validate_input(fields), Ok(()));
+    assert!(route.validate_input(sum).is_err());
+}
@@ -0,0 +1,3 @@
+rmcp::elicit_safe!(String);
+
+fn main() {}
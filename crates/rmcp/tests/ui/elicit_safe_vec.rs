@@ -0,0 +1,8 @@
+#[derive(schemars::JsonSchema)]
+struct Item {
+    _name: String,
+}
+
+rmcp::elicit_safe!(Item, Vec<Item>);
+
+fn main() {}
@@ -0,0 +1,59 @@
+//! Print the tools, prompts and resources of a stdio server, e.g. for an IDE plugin.
+//!
+//! ```sh
+//! cargo run --example clients_mcp_inspect -- uvx mcp-server-git
+//! cargo run --example clients_mcp_inspect -- --json-schema npx -y @modelcontextprotocol/server-everything
+//! ```
+use anyhow::Result;
+use clap::Parser;
+use rmcp::{
+    ServiceExt,
+    transport::{ConfigureCommandExt, TokioChildProcess},
+};
+use tokio::process::Command;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Parser)]
+#[command(name = "clients_mcp_inspect")]
+#[command(about = "Print the spec of an MCP server launched as a child process")]
+struct Args {
+    /// Print the spec as a JSON Schema document, instead of the listed items
+    #[arg(long)]
+    json_schema: bool,
+
+    /// The server command
+    command: String,
+
+    /// The arguments of the server command
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // logs go to stderr, stdout is the spec only
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "warn".into()),
+        )
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .init();
+    let args = Args::parse();
+
+    let client = ()
+        .serve(TokioChildProcess::new(
+            Command::new(&args.command).configure(|cmd| {
+                cmd.args(&args.args);
+            }),
+        )?)
+        .await?;
+    let spec = client.fetch_server_spec().await?;
+    let output = if args.json_schema {
+        spec.to_json_schema()
+    } else {
+        serde_json::to_value(&spec)?
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    client.cancel().await?;
+    Ok(())
+}
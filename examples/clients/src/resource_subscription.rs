@@ -0,0 +1,55 @@
+//! Subscribe to a resource and print each of its updates
+//!
+//! Starts the `servers_resource_updates_stdio` example and reads `clock://ticks` every time
+//! the server notifies a change, until ten updates were received.
+use anyhow::Result;
+use futures::StreamExt;
+use rmcp::{ServiceExt, model::ReadResourceRequestParam, transport::TokioChildProcess};
+use tokio::process::Command;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const UPDATES: usize = 10;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| format!("info,{}=debug", env!("CARGO_CRATE_NAME")).into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let servers_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("servers");
+    let mut server_cmd = Command::new("cargo");
+    server_cmd
+        .current_dir(servers_dir)
+        .arg("run")
+        .arg("--example")
+        .arg("servers_resource_updates_stdio");
+    let client = ().serve(TokioChildProcess::new(server_cmd)?).await?;
+
+    let mut updates = client.subscribe_resource("clock://ticks").await?;
+    for _ in 0..UPDATES {
+        let Some(update) = updates.next().await else {
+            break;
+        };
+        let resource = client
+            .read_resource(ReadResourceRequestParam {
+                uri: update.uri.clone(),
+            })
+            .await?;
+        if let Some(text) = resource.contents.first().and_then(|c| match c {
+            rmcp::model::ResourceContents::TextResourceContents { text, .. } => Some(text),
+            _ => None,
+        }) {
+            tracing::info!("{} updated: {text}", update.uri);
+        }
+    }
+    updates.unsubscribe().await?;
+
+    client.cancel().await?;
+    Ok(())
+}
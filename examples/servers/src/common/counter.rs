@@ -1,8 +1,8 @@
 #![allow(dead_code)]
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use rmcp::{
-    ErrorData as McpError, RoleServer, ServerHandler,
+    ErrorData as McpError, Peer, RoleServer, ServerHandler,
     handler::server::{
         router::{prompt::PromptRouter, tool::ToolRouter},
         wrapper::Parameters,
@@ -36,9 +36,17 @@ pub struct CounterAnalysisArgs {
     pub strategy: Option<String>,
 }
 
+/// The resource holding the value of the counter, updated by the tools
+pub const COUNTER_URI: &str = "counter://value";
+
 #[derive(Clone)]
 pub struct Counter {
     counter: Arc<Mutex<i32>>,
+    /// The subscribed resources, with the peer to notify of their updates.
+    ///
+    /// Every instance of the server is bound to a single session, so a resource has at most one
+    /// subscriber.
+    subscribers: Arc<Mutex<HashMap<String, Peer<RoleServer>>>>,
     tool_router: ToolRouter<Counter>,
     prompt_router: PromptRouter<Counter>,
 }
@@ -49,6 +57,7 @@ impl Counter {
     pub fn new() -> Self {
         Self {
             counter: Arc::new(Mutex::new(0)),
+            subscribers: Default::default(),
             tool_router: Self::tool_router(),
             prompt_router: Self::prompt_router(),
         }
@@ -58,22 +67,35 @@ impl Counter {
         RawResource::new(uri, name.to_string()).no_annotation()
     }
 
+    /// Send a `notifications/resources/updated` to the subscribers of the resource, and only them
+    async fn notify_updated(&self, uri: &str) {
+        let subscriber = self.subscribers.lock().await.get(uri).cloned();
+        if let Some(peer) = subscriber {
+            let param = ResourceUpdatedNotificationParam { uri: uri.into() };
+            if let Err(e) = peer.notify_resource_updated(param).await {
+                tracing::warn!("failed to notify the update of {uri}: {e}");
+            }
+        }
+    }
+
     #[tool(description = "Increment the counter by 1")]
     async fn increment(&self) -> Result<CallToolResult, McpError> {
         let mut counter = self.counter.lock().await;
         *counter += 1;
-        Ok(CallToolResult::success(vec![Content::text(
-            counter.to_string(),
-        )]))
+        let value = counter.to_string();
+        drop(counter);
+        self.notify_updated(COUNTER_URI).await;
+        Ok(CallToolResult::success(vec![Content::text(value)]))
     }
 
     #[tool(description = "Decrement the counter by 1")]
     async fn decrement(&self) -> Result<CallToolResult, McpError> {
         let mut counter = self.counter.lock().await;
         *counter -= 1;
-        Ok(CallToolResult::success(vec![Content::text(
-            counter.to_string(),
-        )]))
+        let value = counter.to_string();
+        drop(counter);
+        self.notify_updated(COUNTER_URI).await;
+        Ok(CallToolResult::success(vec![Content::text(value)]))
     }
 
     #[tool(description = "Get the current counter value")]
@@ -170,6 +192,7 @@ impl ServerHandler for Counter {
             capabilities: ServerCapabilities::builder()
                 .enable_prompts()
                 .enable_resources()
+                .enable_resources_subscribe()
                 .enable_tools()
                 .build(),
             server_info: Implementation::from_build_env(),
@@ -186,6 +209,7 @@ impl ServerHandler for Counter {
             resources: vec![
                 self._create_resource_text("str:////Users/to/some/path/", "cwd"),
                 self._create_resource_text("memo://insights", "memo-name"),
+                self._create_resource_text(COUNTER_URI, "counter"),
             ],
             next_cursor: None,
         })
@@ -209,6 +233,12 @@ impl ServerHandler for Counter {
                     contents: vec![ResourceContents::text(memo, uri)],
                 })
             }
+            COUNTER_URI => {
+                let value = self.counter.lock().await.to_string();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(value, uri)],
+                })
+            }
             _ => Err(McpError::resource_not_found(
                 "resource_not_found",
                 Some(json!({
@@ -218,6 +248,32 @@ impl ServerHandler for Counter {
         }
     }
 
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if uri != COUNTER_URI {
+            return Err(McpError::resource_not_found(
+                "resource_not_found",
+                Some(json!({
+                    "uri": uri
+                })),
+            ));
+        }
+        self.subscribers.lock().await.insert(uri, context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscribers.lock().await.remove(&uri);
+        Ok(())
+    }
+
     async fn list_resource_templates(
         &self,
         _request: Option<PaginatedRequestParam>,
@@ -0,0 +1,45 @@
+//! The counter server with three middlewares: a timeout, a tracing span per request and an
+//! audit trail logged to stderr.
+//!
+//! Run with MCP Inspector:
+//! ```bash
+//! npx @modelcontextprotocol/inspector cargo run -p mcp-server-examples --example servers_counter_layered_stdio
+//! ```
+use std::time::Duration;
+
+use anyhow::Result;
+use common::counter::Counter;
+use rmcp::{
+    ServiceExt,
+    handler::server::audit::{AuditLayer, AuditRecord},
+    service::{TimeoutLayer, TracingLayer},
+    transport::stdio,
+};
+use tracing_subscriber::{self, EnvFilter};
+mod common;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into()))
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .init();
+
+    tracing::info!("Starting layered MCP server");
+
+    // the last layer added is the outermost one, so the audit records the timed out requests
+    let service = Counter::new()
+        .with_layer(TimeoutLayer::new(Duration::from_secs(30)))
+        .with_layer(AuditLayer::new(|record: AuditRecord| async move {
+            tracing::info!(method = record.method, success = record.success, "audit");
+        }))
+        .with_layer(TracingLayer);
+
+    let service = service.serve(stdio()).await.inspect_err(|e| {
+        tracing::error!("serving error: {:?}", e);
+    })?;
+
+    service.waiting().await?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
@@ -27,10 +29,15 @@ async fn main() -> anyhow::Result<()> {
         Default::default(),
     );
 
+    // close the sessions before stopping, giving them 10 seconds
+    let shutdown = service.drain_on_signal(
+        async { tokio::signal::ctrl_c().await.unwrap() },
+        Duration::from_secs(10),
+    );
     let router = axum::Router::new().nest_service("/mcp", service);
     let tcp_listener = tokio::net::TcpListener::bind(BIND_ADDRESS).await?;
     let _ = axum::serve(tcp_listener, router)
-        .with_graceful_shutdown(async { tokio::signal::ctrl_c().await.unwrap() })
+        .with_graceful_shutdown(shutdown)
         .await;
     Ok(())
 }
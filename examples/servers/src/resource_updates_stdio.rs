@@ -0,0 +1,132 @@
+//! MCP Server demonstrating resource subscriptions
+//!
+//! The `clock://ticks` resource counts the seconds since the server started, and the subscribed
+//! client is sent a `notifications/resources/updated` every time it changes.
+//!
+//! Run with the matching client:
+//! ```bash
+//! cargo run -p mcp-client-examples --example clients_resource_subscription
+//! ```
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Result;
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer, ServerHandler, ServiceExt, model::*,
+    service::RequestContext, transport::stdio,
+};
+use tracing_subscriber::{self, EnvFilter};
+
+const TICKS_URI: &str = "clock://ticks";
+
+#[derive(Clone, Default)]
+pub struct ClockServer {
+    ticks: Arc<Mutex<u64>>,
+    subscriber: Arc<Mutex<Option<Peer<RoleServer>>>>,
+}
+
+impl ClockServer {
+    /// Update the resource every second, and notify the subscriber
+    fn start(&self) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                *this.ticks.lock().unwrap() += 1;
+                let subscriber = this.subscriber.lock().unwrap().clone();
+                if let Some(peer) = subscriber {
+                    let param = ResourceUpdatedNotificationParam {
+                        uri: TICKS_URI.into(),
+                    };
+                    if let Err(e) = peer.notify_resource_updated(param).await {
+                        tracing::warn!("failed to notify the update: {e}");
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl ServerHandler for ClockServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+            instructions: Some("Subscribe to clock://ticks to be notified every second".into()),
+            ..Default::default()
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: vec![RawResource::new(TICKS_URI, "ticks").no_annotation()],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        if uri != TICKS_URI {
+            return Err(McpError::resource_not_found("resource not found", None));
+        }
+        let ticks = *self.ticks.lock().unwrap();
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(ticks.to_string(), uri)],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if uri != TICKS_URI {
+            return Err(McpError::resource_not_found("resource not found", None));
+        }
+        tracing::info!("client subscribed to {uri}");
+        *self.subscriber.lock().unwrap() = Some(context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        tracing::info!("client unsubscribed from {uri}");
+        *self.subscriber.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into()))
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .init();
+
+    let server = ClockServer::default();
+    server.start();
+    let service = server.serve(stdio()).await.inspect_err(|e| {
+        tracing::error!("serving error: {:?}", e);
+    })?;
+
+    service.waiting().await?;
+    Ok(())
+}
@@ -89,6 +89,32 @@ impl ServerHandler for SamplingDemoServer {
                 ))]))
             }
 
+            "ask_llm_follow_up" => {
+                // A multi-turn conversation built with the request builder
+                let params = CreateMessageRequestParam::builder()
+                    .system_prompt("You are a concise geography assistant.")
+                    .user("What is the capital of France?")
+                    .assistant("Paris.")
+                    .user("And what is its population?")
+                    .max_tokens(150)
+                    .build();
+                let response = context.peer.create_message(params).await.map_err(|e| {
+                    ErrorData::new(
+                        ErrorCode::INTERNAL_ERROR,
+                        format!("Sampling request failed: {}", e),
+                        None,
+                    )
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(
+                    response
+                        .message
+                        .content
+                        .as_text()
+                        .map(|t| t.text.clone())
+                        .unwrap_or_else(|| "No text response".to_string()),
+                )]))
+            }
+
             _ => Err(ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
                 format!("Unknown tool: {}", request.name),
@@ -103,27 +129,47 @@ impl ServerHandler for SamplingDemoServer {
         _context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, ErrorData> {
         Ok(ListToolsResult {
-            tools: vec![Tool {
-                name: "ask_llm".into(),
-                title: None,
-                description: Some("Ask a question to the LLM through sampling".into()),
-                input_schema: Arc::new(
-                    serde_json::from_value(serde_json::json!({
-                        "type": "object",
-                        "properties": {
-                            "question": {
-                                "type": "string",
-                                "description": "The question to ask the LLM"
-                            }
-                        },
-                        "required": ["question"]
-                    }))
-                    .unwrap(),
-                ),
-                output_schema: None,
-                annotations: None,
-                icons: None,
-            }],
+            tools: vec![
+                Tool {
+                    name: "ask_llm".into(),
+                    title: None,
+                    description: Some("Ask a question to the LLM through sampling".into()),
+                    input_schema: Arc::new(
+                        serde_json::from_value(serde_json::json!({
+                            "type": "object",
+                            "properties": {
+                                "question": {
+                                    "type": "string",
+                                    "description": "The question to ask the LLM"
+                                }
+                            },
+                            "required": ["question"]
+                        }))
+                        .unwrap(),
+                    ),
+                    output_schema: None,
+                    annotations: None,
+                    icons: None,
+                },
+                Tool {
+                    name: "ask_llm_follow_up".into(),
+                    title: None,
+                    description: Some(
+                        "Ask a follow-up question in a multi-turn conversation through sampling"
+                            .into(),
+                    ),
+                    input_schema: Arc::new(
+                        serde_json::from_value(serde_json::json!({
+                            "type": "object",
+                            "properties": {}
+                        }))
+                        .unwrap(),
+                    ),
+                    output_schema: None,
+                    annotations: None,
+                    icons: None,
+                },
+            ],
             next_cursor: None,
         })
     }
@@ -0,0 +1,143 @@
+//! MCP Server demonstrating streaming resources
+//!
+//! Every file in the served directory is exposed as a resource. Clients which declare the
+//! `streamingResources` capability receive the file content as 64 KB chunk notifications,
+//! other clients receive the whole file as text.
+//!
+//! Run with MCP Inspector:
+//! ```bash
+//! npx @modelcontextprotocol/inspector cargo run -p mcp-server-examples --example servers_streaming_resource_stdio -- <directory>
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use futures::StreamExt;
+use rmcp::{
+    ErrorData as McpError, RoleServer, ServerHandler, ServiceExt,
+    handler::server::streaming::StreamingReadResourceResult, model::*, service::RequestContext,
+    transport::stdio,
+};
+use tokio_util::io::ReaderStream;
+use tracing_subscriber::{self, EnvFilter};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Clone)]
+pub struct FileServer {
+    root: PathBuf,
+}
+
+impl FileServer {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn resolve(&self, uri: &str) -> Result<PathBuf, McpError> {
+        let name = uri
+            .strip_prefix("file:///")
+            .ok_or_else(|| McpError::resource_not_found("unsupported uri", None))?;
+        // only files directly under the root are served
+        if name.is_empty() || name.contains(['/', '\\']) || name == ".." {
+            return Err(McpError::resource_not_found("resource not found", None));
+        }
+        Ok(self.root.join(name))
+    }
+}
+
+impl ServerHandler for FileServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_resources().build(),
+            instructions: Some("Serves the files of a directory as streaming resources".into()),
+            ..Default::default()
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let mut entries = tokio::fs::read_dir(&self.root)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let mut resources = vec![];
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+        {
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let mut resource = RawResource::new(format!("file:///{name}"), name);
+            resource.size = u32::try_from(metadata.len()).ok();
+            resources.push(resource.no_annotation());
+        }
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let path = self.resolve(&uri)?;
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| McpError::resource_not_found(e.to_string(), None))?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, uri)],
+        })
+    }
+
+    async fn read_resource_streaming(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<StreamingReadResourceResult, McpError> {
+        let path = self.resolve(&uri)?;
+        let file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| McpError::resource_not_found(e.to_string(), None))?;
+        let chunks = ReaderStream::with_capacity(file, CHUNK_SIZE)
+            .map(|chunk| chunk.map_err(|e| McpError::internal_error(e.to_string(), None)));
+        Ok(
+            StreamingReadResourceResult::new(ReadResourceResult { contents: vec![] }).with_stream(
+                uri,
+                Some("application/octet-stream".into()),
+                chunks,
+            ),
+        )
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into()))
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .init();
+
+    let root = std::env::args().nth(1).unwrap_or_else(|| ".".into());
+    tracing::info!(%root, "Starting streaming resource server");
+
+    let service = FileServer::new(root)
+        .serve(stdio())
+        .await
+        .inspect_err(|e| {
+            tracing::error!("serving error: {:?}", e);
+        })?;
+
+    service.waiting().await?;
+    Ok(())
+}
@@ -1,7 +1,7 @@
-use std::{collections::HashMap, path::Path, process::Stdio};
+use std::{collections::HashMap, path::Path};
 
 use anyhow::Result;
-use rmcp::{RoleClient, ServiceExt, service::RunningService, transport::ConfigureCommandExt};
+use rmcp::{RoleClient, ServiceExt, service::RunningService, transport::ChildProcessConfig};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,14 +62,11 @@ impl McpServerTransportConfig {
                 args,
                 envs,
             } => {
-                let transport = rmcp::transport::child_process::TokioChildProcess::new(
-                    tokio::process::Command::new(command).configure(|cmd| {
-                        cmd.args(args)
-                            .envs(envs)
-                            .stderr(Stdio::inherit())
-                            .stdout(Stdio::inherit());
-                    }),
-                )?;
+                let config = ChildProcessConfig::builder(command)
+                    .args(args)
+                    .envs(envs)
+                    .build();
+                let transport = rmcp::transport::TokioChildProcess::new(config)?;
                 ().serve(transport).await?
             }
         };
@@ -1,9 +1,11 @@
 pub mod calculator;
-use std::task::{Poll, Waker};
+use std::{
+    task::{Poll, Waker},
+    time::Duration,
+};
 
 use rmcp::ServiceExt;
 use tokio::io::{AsyncRead, AsyncWrite};
-use tracing_subscriber::EnvFilter;
 use wasi::{
     cli::{
         stdin::{InputStream, get_stdin},
@@ -12,16 +14,40 @@ use wasi::{
     io::streams::Pollable,
 };
 
+/// How long a blocked stream sleeps between two checks of its readiness.
+///
+/// A shorter interval answers faster, a longer one spends less CPU while waiting. Zero yields to
+/// the runtime instead of sleeping, which is the fastest but busy polls.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
 pub fn wasi_io() -> (AsyncInputStream, AsyncOutputStream) {
-    let input = AsyncInputStream { inner: get_stdin() };
-    let output = AsyncOutputStream {
-        inner: get_stdout(),
-    };
-    (input, output)
+    (AsyncInputStream::new(), AsyncOutputStream::new())
 }
 
 pub struct AsyncInputStream {
     inner: InputStream,
+    poll_interval: Duration,
+}
+
+impl AsyncInputStream {
+    pub fn new() -> Self {
+        Self {
+            inner: get_stdin(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// See [`DEFAULT_POLL_INTERVAL`]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl Default for AsyncInputStream {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AsyncRead for AsyncInputStream {
@@ -37,7 +63,7 @@ impl AsyncRead for AsyncInputStream {
         if bytes.is_empty() {
             let pollable = self.inner.subscribe();
             let waker = cx.waker().clone();
-            runtime_poll(waker, pollable);
+            runtime_poll(waker, pollable, self.poll_interval);
             return Poll::Pending;
         }
         buf.put_slice(&bytes);
@@ -47,15 +73,40 @@ impl AsyncRead for AsyncInputStream {
 
 pub struct AsyncOutputStream {
     inner: OutputStream,
+    poll_interval: Duration,
 }
-fn runtime_poll(waker: Waker, pollable: Pollable) {
+
+impl AsyncOutputStream {
+    pub fn new() -> Self {
+        Self {
+            inner: get_stdout(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// See [`DEFAULT_POLL_INTERVAL`]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl Default for AsyncOutputStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn runtime_poll(waker: Waker, pollable: Pollable, poll_interval: Duration) {
     tokio::task::spawn(async move {
         loop {
             if pollable.ready() {
                 waker.wake();
                 break;
-            } else {
+            } else if poll_interval.is_zero() {
                 tokio::task::yield_now().await;
+            } else {
+                tokio::time::sleep(poll_interval).await;
             }
         }
     });
@@ -70,7 +121,7 @@ impl AsyncWrite for AsyncOutputStream {
         if writable_len == 0 {
             let pollable = self.inner.subscribe();
             let waker = cx.waker().clone();
-            runtime_poll(waker, pollable);
+            runtime_poll(waker, pollable, self.poll_interval);
             return Poll::Pending;
         }
         let bytes_to_write = buf.len().min(writable_len as usize);
@@ -105,9 +156,11 @@ impl wasi::exports::cli::run::Guest for TokioCliRunner {
             .build()
             .unwrap();
         rt.block_on(async move {
+            #[cfg(feature = "log")]
             tracing_subscriber::fmt()
                 .with_env_filter(
-                    EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into()),
+                    tracing_subscriber::EnvFilter::from_default_env()
+                        .add_directive(tracing::Level::DEBUG.into()),
                 )
                 .with_writer(std::io::stderr)
                 .with_ansi(false)
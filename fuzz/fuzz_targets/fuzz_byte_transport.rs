@@ -0,0 +1,22 @@
+//! Feed arbitrary bytes to the transport reading the messages of a client, as a server does
+#![no_main]
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+use rmcp::{
+    RoleServer,
+    transport::{Transport, async_rw::AsyncRwTransport},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("runtime");
+    runtime.block_on(async {
+        let mut transport: AsyncRwTransport<RoleServer, _, _> =
+            AsyncRwTransport::new(Cursor::new(data.to_vec()), tokio::io::sink());
+        // read until the end of the input, or until an invalid message closes the transport
+        while Transport::<RoleServer>::receive(&mut transport).await.is_some() {}
+    });
+});
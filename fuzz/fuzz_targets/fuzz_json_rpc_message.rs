@@ -0,0 +1,12 @@
+//! Feed arbitrary json to the deserialization of the messages of a client
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rmcp::model::ClientJsonRpcMessage;
+
+fuzz_target!(|data: &str| {
+    if let Ok(message) = serde_json::from_str::<ClientJsonRpcMessage>(data) {
+        // a parsed message serializes back
+        serde_json::to_string(&message).expect("serializable message");
+    }
+});